@@ -0,0 +1,204 @@
+use serde_json::Value as JsonValue;
+
+/// Encode a parsed blob back to its binary form
+///
+/// Inverse of [`parse_blob`](super::parse_blob) for the blob types it knows.
+///
+/// # Arguments
+/// * `blob_type` - Type of blob ("commands", "vectorNetwork", etc.)
+/// * `value` - Parsed blob content
+///
+/// # Returns
+/// * `Some(Vec<u8>)` - Binary blob data
+/// * `None` - If the blob type is unknown or the content is malformed
+pub fn encode_blob(blob_type: &str, value: &JsonValue) -> Option<Vec<u8>> {
+    match blob_type {
+        "commands" => encode_commands(value),
+        "vectorNetwork" => encode_vector_network(value),
+        _ => None,
+    }
+}
+
+/// Encode a JSON command array into binary path commands
+///
+/// Accepts the flat array produced by [`parse_commands`](super::parse_commands):
+/// `["M", x, y, "L", x, y, ..., "Z"]`. `null` coordinates are written as NaN.
+///
+/// # Examples
+/// ```
+/// use fig2json::blobs::{encode_commands, parse_commands};
+/// use serde_json::json;
+///
+/// let commands = json!(["M", 0.0, 0.0, "L", 10.0, 5.0, "Z"]);
+/// let bytes = encode_commands(&commands).unwrap();
+/// assert_eq!(parse_commands(&bytes), Some(commands));
+/// ```
+pub fn encode_commands(commands: &JsonValue) -> Option<Vec<u8>> {
+    let items = commands.as_array()?;
+    let mut bytes = Vec::new();
+    let mut i = 0;
+
+    while i < items.len() {
+        let (code, float_count) = match items[i].as_str()? {
+            "Z" => (0u8, 0),
+            "M" => (1, 2),
+            "L" => (2, 2),
+            "Q" => (3, 4),
+            "C" => (4, 6),
+            _ => return None,
+        };
+        bytes.push(code);
+
+        let floats = items.get(i + 1..i + 1 + float_count)?;
+        for value in floats {
+            push_f32(&mut bytes, value)?;
+        }
+        i += 1 + float_count;
+    }
+
+    Some(bytes)
+}
+
+/// Encode a JSON vector network into its binary form
+///
+/// Accepts the object produced by [`parse_vector_network`](super::parse_vector_network).
+///
+/// # Examples
+/// ```
+/// use fig2json::blobs::{encode_vector_network, parse_vector_network};
+/// use serde_json::json;
+///
+/// let network = json!({
+///     "vertices": [{"styleID": 0, "x": 0.0, "y": 0.0}, {"styleID": 0, "x": 10.0, "y": 0.0}],
+///     "segments": [{
+///         "styleID": 0,
+///         "start": {"vertex": 0, "dx": 0.0, "dy": 0.0},
+///         "end": {"vertex": 1, "dx": 0.0, "dy": 0.0}
+///     }],
+///     "regions": []
+/// });
+/// let bytes = encode_vector_network(&network).unwrap();
+/// assert_eq!(parse_vector_network(&bytes), Some(network));
+/// ```
+pub fn encode_vector_network(network: &JsonValue) -> Option<Vec<u8>> {
+    let vertices = network.get("vertices")?.as_array()?;
+    let segments = network.get("segments")?.as_array()?;
+    let regions = network.get("regions")?.as_array()?;
+
+    let mut bytes = Vec::new();
+    push_u32(&mut bytes, vertices.len() as u32);
+    push_u32(&mut bytes, segments.len() as u32);
+    push_u32(&mut bytes, regions.len() as u32);
+
+    for vertex in vertices {
+        push_u32(&mut bytes, field_u32(vertex, "styleID")?);
+        push_f32(&mut bytes, vertex.get("x")?)?;
+        push_f32(&mut bytes, vertex.get("y")?)?;
+    }
+
+    for segment in segments {
+        push_u32(&mut bytes, field_u32(segment, "styleID")?);
+        for end in ["start", "end"] {
+            let end = segment.get(end)?;
+            push_u32(&mut bytes, field_u32(end, "vertex")?);
+            push_f32(&mut bytes, end.get("dx")?)?;
+            push_f32(&mut bytes, end.get("dy")?)?;
+        }
+    }
+
+    for region in regions {
+        // styleID and winding rule are packed into one u32
+        let nonzero = match region.get("windingRule")?.as_str()? {
+            "NONZERO" => 1,
+            "ODD" => 0,
+            _ => return None,
+        };
+        push_u32(&mut bytes, (field_u32(region, "styleID")? << 1) | nonzero);
+
+        let loops = region.get("loops")?.as_array()?;
+        push_u32(&mut bytes, loops.len() as u32);
+        for region_loop in loops {
+            let indices = region_loop.get("segments")?.as_array()?;
+            push_u32(&mut bytes, indices.len() as u32);
+            for index in indices {
+                push_u32(&mut bytes, u32::try_from(index.as_u64()?).ok()?);
+            }
+        }
+    }
+
+    Some(bytes)
+}
+
+fn field_u32(value: &JsonValue, key: &str) -> Option<u32> {
+    u32::try_from(value.get(key)?.as_u64()?).ok()
+}
+
+fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Append a JSON number as a little-endian f32 (`null` is written as NaN)
+fn push_f32(bytes: &mut Vec<u8>, value: &JsonValue) -> Option<()> {
+    let value = if value.is_null() {
+        f32::NAN
+    } else {
+        value.as_f64()? as f32
+    };
+    bytes.extend_from_slice(&value.to_le_bytes());
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blobs::{parse_commands, parse_vector_network};
+    use serde_json::json;
+
+    #[test]
+    fn test_encode_commands_round_trip() {
+        let commands = json!([
+            "M", 0.0, 0.0, "L", 10.5, 0.0, "Q", 1.0, 2.0, 3.0, 4.0, "C", 1.0, 2.0, 3.0, 4.0, 5.0,
+            6.0, "Z"
+        ]);
+        let bytes = encode_commands(&commands).unwrap();
+
+        assert_eq!(bytes.len(), 5 + 4 * (2 + 2 + 4 + 6));
+        assert_eq!(parse_commands(&bytes), Some(commands));
+    }
+
+    #[test]
+    fn test_encode_commands_invalid() {
+        assert!(encode_commands(&json!(["X", 1.0])).is_none());
+        assert!(encode_commands(&json!(["M", 1.0])).is_none());
+        assert!(encode_commands(&json!({"M": 1.0})).is_none());
+    }
+
+    #[test]
+    fn test_encode_vector_network_round_trip() {
+        let network = json!({
+            "vertices": [
+                {"styleID": 0, "x": 0.0, "y": 0.0},
+                {"styleID": 0, "x": 10.0, "y": 0.0},
+                {"styleID": 2, "x": 10.0, "y": 10.0}
+            ],
+            "segments": [
+                {"styleID": 0, "start": {"vertex": 0, "dx": 0.0, "dy": 0.0}, "end": {"vertex": 1, "dx": 0.0, "dy": 0.0}},
+                {"styleID": 0, "start": {"vertex": 1, "dx": 1.5, "dy": 0.0}, "end": {"vertex": 2, "dx": 0.0, "dy": -1.5}},
+                {"styleID": 0, "start": {"vertex": 2, "dx": 0.0, "dy": 0.0}, "end": {"vertex": 0, "dx": 0.0, "dy": 0.0}}
+            ],
+            "regions": [
+                {"styleID": 3, "windingRule": "NONZERO", "loops": [{"segments": [0, 1, 2]}]},
+                {"styleID": 0, "windingRule": "ODD", "loops": []}
+            ]
+        });
+        let bytes = encode_vector_network(&network).unwrap();
+
+        assert_eq!(parse_vector_network(&bytes), Some(network));
+    }
+
+    #[test]
+    fn test_encode_blob_unknown_type() {
+        assert!(encode_blob("unknown", &json!([])).is_none());
+        assert!(encode_blob("commands", &json!(["Z"])).is_some());
+    }
+}
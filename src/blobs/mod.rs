@@ -1,3 +1,4 @@
+pub mod encoder;
 pub mod parser;
 pub mod substitution;
 
@@ -6,6 +7,7 @@ use base64::{engine::general_purpose, Engine as _};
 use serde_json::Value as JsonValue;
 
 // Re-export commonly used items
+pub use encoder::{encode_blob, encode_commands, encode_vector_network};
 pub use parser::{parse_blob, parse_commands, parse_vector_network};
 pub use substitution::substitute_blobs;
 
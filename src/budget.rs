@@ -0,0 +1,552 @@
+//! Token budget
+//!
+//! [`fit_to_budget`] shrinks a converted document until its estimated token
+//! count fits a budget, degrading detail step by step, from the least to the
+//! most semantic loss:
+//!
+//! 1. `derived_data`: the geometry and text layout removal passes
+//! 2. `design_metadata`: the edit info, plugin data, export settings, layout
+//!    aids, constraint and version removal passes
+//! 3. `vector_data`: the path data kept for icons by `geometry_removal`
+//! 4. `hidden_nodes`: nodes with `visible: false`
+//! 5. `deep_subtrees`: subtrees below depth 8, then 6, 4, 3 and 2 (counted from
+//!    the top-level frames), each collapsed into an `elided` summary with
+//!    its node count and first texts
+//! 6. `long_text`: text content longer than 200, then 50 characters
+//!
+//! Steps 1 and 2 are the standard pipeline's own passes: they only matter
+//! when some were skipped, and are cheap to run again otherwise.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::budget::fit_to_budget;
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let mut json = fig2json::convert(&bytes, None).unwrap();
+//!
+//! let report = fit_to_budget(&mut json, 50_000).unwrap();
+//! if !report.fits {
+//!     eprintln!("Still {} tokens", report.final_tokens);
+//! }
+//! ```
+
+use crate::error::Result;
+use crate::pipeline::{PassContext, Pipeline};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+
+/// Average number of characters of compact JSON per token
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Passes of the `derived_data` step
+const DERIVED_DATA_PASSES: &[&str] = &[
+    "text_glyphs_removal",
+    "geometry_removal",
+    "text_layout_removal",
+    "derived_text_layout_size_removal",
+    "empty_derived_text_data_removal",
+];
+
+/// Passes of the `design_metadata` step
+const DESIGN_METADATA_PASSES: &[&str] = &[
+    "edit_info_removal",
+    "document_properties_removal",
+    "blobs_removal",
+    "user_facing_version_removal",
+    "export_settings_removal",
+    "plugin_data_removal",
+    "constraint_properties_removal",
+    "scroll_resize_properties_removal",
+    "layout_aids_removal",
+];
+
+/// Path data fields dropped by the `vector_data` step
+const VECTOR_FIELDS: &[&str] = &["fillGeometry", "strokeGeometry", "vectorData"];
+
+/// Depths below which subtrees are collapsed, tried in order
+const COLLAPSE_DEPTHS: &[usize] = &[8, 6, 4, 3, 2];
+
+/// Lengths text content is truncated to, tried in order
+const TEXT_LENGTHS: &[usize] = &[200, 50];
+
+/// Texts quoted in the summary of a collapsed subtree
+const SUMMARY_TEXTS: usize = 5;
+
+/// Characters kept of each text quoted in a summary
+const SUMMARY_TEXT_LENGTH: usize = 40;
+
+/// Outcome of [`fit_to_budget`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetReport {
+    pub max_tokens: usize,
+    /// Estimated tokens before any step
+    pub initial_tokens: usize,
+    /// Estimated tokens after the applied steps, including the `budget` field
+    pub final_tokens: usize,
+    /// Whether the output fits the budget
+    pub fits: bool,
+    /// Steps that reduced the output, in order
+    pub steps: Vec<AppliedStep>,
+}
+
+/// A degradation step that was applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedStep {
+    /// Step name, with its depth or length for progressive steps
+    /// (`deep_subtrees:4`, `long_text:50`)
+    pub name: String,
+    /// Estimated tokens removed by the step
+    pub tokens_saved: usize,
+}
+
+/// Estimate the token count of a JSON value
+///
+/// Counts one token per four characters of compact JSON, which is close to
+/// what LLM tokenizers produce for Figma output (short keys, numbers and
+/// English text).
+pub fn estimate_tokens(value: &JsonValue) -> usize {
+    value.to_string().len().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Degrade a converted document until it fits a token budget
+///
+/// Steps are applied in order until the estimate is at most `max_tokens`.
+/// When any step was applied, the report is also added to the output as a
+/// root-level `budget` field, which counts towards the estimate.
+///
+/// # Arguments
+/// * `output` - Output of [`convert`](crate::convert)
+/// * `max_tokens` - Token budget
+///
+/// # Returns
+/// * `Ok(BudgetReport)` - What was elided, and whether the output fits
+/// * `Err(FigError)` - If a removal pass fails
+///
+/// # Examples
+/// ```
+/// use fig2json::budget::fit_to_budget;
+/// use serde_json::json;
+///
+/// let long_text = "Lorem ipsum dolor sit amet. ".repeat(50);
+/// let mut output = json!({"document": {"children": [{"name": "Page", "children": [
+///     {"name": "Body", "textData": {"characters": long_text}}
+/// ]}]}});
+/// let report = fit_to_budget(&mut output, 100).unwrap();
+///
+/// assert!(report.fits);
+/// assert_eq!(report.steps[0].name, "long_text:200");
+/// assert_eq!(output["budget"]["fits"], true);
+/// assert!(fig2json::budget::estimate_tokens(&output) <= 100);
+/// ```
+pub fn fit_to_budget(output: &mut JsonValue, max_tokens: usize) -> Result<BudgetReport> {
+    let initial_tokens = estimate_tokens(output);
+    let mut report = BudgetReport {
+        max_tokens,
+        initial_tokens,
+        final_tokens: initial_tokens,
+        fits: initial_tokens <= max_tokens,
+        steps: Vec::new(),
+    };
+
+    let mut steps: Vec<(String, Step)> = vec![
+        (
+            "derived_data".to_string(),
+            Step::Passes(DERIVED_DATA_PASSES),
+        ),
+        (
+            "design_metadata".to_string(),
+            Step::Passes(DESIGN_METADATA_PASSES),
+        ),
+        ("vector_data".to_string(), Step::VectorData),
+        ("hidden_nodes".to_string(), Step::HiddenNodes),
+    ];
+    for &depth in COLLAPSE_DEPTHS {
+        steps.push((format!("deep_subtrees:{}", depth), Step::Collapse(depth)));
+    }
+    for &length in TEXT_LENGTHS {
+        steps.push((format!("long_text:{}", length), Step::Truncate(length)));
+    }
+
+    let mut document_tokens = initial_tokens;
+    for (name, step) in steps {
+        if report.fits {
+            break;
+        }
+        step.apply(output)?;
+        let tokens = estimate_tokens(output);
+        if tokens < document_tokens {
+            report.steps.push(AppliedStep {
+                name,
+                tokens_saved: document_tokens - tokens,
+            });
+        }
+        document_tokens = tokens;
+        report.final_tokens = if report.steps.is_empty() {
+            tokens
+        } else {
+            tokens_with_report(output, &report)?
+        };
+        report.fits = report.final_tokens <= max_tokens;
+    }
+
+    if !report.steps.is_empty() {
+        if let Some(root) = output.as_object_mut() {
+            root.insert("budget".to_string(), serde_json::to_value(&report)?);
+        }
+    }
+    Ok(report)
+}
+
+/// Estimated tokens of `output` once `report` is added as its `budget` field
+fn tokens_with_report(output: &JsonValue, report: &BudgetReport) -> Result<usize> {
+    let output_chars = output.to_string().len() + r#","budget":"#.len();
+    // Assume the longest `fits` value; the report holds its own estimate,
+    // whose digits are counted on a second round
+    let mut report = report.clone();
+    report.fits = false;
+    let mut tokens = output_chars.div_ceil(CHARS_PER_TOKEN);
+    for _ in 0..2 {
+        report.final_tokens = tokens;
+        tokens = (output_chars + serde_json::to_string(&report)?.len()).div_ceil(CHARS_PER_TOKEN);
+    }
+    Ok(tokens)
+}
+
+/// A degradation step
+enum Step {
+    /// Run passes of the standard pipeline
+    Passes(&'static [&'static str]),
+    VectorData,
+    HiddenNodes,
+    /// Collapse subtrees below a depth
+    Collapse(usize),
+    /// Truncate text content to a number of characters
+    Truncate(usize),
+}
+
+impl Step {
+    fn apply(&self, output: &mut JsonValue) -> Result<()> {
+        match self {
+            Step::Passes(names) => {
+                let mut pipeline = Pipeline::standard();
+                pipeline.only(names)?;
+                pipeline.run(output, &PassContext::default())?;
+            }
+            Step::VectorData => remove_fields(output, VECTOR_FIELDS),
+            Step::HiddenNodes => remove_hidden_nodes(document_mut(output)),
+            Step::Collapse(depth) => {
+                for page in children_mut(document_mut(output)) {
+                    for frame in children_mut(page) {
+                        collapse(frame, 1, *depth);
+                    }
+                }
+            }
+            Step::Truncate(length) => truncate_texts(output, *length),
+        }
+        Ok(())
+    }
+}
+
+/// Document node of an output
+fn document_mut(output: &mut JsonValue) -> &mut JsonValue {
+    match output.get("document") {
+        Some(_) => &mut output["document"],
+        None => output,
+    }
+}
+
+fn children_mut(node: &mut JsonValue) -> impl Iterator<Item = &mut JsonValue> {
+    node.get_mut("children")
+        .and_then(|c| c.as_array_mut())
+        .into_iter()
+        .flatten()
+}
+
+fn remove_fields(value: &mut JsonValue, fields: &[&str]) {
+    match value {
+        JsonValue::Object(map) => {
+            map.retain(|key, _| !fields.contains(&key.as_str()));
+            for val in map.values_mut() {
+                remove_fields(val, fields);
+            }
+        }
+        JsonValue::Array(arr) => {
+            for val in arr.iter_mut() {
+                remove_fields(val, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn remove_hidden_nodes(value: &mut JsonValue) {
+    if let Some(JsonValue::Array(children)) = value.get_mut("children") {
+        children.retain(|child| child.get("visible").and_then(|v| v.as_bool()) != Some(false));
+        for child in children.iter_mut() {
+            remove_hidden_nodes(child);
+        }
+    }
+}
+
+/// Replace the children of the nodes at `max_depth` with a summary
+fn collapse(node: &mut JsonValue, depth: usize, max_depth: usize) {
+    let Some(map) = node.as_object_mut() else {
+        return;
+    };
+    if depth < max_depth {
+        if let Some(JsonValue::Array(children)) = map.get_mut("children") {
+            for child in children.iter_mut() {
+                collapse(child, depth + 1, max_depth);
+            }
+        }
+        return;
+    }
+
+    let Some(JsonValue::Array(children)) = map.remove("children") else {
+        return;
+    };
+    let mut summary = Summary::default();
+    for child in &children {
+        summary.add(child);
+    }
+    map.insert("elided".to_string(), summary.into_json());
+}
+
+/// Node count and texts of a collapsed subtree
+#[derive(Default)]
+struct Summary {
+    nodes: u64,
+    texts: Vec<String>,
+}
+
+impl Summary {
+    fn add(&mut self, node: &JsonValue) {
+        self.nodes += 1;
+        if let Some(elided) = node.get("elided") {
+            self.nodes += elided.get("nodes").and_then(|n| n.as_u64()).unwrap_or(0);
+            for text in elided
+                .get("texts")
+                .and_then(|t| t.as_array())
+                .into_iter()
+                .flatten()
+            {
+                self.add_text(text.as_str().unwrap_or(""));
+            }
+        }
+        if let Some(text) = node
+            .get("textData")
+            .and_then(|t| t.get("characters"))
+            .and_then(|c| c.as_str())
+        {
+            self.add_text(text);
+        }
+        for child in node
+            .get("children")
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+        {
+            self.add(child);
+        }
+    }
+
+    fn add_text(&mut self, text: &str) {
+        if self.texts.len() < SUMMARY_TEXTS && !text.trim().is_empty() {
+            self.texts.push(truncate(text, SUMMARY_TEXT_LENGTH));
+        }
+    }
+
+    fn into_json(self) -> JsonValue {
+        let mut summary = Map::new();
+        summary.insert("nodes".to_string(), JsonValue::from(self.nodes));
+        if !self.texts.is_empty() {
+            summary.insert("texts".to_string(), JsonValue::from(self.texts));
+        }
+        JsonValue::Object(summary)
+    }
+}
+
+fn truncate_texts(value: &mut JsonValue, length: usize) {
+    match value {
+        JsonValue::Object(map) => {
+            if let Some(JsonValue::Object(text_data)) = map.get_mut("textData") {
+                truncate_text_data(text_data, length);
+            }
+            for val in map.values_mut() {
+                truncate_texts(val, length);
+            }
+        }
+        JsonValue::Array(arr) => {
+            for val in arr.iter_mut() {
+                truncate_texts(val, length);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Truncate `characters` and, when it is cut, the runs spelling it out
+fn truncate_text_data(text_data: &mut Map<String, JsonValue>, length: usize) {
+    let Some(JsonValue::String(text)) = text_data.get_mut("characters") else {
+        return;
+    };
+    if text.chars().count() <= length {
+        return;
+    }
+    *text = truncate(text, length);
+
+    let Some(JsonValue::Array(runs)) = text_data.get_mut("runs") else {
+        return;
+    };
+    let mut remaining = length.saturating_sub(1);
+    runs.retain_mut(|run| {
+        if remaining == 0 {
+            return false;
+        }
+        if let Some(JsonValue::String(text)) = run.get_mut("text") {
+            let count = text.chars().count();
+            if count > remaining {
+                *text = text.chars().take(remaining).collect();
+            }
+            remaining -= count.min(remaining);
+        }
+        true
+    });
+    match runs.last_mut().and_then(|run| run.get_mut("text")) {
+        Some(JsonValue::String(text)) => text.push('…'),
+        _ => {
+            text_data.remove("runs");
+        }
+    }
+}
+
+/// Text shortened to `length` characters, ending with `…` when cut
+fn truncate(text: &str, length: usize) -> String {
+    if text.chars().count() <= length {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(length.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn output() -> JsonValue {
+        json!({"document": {"children": [{"name": "Page", "children": [{
+            "name": "Screen",
+            "children": [
+                {"name": "icon/check", "fillGeometry": [{"commands": ["M", 0.0, 0.0, "L", 24.0, 24.0, "Z"]}]},
+                {"name": "Hidden", "visible": false, "children": [{"name": "Old"}]},
+                {"name": "Card", "children": [
+                    {"name": "Title", "textData": {"characters": "Weekly report"}},
+                    {"name": "Body", "children": [{"name": "Line", "textData": {"characters": "All good"}}]}
+                ]}
+            ]
+        }]}]}})
+    }
+
+    #[test]
+    fn test_no_steps_when_within_budget() {
+        let mut output = output();
+        let expected = output.clone();
+
+        let report = fit_to_budget(&mut output, 10_000).unwrap();
+
+        assert!(report.fits);
+        assert!(report.steps.is_empty());
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_steps_in_order() {
+        let mut output = output();
+        let report = fit_to_budget(&mut output, 1).unwrap();
+
+        let names: Vec<&str> = report.steps.iter().map(|s| s.name.as_str()).collect();
+        // geometry_removal keeps the icon's path data, which vector_data drops
+        assert_eq!(
+            names,
+            [
+                "vector_data",
+                "hidden_nodes",
+                "deep_subtrees:3",
+                "deep_subtrees:2"
+            ]
+        );
+        assert!(!report.fits);
+        assert!(report.steps.iter().all(|step| step.tokens_saved > 0));
+        // The estimate includes the report added to the output
+        assert!(report.final_tokens >= estimate_tokens(&output));
+        assert_eq!(output["budget"]["steps"][0]["name"], json!("vector_data"));
+    }
+
+    #[test]
+    fn test_collapse_summary() {
+        let mut output = output();
+        Step::Collapse(2).apply(&mut output).unwrap();
+
+        let card = &output["document"]["children"][0]["children"][0]["children"][2];
+        assert_eq!(card["children"], JsonValue::Null);
+        assert_eq!(
+            card["elided"],
+            json!({"nodes": 3, "texts": ["Weekly report", "All good"]})
+        );
+
+        // Collapsing further keeps the counts of the previous summaries
+        Step::Collapse(1).apply(&mut output).unwrap();
+        let screen = &output["document"]["children"][0]["children"][0];
+        assert_eq!(screen["elided"]["nodes"], json!(7));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("Weekly report", 7), "Weekly…");
+        assert_eq!(truncate("Short", 7), "Short");
+
+        let mut output = output();
+        Step::Truncate(5).apply(&mut output).unwrap();
+        let title = &output["document"]["children"][0]["children"][0]["children"][2]["children"][0];
+        assert_eq!(title["textData"]["characters"], json!("Week…"));
+    }
+
+    #[test]
+    fn test_truncate_runs() {
+        let mut output = json!({"textData": {
+            "characters": "Read the docs",
+            "runs": [{"text": "Rea", "fontSize": 16.0}, {"text": "d the ", "fontSize": 12.0}, {"text": "docs"}]
+        }});
+        Step::Truncate(6).apply(&mut output).unwrap();
+
+        assert_eq!(
+            output["textData"],
+            json!({
+                "characters": "Read …",
+                "runs": [{"text": "Rea", "fontSize": 16.0}, {"text": "d …", "fontSize": 12.0}]
+            })
+        );
+    }
+
+    #[test]
+    fn test_report_counts_towards_budget() {
+        for max_tokens in (20..400).step_by(7) {
+            let long_text = "Lorem ipsum dolor sit amet. ".repeat(50);
+            let mut output = json!({"document": {"children": [{"name": "Page", "children": [
+                {"name": "Body", "textData": {"characters": long_text}}
+            ]}]}});
+
+            let report = fit_to_budget(&mut output, max_tokens).unwrap();
+
+            assert!(estimate_tokens(&output) <= report.final_tokens, "{}", max_tokens);
+            if report.fits {
+                assert!(estimate_tokens(&output) <= max_tokens, "{}", max_tokens);
+            }
+        }
+    }
+}
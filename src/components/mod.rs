@@ -0,0 +1,486 @@
+//! Component catalog
+//!
+//! [`catalog`] lists every main component of a file:
+//!
+//! - standalone SYMBOL nodes as [`Component`]s
+//! - component sets (frames with `isStateGroup`) as [`ComponentSet`]s, their
+//!   SYMBOL children as variants, with the variant names ("Size=Large,
+//!   State=Hover") parsed into a property matrix
+//!
+//! Each entry carries its description, publishing key, component property
+//! definitions (`componentPropDefs`) and the number of INSTANCE nodes using it.
+//!
+//! The input is the raw output of [`convert_raw`](crate::convert_raw), which
+//! still has the GUIDs and `symbolData` that the standard pipeline removes.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let raw = fig2json::convert_raw(&bytes).unwrap();
+//!
+//! let catalog = fig2json::components::catalog(&raw);
+//! std::fs::write("components.json", serde_json::to_string_pretty(&catalog).unwrap()).unwrap();
+//! ```
+
+use crate::schema::tree::{enum_str, guid_key, node_type};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::{BTreeMap, HashMap};
+
+/// Components and component sets of a file
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Catalog {
+    /// Standalone components, in document order
+    pub components: Vec<Component>,
+    /// Component sets, in document order
+    pub component_sets: Vec<ComponentSet>,
+}
+
+/// A standalone main component
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Component {
+    /// GUID (`sessionID:localID`)
+    pub id: String,
+    pub name: String,
+    /// Name of the page the component is on
+    pub page: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Key of the published component
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<Property>,
+    /// Number of INSTANCE nodes of the component
+    pub instances: usize,
+}
+
+/// A component set and its variants
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentSet {
+    /// GUID (`sessionID:localID`)
+    pub id: String,
+    pub name: String,
+    /// Name of the page the component set is on
+    pub page: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Key of the published component set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Variant properties and their possible values
+    pub variant_properties: Vec<VariantProperty>,
+    /// Component properties shared by the variants
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<Property>,
+    pub variants: Vec<Variant>,
+    /// Number of INSTANCE nodes of any variant
+    pub instances: usize,
+}
+
+/// A variant property ("Size") and its values ("Small", "Large")
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariantProperty {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+/// A SYMBOL of a component set
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variant {
+    /// GUID (`sessionID:localID`)
+    pub id: String,
+    /// Full name ("Size=Large, State=Hover")
+    pub name: String,
+    /// Value of each variant property
+    pub values: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Number of INSTANCE nodes of the variant
+    pub instances: usize,
+}
+
+/// A component property definition
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Property {
+    /// Name without the `#id` suffix Figma appends
+    pub name: String,
+    /// `BOOL`, `TEXT`, `NUMBER` or `INSTANCE_SWAP`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Initial value (a component GUID for instance swaps)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<JsonValue>,
+}
+
+/// List the components of a file
+///
+/// # Arguments
+/// * `output` - Output of [`convert_raw`](crate::convert_raw), or its `document` node
+///
+/// # Returns
+/// Standalone components and component sets, in document order
+///
+/// # Examples
+/// ```
+/// use fig2json::components::catalog;
+/// use serde_json::json;
+///
+/// let document = json!({"children": [{"type": "CANVAS", "name": "Page 1", "children": [
+///     {"type": "SYMBOL", "name": "Badge", "guid": {"sessionID": 1, "localID": 2}},
+///     {"type": "INSTANCE", "symbolData": {"symbolID": {"sessionID": 1, "localID": 2}}}
+/// ]}]});
+/// let catalog = catalog(&document);
+///
+/// assert_eq!(catalog.components[0].name, "Badge");
+/// assert_eq!(catalog.components[0].instances, 1);
+/// ```
+pub fn catalog(output: &JsonValue) -> Catalog {
+    let document = output.get("document").unwrap_or(output);
+
+    let mut instances = HashMap::new();
+    count_instances(document, &mut instances);
+
+    let mut catalog = Catalog::default();
+    collect(document, "", &instances, &mut catalog);
+    catalog
+}
+
+/// Count INSTANCE nodes by the GUID of their main component
+fn count_instances(value: &JsonValue, counts: &mut HashMap<String, usize>) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+    if node_type(map) == Some("INSTANCE") {
+        let symbol = map.get("symbolData").and_then(|d| d.get("symbolID"));
+        if let Some(id) = symbol.and_then(guid_key) {
+            *counts.entry(id).or_insert(0) += 1;
+        }
+    }
+    for child in children(map) {
+        count_instances(child, counts);
+    }
+}
+
+fn collect(
+    value: &JsonValue,
+    page: &str,
+    instances: &HashMap<String, usize>,
+    catalog: &mut Catalog,
+) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+    let count = |node: &Map<String, JsonValue>| {
+        id(node)
+            .and_then(|id| instances.get(&id))
+            .copied()
+            .unwrap_or(0)
+    };
+
+    if map.get("isStateGroup").and_then(|v| v.as_bool()) == Some(true) {
+        let symbols: Vec<&Map<String, JsonValue>> = children(map)
+            .iter()
+            .filter_map(|c| c.as_object())
+            .filter(|c| node_type(c) == Some("SYMBOL"))
+            .collect();
+        let variants: Vec<Variant> = symbols
+            .iter()
+            .map(|symbol| Variant {
+                id: id(symbol).unwrap_or_default(),
+                name: name(symbol).to_string(),
+                values: parse_variant_name(name(symbol)).into_iter().collect(),
+                description: description(symbol),
+                key: key(symbol),
+                instances: count(symbol),
+            })
+            .collect();
+
+        catalog.component_sets.push(ComponentSet {
+            id: id(map).unwrap_or_default(),
+            name: name(map).to_string(),
+            page: page.to_string(),
+            description: description(map),
+            key: key(map),
+            variant_properties: variant_properties(map, &variants),
+            properties: properties(std::iter::once(map).chain(symbols.iter().copied())),
+            instances: variants.iter().map(|v| v.instances).sum(),
+            variants,
+        });
+        return;
+    }
+
+    if node_type(map) == Some("SYMBOL") {
+        catalog.components.push(Component {
+            id: id(map).unwrap_or_default(),
+            name: name(map).to_string(),
+            page: page.to_string(),
+            description: description(map),
+            key: key(map),
+            properties: properties([map]),
+            instances: count(map),
+        });
+        return;
+    }
+
+    let page = if node_type(map) == Some("CANVAS") {
+        name(map)
+    } else {
+        page
+    };
+    for child in children(map) {
+        collect(child, page, instances, catalog);
+    }
+}
+
+/// Variant properties, in Figma's order when available, else by appearance
+fn variant_properties(set: &Map<String, JsonValue>, variants: &[Variant]) -> Vec<VariantProperty> {
+    let mut properties: Vec<VariantProperty> = set
+        .get("stateGroupPropertyValueOrders")
+        .and_then(|o| o.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|order| {
+            let values = order
+                .get("values")?
+                .as_array()?
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
+            Some(VariantProperty {
+                name: order.get("property")?.as_str()?.to_string(),
+                values,
+            })
+        })
+        .collect();
+
+    for variant in variants {
+        for (property, value) in parse_variant_name(&variant.name) {
+            let index = match properties.iter().position(|p| p.name == property) {
+                Some(index) => index,
+                None => {
+                    properties.push(VariantProperty {
+                        name: property,
+                        values: Vec::new(),
+                    });
+                    properties.len() - 1
+                }
+            };
+            if !properties[index].values.contains(&value) {
+                properties[index].values.push(value);
+            }
+        }
+    }
+    properties
+}
+
+/// Component property definitions of `nodes`, deduplicated by name
+fn properties<'a>(nodes: impl IntoIterator<Item = &'a Map<String, JsonValue>>) -> Vec<Property> {
+    let mut properties: Vec<Property> = Vec::new();
+    for def in nodes
+        .into_iter()
+        .filter_map(|node| node.get("componentPropDefs")?.as_array())
+        .flatten()
+    {
+        let Some(kind) = def.get("type").and_then(enum_str) else {
+            continue;
+        };
+        let label = def.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        let label = label.split('#').next().unwrap_or(label).to_string();
+        if properties.iter().any(|p| p.name == label) {
+            continue;
+        }
+
+        let initial = def.get("initialValue");
+        let default = match kind {
+            "BOOL" => initial.and_then(|v| v.get("boolValue")).cloned(),
+            "TEXT" => initial
+                .and_then(|v| v.get("textValue"))
+                .and_then(|t| t.get("characters"))
+                .cloned(),
+            "NUMBER" => initial.and_then(|v| v.get("floatValue")).cloned(),
+            "INSTANCE_SWAP" => initial
+                .and_then(|v| v.get("guidValue"))
+                .and_then(guid_key)
+                .map(JsonValue::String),
+            _ => None,
+        };
+        properties.push(Property {
+            name: label,
+            kind: kind.to_string(),
+            default,
+        });
+    }
+    properties
+}
+
+/// Variant properties of a SYMBOL name ("Size=Large, State=Hover")
+pub(crate) fn parse_variant_name(name: &str) -> Vec<(String, String)> {
+    name.split(',')
+        .map(|pair| match pair.split_once('=') {
+            Some((property, value)) => (property.trim().to_string(), value.trim().to_string()),
+            None => ("Variant".to_string(), pair.trim().to_string()),
+        })
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+fn id(node: &Map<String, JsonValue>) -> Option<String> {
+    node.get("guid").and_then(guid_key)
+}
+
+fn name(node: &Map<String, JsonValue>) -> &str {
+    node.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+fn description(node: &Map<String, JsonValue>) -> Option<String> {
+    node.get("description")
+        .and_then(|d| d.as_str())
+        .filter(|d| !d.is_empty())
+        .map(str::to_string)
+}
+
+fn key(node: &Map<String, JsonValue>) -> Option<String> {
+    node.get("componentKey")
+        .and_then(|k| k.as_str())
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+}
+
+fn children(node: &Map<String, JsonValue>) -> &[JsonValue] {
+    node.get("children")
+        .and_then(|c| c.as_array())
+        .map(|c| c.as_slice())
+        .unwrap_or(&[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn guid(local: u64) -> JsonValue {
+        json!({"sessionID": 1, "localID": local})
+    }
+
+    fn enum_type(value: &str) -> JsonValue {
+        json!({"__enum__": "NodeType", "value": value})
+    }
+
+    fn instance(symbol: u64) -> JsonValue {
+        json!({"type": enum_type("INSTANCE"), "symbolData": {"symbolID": guid(symbol)}})
+    }
+
+    fn raw_output() -> JsonValue {
+        json!({"version": 101, "document": {"type": enum_type("DOCUMENT"), "children": [
+            {
+                "type": enum_type("CANVAS"),
+                "name": "Components",
+                "children": [
+                    {
+                        "type": enum_type("SYMBOL"),
+                        "name": "Icon",
+                        "guid": guid(1),
+                        "description": "Generic icon",
+                        "componentKey": "abc",
+                        "componentPropDefs": [
+                            {"id": guid(50), "name": "Filled#1:50", "type": {"__enum__": "ComponentPropType", "value": "BOOL"}, "initialValue": {"boolValue": true}}
+                        ]
+                    },
+                    {
+                        "type": enum_type("FRAME"),
+                        "name": "Button",
+                        "guid": guid(10),
+                        "isStateGroup": true,
+                        "stateGroupPropertyValueOrders": [{"property": "State", "values": ["Default", "Hover"]}],
+                        "componentPropDefs": [
+                            {"name": "Label#2:0", "type": "TEXT", "initialValue": {"textValue": {"characters": "OK"}}},
+                            {"name": "Icon#2:1", "type": "INSTANCE_SWAP", "initialValue": {"guidValue": guid(1)}}
+                        ],
+                        "children": [
+                            {"type": enum_type("SYMBOL"), "name": "Size=Small, State=Default", "guid": guid(11)},
+                            {"type": enum_type("SYMBOL"), "name": "Size=Large, State=Hover", "guid": guid(12), "children": [instance(1)]}
+                        ]
+                    }
+                ]
+            },
+            {
+                "type": enum_type("CANVAS"),
+                "name": "Screens",
+                "children": [{"type": enum_type("FRAME"), "children": [instance(11), instance(12), instance(12), instance(1)]}]
+            }
+        ]}})
+    }
+
+    #[test]
+    fn test_components() {
+        let catalog = catalog(&raw_output());
+
+        assert_eq!(catalog.components.len(), 1);
+        let icon = &catalog.components[0];
+        assert_eq!(icon.id, "1:1");
+        assert_eq!(icon.page, "Components");
+        assert_eq!(icon.description.as_deref(), Some("Generic icon"));
+        assert_eq!(icon.key.as_deref(), Some("abc"));
+        // One instance in a screen, one inside a variant of Button
+        assert_eq!(icon.instances, 2);
+        assert_eq!(
+            icon.properties,
+            [Property {
+                name: "Filled".to_string(),
+                kind: "BOOL".to_string(),
+                default: Some(json!(true)),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_component_sets() {
+        let catalog = catalog(&raw_output());
+        let button = &catalog.component_sets[0];
+
+        assert_eq!(button.name, "Button");
+        assert_eq!(button.instances, 3);
+        assert_eq!(button.variants[1].instances, 2);
+        assert_eq!(button.variants[1].values["Size"], "Large");
+        assert_eq!(
+            button.variant_properties,
+            [
+                VariantProperty {
+                    name: "State".to_string(),
+                    values: vec!["Default".to_string(), "Hover".to_string()],
+                },
+                VariantProperty {
+                    name: "Size".to_string(),
+                    values: vec!["Small".to_string(), "Large".to_string()],
+                },
+            ]
+        );
+        assert_eq!(button.properties[1].default, Some(json!("1:1")));
+    }
+
+    #[test]
+    fn test_serialized_catalog() {
+        let json = serde_json::to_value(catalog(&raw_output())).unwrap();
+
+        assert_eq!(
+            json["componentSets"][0]["variantProperties"][0]["name"],
+            "State"
+        );
+        assert_eq!(
+            json["componentSets"][0]["properties"][0],
+            json!({"name": "Label", "type": "TEXT", "default": "OK"})
+        );
+        assert!(json["componentSets"][0]["variants"][0]
+            .get("description")
+            .is_none());
+    }
+}
@@ -0,0 +1,194 @@
+//! CSS values
+//!
+//! Formatting of CSS lengths, numbers and colors, and the flexbox declarations
+//! of auto-layout frames. They are shared by the transformation passes that
+//! write CSS values (`gradient_to_css`, `effects_to_css`, `layout`) and by the
+//! renderers ([`crate::html`], [`crate::outline`], [`crate::tokens`], ...).
+
+use serde_json::{Map, Value as JsonValue};
+
+/// A CSS declaration: property and value
+pub type Declaration = (&'static str, String);
+
+/// Check if a node is a text node
+///
+/// Uses `type` when present and falls back to `textData`, since the standard
+/// pipeline removes node types.
+pub fn is_text(node: &Map<String, JsonValue>) -> bool {
+    match str_field(node, "type") {
+        Some(node_type) => node_type == "TEXT",
+        None => node.contains_key("textData"),
+    }
+}
+
+/// Flexbox declarations of an auto-layout frame
+pub(crate) fn layout_declarations(node: &Map<String, JsonValue>, css: &mut Vec<Declaration>) {
+    let Some(direction) = flex_direction(node) else {
+        return;
+    };
+    css.push(("display", "flex".to_string()));
+    css.push(("flex-direction", direction.to_string()));
+
+    if let Some(spacing) = node.get("stackSpacing").and_then(|s| s.as_f64()) {
+        css.push(("gap", px(spacing)));
+    }
+
+    if let Some(padding) = padding(node) {
+        css.push(("padding", padding));
+    }
+
+    if let Some(align) = str_field(node, "stackPrimaryAlignItems").and_then(align_value) {
+        css.push(("justify-content", align.to_string()));
+    }
+    if let Some(align) = str_field(node, "stackCounterAlignItems").and_then(align_value) {
+        css.push(("align-items", align.to_string()));
+    }
+}
+
+/// Padding shorthand of an auto-layout frame
+pub(crate) fn padding(node: &Map<String, JsonValue>) -> Option<String> {
+    let (top, right, bottom, left) = padding_sides(node)?;
+    Some(box_shorthand(top, right, bottom, left))
+}
+
+/// Top, right, bottom and left padding of an auto-layout frame
+///
+/// The right and bottom paddings default to the left and top ones.
+pub(crate) fn padding_sides(node: &Map<String, JsonValue>) -> Option<(f64, f64, f64, f64)> {
+    let horizontal = node.get("stackHorizontalPadding").and_then(|p| p.as_f64());
+    let vertical = node.get("stackVerticalPadding").and_then(|p| p.as_f64());
+    if horizontal.is_none() && vertical.is_none() {
+        return None;
+    }
+
+    let left = horizontal.unwrap_or(0.0);
+    let top = vertical.unwrap_or(0.0);
+    let right = node
+        .get("stackPaddingRight")
+        .and_then(|p| p.as_f64())
+        .unwrap_or(left);
+    let bottom = node
+        .get("stackPaddingBottom")
+        .and_then(|p| p.as_f64())
+        .unwrap_or(top);
+    Some((top, right, bottom, left))
+}
+
+pub(crate) fn flex_direction(node: &Map<String, JsonValue>) -> Option<&'static str> {
+    match str_field(node, "stackMode")? {
+        "HORIZONTAL" => Some("row"),
+        "VERTICAL" => Some("column"),
+        _ => None,
+    }
+}
+
+fn align_value(align: &str) -> Option<&'static str> {
+    match align {
+        "MIN" => Some("flex-start"),
+        "CENTER" => Some("center"),
+        "MAX" => Some("flex-end"),
+        "SPACE_BETWEEN" => Some("space-between"),
+        "SPACE_EVENLY" => Some("space-evenly"),
+        "BASELINE" => Some("baseline"),
+        _ => None,
+    }
+}
+
+/// Apply an opacity to a `#rrggbb` or `#rrggbbaa` color
+pub fn color_with_opacity(color: &str, opacity: f64) -> String {
+    if (opacity - 1.0).abs() < 1e-6 {
+        return color.to_string();
+    }
+
+    let hex = color.trim_start_matches('#');
+    let alpha = match hex.len() {
+        6 => 1.0,
+        8 => u8::from_str_radix(&hex[6..8], 16).map_or(1.0, |a| a as f64 / 255.0),
+        _ => return color.to_string(),
+    };
+    let alpha = ((alpha * opacity).clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{}{:02x}", &hex[..6], alpha)
+}
+
+/// Format a length in pixels
+pub fn px(value: f64) -> String {
+    if value == 0.0 {
+        "0".to_string()
+    } else {
+        format!("{}px", format_number(value))
+    }
+}
+
+/// Format a number with at most two decimals and no trailing zeros
+pub fn format_number(value: f64) -> String {
+    format_decimals(value, 2)
+}
+
+/// Format a number with at most `decimals` decimals and no trailing zeros
+pub fn format_decimals(value: f64, decimals: usize) -> String {
+    let scale = 10f64.powi(decimals as i32);
+    let rounded = (value * scale).round() / scale;
+    // Avoid "-0"
+    let rounded = if rounded == 0.0 { 0.0 } else { rounded };
+    let text = format!("{:.*}", decimals, rounded);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// CSS shorthand for four sides
+fn box_shorthand(top: f64, right: f64, bottom: f64, left: f64) -> String {
+    if top == bottom && left == right {
+        if top == left {
+            px(top)
+        } else {
+            format!("{} {}", px(top), px(left))
+        }
+    } else {
+        format!("{} {} {} {}", px(top), px(right), px(bottom), px(left))
+    }
+}
+
+pub(crate) fn str_field<'a>(node: &'a Map<String, JsonValue>, key: &str) -> Option<&'a str> {
+    node.get(key)?.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_number_helpers() {
+        assert_eq!(px(0.0), "0");
+        assert_eq!(px(12.345), "12.35px");
+        assert_eq!(format_number(-0.001), "0");
+        assert_eq!(format_decimals(12.3456, 3), "12.346");
+        assert_eq!(format_decimals(2.0, 3), "2");
+        assert_eq!(color_with_opacity("#ff000080", 0.5), "#ff000040");
+    }
+
+    #[test]
+    fn test_align_values() {
+        assert_eq!(align_value("SPACE_BETWEEN"), Some("space-between"));
+        assert_eq!(align_value("SPACE_EVENLY"), Some("space-evenly"));
+        assert_eq!(align_value("STRETCH"), None);
+    }
+
+    #[test]
+    fn test_padding() {
+        let frame = |value: serde_json::Value| value.as_object().unwrap().clone();
+
+        assert_eq!(padding(&frame(json!({}))), None);
+        assert_eq!(
+            padding(&frame(
+                json!({"stackHorizontalPadding": 16.0, "stackVerticalPadding": 16.0})
+            )),
+            Some("16px".to_string())
+        );
+        assert_eq!(
+            padding(&frame(
+                json!({"stackHorizontalPadding": 16.0, "stackPaddingBottom": 4.0})
+            )),
+            Some("0 16px 4px 16px".to_string())
+        );
+    }
+}
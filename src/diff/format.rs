@@ -0,0 +1,179 @@
+//! Human-readable diff
+//!
+//! One block per node, prefixed with `+` (added), `-` (removed) or `~`
+//! (changed), followed by a summary line:
+//!
+//! ```text
+//! ~ Page / Footer / Logo (RECTANGLE)
+//!     reparented: Page / Header → Page / Footer
+//!     moved: (0, 0) → (24, 0)
+//! ~ Page / Footer (FRAME)
+//!     stackSpacing: 8 → 16
+//!
+//! 0 added, 0 removed, 1 moved, 1 reparented, 1 modified
+//! ```
+
+use super::{ChangeKind, Diff, NodeDiff, Point};
+use crate::css::format_number;
+use serde_json::Value as JsonValue;
+use std::fmt::Write;
+
+/// Longest value shown before it is shortened with `…`
+const MAX_VALUE_LENGTH: usize = 80;
+
+/// Render a diff as text
+///
+/// # Arguments
+/// * `diff` - Result of [`diff`](super::diff)
+///
+/// # Returns
+/// One block per node and a summary line, or `No changes` and a newline
+pub fn to_text(diff: &Diff) -> String {
+    if diff.nodes.is_empty() {
+        return "No changes\n".to_string();
+    }
+
+    let mut text = String::new();
+    for node in &diff.nodes {
+        write_node(&mut text, node);
+    }
+    let summary = &diff.summary;
+    let _ = writeln!(
+        text,
+        "\n{} added, {} removed, {} moved, {} reparented, {} modified",
+        summary.added, summary.removed, summary.moved, summary.reparented, summary.modified
+    );
+    text
+}
+
+fn write_node(text: &mut String, node: &NodeDiff) {
+    let marker = match node.change {
+        ChangeKind::Added => "+",
+        ChangeKind::Removed => "-",
+        ChangeKind::Changed => "~",
+    };
+    let mut path = node.path.clone();
+    path.push(node.name.clone());
+    let _ = write!(text, "{} {}", marker, path.join(" / "));
+    if let Some(node_type) = &node.node_type {
+        let _ = write!(text, " ({})", node_type);
+    }
+    text.push('\n');
+
+    if let Some(reparented) = &node.reparented {
+        let _ = writeln!(
+            text,
+            "    reparented: {} → {}",
+            reparented.from.join(" / "),
+            reparented.to.join(" / ")
+        );
+    }
+    if let Some(moved) = &node.moved {
+        let _ = writeln!(
+            text,
+            "    moved: {} → {}",
+            point(&moved.from),
+            point(&moved.to)
+        );
+    }
+    for change in &node.properties {
+        let _ = writeln!(
+            text,
+            "    {}: {} → {}",
+            change.property,
+            value(change.old.as_ref()),
+            value(change.new.as_ref())
+        );
+    }
+}
+
+fn point(point: &Point) -> String {
+    format!("({}, {})", format_number(point.x), format_number(point.y))
+}
+
+/// Compact JSON of a value, shortened when long
+fn value(value: Option<&JsonValue>) -> String {
+    let text = match value {
+        None => return "(none)".to_string(),
+        Some(JsonValue::Number(number)) => format_number(number.as_f64().unwrap_or_default()),
+        Some(value) => value.to_string(),
+    };
+    if text.chars().count() > MAX_VALUE_LENGTH {
+        let shortened: String = text.chars().take(MAX_VALUE_LENGTH - 1).collect();
+        format!("{}…", shortened)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Move, PropertyChange, Reparent, Summary};
+    use super::*;
+    use serde_json::json;
+
+    fn node(name: &str, change: ChangeKind) -> NodeDiff {
+        NodeDiff {
+            id: "1:2".to_string(),
+            name: name.to_string(),
+            node_type: Some("FRAME".to_string()),
+            path: vec!["Page".to_string()],
+            change,
+            reparented: None,
+            moved: None,
+            properties: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_to_text() {
+        let mut changed = node("Logo", ChangeKind::Changed);
+        changed.reparented = Some(Reparent {
+            from: vec!["Page".into(), "Header".into()],
+            to: vec!["Page".into()],
+        });
+        changed.moved = Some(Move {
+            from: Point { x: 0.0, y: 0.0 },
+            to: Point { x: 24.5, y: 0.0 },
+        });
+        changed.properties.push(PropertyChange {
+            property: "textData.characters".to_string(),
+            old: Some(json!("Sign up")),
+            new: None,
+        });
+        let diff = Diff {
+            summary: Summary {
+                added: 1,
+                removed: 1,
+                moved: 1,
+                reparented: 1,
+                modified: 1,
+            },
+            nodes: vec![
+                node("Nav", ChangeKind::Added),
+                node("Banner", ChangeKind::Removed),
+                changed,
+            ],
+        };
+
+        assert_eq!(
+            to_text(&diff),
+            "+ Page / Nav (FRAME)\n\
+             - Page / Banner (FRAME)\n\
+             ~ Page / Logo (FRAME)\n    \
+             reparented: Page / Header → Page\n    \
+             moved: (0, 0) → (24.5, 0)\n    \
+             textData.characters: \"Sign up\" → (none)\n\
+             \n1 added, 1 removed, 1 moved, 1 reparented, 1 modified\n"
+        );
+    }
+
+    #[test]
+    fn test_long_values_are_shortened() {
+        let long = json!("x".repeat(200));
+
+        assert_eq!(value(Some(&long)).chars().count(), MAX_VALUE_LENGTH);
+        assert_eq!(value(Some(&json!(16.0))), "16");
+        assert_eq!(to_text(&Diff::default()), "No changes\n");
+    }
+}
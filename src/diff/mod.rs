@@ -0,0 +1,515 @@
+//! Structural diff
+//!
+//! [`diff`] compares two converted files node by node. Nodes are matched by
+//! the GUID `build_tree` gives them; nodes inside component instances, whose
+//! GUIDs are the ones of the main component's nodes, are matched by the GUID
+//! path through their instances (`1:2/3:4`).
+//!
+//! Each node that differs is reported once, as:
+//!
+//! - added or removed (only the topmost node of an added or removed subtree)
+//! - changed, with any of: a new parent, a new position (`transform` x/y),
+//!   and the properties that differ, by dotted path (`textData.characters`,
+//!   `size.x`, `fillPaints`, `stackSpacing`, ...)
+//!
+//! [`format::to_text`] renders the result for humans; [`Diff`] serializes to
+//! JSON.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::diff::{diff, format::to_text, pipeline};
+//!
+//! let old = std::fs::read("old.fig").unwrap();
+//! let new = std::fs::read("new.fig").unwrap();
+//! let old = fig2json::convert_with_pipeline(&old, None, &pipeline()).unwrap();
+//! let new = fig2json::convert_with_pipeline(&new, None, &pipeline()).unwrap();
+//!
+//! print!("{}", to_text(&diff(&old, &new)));
+//! ```
+
+pub mod format;
+
+use crate::pipeline::Pipeline;
+use crate::schema::tree::{guid_key, node_type};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Passes of the standard pipeline removing the fields nodes are matched on or
+/// the auto-layout properties, or renaming the image files next to the inputs
+const DISABLED_PASSES: &[&str] = &[
+    "image_hash",
+    "guid_removal",
+    "type_removal",
+    "redundant_padding_removal",
+    "stack_child_properties_removal",
+    "stack_sizing_properties_removal",
+    "stack_align_items_removal",
+];
+
+/// Top-level node fields that are not compared as properties
+const IGNORED_PROPERTIES: &[&str] = &["children", "guid"];
+
+/// Standard pipeline keeping the node GUIDs, types and auto-layout properties,
+/// without touching the image files
+pub fn pipeline() -> Pipeline {
+    Pipeline::standard_without(DISABLED_PASSES)
+}
+
+/// Differences between two files
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct Diff {
+    pub summary: Summary,
+    /// Changed nodes: added and changed nodes in the order of the new file,
+    /// then removed nodes in the order of the old file
+    pub nodes: Vec<NodeDiff>,
+}
+
+/// Number of nodes per kind of change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct Summary {
+    pub added: usize,
+    pub removed: usize,
+    pub moved: usize,
+    pub reparented: usize,
+    /// Nodes with property changes
+    pub modified: usize,
+}
+
+/// A node that differs between the two files
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeDiff {
+    /// GUID, or GUID path through instances
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<String>,
+    /// Names of the ancestors, from the page (in the new file unless removed)
+    pub path: Vec<String>,
+    pub change: ChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reparented: Option<Reparent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moved: Option<Move>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<PropertyChange>,
+}
+
+/// Whether a node was added, removed or changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Ancestor names of the old and new parents
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Reparent {
+    pub from: Vec<String>,
+    pub to: Vec<String>,
+}
+
+/// Old and new position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Move {
+    pub from: Point,
+    pub to: Point,
+}
+
+/// Position relative to the parent
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A property that differs, `None` where it is absent
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PropertyChange {
+    /// Dotted path of the property
+    pub property: String,
+    pub old: Option<JsonValue>,
+    pub new: Option<JsonValue>,
+}
+
+/// Compare two converted files
+///
+/// Both outputs should come from [`pipeline`] (or at least keep the `guid`
+/// fields), so that nodes can be matched.
+///
+/// # Arguments
+/// * `old` - Output for the old version of the file
+/// * `new` - Output for the new version of the file
+///
+/// # Returns
+/// The nodes that differ, with a summary
+///
+/// # Examples
+/// ```
+/// use fig2json::diff::{diff, ChangeKind};
+/// use serde_json::json;
+///
+/// let old = json!({"document": {"guid": {"sessionID": 0, "localID": 0}, "children": [
+///     {"name": "Title", "guid": {"sessionID": 1, "localID": 2}, "textData": {"characters": "Sign up"}}
+/// ]}});
+/// let new = json!({"document": {"guid": {"sessionID": 0, "localID": 0}, "children": [
+///     {"name": "Title", "guid": {"sessionID": 1, "localID": 2}, "textData": {"characters": "Join"}}
+/// ]}});
+/// let diff = diff(&old, &new);
+///
+/// assert_eq!(diff.nodes[0].change, ChangeKind::Changed);
+/// assert_eq!(diff.nodes[0].properties[0].property, "textData.characters");
+/// assert_eq!(diff.nodes[0].properties[0].new, Some(json!("Join")));
+/// ```
+pub fn diff(old: &JsonValue, new: &JsonValue) -> Diff {
+    let old = NodeIndex::build(old);
+    let new = NodeIndex::build(new);
+    let mut result = Diff::default();
+
+    for node in &new.nodes {
+        match old.get(&node.id) {
+            None => {
+                if node.parent.as_ref().is_some_and(|p| old.get(p).is_none()) {
+                    continue;
+                }
+                result.summary.added += 1;
+                result.nodes.push(node.report(ChangeKind::Added));
+            }
+            Some(previous) => {
+                let mut report = node.report(ChangeKind::Changed);
+                if previous.parent != node.parent {
+                    report.reparented = Some(Reparent {
+                        from: previous.path.clone(),
+                        to: node.path.clone(),
+                    });
+                    result.summary.reparented += 1;
+                }
+                let (from, to) = (position(previous.map), position(node.map));
+                if from != to {
+                    report.moved = Some(Move { from, to });
+                    result.summary.moved += 1;
+                }
+                diff_properties("", previous.map, node.map, &mut report.properties);
+                if !report.properties.is_empty() {
+                    result.summary.modified += 1;
+                }
+                if report.reparented.is_some()
+                    || report.moved.is_some()
+                    || !report.properties.is_empty()
+                {
+                    result.nodes.push(report);
+                }
+            }
+        }
+    }
+
+    for node in &old.nodes {
+        if new.get(&node.id).is_some() || node.parent.as_ref().is_some_and(|p| new.get(p).is_none())
+        {
+            continue;
+        }
+        result.summary.removed += 1;
+        result.nodes.push(node.report(ChangeKind::Removed));
+    }
+
+    result
+}
+
+/// Nodes of a tree by id, in document order
+struct NodeIndex<'a> {
+    nodes: Vec<IndexedNode<'a>>,
+    by_id: HashMap<String, usize>,
+}
+
+struct IndexedNode<'a> {
+    id: String,
+    parent: Option<String>,
+    /// Ancestor names, from the page
+    path: Vec<String>,
+    map: &'a Map<String, JsonValue>,
+}
+
+impl<'a> NodeIndex<'a> {
+    fn build(output: &'a JsonValue) -> Self {
+        let mut index = Self {
+            nodes: Vec::new(),
+            by_id: HashMap::new(),
+        };
+        if let Some(root) = output.get("document").unwrap_or(output).as_object() {
+            index.add(root, "", None, None);
+        }
+        index
+    }
+
+    /// Index a node and its descendants
+    ///
+    /// `prefix` is the GUID path of the enclosing instances, `path` the
+    /// ancestor names (`None` for the document node, which has no place in
+    /// paths).
+    fn add(
+        &mut self,
+        map: &'a Map<String, JsonValue>,
+        prefix: &str,
+        parent: Option<&str>,
+        path: Option<&[String]>,
+    ) {
+        let id = map
+            .get("guid")
+            .and_then(guid_key)
+            .map(|guid| format!("{}{}", prefix, guid));
+        if let Some(id) = &id {
+            if !self.by_id.contains_key(id) {
+                self.by_id.insert(id.clone(), self.nodes.len());
+                self.nodes.push(IndexedNode {
+                    id: id.clone(),
+                    parent: parent.map(str::to_string),
+                    path: path.map(<[String]>::to_vec).unwrap_or_default(),
+                    map,
+                });
+            }
+        }
+
+        let Some(JsonValue::Array(children)) = map.get("children") else {
+            return;
+        };
+        let prefix = match (&id, node_type(map)) {
+            (Some(id), Some("INSTANCE")) => format!("{}/", id),
+            _ => prefix.to_string(),
+        };
+        let parent = id.as_deref().or(parent);
+        let child_path = path.map(|path| {
+            let mut path = path.to_vec();
+            path.push(node_name(map).to_string());
+            path
+        });
+        for child in children.iter().filter_map(|c| c.as_object()) {
+            self.add(
+                child,
+                &prefix,
+                parent,
+                Some(child_path.as_deref().unwrap_or_default()),
+            );
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<&IndexedNode<'a>> {
+        self.by_id.get(id).map(|&i| &self.nodes[i])
+    }
+}
+
+impl IndexedNode<'_> {
+    fn report(&self, change: ChangeKind) -> NodeDiff {
+        NodeDiff {
+            id: self.id.clone(),
+            name: node_name(self.map).to_string(),
+            node_type: node_type(self.map).map(str::to_string),
+            path: self.path.clone(),
+            change,
+            reparented: None,
+            moved: None,
+            properties: Vec::new(),
+        }
+    }
+}
+
+/// Collect the properties that differ between two objects, recursing into
+/// nested objects
+fn diff_properties(
+    prefix: &str,
+    old: &Map<String, JsonValue>,
+    new: &Map<String, JsonValue>,
+    changes: &mut Vec<PropertyChange>,
+) {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    for key in keys {
+        let ignored = match prefix {
+            "" => IGNORED_PROPERTIES.contains(&key.as_str()),
+            // Positions are reported as moves
+            "transform." => key == "x" || key == "y",
+            _ => false,
+        };
+        if ignored {
+            continue;
+        }
+
+        let property = format!("{}{}", prefix, key);
+        match (old.get(key), new.get(key)) {
+            (Some(JsonValue::Object(old)), Some(JsonValue::Object(new))) => {
+                diff_properties(&format!("{}.", property), old, new, changes);
+            }
+            (old, new) if old != new => changes.push(PropertyChange {
+                property,
+                old: old.cloned(),
+                new: new.cloned(),
+            }),
+            _ => {}
+        }
+    }
+}
+
+fn position(map: &Map<String, JsonValue>) -> Point {
+    let coordinate = |key: &str| {
+        map.get("transform")
+            .and_then(|t| t.get(key))
+            .and_then(|v| v.as_f64())
+            .unwrap_or_default()
+    };
+    Point {
+        x: coordinate("x"),
+        y: coordinate("y"),
+    }
+}
+
+fn node_name(map: &Map<String, JsonValue>) -> &str {
+    map.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn guid(local_id: u64) -> JsonValue {
+        json!({"sessionID": 1, "localID": local_id})
+    }
+
+    fn old() -> JsonValue {
+        json!({"document": {"guid": guid(0), "name": "Document", "children": [{
+            "guid": guid(1), "name": "Page", "type": "CANVAS", "children": [
+                {"guid": guid(2), "name": "Header", "type": "FRAME", "children": [
+                    {"guid": guid(3), "name": "Logo", "type": "RECTANGLE", "transform": {"x": 0.0, "y": 0.0, "rotation": 0.0}},
+                    {"guid": guid(4), "name": "Banner", "type": "FRAME", "children": [
+                        {"guid": guid(5), "name": "Promo", "type": "TEXT"}
+                    ]}
+                ]},
+                {"guid": guid(6), "name": "Footer", "type": "FRAME", "stackSpacing": 8.0},
+                {"guid": guid(7), "name": "Button", "type": "INSTANCE", "children": [
+                    {"guid": guid(20), "name": "Label", "type": "TEXT", "textData": {"characters": "OK"}}
+                ]}
+            ]
+        }]}})
+    }
+
+    fn new() -> JsonValue {
+        json!({"document": {"guid": guid(0), "name": "Document", "children": [{
+            "guid": guid(1), "name": "Page", "type": "CANVAS", "children": [
+                {"guid": guid(2), "name": "Header", "type": "FRAME", "children": [
+                    {"guid": guid(8), "name": "Nav", "type": "FRAME", "children": [
+                        {"guid": guid(9), "name": "Link", "type": "TEXT"}
+                    ]}
+                ]},
+                {"guid": guid(6), "name": "Footer", "type": "FRAME", "stackSpacing": 16.0, "children": [
+                    {"guid": guid(3), "name": "Logo", "type": "RECTANGLE", "transform": {"x": 24.0, "y": 0.0, "rotation": 0.0}}
+                ]},
+                {"guid": guid(7), "name": "Button", "type": "INSTANCE", "children": [
+                    {"guid": guid(20), "name": "Label", "type": "TEXT", "textData": {"characters": "Cancel"}}
+                ]}
+            ]
+        }]}})
+    }
+
+    #[test]
+    fn test_added_and_removed_subtrees() {
+        let diff = diff(&old(), &new());
+
+        let added: Vec<&str> = diff
+            .nodes
+            .iter()
+            .filter(|n| n.change == ChangeKind::Added)
+            .map(|n| n.name.as_str())
+            .collect();
+        let removed: Vec<&str> = diff
+            .nodes
+            .iter()
+            .filter(|n| n.change == ChangeKind::Removed)
+            .map(|n| n.name.as_str())
+            .collect();
+        // Only the topmost nodes of added and removed subtrees
+        assert_eq!(added, ["Nav"]);
+        assert_eq!(removed, ["Banner"]);
+        assert_eq!(diff.nodes.last().unwrap().path, ["Page", "Header"]);
+    }
+
+    #[test]
+    fn test_reparented_and_moved() {
+        let diff = diff(&old(), &new());
+        let logo = diff.nodes.iter().find(|n| n.name == "Logo").unwrap();
+
+        assert_eq!(logo.change, ChangeKind::Changed);
+        assert_eq!(
+            logo.reparented,
+            Some(Reparent {
+                from: vec!["Page".into(), "Header".into()],
+                to: vec!["Page".into(), "Footer".into()],
+            })
+        );
+        assert_eq!(logo.moved.unwrap().to, Point { x: 24.0, y: 0.0 });
+        // x and y are reported as a move, not as property changes
+        assert!(logo.properties.is_empty());
+    }
+
+    #[test]
+    fn test_property_changes() {
+        let diff = diff(&old(), &new());
+
+        let footer = diff.nodes.iter().find(|n| n.name == "Footer").unwrap();
+        assert_eq!(
+            footer.properties,
+            [PropertyChange {
+                property: "stackSpacing".to_string(),
+                old: Some(json!(8.0)),
+                new: Some(json!(16.0)),
+            }]
+        );
+        // Nodes of instances are matched through the instance GUID
+        let label = diff.nodes.iter().find(|n| n.name == "Label").unwrap();
+        assert_eq!(label.id, "1:7/1:20");
+        assert_eq!(label.properties[0].property, "textData.characters");
+    }
+
+    #[test]
+    fn test_summary() {
+        let diff = diff(&old(), &new());
+
+        assert_eq!(
+            diff.summary,
+            Summary {
+                added: 1,
+                removed: 1,
+                moved: 1,
+                reparented: 1,
+                modified: 2,
+            }
+        );
+        assert_eq!(super::diff(&old(), &old()), Diff::default());
+    }
+
+    #[test]
+    fn test_pipeline_leaves_image_files_alone() {
+        let temp_dir = std::env::temp_dir().join("fig2json_test_diff_images");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let image = temp_dir.join("images").join("6049a17a");
+        std::fs::create_dir_all(image.parent().unwrap()).unwrap();
+        std::fs::write(&image, [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]).unwrap();
+
+        let mut output = json!({"document": {"children": [{
+            "guid": guid(1), "name": "Page", "type": "CANVAS",
+            "children": [{"guid": guid(2), "name": "Photo", "fillPaints": [{"image": {"hash": [96, 73, 161, 122]}}]}]
+        }]}});
+        pipeline().run(&mut output, &crate::PassContext::new(&temp_dir)).unwrap();
+
+        let mut files: Vec<_> = std::fs::read_dir(temp_dir.join("images"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        files.sort();
+        assert_eq!(files, ["6049a17a"]);
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+}
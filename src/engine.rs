@@ -0,0 +1,254 @@
+//! Single-traversal execution of transformation passes
+//!
+//! Running every pass of a [`Pipeline`](crate::pipeline::Pipeline) as its own
+//! full tree walk is simple but slow on large files. Most passes only look at
+//! one object at a time, so the engine groups consecutive passes into a single
+//! depth-first traversal that calls each pass' per-node hooks in pipeline
+//! order.
+//!
+//! Whether a pass can share a traversal is declared by [`Transform::fusion`]:
+//!
+//! - [`Fusion::None`]: the pass only provides [`Transform::apply`] and runs alone
+//! - [`Fusion::Local`]: the hooks only depend on the visited node itself, so the
+//!   pass can join the current group
+//! - [`Fusion::Barrier`]: the hooks inspect nested values that earlier passes may
+//!   still change, so the pass starts a new group
+//! - [`Fusion::PostOrder`]: the pass uses leave hooks and closes its group
+//!
+//! Fused runs produce the same output as running the passes one after another
+//! (see [`Pipeline::run_unfused`](crate::pipeline::Pipeline::run_unfused)).
+
+use crate::error::Result;
+use crate::pipeline::{PassContext, PassTarget, Transform};
+use serde_json::Value as JsonValue;
+
+/// How a pass can be combined with its neighbours in a single traversal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fusion {
+    /// Run the pass on its own through [`Transform::apply`]
+    None,
+    /// Enter hooks only read and write the visited node (its keys, scalar values
+    /// or the array elements being filtered)
+    Local,
+    /// Enter hooks read nested values, so every earlier pass must have completed
+    Barrier,
+    /// Leave hooks run once the children have been visited; no pass may follow
+    /// in the same traversal
+    PostOrder,
+}
+
+/// Position of the node being visited by a fused traversal
+#[derive(Debug, Clone, Copy)]
+pub struct VisitContext<'a> {
+    /// Number of containers above the node (0 for the value the pass targets)
+    pub depth: usize,
+    /// Context of the pipeline run
+    pub pass: &'a PassContext,
+}
+
+/// Split enabled passes into groups sharing a single traversal
+///
+/// Only consecutive passes with the same [`PassTarget`] are grouped. Passes
+/// with [`Fusion::None`] always end up alone in their group.
+///
+/// # Arguments
+/// * `passes` - Enabled passes in execution order
+///
+/// # Returns
+/// * Groups of passes, in execution order
+pub(crate) fn plan<'p>(passes: &[&'p dyn Transform]) -> Vec<Vec<&'p dyn Transform>> {
+    let mut groups: Vec<Vec<&dyn Transform>> = Vec::new();
+    let mut open = false;
+
+    for &pass in passes {
+        let joins = open
+            && matches!(pass.fusion(), Fusion::Local | Fusion::PostOrder)
+            && groups
+                .last()
+                .and_then(|group| group.last())
+                .is_some_and(|last| last.target() == pass.target());
+
+        if joins {
+            groups.last_mut().unwrap().push(pass);
+        } else {
+            groups.push(vec![pass]);
+        }
+
+        open = matches!(pass.fusion(), Fusion::Local | Fusion::Barrier);
+    }
+
+    groups
+}
+
+/// Run a group of passes on a value
+///
+/// A group with a single pass uses its [`Transform::apply`], larger groups
+/// share one depth-first traversal.
+pub(crate) fn run_group(group: &[&dyn Transform], tree: &mut JsonValue, ctx: &PassContext) -> Result<()> {
+    match group {
+        [pass] => pass.apply(tree, ctx),
+        _ => visit(tree, group, 0, ctx),
+    }
+}
+
+/// Fused depth-first traversal
+fn visit(value: &mut JsonValue, group: &[&dyn Transform], depth: usize, ctx: &PassContext) -> Result<()> {
+    let cx = VisitContext { depth, pass: ctx };
+
+    match value {
+        JsonValue::Object(map) => {
+            for pass in group {
+                pass.enter_object(map, &cx)?;
+            }
+            for val in map.values_mut() {
+                visit(val, group, depth + 1, ctx)?;
+            }
+            for pass in group {
+                pass.leave_object(map, &cx)?;
+            }
+        }
+        JsonValue::Array(arr) => {
+            for pass in group {
+                pass.enter_array(arr, &cx)?;
+            }
+            for val in arr.iter_mut() {
+                visit(val, group, depth + 1, ctx)?;
+            }
+            for pass in group {
+                pass.leave_array(arr, &cx)?;
+            }
+        }
+        _ => {
+            // Primitives - nothing to do
+        }
+    }
+
+    Ok(())
+}
+
+/// Select the value a pass targets inside a conversion output
+pub(crate) fn target_value(output: &mut JsonValue, target: PassTarget) -> Option<&mut JsonValue> {
+    match target {
+        PassTarget::Document => output.get_mut("document"),
+        PassTarget::Root => Some(output),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Map};
+
+    struct Hooked {
+        name: &'static str,
+        fusion: Fusion,
+        target: PassTarget,
+    }
+
+    impl Transform for Hooked {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn target(&self) -> PassTarget {
+            self.target
+        }
+
+        fn fusion(&self) -> Fusion {
+            self.fusion
+        }
+
+        fn apply(&self, _tree: &mut JsonValue, _ctx: &PassContext) -> Result<()> {
+            Ok(())
+        }
+
+        fn enter_object(&self, map: &mut Map<String, JsonValue>, cx: &VisitContext) -> Result<()> {
+            if self.fusion != Fusion::PostOrder {
+                let seen = format!("{}@{}", self.name, cx.depth);
+                map.entry("trace")
+                    .or_insert_with(|| json!([]))
+                    .as_array_mut()
+                    .unwrap()
+                    .push(json!(seen));
+            }
+            Ok(())
+        }
+
+        fn leave_object(&self, map: &mut Map<String, JsonValue>, _cx: &VisitContext) -> Result<()> {
+            if self.fusion == Fusion::PostOrder {
+                map.remove("child");
+            }
+            Ok(())
+        }
+    }
+
+    fn pass(name: &'static str, fusion: Fusion, target: PassTarget) -> Hooked {
+        Hooked { name, fusion, target }
+    }
+
+    fn group_names(passes: &[Hooked]) -> Vec<Vec<String>> {
+        let refs: Vec<&dyn Transform> = passes.iter().map(|p| p as &dyn Transform).collect();
+        plan(&refs)
+            .iter()
+            .map(|group| group.iter().map(|p| p.name().to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_groups_local_passes() {
+        use PassTarget::Document;
+        let passes = [
+            pass("a", Fusion::Local, Document),
+            pass("b", Fusion::Local, Document),
+            pass("c", Fusion::Barrier, Document),
+            pass("d", Fusion::Local, Document),
+            pass("e", Fusion::PostOrder, Document),
+            pass("f", Fusion::Local, Document),
+        ];
+
+        assert_eq!(
+            group_names(&passes),
+            vec![vec!["a", "b"], vec!["c", "d", "e"], vec!["f"]]
+        );
+    }
+
+    #[test]
+    fn test_plan_isolates_unfusable_passes() {
+        use PassTarget::{Document, Root};
+        let passes = [
+            pass("a", Fusion::Local, Document),
+            pass("b", Fusion::None, Document),
+            pass("c", Fusion::Local, Document),
+            pass("d", Fusion::Local, Root),
+            pass("e", Fusion::PostOrder, Root),
+            pass("f", Fusion::PostOrder, Root),
+        ];
+
+        assert_eq!(
+            group_names(&passes),
+            vec![vec!["a"], vec!["b"], vec!["c"], vec!["d", "e"], vec!["f"]]
+        );
+    }
+
+    #[test]
+    fn test_visit_calls_hooks_in_pass_order() {
+        use PassTarget::Document;
+        let passes = [
+            pass("a", Fusion::Local, Document),
+            pass("b", Fusion::Local, Document),
+            pass("c", Fusion::PostOrder, Document),
+        ];
+        let group: Vec<&dyn Transform> = passes.iter().map(|p| p as &dyn Transform).collect();
+
+        let mut tree = json!({"items": [{"child": {}}]});
+        run_group(&group, &mut tree, &PassContext::default()).unwrap();
+
+        assert_eq!(
+            tree,
+            json!({
+                "trace": ["a@0", "b@0"],
+                "items": [{"trace": ["a@2", "b@2"]}]
+            })
+        );
+    }
+}
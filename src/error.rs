@@ -24,6 +24,30 @@ pub enum FigError {
     #[error("Canvas file not found in ZIP archive")]
     CanvasNotFoundInZip,
 
+    #[error("Unknown transformation pass: {0}")]
+    UnknownPass(String),
+
+    #[error("Transformation pass already registered: {0}")]
+    DuplicatePass(String),
+
+    #[error("Transformation pass {pass} must run after {dependency}")]
+    PassOrder { pass: String, dependency: String },
+
+    #[error("Kiwi encoding failed at {path}: {message}")]
+    EncodeError { path: String, message: String },
+
+    #[error("Invalid Tailwind theme: {0}")]
+    InvalidTheme(String),
+
+    #[error("No node matches the selection: {0}")]
+    EmptySelection(String),
+
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -0,0 +1,520 @@
+use crate::css::{
+    color_with_opacity, flex_direction, format_number, is_text, layout_declarations, px, str_field,
+    Declaration,
+};
+use crate::schema::transformations::layout::CSS_PROPERTIES;
+use serde_json::{Map, Value as JsonValue};
+
+/// How a node is placed inside its parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// Top-level frame of a page
+    Root,
+    /// Child of an auto-layout frame, laid out by flexbox
+    Flow,
+    /// Positioned with `left`/`top` from its transform
+    Absolute,
+}
+
+impl Placement {
+    /// Placement of the children of `node`
+    pub fn of_children(node: &Map<String, JsonValue>) -> Self {
+        let display = layout_block(node).and_then(|css| str_field(css, "display"));
+        if flex_direction(node).is_some() || matches!(display, Some("flex" | "grid")) {
+            Placement::Flow
+        } else {
+            Placement::Absolute
+        }
+    }
+
+    /// Placement of `node` inside a parent whose children are placed with `self`
+    ///
+    /// Auto-layout children with `stackPositioning: ABSOLUTE` opt out of the flow.
+    pub fn for_child(self, node: &Map<String, JsonValue>) -> Self {
+        let position = layout_block(node).and_then(|css| str_field(css, "position"));
+        let absolute = str_field(node, "stackPositioning") == Some("ABSOLUTE")
+            || position == Some("absolute");
+        if self == Placement::Flow && absolute {
+            Placement::Absolute
+        } else {
+            self
+        }
+    }
+}
+
+/// CSS declarations of a node of the transformed tree
+///
+/// # Arguments
+/// * `node` - Node object (output of [`convert`](crate::convert))
+/// * `placement` - How the node is placed inside its parent
+///
+/// # Returns
+/// Declarations in a fixed order: box, position, layout, fills, strokes, effects, text
+///
+/// # Examples
+/// ```
+/// use fig2json::html::css::{node_declarations, Placement};
+/// use serde_json::json;
+///
+/// let node = json!({
+///     "size": {"x": 100.0, "y": 40.0},
+///     "transform": {"x": 10.0, "y": 20.0},
+///     "fillPaints": [{"color": "#ff0000"}]
+/// });
+/// let declarations = node_declarations(node.as_object().unwrap(), Placement::Absolute);
+///
+/// assert!(declarations.contains(&("left", "10px".to_string())));
+/// assert!(declarations.contains(&("background-color", "#ff0000".to_string())));
+/// ```
+pub fn node_declarations(node: &Map<String, JsonValue>, placement: Placement) -> Vec<Declaration> {
+    let mut css = Vec::new();
+    let has_children = node
+        .get("children")
+        .and_then(|c| c.as_array())
+        .is_some_and(|c| !c.is_empty());
+
+    match placement {
+        Placement::Absolute => css.push(("position", "absolute".to_string())),
+        Placement::Root | Placement::Flow if has_children => {
+            css.push(("position", "relative".to_string()))
+        }
+        _ => {}
+    }
+
+    let transform = node.get("transform");
+    if placement == Placement::Absolute {
+        let x = transform.and_then(|t| number(t, "x")).unwrap_or(0.0);
+        let y = transform.and_then(|t| number(t, "y")).unwrap_or(0.0);
+        css.push(("left", px(x)));
+        css.push(("top", px(y)));
+    }
+
+    if let Some(size) = node.get("size") {
+        if let Some(width) = number(size, "x") {
+            css.push(("width", px(width)));
+        }
+        if let Some(height) = number(size, "y") {
+            css.push(("height", px(height)));
+        }
+    }
+    if placement == Placement::Flow {
+        css.push(("flex-shrink", "0".to_string()));
+    }
+
+    if placement != Placement::Root {
+        if let Some(rotation) = transform.and_then(|t| number(t, "rotation")) {
+            css.push(("transform", format!("rotate({}deg)", format_number(rotation))));
+            css.push(("transform-origin", "top left".to_string()));
+        }
+    }
+
+    layout_declarations(node, &mut css);
+    layout_block_declarations(node, &mut css);
+
+    if let Some(opacity) = node.get("opacity").and_then(|o| o.as_f64()) {
+        css.push(("opacity", format_number(opacity)));
+    }
+    if let Some(radius) = node.get("cornerRadius").and_then(|r| r.as_f64()) {
+        css.push(("border-radius", px(radius)));
+    }
+
+    let text = is_text(node);
+    fill_declarations(node, text, &mut css);
+    stroke_declarations(node, &mut css);
+    effect_declarations(node, &mut css);
+    if text {
+        text_declarations(node, &mut css);
+    }
+
+    css
+}
+
+/// Declarations of the `css` block of the `layout` pass, replacing the
+/// declarations of the same properties (sizes, positions)
+fn layout_block_declarations(node: &Map<String, JsonValue>, css: &mut Vec<Declaration>) {
+    let Some(block) = layout_block(node) else {
+        return;
+    };
+    for (property, value) in block {
+        let Some(property) = CSS_PROPERTIES.iter().find(|p| **p == property) else {
+            continue;
+        };
+        let Some(value) = value.as_str() else {
+            continue;
+        };
+        match css.iter_mut().find(|(p, _)| p == property) {
+            Some(declaration) => declaration.1 = value.to_string(),
+            None => css.push((property, value.to_string())),
+        }
+    }
+}
+
+/// `css` block written by the `layout` pass
+fn layout_block(node: &Map<String, JsonValue>) -> Option<&Map<String, JsonValue>> {
+    node.get("css")?.as_object()
+}
+
+/// Shadows and blurs from the `effects` object of the `effects_to_css` pass
+fn effect_declarations(node: &Map<String, JsonValue>, css: &mut Vec<Declaration>) {
+    let Some(effects) = node.get("effects").and_then(|e| e.as_object()) else {
+        return;
+    };
+    for (key, property) in [
+        ("boxShadow", "box-shadow"),
+        ("textShadow", "text-shadow"),
+        ("filter", "filter"),
+        ("backdropFilter", "backdrop-filter"),
+    ] {
+        if let Some(value) = str_field(effects, key) {
+            css.push((property, value.to_string()));
+        }
+    }
+}
+
+/// Backgrounds from `fillPaints` (text color for text nodes)
+///
+/// Figma lists paints bottom to top while CSS lists background layers top to
+/// bottom, so layers are reversed. Gradients are used when the
+/// `gradient_to_css` pass gave them a `css` string; other paints are skipped.
+fn fill_declarations(node: &Map<String, JsonValue>, text: bool, css: &mut Vec<Declaration>) {
+    let Some(paints) = node.get("fillPaints").and_then(|p| p.as_array()) else {
+        return;
+    };
+
+    if text {
+        if let Some(color) = paints.iter().rev().find_map(solid_color) {
+            css.push(("color", color));
+        }
+        return;
+    }
+
+    let mut layers: Vec<(String, Option<&'static str>)> = paints
+        .iter()
+        .filter_map(|paint| {
+            if let Some(color) = solid_color(paint) {
+                Some((color, None))
+            } else if let Some(gradient) = paint.get("css").and_then(|c| c.as_str()) {
+                // Gradients are added by the gradient_to_css pass
+                Some((gradient.to_string(), Some("auto")))
+            } else {
+                image_layer(paint).map(|(url, size)| (url, Some(size)))
+            }
+        })
+        .collect();
+
+    // A single solid color (or a solid color under images) maps to background-color
+    let base_color = match layers.first() {
+        Some((color, None)) => {
+            let color = color.clone();
+            layers.remove(0);
+            Some(color)
+        }
+        _ => None,
+    };
+
+    if !layers.is_empty() {
+        layers.reverse();
+        let images: Vec<String> = layers
+            .iter()
+            .map(|(layer, size)| match size {
+                Some(_) => layer.clone(),
+                None => format!("linear-gradient({}, {})", layer, layer),
+            })
+            .collect();
+        let sizes: Vec<&str> = layers.iter().map(|(_, size)| size.unwrap_or("auto")).collect();
+
+        css.push(("background-image", images.join(", ")));
+        if sizes.iter().any(|size| *size != "auto") {
+            css.push(("background-size", sizes.join(", ")));
+            css.push(("background-position", "center".to_string()));
+            css.push(("background-repeat", "no-repeat".to_string()));
+        }
+    }
+
+    if let Some(color) = base_color {
+        css.push(("background-color", color));
+    }
+}
+
+/// CSS color of a solid paint, with the paint opacity applied
+fn solid_color(paint: &JsonValue) -> Option<String> {
+    if str_field(paint.as_object()?, "type").is_some_and(|t| t != "SOLID") {
+        return None;
+    }
+    let color = paint.get("color")?.as_str()?;
+    let opacity = paint.get("opacity").and_then(|o| o.as_f64()).unwrap_or(1.0);
+    Some(color_with_opacity(color, opacity))
+}
+
+/// `url(...)` and `background-size` of an image paint
+fn image_layer(paint: &JsonValue) -> Option<(String, &'static str)> {
+    let filename = paint.get("image")?.get("filename")?.as_str()?;
+    let size = match paint.get("imageScaleMode").and_then(|m| m.as_str()) {
+        Some("FIT") => "contain",
+        Some("STRETCH") => "100% 100%",
+        Some("TILE") => "auto",
+        _ => "cover",
+    };
+    Some((format!("url(\"{}\")", filename), size))
+}
+
+/// Border from the first solid `strokePaints` entry and `strokeWeight`
+fn stroke_declarations(node: &Map<String, JsonValue>, css: &mut Vec<Declaration>) {
+    let color = node
+        .get("strokePaints")
+        .and_then(|p| p.as_array())
+        .and_then(|paints| paints.iter().find_map(solid_color));
+    if let Some(color) = color {
+        let weight = node
+            .get("strokeWeight")
+            .and_then(|w| w.as_f64())
+            .unwrap_or(1.0);
+        css.push(("border", format!("{} solid {}", px(weight), color)));
+    }
+}
+
+/// Font declarations of a text node, from the simplified text properties
+fn text_declarations(node: &Map<String, JsonValue>, css: &mut Vec<Declaration>) {
+    if let Some(font_name) = node.get("fontName") {
+        if let Some(family) = font_name.get("family").and_then(|f| f.as_str()) {
+            css.push(("font-family", format!("\"{}\", sans-serif", family)));
+        }
+        if let Some(style) = font_name.get("style").and_then(|s| s.as_str()) {
+            css.push(("font-weight", font_weight(style).to_string()));
+            if style.to_lowercase().contains("italic") {
+                css.push(("font-style", "italic".to_string()));
+            }
+        }
+    }
+    if let Some(size) = node.get("fontSize").and_then(|s| s.as_f64()) {
+        css.push(("font-size", px(size)));
+    }
+    if let Some(line_height) = str_field(node, "lineHeight") {
+        css.push(("line-height", line_height.to_string()));
+    }
+    if let Some(spacing) = str_field(node, "letterSpacing") {
+        css.push(("letter-spacing", letter_spacing(spacing)));
+    }
+    if let Some(align) = str_field(node, "textAlignHorizontal") {
+        let align = match align {
+            "CENTER" => "center",
+            "RIGHT" => "right",
+            "JUSTIFIED" => "justify",
+            _ => "left",
+        };
+        css.push(("text-align", align.to_string()));
+    }
+    match str_field(node, "textCase") {
+        Some("UPPER") => css.push(("text-transform", "uppercase".to_string())),
+        Some("LOWER") => css.push(("text-transform", "lowercase".to_string())),
+        Some("TITLE") => css.push(("text-transform", "capitalize".to_string())),
+        _ => {}
+    }
+    match str_field(node, "textDecoration") {
+        Some("UNDERLINE") => css.push(("text-decoration", "underline".to_string())),
+        Some("STRIKETHROUGH") => css.push(("text-decoration", "line-through".to_string())),
+        _ => {}
+    }
+}
+
+/// Numeric font weight from a font style name ("Semi Bold Italic" → 600)
+pub fn font_weight(style: &str) -> u32 {
+    let style = style.to_lowercase().replace([' ', '-'], "");
+    let weights = [
+        ("thin", 100),
+        ("hairline", 100),
+        ("extralight", 200),
+        ("ultralight", 200),
+        ("light", 300),
+        ("medium", 500),
+        ("semibold", 600),
+        ("demibold", 600),
+        ("extrabold", 800),
+        ("ultrabold", 800),
+        ("bold", 700),
+        ("black", 900),
+        ("heavy", 900),
+    ];
+    weights
+        .iter()
+        .find(|(name, _)| style.contains(name))
+        .map(|(_, weight)| *weight)
+        .unwrap_or(400)
+}
+
+/// CSS letter-spacing: percentages of the font size become `em`
+fn letter_spacing(value: &str) -> String {
+    match value.strip_suffix('%').and_then(|v| v.parse::<f64>().ok()) {
+        Some(percent) => format!("{}em", format_number(percent / 100.0)),
+        None => value.to_string(),
+    }
+}
+
+fn number(value: &JsonValue, key: &str) -> Option<f64> {
+    value.get(key)?.as_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn declarations(node: JsonValue, placement: Placement) -> Vec<Declaration> {
+        node_declarations(node.as_object().unwrap(), placement)
+    }
+
+    fn get<'a>(css: &'a [Declaration], property: &str) -> Option<&'a str> {
+        css.iter()
+            .find(|(p, _)| *p == property)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_auto_layout_to_flexbox() {
+        let css = declarations(
+            json!({
+                "stackMode": "VERTICAL",
+                "stackSpacing": 8.0,
+                "stackHorizontalPadding": 16.0,
+                "stackVerticalPadding": 12.0,
+                "stackPrimaryAlignItems": "CENTER",
+                "children": [{}]
+            }),
+            Placement::Root,
+        );
+
+        assert_eq!(get(&css, "display"), Some("flex"));
+        assert_eq!(get(&css, "flex-direction"), Some("column"));
+        assert_eq!(get(&css, "gap"), Some("8px"));
+        assert_eq!(get(&css, "padding"), Some("12px 16px"));
+        assert_eq!(get(&css, "justify-content"), Some("center"));
+        assert_eq!(get(&css, "position"), Some("relative"));
+    }
+
+    #[test]
+    fn test_layout_block() {
+        let grid = json!({"css": {"display": "grid", "grid-template-columns": "repeat(3, 1fr)"}});
+        let placement = Placement::of_children(grid.as_object().unwrap());
+        assert_eq!(placement, Placement::Flow);
+
+        let pinned = json!({"css": {"position": "absolute"}});
+        assert_eq!(placement.for_child(pinned.as_object().unwrap()), Placement::Absolute);
+
+        let css = declarations(
+            json!({
+                "size": {"x": 100.0, "y": 40.0},
+                "css": {"flex": "1 0 0", "width": "auto", "flex-shrink": "0", "unknown": "x"}
+            }),
+            Placement::Flow,
+        );
+        assert_eq!(get(&css, "width"), Some("auto"));
+        assert_eq!(get(&css, "height"), Some("40px"));
+        assert_eq!(get(&css, "flex"), Some("1 0 0"));
+        assert_eq!(css.iter().filter(|(p, _)| *p == "flex-shrink").count(), 1);
+        assert_eq!(get(&css, "unknown"), None);
+    }
+
+    #[test]
+    fn test_placement() {
+        let frame = json!({"stackMode": "HORIZONTAL"});
+        let placement = Placement::of_children(frame.as_object().unwrap());
+        assert_eq!(placement, Placement::Flow);
+
+        let pinned = json!({"stackPositioning": "ABSOLUTE"});
+        assert_eq!(placement.for_child(pinned.as_object().unwrap()), Placement::Absolute);
+
+        let css = declarations(
+            json!({"transform": {"x": 5.0, "y": 6.5, "rotation": 45.0}}),
+            Placement::Flow,
+        );
+        assert_eq!(get(&css, "left"), None);
+        assert_eq!(get(&css, "flex-shrink"), Some("0"));
+        assert_eq!(get(&css, "transform"), Some("rotate(45deg)"));
+
+        let css = declarations(json!({"transform": {"x": 5.0, "y": 6.5}}), Placement::Absolute);
+        assert_eq!(get(&css, "left"), Some("5px"));
+        assert_eq!(get(&css, "top"), Some("6.5px"));
+    }
+
+    #[test]
+    fn test_fills_to_backgrounds() {
+        let css = declarations(
+            json!({"fillPaints": [
+                {"color": "#ffffff"},
+                {"image": {"filename": "images/abc.png"}, "imageScaleMode": "FIT"},
+                {"color": "#000000", "opacity": 0.5}
+            ]}),
+            Placement::Root,
+        );
+
+        assert_eq!(get(&css, "background-color"), Some("#ffffff"));
+        assert_eq!(
+            get(&css, "background-image"),
+            Some("linear-gradient(#00000080, #00000080), url(\"images/abc.png\")")
+        );
+        assert_eq!(get(&css, "background-size"), Some("auto, contain"));
+    }
+
+    #[test]
+    fn test_effects() {
+        let css = declarations(
+            json!({"effects": {"boxShadow": "0 4px 8px 0 #00000040", "backdropFilter": "blur(10px)"}}),
+            Placement::Root,
+        );
+
+        assert_eq!(get(&css, "box-shadow"), Some("0 4px 8px 0 #00000040"));
+        assert_eq!(get(&css, "backdrop-filter"), Some("blur(10px)"));
+        assert_eq!(get(&css, "filter"), None);
+    }
+
+    #[test]
+    fn test_gradient_fills() {
+        let css = declarations(
+            json!({"fillPaints": [
+                {"type": "SOLID", "color": "#ffffff"},
+                {"type": "GRADIENT_LINEAR", "css": "linear-gradient(90deg, #ff0000 0%, #0000ff 100%)"}
+            ]}),
+            Placement::Root,
+        );
+
+        assert_eq!(get(&css, "background-color"), Some("#ffffff"));
+        assert_eq!(
+            get(&css, "background-image"),
+            Some("linear-gradient(90deg, #ff0000 0%, #0000ff 100%)")
+        );
+        assert_eq!(get(&css, "background-size"), None);
+    }
+
+    #[test]
+    fn test_text_properties() {
+        let css = declarations(
+            json!({
+                "textData": {"characters": "Hi"},
+                "fontName": {"family": "Inter", "style": "Semi Bold Italic"},
+                "fontSize": 14.0,
+                "lineHeight": "20px",
+                "letterSpacing": "-1%",
+                "textAlignHorizontal": "CENTER",
+                "textCase": "UPPER",
+                "fillPaints": [{"color": "#333333"}]
+            }),
+            Placement::Absolute,
+        );
+
+        assert_eq!(get(&css, "font-family"), Some("\"Inter\", sans-serif"));
+        assert_eq!(get(&css, "font-weight"), Some("600"));
+        assert_eq!(get(&css, "font-style"), Some("italic"));
+        assert_eq!(get(&css, "line-height"), Some("20px"));
+        assert_eq!(get(&css, "letter-spacing"), Some("-0.01em"));
+        assert_eq!(get(&css, "text-align"), Some("center"));
+        assert_eq!(get(&css, "text-transform"), Some("uppercase"));
+        assert_eq!(get(&css, "color"), Some("#333333"));
+        assert_eq!(get(&css, "background-color"), None);
+    }
+
+    #[test]
+    fn test_font_weight() {
+        assert_eq!(font_weight("Regular"), 400);
+        assert_eq!(font_weight("ExtraBold"), 800);
+    }
+}
@@ -0,0 +1,323 @@
+//! Static HTML/CSS pages from the transformed tree
+//!
+//! [`render_html`] takes the output of [`convert`](crate::convert) and emits
+//! one HTML document and one stylesheet per top-level frame:
+//!
+//! - auto-layout frames (`stackMode`, `stackSpacing`, paddings) become flexbox containers
+//! - other children are positioned absolutely from their `transform` x/y
+//! - solid and image `fillPaints` become backgrounds (text color for text nodes)
+//! - text nodes become `<p>` elements with their font properties
+//!
+//! The output is deterministic, which makes it a stable baseline to refine by
+//! hand or with an LLM.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::html::render_html;
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let json = fig2json::convert(&bytes, None).unwrap();
+//!
+//! for page in render_html(&json) {
+//!     std::fs::write(format!("{}.html", page.file_stem), &page.html).unwrap();
+//!     std::fs::write(format!("{}.css", page.file_stem), &page.css).unwrap();
+//! }
+//! ```
+
+pub mod css;
+
+use crate::css::is_text;
+use css::{node_declarations, Placement};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Rules shared by every stylesheet
+const BASE_CSS: &str = "*, *::before, *::after {\n  box-sizing: border-box;\n  margin: 0;\n}\n";
+
+/// HTML document and stylesheet of a top-level frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlPage {
+    /// Name of the frame
+    pub name: String,
+    /// File name without extension, unique among the returned pages
+    pub file_stem: String,
+    /// HTML document, linking `<file_stem>.css`
+    pub html: String,
+    /// Stylesheet
+    pub css: String,
+}
+
+/// Render every top-level frame as a static page
+///
+/// Top-level frames are the children of the pages (CANVAS nodes) of the
+/// document. Invisible nodes are skipped.
+///
+/// # Arguments
+/// * `output` - Output of [`convert`](crate::convert), or its `document` node
+///
+/// # Returns
+/// Pages in document order
+pub fn render_html(output: &JsonValue) -> Vec<HtmlPage> {
+    let document = output.get("document").unwrap_or(output);
+    let mut stems = UniqueNames::default();
+
+    children(document)
+        .iter()
+        .flat_map(children)
+        .filter_map(|frame| frame.as_object())
+        .filter(|frame| is_visible(frame))
+        .map(|frame| {
+            let name = node_name(frame).to_string();
+            let file_stem = stems.next(&slug(&name, "frame"));
+            render_frame(frame, name, file_stem)
+        })
+        .collect()
+}
+
+fn render_frame(frame: &Map<String, JsonValue>, name: String, file_stem: String) -> HtmlPage {
+    let mut renderer = Renderer::default();
+    renderer.node(frame, Placement::Root, 1);
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    html.push_str("  <meta charset=\"utf-8\">\n");
+    let _ = writeln!(html, "  <title>{}</title>", escape(&name));
+    let _ = writeln!(html, "  <link rel=\"stylesheet\" href=\"{}.css\">", file_stem);
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&renderer.markup);
+    html.push_str("</body>\n</html>\n");
+
+    let mut css = String::from(BASE_CSS);
+    css.push_str(&renderer.rules);
+
+    HtmlPage {
+        name,
+        file_stem,
+        html,
+        css,
+    }
+}
+
+/// Accumulates the markup and the CSS rules of a frame
+#[derive(Default)]
+struct Renderer {
+    markup: String,
+    rules: String,
+    classes: UniqueNames,
+}
+
+impl Renderer {
+    fn node(&mut self, node: &Map<String, JsonValue>, placement: Placement, depth: usize) {
+        let class = self.classes.next(&slug(node_name(node), "node"));
+        let indent = "  ".repeat(depth);
+
+        let declarations = node_declarations(node, placement);
+        let _ = writeln!(self.rules, "\n.{} {{", class);
+        for (property, value) in &declarations {
+            let _ = writeln!(self.rules, "  {}: {};", property, value);
+        }
+        self.rules.push_str("}\n");
+
+        if is_text(node) {
+            let characters = node
+                .get("textData")
+                .and_then(|t| t.get("characters"))
+                .and_then(|c| c.as_str())
+                .unwrap_or("");
+            let text = escape(characters).replace('\n', "<br>");
+            let _ = writeln!(self.markup, "{}<p class=\"{}\">{}</p>", indent, class, text);
+            return;
+        }
+
+        let visible_children: Vec<&Map<String, JsonValue>> = node
+            .get("children")
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|child| child.as_object())
+            .filter(|child| is_visible(child))
+            .collect();
+
+        if visible_children.is_empty() {
+            let _ = writeln!(self.markup, "{}<div class=\"{}\"></div>", indent, class);
+            return;
+        }
+
+        let _ = writeln!(self.markup, "{}<div class=\"{}\">", indent, class);
+        let child_placement = Placement::of_children(node);
+        for child in visible_children {
+            self.node(child, child_placement.for_child(child), depth + 1);
+        }
+        let _ = writeln!(self.markup, "{}</div>", indent);
+    }
+}
+
+/// Generates unique names by appending `-2`, `-3`, ... to repeated names
+#[derive(Default)]
+pub(crate) struct UniqueNames {
+    /// Names handed out so far
+    used: HashSet<String>,
+    /// Last suffix tried for each repeated name
+    suffixes: HashMap<String, usize>,
+}
+
+impl UniqueNames {
+    pub(crate) fn next(&mut self, base: &str) -> String {
+        if self.used.insert(base.to_string()) {
+            return base.to_string();
+        }
+
+        // Skip suffixed names already taken literally ("a 2" before a second "a")
+        let suffix = self.suffixes.entry(base.to_string()).or_insert(1);
+        loop {
+            *suffix += 1;
+            let name = format!("{}-{}", base, suffix);
+            if self.used.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+}
+
+/// Lowercase ASCII identifier from a node name ("Primary Button" → "primary-button")
+///
+/// Falls back to `fallback` when the name has no ASCII letters or digits, and
+/// prefixes names starting with a digit so they are valid CSS classes.
+pub fn slug(name: &str, fallback: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+
+    if slug.is_empty() {
+        fallback.to_string()
+    } else if slug.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("{}-{}", fallback, slug)
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Escape text for HTML content and attribute values
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn children(node: &JsonValue) -> &[JsonValue] {
+    node.get("children")
+        .and_then(|c| c.as_array())
+        .map(|c| c.as_slice())
+        .unwrap_or(&[])
+}
+
+fn node_name(node: &Map<String, JsonValue>) -> &str {
+    node.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+fn is_visible(node: &Map<String, JsonValue>) -> bool {
+    node.get("visible").and_then(|v| v.as_bool()).unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_output() -> JsonValue {
+        json!({
+            "document": {
+                "name": "Document",
+                "children": [{
+                    "name": "Page 1",
+                    "children": [
+                        {
+                            "name": "Card",
+                            "size": {"x": 200.0, "y": 100.0},
+                            "transform": {"x": 400.0, "y": 300.0},
+                            "stackMode": "VERTICAL",
+                            "stackSpacing": 8.0,
+                            "fillPaints": [{"color": "#ffffff"}],
+                            "children": [
+                                {
+                                    "name": "Title",
+                                    "size": {"x": 100.0, "y": 20.0},
+                                    "textData": {"characters": "Fish & <Chips>"},
+                                    "fontSize": 16.0
+                                },
+                                {"name": "Hidden", "visible": false},
+                                {"name": "Title", "size": {"x": 10.0, "y": 10.0}}
+                            ]
+                        },
+                        {"name": "Card", "children": [
+                            {"name": "Badge", "transform": {"x": 4.0, "y": 2.0}}
+                        ]}
+                    ]
+                }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_one_page_per_top_level_frame() {
+        let pages = render_html(&sample_output());
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].name, "Card");
+        assert_eq!(pages[0].file_stem, "card");
+        assert_eq!(pages[1].file_stem, "card-2");
+        assert!(pages[1].html.contains("<link rel=\"stylesheet\" href=\"card-2.css\">"));
+    }
+
+    #[test]
+    fn test_markup() {
+        let page = &render_html(&sample_output())[0];
+
+        assert!(page.html.contains(
+            "  <div class=\"card\">\n    <p class=\"title\">Fish &amp; &lt;Chips&gt;</p>\n    <div class=\"title-2\"></div>\n  </div>\n"
+        ));
+        assert!(!page.html.contains("hidden"));
+    }
+
+    #[test]
+    fn test_stylesheet() {
+        let pages = render_html(&sample_output());
+
+        assert!(pages[0].css.starts_with(BASE_CSS));
+        assert!(pages[0].css.contains(".card {\n  position: relative;\n  width: 200px;\n  height: 100px;\n  display: flex;\n  flex-direction: column;\n  gap: 8px;\n  background-color: #ffffff;\n}\n"));
+        assert!(pages[0].css.contains(".title {\n  width: 100px;\n  height: 20px;\n  flex-shrink: 0;\n  font-size: 16px;\n}\n"));
+        assert!(pages[1].css.contains(".badge {\n  position: absolute;\n  left: 4px;\n  top: 2px;\n}\n"));
+    }
+
+    #[test]
+    fn test_render_is_deterministic() {
+        assert_eq!(render_html(&sample_output()), render_html(&sample_output()));
+    }
+
+    #[test]
+    fn test_unique_names() {
+        let mut names = UniqueNames::default();
+        let generated: Vec<String> = ["a", "a-2", "a", "a", "a-2"]
+            .iter()
+            .map(|name| names.next(name))
+            .collect();
+
+        assert_eq!(generated, ["a", "a-2", "a-3", "a-4", "a-2-2"]);
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Primary Button / Hover", "node"), "primary-button-hover");
+        assert_eq!(slug("2 columns", "node"), "node-2-columns");
+        assert_eq!(slug("日本", "frame"), "frame");
+    }
+}
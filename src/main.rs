@@ -1,7 +1,11 @@
 use anyhow::{anyhow, bail, Context, Result};
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use fig2json::schema::SchemaFormat;
+use fig2json::split::SplitMode;
+use fig2json::tailwind::Theme;
+use fig2json::{PassContext, Pipeline, Selector};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "fig2json")]
@@ -11,18 +15,65 @@ use std::path::PathBuf;
     For regular .fig files:\n  \
     fig2json input.fig [-o output.json] [--compact] [-v]\n\n\
     For ZIP files (extracts all and converts all .fig files inside):\n  \
-    fig2json input.zip extract-dir [--compact] [-v]")]
+    fig2json input.zip extract-dir [--compact] [-v]\n\n\
+    With --format html, one .html and one .css file are written per top-level frame\n\
+    into the -o directory (default: current directory)\n\n\
+    With --export-svg <dir>, vector nodes are also written as SVG files and\n\
+    referenced by their `svg` field\n\n\
+    With --format tailwind, each node gets a `tailwind` class string, snapped to\n\
+    the theme of --tailwind-config <file.json> when given\n\n\
+    With --layout-css, auto-layout properties (direction, spacing, padding, alignment,\n\
+    sizing, wrap, grid) are replaced by a `css` block of flexbox or grid declarations\n\n\
+    With --text-runs, text with mixed styles gets `textData.runs`, one entry per\n\
+    styled span with its text, font, size, fills, decoration and hyperlink\n\n\
+    With --gradient-css, gradient paints get a `css` string such as\n\
+    linear-gradient(90deg, #ff0000 0%, #0000ff 100%) (always done by --format html)\n\n\
+    With --effects-css, the `effects` array of each node is replaced by an object of\n\
+    CSS box-shadow, text-shadow, filter and backdrop-filter values (always done by --format html)\n\n\
+    With --absolute-bounds, each node gets its absoluteBoundingBox, absoluteRenderBounds\n\
+    and framePosition (position inside its top-level frame)\n\n\
+    With --expand-instances, component instances get the layers of their main component\n\
+    with the instance overrides applied (always done by --format html and outline)\n\n\
+    Transformation passes can be listed with --list-passes and toggled with\n\
+    --skip-pass/--only-pass (e.g. --skip-pass stroke_properties_removal,layout_aids_removal)\n\n\
+    Parts of the file can be selected with --page <name>, --node <sessionID:localID>,\n\
+    --name <glob> and --type FRAME,TEXT; selected nodes get a `breadcrumbs` field\n\n\
+    With --split pages|frames, one JSON file per page (pages/<page>.json) or per\n\
+    top-level frame (pages/<page>/<frame>.json) and an index.json manifest are\n\
+    written into the -o directory (default: current directory)\n\n\
+    With --max-tokens N, detail is dropped progressively (geometry, metadata,\n\
+    hidden nodes, deep subtrees, long text) until the output fits about N tokens\n\n\
+    With --format outline, each node is written as one indented line with its main\n\
+    properties (--depth N limits the levels below the pages); --format yaml writes\n\
+    the JSON tree as YAML\n\n\
+    Subcommands:\n  \
+    fig2json schema input.fig [--format kiwi|json-schema|typescript] [-o output]\n  \
+    fig2json react input.fig out-dir [-v]\n  \
+    fig2json tokens input.fig out-dir [-v]\n  \
+    fig2json components input.fig [-o components.json] [--compact]\n  \
+    fig2json query input.fig '<selector>' [--raw] [-o results.json] [--compact]\n  \
+    fig2json diff old.fig new.fig [--format text|json] [-o output] [--compact]\n  \
+    fig2json text input.fig [--format csv|xliff|json] [--source-language en] [-o output]")]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Input .fig or .zip file path
-    input: PathBuf,
+    #[arg(required_unless_present = "list_passes")]
+    input: Option<PathBuf>,
 
     /// Directory to extract ZIP contents (required for ZIP files, converts all .fig files found)
     extract_dir: Option<PathBuf>,
 
-    /// Output JSON file path (default: stdout) - Cannot be used with extract_dir
+    /// Output JSON file path (default: stdout), or output directory with --format html - Cannot be used with extract_dir
     #[arg(short, long)]
     output: Option<PathBuf>,
 
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+
     /// Compact JSON output (default is pretty-printed with indentation)
     #[arg(long)]
     compact: bool,
@@ -34,18 +85,338 @@ struct Cli {
     /// Generate both transformed .json and raw .raw.json files (without transformations)
     #[arg(long)]
     raw: bool,
+
+    /// Skip a transformation pass (repeatable or comma-separated, see --list-passes)
+    #[arg(long, value_name = "PASS", value_delimiter = ',')]
+    skip_pass: Vec<String>,
+
+    /// Only run the given transformation passes (repeatable or comma-separated, see --list-passes)
+    #[arg(long, value_name = "PASS", value_delimiter = ',')]
+    only_pass: Vec<String>,
+
+    /// List available transformation passes in execution order and exit
+    #[arg(long)]
+    list_passes: bool,
+
+    /// Write one SVG file per vector node to this directory, referenced by the node's `svg` field
+    #[arg(long, value_name = "DIR")]
+    export_svg: Option<PathBuf>,
+
+    /// Tailwind config as JSON, whose theme colors and scales are used by --format tailwind
+    #[arg(long, value_name = "FILE")]
+    tailwind_config: Option<PathBuf>,
+
+    /// Replace auto-layout properties with a `css` block of flexbox or grid declarations on each node
+    #[arg(long)]
+    layout_css: bool,
+
+    /// Split styled text into `textData.runs`, one per span of text sharing the same style
+    #[arg(long)]
+    text_runs: bool,
+
+    /// Add a CSS linear, radial or conic gradient string to each gradient paint
+    #[arg(long)]
+    gradient_css: bool,
+
+    /// Replace the effects of each node with CSS box-shadow, text-shadow, filter and backdrop-filter values
+    #[arg(long)]
+    effects_css: bool,
+
+    /// Add absolute bounding boxes, render bounds and positions inside the top-level frame to each node
+    #[arg(long)]
+    absolute_bounds: bool,
+
+    /// Expand component instances into a copy of their main component with the overrides applied
+    #[arg(long)]
+    expand_instances: bool,
+
+    /// Only convert the page with this name (repeatable)
+    #[arg(long, value_name = "NAME")]
+    page: Vec<String>,
+
+    /// Only convert the node with this GUID, as sessionID:localID (repeatable or comma-separated)
+    #[arg(long, value_name = "GUID", value_delimiter = ',')]
+    node: Vec<String>,
+
+    /// Only convert the nodes whose name matches this glob pattern, e.g. "Button/*" (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    name: Vec<String>,
+
+    /// Only convert the nodes of these types, e.g. FRAME,TEXT (repeatable or comma-separated)
+    #[arg(long = "type", value_name = "TYPE", value_delimiter = ',')]
+    node_type: Vec<String>,
+
+    /// Write one JSON file per page or per top-level frame, and an index.json, to the -o directory
+    #[arg(long, value_enum, value_name = "MODE")]
+    split: Option<SplitOption>,
+
+    /// Drop detail progressively until the estimated token count of the JSON output fits N
+    #[arg(long, value_name = "N")]
+    max_tokens: Option<usize>,
+
+    /// Number of levels shown below the pages by --format outline (default: all)
+    #[arg(long, value_name = "N")]
+    depth: Option<usize>,
+}
+
+/// Granularity of --split
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SplitOption {
+    /// pages/<page>.json
+    Pages,
+    /// pages/<page>/<frame>.json
+    Frames,
+}
+
+/// How to write split output
+struct SplitOutput {
+    mode: SplitMode,
+    /// Remove the GUIDs kept for index.json from the written files
+    strip_guids: bool,
+}
+
+/// Output formats of the conversion
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Transformed JSON tree
+    Json,
+    /// One static HTML page and stylesheet per top-level frame
+    Html,
+    /// Transformed JSON tree with a `tailwind` class string on each node
+    Tailwind,
+    /// Indented text, one line per node with its main properties
+    Outline,
+    /// Transformed tree as YAML
+    Yaml,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Export the Kiwi schema embedded in a .fig file
+    Schema(SchemaArgs),
+    /// Generate one React component (.tsx) per main component
+    React(ReactArgs),
+    /// Extract design tokens as W3C Design Tokens JSON and CSS custom properties
+    Tokens(TokensArgs),
+    /// List components, component sets and their variants, properties and usage
+    Components(ComponentsArgs),
+    /// Find nodes with a CSS-like selector, e.g. 'FRAME[name=Checkout] TEXT[fontSize>20]'
+    Query(QueryArgs),
+    /// Compare two versions of a file: added, removed, moved, reparented and modified nodes
+    Diff(DiffArgs),
+    /// Extract every string for copy review and localization, as CSV, XLIFF or JSON
+    Text(TextArgs),
+}
+
+#[derive(Args)]
+struct ReactArgs {
+    /// Input .fig or .zip file path
+    input: PathBuf,
+
+    /// Directory to write the .tsx files and index.ts to
+    output_dir: PathBuf,
+
+    /// Verbose output for debugging
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+#[derive(Args)]
+struct TokensArgs {
+    /// Input .fig or .zip file path
+    input: PathBuf,
+
+    /// Directory to write tokens.json and tokens.css to
+    output_dir: PathBuf,
+
+    /// Verbose output for debugging
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+#[derive(Args)]
+struct ComponentsArgs {
+    /// Input .fig or .zip file path
+    input: PathBuf,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Compact JSON output (default is pretty-printed with indentation)
+    #[arg(long)]
+    compact: bool,
+}
+
+#[derive(Args)]
+struct QueryArgs {
+    /// Input .fig file path
+    input: PathBuf,
+
+    /// Selector: node types, #sessionID:localID, [property op value] predicates,
+    /// descendant (space) and child (>) combinators, comma-separated alternatives
+    selector: String,
+
+    /// Query the raw tree (without transformations) instead of the transformed one
+    #[arg(long)]
+    raw: bool,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Compact JSON output (default is pretty-printed with indentation)
+    #[arg(long)]
+    compact: bool,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// Old .fig file path
+    old: PathBuf,
+
+    /// New .fig file path
+    new: PathBuf,
+
+    /// Report format
+    #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+    format: DiffFormat,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Compact JSON output (default is pretty-printed with indentation)
+    #[arg(long)]
+    compact: bool,
+}
+
+/// Report formats of the diff subcommand
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DiffFormat {
+    /// One block per changed node, for humans
+    Text,
+    /// Summary and changed nodes as JSON
+    Json,
+}
+
+#[derive(Args)]
+struct TextArgs {
+    /// Input .fig file path
+    input: PathBuf,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = TextFormat::Csv)]
+    format: TextFormat,
+
+    /// Language of the texts, for the XLIFF source-language attribute
+    #[arg(long, value_name = "LANG", default_value = "en")]
+    source_language: String,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Compact JSON output (default is pretty-printed with indentation)
+    #[arg(long)]
+    compact: bool,
+}
+
+/// Output formats of the text subcommand
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TextFormat {
+    /// One row per string: key, page, frame, id, source and text
+    Csv,
+    /// XLIFF 1.2 file with one trans-unit per string
+    Xliff,
+    /// JSON object from page/frame/node path to text
+    Json,
+}
+
+#[derive(Args)]
+struct SchemaArgs {
+    /// Input .fig or .zip file path
+    input: PathBuf,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = SchemaFormatArg::Kiwi)]
+    format: SchemaFormatArg,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// Output formats of the schema subcommand
+#[derive(Clone, Copy, ValueEnum)]
+enum SchemaFormatArg {
+    /// Canonical .kiwi text
+    Kiwi,
+    /// JSON Schema of the raw JSON output
+    JsonSchema,
+    /// TypeScript declarations of the raw JSON output
+    Typescript,
+}
+
+impl From<SchemaFormatArg> for SchemaFormat {
+    fn from(format: SchemaFormatArg) -> Self {
+        match format {
+            SchemaFormatArg::Kiwi => SchemaFormat::Kiwi,
+            SchemaFormatArg::JsonSchema => SchemaFormat::JsonSchema,
+            SchemaFormatArg::Typescript => SchemaFormat::TypeScript,
+        }
+    }
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(command) = &cli.command {
+        return match command {
+            Command::Schema(args) => handle_schema(args),
+            Command::React(args) => handle_react(args),
+            Command::Tokens(args) => handle_tokens(args),
+            Command::Components(args) => handle_components(args),
+            Command::Query(args) => handle_query(args),
+            Command::Diff(args) => handle_diff(args),
+            Command::Text(args) => handle_text(args),
+        };
+    }
+
+    // Build transformation pipeline from --format, the pass flags and --only-pass/--skip-pass
+    let mut pipeline = build_pipeline(&cli)?;
+
+    if cli.list_passes {
+        for (pass, enabled) in pipeline.passes() {
+            let marker = if enabled { " " } else { "-" };
+            println!("{} {:<44} {}", marker, pass.name(), pass.description());
+        }
+        return Ok(());
+    }
+
+    let input = cli.input.as_ref().expect("input is required unless --list-passes");
+    let ctx = build_pass_context(&cli)?;
+    let split = build_split_output(&cli, &mut pipeline)?;
+    if cli.max_tokens.is_some() && cli.format == OutputFormat::Html {
+        bail!("--max-tokens cannot be used with --format html");
+    }
+    if cli.depth.is_some() && cli.format != OutputFormat::Outline {
+        bail!("--depth requires --format outline");
+    }
+    let selector = Selector {
+        pages: cli.page.clone(),
+        nodes: cli.node.clone(),
+        names: cli.name.clone(),
+        types: cli.node_type.clone(),
+    };
+
     if cli.verbose {
-        eprintln!("Reading input file: {}", cli.input.display());
+        eprintln!("Reading input file: {}", input.display());
     }
 
     // Read input file
-    let bytes = fs::read(&cli.input)
-        .with_context(|| format!("Failed to read input file: {}", cli.input.display()))?;
+    let bytes = fs::read(input)
+        .with_context(|| format!("Failed to read input file: {}", input.display()))?;
 
     if cli.verbose {
         eprintln!("File size: {} bytes", bytes.len());
@@ -65,7 +436,7 @@ fn main() -> Result<()> {
         }
 
         // ZIP extraction mode
-        handle_zip_mode(&bytes, extract_dir, cli.compact, cli.verbose, cli.raw)?;
+        handle_zip_mode(&bytes, extract_dir, &pipeline, &ctx, &selector, split.as_ref(), cli.max_tokens, cli.depth, cli.format, cli.compact, cli.verbose, cli.raw)?;
     } else {
         // Regular .fig file mode
         if cli.verbose {
@@ -74,40 +445,52 @@ fn main() -> Result<()> {
 
         // Determine base directory for image file operations
         let base_dir = if let Some(output_path) = &cli.output {
-            output_path.parent()
+            if cli.format == OutputFormat::Html || split.is_some() {
+                Some(output_path.as_path())
+            } else {
+                output_path.parent()
+            }
         } else {
             // If outputting to stdout, use current directory
             Some(std::path::Path::new("."))
         };
 
-        let json = fig2json::convert(&bytes, base_dir).context("Failed to convert .fig file to JSON")?;
+        let ctx = with_base_dir(&ctx, base_dir);
+        let mut json = fig2json::convert_with_selector(&bytes, &pipeline, &ctx, &selector)
+            .context("Failed to convert .fig file to JSON")?;
+        if let Some(max_tokens) = cli.max_tokens {
+            fit_to_budget(&mut json, max_tokens, cli.verbose)?;
+        }
 
         if cli.verbose {
             eprintln!("Conversion successful!");
         }
 
-        // Format output (pretty by default, compact if flag is set)
-        let output = if cli.compact {
-            serde_json::to_string(&json)?
+        if cli.format == OutputFormat::Html {
+            let dir = cli.output.as_deref().unwrap_or(std::path::Path::new("."));
+            write_html_pages(&json, dir, cli.verbose)?;
+        } else if let Some(split) = &split {
+            let dir = cli.output.as_deref().unwrap_or(std::path::Path::new("."));
+            write_split_files(&json, dir, split, cli.compact, cli.verbose)?;
         } else {
-            serde_json::to_string_pretty(&json)?
-        };
+            let output = format_output(&json, cli.format, cli.depth, cli.compact)?;
 
-        // Write output
-        match cli.output.as_ref() {
-            Some(path) => {
-                if cli.verbose {
-                    eprintln!("Writing output to: {}", path.display());
+            // Write output
+            match cli.output.as_ref() {
+                Some(path) => {
+                    if cli.verbose {
+                        eprintln!("Writing output to: {}", path.display());
+                    }
+                    fs::write(path, &output)
+                        .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+                    if cli.verbose {
+                        eprintln!("Done!");
+                    }
                 }
-                fs::write(path, &output)
-                    .with_context(|| format!("Failed to write output file: {}", path.display()))?;
-                if cli.verbose {
-                    eprintln!("Done!");
+                None => {
+                    println!("{}", output.trim_end_matches('\n'));
                 }
             }
-            None => {
-                println!("{}", output);
-            }
         }
 
         // If --raw flag is set, also generate raw JSON file
@@ -134,7 +517,7 @@ fn main() -> Result<()> {
                 }
                 None => {
                     // Derive from input path
-                    cli.input.with_extension("raw.json")
+                    input.with_extension("raw.json")
                 }
             };
 
@@ -154,8 +537,105 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Pass context from --export-svg and --tailwind-config
+fn build_pass_context(cli: &Cli) -> Result<PassContext> {
+    let mut ctx = PassContext::default();
+    if let Some(dir) = &cli.export_svg {
+        ctx = ctx.with_svg_dir(dir);
+    }
+
+    match (&cli.tailwind_config, cli.format) {
+        (Some(path), OutputFormat::Tailwind) => {
+            let config = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read Tailwind config: {}", path.display()))?;
+            let config = serde_json::from_str(&config)
+                .with_context(|| format!("Failed to parse Tailwind config: {}", path.display()))?;
+            ctx = ctx.with_tailwind(Theme::from_config(&config)?);
+        }
+        (Some(_), _) => bail!("--tailwind-config requires --format tailwind"),
+        (None, _) => {}
+    }
+
+    Ok(ctx)
+}
+
+/// Split mode from --split, disabling guid_removal so that index.json gets the GUIDs
+fn build_split_output(cli: &Cli, pipeline: &mut Pipeline) -> Result<Option<SplitOutput>> {
+    let Some(split) = cli.split else {
+        return Ok(None);
+    };
+    if matches!(cli.format, OutputFormat::Html | OutputFormat::Outline | OutputFormat::Yaml) {
+        bail!("--split only writes JSON files, it cannot be used with --format html, outline or yaml");
+    }
+
+    let strip_guids = pipeline.is_enabled("guid_removal");
+    pipeline.disable("guid_removal")?;
+    let mode = match split {
+        SplitOption::Pages => SplitMode::Pages,
+        SplitOption::Frames => SplitMode::Frames,
+    };
+    Ok(Some(SplitOutput { mode, strip_guids }))
+}
+
+/// Serialize the converted tree in the text formats: JSON (pretty by default,
+/// compact with --compact), outline or YAML
+fn format_output(
+    json: &serde_json::Value,
+    format: OutputFormat,
+    depth: Option<usize>,
+    compact: bool,
+) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Outline => fig2json::outline::to_outline(json, depth),
+        OutputFormat::Yaml => fig2json::yaml::to_yaml(json),
+        _ if compact => serde_json::to_string(json)?,
+        _ => serde_json::to_string_pretty(json)?,
+    })
+}
+
+/// Apply --max-tokens, reporting the elided detail in verbose mode and
+/// warning when the output still does not fit
+fn fit_to_budget(json: &mut serde_json::Value, max_tokens: usize, verbose: bool) -> Result<()> {
+    let report = fig2json::budget::fit_to_budget(json, max_tokens)?;
+    if verbose {
+        eprintln!("Estimated tokens: {}", report.initial_tokens);
+        for step in &report.steps {
+            eprintln!("  - {} (-{} tokens)", step.name, step.tokens_saved);
+        }
+    }
+    if !report.fits {
+        eprintln!(
+            "Warning: output is still ~{} tokens, over the budget of {}",
+            report.final_tokens, max_tokens
+        );
+    }
+    Ok(())
+}
+
+/// Copy of `ctx` with the base directory used for image files
+fn with_base_dir(ctx: &PassContext, base_dir: Option<&Path>) -> PassContext {
+    PassContext {
+        base_dir: base_dir.unwrap_or(Path::new(".")).to_path_buf(),
+        ..ctx.clone()
+    }
+}
+
 /// Handle ZIP extraction mode: extract all files and convert all .fig files found
-fn handle_zip_mode(zip_bytes: &[u8], extract_dir: &PathBuf, compact: bool, verbose: bool, raw: bool) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+fn handle_zip_mode(
+    zip_bytes: &[u8],
+    extract_dir: &PathBuf,
+    pipeline: &Pipeline,
+    ctx: &PassContext,
+    selector: &Selector,
+    split: Option<&SplitOutput>,
+    max_tokens: Option<usize>,
+    depth: Option<usize>,
+    format: OutputFormat,
+    compact: bool,
+    verbose: bool,
+    raw: bool,
+) -> Result<()> {
     if verbose {
         eprintln!("ZIP file detected - extracting to: {}", extract_dir.display());
     }
@@ -199,25 +679,40 @@ fn handle_zip_mode(zip_bytes: &[u8], extract_dir: &PathBuf, compact: bool, verbo
         let base_dir = fig_path.parent();
 
         // Convert to JSON
-        let json = fig2json::convert(&fig_bytes, base_dir)
+        let ctx = with_base_dir(ctx, base_dir);
+        let mut json = fig2json::convert_with_selector(&fig_bytes, pipeline, &ctx, selector)
             .with_context(|| format!("Failed to convert: {}", fig_path.display()))?;
+        if let Some(max_tokens) = max_tokens {
+            fit_to_budget(&mut json, max_tokens, verbose)?;
+        }
 
-        // Format output (pretty by default, compact if flag is set)
-        let output = if compact {
-            serde_json::to_string(&json)?
+        if format == OutputFormat::Html {
+            // Next to the .fig file, so that images/ paths resolve
+            write_html_pages(&json, base_dir.unwrap_or(extract_dir), verbose)?;
+        } else if let Some(split) = split {
+            // One directory per .fig file, named after it, so that the index.json files don't collide
+            let dir = fig_path.with_extension("");
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+            write_split_files(&json, &dir, split, compact, verbose)?;
         } else {
-            serde_json::to_string_pretty(&json)?
-        };
+            let output = format_output(&json, format, depth, compact)?;
 
-        // Determine output path: same as .fig but with .json extension
-        let output_path = fig_path.with_extension("json");
+            // Determine output path: same as .fig but with the extension of the format
+            let extension = match format {
+                OutputFormat::Outline => "txt",
+                OutputFormat::Yaml => "yaml",
+                _ => "json",
+            };
+            let output_path = fig_path.with_extension(extension);
 
-        // Write JSON file
-        fs::write(&output_path, output)
-            .with_context(|| format!("Failed to write output: {}", output_path.display()))?;
+            // Write output file
+            fs::write(&output_path, output)
+                .with_context(|| format!("Failed to write output: {}", output_path.display()))?;
 
-        if verbose {
-            eprintln!("  → {}", output_path.strip_prefix(extract_dir).unwrap_or(&output_path).display());
+            if verbose {
+                eprintln!("  → {}", output_path.strip_prefix(extract_dir).unwrap_or(&output_path).display());
+            }
         }
 
         // If --raw flag is set, also generate raw JSON file
@@ -251,6 +746,293 @@ fn handle_zip_mode(zip_bytes: &[u8], extract_dir: &PathBuf, compact: bool, verbo
     Ok(())
 }
 
+/// Handle the schema subcommand: export the embedded Kiwi schema
+fn handle_schema(args: &SchemaArgs) -> Result<()> {
+    let bytes = fs::read(&args.input)
+        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?;
+
+    let schema = fig2json::schema::read_schema(&bytes).context("Failed to read Kiwi schema")?;
+    let output = fig2json::schema::export_schema(&schema, args.format.into());
+
+    write_output(args.output.as_ref(), &output)
+}
+
+fn handle_react(args: &ReactArgs) -> Result<()> {
+    let bytes = fs::read(&args.input)
+        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?;
+
+    let json = fig2json::convert_with_pipeline(&bytes, Some(&args.output_dir), &fig2json::react::pipeline())
+        .context("Failed to convert .fig file to JSON")?;
+    let components = fig2json::react::render_components(&json);
+    if components.is_empty() {
+        bail!("No components (SYMBOL nodes) found in {}", args.input.display());
+    }
+
+    fs::create_dir_all(&args.output_dir)
+        .with_context(|| format!("Failed to create output directory: {}", args.output_dir.display()))?;
+    for component in &components {
+        let path = args.output_dir.join(&component.file_name);
+        if args.verbose {
+            eprintln!("Writing: {}", path.display());
+        }
+        fs::write(&path, &component.source)
+            .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+    }
+
+    let index = args.output_dir.join("index.ts");
+    fs::write(&index, fig2json::react::index_source(&components))
+        .with_context(|| format!("Failed to write output file: {}", index.display()))?;
+
+    if args.verbose {
+        eprintln!("{} component(s) written", components.len());
+    }
+    Ok(())
+}
+
+fn handle_components(args: &ComponentsArgs) -> Result<()> {
+    let bytes = fs::read(&args.input)
+        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?;
+
+    let raw = fig2json::convert_raw(&bytes).context("Failed to convert .fig file to JSON")?;
+    let catalog = fig2json::components::catalog(&raw);
+    let output = if args.compact {
+        serde_json::to_string(&catalog)
+    } else {
+        serde_json::to_string_pretty(&catalog)
+    }
+    .context("Failed to serialize component catalog")?;
+
+    write_output(args.output.as_ref(), &format!("{}\n", output))
+}
+
+fn handle_query(args: &QueryArgs) -> Result<()> {
+    let query = fig2json::query::Query::parse(&args.selector)?;
+    let bytes = fs::read(&args.input)
+        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?;
+
+    let json = if args.raw {
+        fig2json::convert_raw(&bytes)
+    } else {
+        fig2json::convert_with_pipeline(&bytes, None, &fig2json::query::pipeline())
+    }
+    .context("Failed to convert .fig file to JSON")?;
+
+    let results = query.find(&json);
+    let output = if args.compact {
+        serde_json::to_string(&results)
+    } else {
+        serde_json::to_string_pretty(&results)
+    }
+    .context("Failed to serialize query results")?;
+
+    write_output(args.output.as_ref(), &format!("{}\n", output))
+}
+
+fn handle_diff(args: &DiffArgs) -> Result<()> {
+    let pipeline = fig2json::diff::pipeline();
+    let convert = |path: &PathBuf| -> Result<serde_json::Value> {
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read input file: {}", path.display()))?;
+        fig2json::convert_with_pipeline(&bytes, None, &pipeline)
+            .with_context(|| format!("Failed to convert: {}", path.display()))
+    };
+    let diff = fig2json::diff::diff(&convert(&args.old)?, &convert(&args.new)?);
+
+    let output = match args.format {
+        DiffFormat::Text => fig2json::diff::format::to_text(&diff),
+        DiffFormat::Json => {
+            let json = if args.compact {
+                serde_json::to_string(&diff)
+            } else {
+                serde_json::to_string_pretty(&diff)
+            }
+            .context("Failed to serialize diff")?;
+            format!("{}\n", json)
+        }
+    };
+
+    write_output(args.output.as_ref(), &output)
+}
+
+fn handle_text(args: &TextArgs) -> Result<()> {
+    let bytes = fs::read(&args.input)
+        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?;
+
+    let raw = fig2json::convert_raw(&bytes).context("Failed to convert .fig file to JSON")?;
+    let entries = fig2json::text::extract(&raw);
+    if entries.is_empty() {
+        eprintln!("Warning: no text found in {}", args.input.display());
+    }
+
+    let output = match args.format {
+        TextFormat::Csv => fig2json::text::format::to_csv(&entries),
+        TextFormat::Xliff => {
+            let original = args.input.file_name().unwrap_or_default().to_string_lossy();
+            fig2json::text::format::to_xliff(&entries, &original, &args.source_language)
+        }
+        TextFormat::Json => fig2json::text::format::to_json(&entries, args.compact),
+    };
+
+    write_output(args.output.as_ref(), &output)
+}
+
+fn handle_tokens(args: &TokensArgs) -> Result<()> {
+    let bytes = fs::read(&args.input)
+        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?;
+
+    let json = fig2json::convert_with_pipeline(&bytes, Some(&args.output_dir), &fig2json::tokens::pipeline())
+        .context("Failed to convert .fig file to JSON")?;
+    let tokens = fig2json::tokens::extract_tokens(&json);
+
+    fs::create_dir_all(&args.output_dir)
+        .with_context(|| format!("Failed to create output directory: {}", args.output_dir.display()))?;
+    let design_tokens = serde_json::to_string_pretty(&fig2json::tokens::to_design_tokens(&tokens))
+        .context("Failed to serialize design tokens")?;
+    let files = [
+        ("tokens.json", design_tokens + "\n"),
+        ("tokens.css", fig2json::tokens::to_css(&tokens)),
+    ];
+    for (name, contents) in files {
+        let path = args.output_dir.join(name);
+        if args.verbose {
+            eprintln!("Writing: {}", path.display());
+        }
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+    }
+
+    if args.verbose {
+        eprintln!("{} token(s) written", tokens.len());
+    }
+    Ok(())
+}
+
+/// Write one .html and one .css file per top-level frame into `dir`
+fn write_html_pages(json: &serde_json::Value, dir: &std::path::Path, verbose: bool) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+
+    for page in fig2json::html::render_html(json) {
+        for (extension, contents) in [("html", &page.html), ("css", &page.css)] {
+            let path = dir.join(format!("{}.{}", page.file_stem, extension));
+            fs::write(&path, contents)
+                .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+            if verbose {
+                eprintln!("  → {}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write one JSON file per page or top-level frame and index.json to a directory
+fn write_split_files(
+    json: &serde_json::Value,
+    dir: &Path,
+    split: &SplitOutput,
+    compact: bool,
+    verbose: bool,
+) -> Result<()> {
+    let fig2json::split::Split { index, files } = fig2json::split::split(json, split.mode);
+    let to_string = |value: &serde_json::Value| {
+        if compact {
+            serde_json::to_string(value)
+        } else {
+            serde_json::to_string_pretty(value)
+        }
+    };
+
+    for mut file in files {
+        if split.strip_guids {
+            fig2json::schema::remove_guid_fields(&mut file.json)?;
+        }
+        let path = dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create output directory: {}", parent.display()))?;
+        }
+        fs::write(&path, to_string(&file.json)?)
+            .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+        if verbose {
+            eprintln!("  → {}", path.display());
+        }
+    }
+
+    let path = dir.join("index.json");
+    fs::write(&path, to_string(&serde_json::to_value(&index)?)?)
+        .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+    if verbose {
+        eprintln!("  → {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Write text to a file, or to stdout when no path is given
+fn write_output(path: Option<&PathBuf>, output: &str) -> Result<()> {
+    match path {
+        Some(path) => fs::write(path, output)
+            .with_context(|| format!("Failed to write output file: {}", path.display())),
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
+
+/// Build the transformation pipeline of a format from the pass flags, --only-pass and --skip-pass
+///
+/// --only-pass and --skip-pass are applied last, so that they take precedence
+/// over the passes enabled by --format, --expand-instances, --layout-css, --text-runs,
+/// --gradient-css, --effects-css and --absolute-bounds.
+fn build_pipeline(cli: &Cli) -> Result<Pipeline> {
+    let mut pipeline = match cli.format {
+        OutputFormat::Outline => fig2json::outline::pipeline(),
+        _ => Pipeline::standard(),
+    };
+
+    if cli.format == OutputFormat::Html {
+        // Auto-layout frames are rendered as flexbox, instances with the layers of their component
+        pipeline.disable("redundant_padding_removal")?;
+        pipeline.disable("stack_align_items_removal")?;
+        pipeline.enable("instance_expansion")?;
+        pipeline.enable("gradient_to_css")?;
+        pipeline.enable("effects_to_css")?;
+    }
+    if cli.expand_instances {
+        pipeline.enable("instance_expansion")?;
+    }
+    if cli.format == OutputFormat::Tailwind {
+        pipeline.enable("tailwind")?;
+    }
+    if cli.layout_css {
+        pipeline.enable("layout")?;
+    }
+    if cli.text_runs {
+        pipeline.enable("text_runs")?;
+    }
+    if cli.gradient_css {
+        pipeline.enable("gradient_to_css")?;
+    }
+    if cli.effects_css {
+        pipeline.enable("effects_to_css")?;
+    }
+    if cli.absolute_bounds {
+        pipeline.enable("absolute_bounds")?;
+    }
+
+    if !cli.only_pass.is_empty() {
+        pipeline.only(&cli.only_pass)?;
+    }
+
+    for name in &cli.skip_pass {
+        pipeline.disable(name)?;
+    }
+
+    Ok(pipeline)
+}
+
 /// Recursively find all .fig files in a directory
 fn find_fig_files(dir: &PathBuf) -> Result<Vec<PathBuf>> {
     let mut fig_files = Vec::new();
@@ -0,0 +1,279 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Serialized forms accepted for Kiwi enums
+///
+/// The decoder emits `{"__enum__": "BlendMode", "value": "NORMAL"}` while the
+/// transformed output (after `enum_simplification`) uses plain `"NORMAL"`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EnumRepr {
+    Plain(String),
+    Kiwi { value: String },
+}
+
+impl EnumRepr {
+    fn into_value(self) -> String {
+        match self {
+            EnumRepr::Plain(value) | EnumRepr::Kiwi { value } => value,
+        }
+    }
+}
+
+/// Declare a Kiwi enum with its known variants and an `Other` fallback
+macro_rules! kiwi_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", $value, "`")]
+                $variant,
+            )*
+            /// Value not known to this version of fig2json
+            Other(String),
+        }
+
+        impl $name {
+            /// Name of the variant as stored in the .fig file
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Other(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Other(other.to_string()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let value = EnumRepr::deserialize(deserializer)?.into_value();
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
+
+kiwi_enum! {
+    /// Kind of a node
+    NodeType {
+        Document => "DOCUMENT",
+        Canvas => "CANVAS",
+        Group => "GROUP",
+        Frame => "FRAME",
+        BooleanOperation => "BOOLEAN_OPERATION",
+        Vector => "VECTOR",
+        Star => "STAR",
+        Line => "LINE",
+        Ellipse => "ELLIPSE",
+        Rectangle => "RECTANGLE",
+        RegularPolygon => "REGULAR_POLYGON",
+        RoundedRectangle => "ROUNDED_RECTANGLE",
+        Text => "TEXT",
+        Slice => "SLICE",
+        Symbol => "SYMBOL",
+        Instance => "INSTANCE",
+        Sticky => "STICKY",
+        ShapeWithText => "SHAPE_WITH_TEXT",
+        Connector => "CONNECTOR",
+        Section => "SECTION",
+        Table => "TABLE",
+    }
+}
+
+kiwi_enum! {
+    /// Blend mode of a node, paint or effect
+    BlendMode {
+        PassThrough => "PASS_THROUGH",
+        Normal => "NORMAL",
+        Darken => "DARKEN",
+        Multiply => "MULTIPLY",
+        ColorBurn => "COLOR_BURN",
+        Lighten => "LIGHTEN",
+        Screen => "SCREEN",
+        ColorDodge => "COLOR_DODGE",
+        Overlay => "OVERLAY",
+        SoftLight => "SOFT_LIGHT",
+        HardLight => "HARD_LIGHT",
+        Difference => "DIFFERENCE",
+        Exclusion => "EXCLUSION",
+        Hue => "HUE",
+        Saturation => "SATURATION",
+        Color => "COLOR",
+        Luminosity => "LUMINOSITY",
+    }
+}
+
+kiwi_enum! {
+    /// Kind of a fill or stroke paint
+    PaintType {
+        Solid => "SOLID",
+        GradientLinear => "GRADIENT_LINEAR",
+        GradientRadial => "GRADIENT_RADIAL",
+        GradientAngular => "GRADIENT_ANGULAR",
+        GradientDiamond => "GRADIENT_DIAMOND",
+        Image => "IMAGE",
+        Emoji => "EMOJI",
+        Video => "VIDEO",
+    }
+}
+
+kiwi_enum! {
+    /// How an image paint is fitted into its node
+    ImageScaleMode {
+        Stretch => "STRETCH",
+        Fit => "FIT",
+        Fill => "FILL",
+        Tile => "TILE",
+    }
+}
+
+kiwi_enum! {
+    /// Kind of a visual effect
+    EffectType {
+        InnerShadow => "INNER_SHADOW",
+        DropShadow => "DROP_SHADOW",
+        ForegroundBlur => "FOREGROUND_BLUR",
+        BackgroundBlur => "BACKGROUND_BLUR",
+    }
+}
+
+kiwi_enum! {
+    /// Position of a stroke relative to the node outline
+    StrokeAlign {
+        Center => "CENTER",
+        Inside => "INSIDE",
+        Outside => "OUTSIDE",
+    }
+}
+
+kiwi_enum! {
+    /// Unit of a letter spacing or line height value
+    NumberUnits {
+        Raw => "RAW",
+        Pixels => "PIXELS",
+        Percent => "PERCENT",
+    }
+}
+
+kiwi_enum! {
+    /// Horizontal alignment of text
+    TextAlignHorizontal {
+        Left => "LEFT",
+        Center => "CENTER",
+        Right => "RIGHT",
+        Justified => "JUSTIFIED",
+    }
+}
+
+kiwi_enum! {
+    /// Vertical alignment of text in its box
+    TextAlignVertical {
+        Top => "TOP",
+        Center => "CENTER",
+        Bottom => "BOTTOM",
+    }
+}
+
+kiwi_enum! {
+    /// How a text box grows with its content
+    TextAutoResize {
+        None => "NONE",
+        WidthAndHeight => "WIDTH_AND_HEIGHT",
+        Height => "HEIGHT",
+    }
+}
+
+kiwi_enum! {
+    /// Letter case transformation of text
+    TextCase {
+        Original => "ORIGINAL",
+        Upper => "UPPER",
+        Lower => "LOWER",
+        Title => "TITLE",
+        SmallCaps => "SMALL_CAPS",
+        SmallCapsForced => "SMALL_CAPS_FORCED",
+    }
+}
+
+kiwi_enum! {
+    /// Line decoration of text
+    TextDecoration {
+        None => "NONE",
+        Underline => "UNDERLINE",
+        Strikethrough => "STRIKETHROUGH",
+    }
+}
+
+kiwi_enum! {
+    /// Auto-layout direction of a frame
+    StackMode {
+        None => "NONE",
+        Horizontal => "HORIZONTAL",
+        Vertical => "VERTICAL",
+    }
+}
+
+kiwi_enum! {
+    /// Auto-layout alignment along the primary or counter axis
+    StackAlign {
+        Min => "MIN",
+        Center => "CENTER",
+        Max => "MAX",
+        SpaceEvenly => "SPACE_EVENLY",
+        SpaceBetween => "SPACE_BETWEEN",
+        Baseline => "BASELINE",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_kiwi_enum_object() {
+        let value = json!({"__enum__": "NodeType", "value": "FRAME"});
+        let node_type: NodeType = serde_json::from_value(value).unwrap();
+        assert_eq!(node_type, NodeType::Frame);
+    }
+
+    #[test]
+    fn test_deserialize_plain_string() {
+        let blend_mode: BlendMode = serde_json::from_value(json!("MULTIPLY")).unwrap();
+        assert_eq!(blend_mode, BlendMode::Multiply);
+    }
+
+    #[test]
+    fn test_unknown_variant_is_preserved() {
+        let node_type: NodeType = serde_json::from_value(json!("WIDGET")).unwrap();
+        assert_eq!(node_type, NodeType::Other("WIDGET".to_string()));
+        assert_eq!(serde_json::to_value(&node_type).unwrap(), json!("WIDGET"));
+    }
+
+    #[test]
+    fn test_serialize_as_string() {
+        assert_eq!(serde_json::to_value(PaintType::GradientLinear).unwrap(), json!("GRADIENT_LINEAR"));
+        assert_eq!(StackMode::Vertical.to_string(), "VERTICAL");
+    }
+}
@@ -0,0 +1,310 @@
+//! Typed model of the document tree
+//!
+//! The decoder produces an untyped [`serde_json::Value`]. This module offers
+//! serde structs for the commonly used parts of it (nodes, paints, effects,
+//! text styles, transforms, auto-layout) so that integrations do not have to
+//! probe fields by string. Fields that are not modelled are kept in the
+//! `extra` map of each struct.
+//!
+//! Enums accept both the raw decoder form (`{"__enum__": "NodeType", "value": "FRAME"}`)
+//! and plain strings, so the model can be built from [`crate::convert_raw`]
+//! output as well as from partially transformed JSON.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::model::{Document, NodeType};
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let document = Document::from_fig(&bytes).unwrap();
+//!
+//! document.walk(|node, ancestors| {
+//!     if node.node_type == NodeType::Text {
+//!         let page = ancestors.page().map(|page| page.name()).unwrap_or("");
+//!         println!("{}: {}", page, node.characters().unwrap_or(""));
+//!     }
+//! });
+//! ```
+
+pub mod enums;
+pub mod node;
+pub mod paint;
+pub mod text;
+pub mod walk;
+
+pub use enums::{
+    BlendMode, EffectType, ImageScaleMode, NodeType, NumberUnits, PaintType, StackAlign, StackMode,
+    StrokeAlign, TextAlignHorizontal, TextAlignVertical, TextAutoResize, TextCase, TextDecoration,
+};
+pub use node::{AutoLayout, Guid, Matrix, Node, SymbolData, Vector};
+pub use paint::{Color, ColorStop, Effect, ImageRef, Paint};
+pub use text::{FontName, StyleOverride, TextData, TextStyle, UnitValue};
+pub use walk::Ancestors;
+
+use crate::error::Result;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value as JsonValue;
+
+/// Deserialize a float that the decoder may have written as `null`
+///
+/// The decoder emits non-finite floats (NaN, infinities) as `null`. They are
+/// read back as NaN so that a single bad value does not fail the whole
+/// document; serializing NaN writes `null` again.
+pub(crate) fn lenient_f64<'de, D>(deserializer: D) -> std::result::Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NAN))
+}
+
+/// A decoded file: metadata and the root DOCUMENT node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    /// File format version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    /// `figma` or `figjam`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_type: Option<String>,
+    /// Root DOCUMENT node
+    #[serde(rename = "document")]
+    pub root: Node,
+}
+
+impl Document {
+    /// Decode a .fig file into the typed model
+    ///
+    /// # Arguments
+    /// * `bytes` - Raw bytes from the .fig file
+    ///
+    /// # Returns
+    /// * `Ok(Document)` - Typed document
+    /// * `Err(FigError)` - If decoding fails
+    pub fn from_fig(bytes: &[u8]) -> Result<Self> {
+        Self::from_value(crate::convert_raw(bytes)?)
+    }
+
+    /// Build the typed model from JSON
+    ///
+    /// Accepts either a conversion output (an object with a `document` field)
+    /// or a bare node.
+    ///
+    /// # Examples
+    /// ```
+    /// use fig2json::model::{Document, NodeType};
+    /// use serde_json::json;
+    ///
+    /// let document = Document::from_value(json!({
+    ///     "type": {"__enum__": "NodeType", "value": "DOCUMENT"},
+    ///     "children": [{"type": "CANVAS", "name": "Page 1"}]
+    /// }))
+    /// .unwrap();
+    ///
+    /// assert_eq!(document.root.children[0].node_type, NodeType::Canvas);
+    /// ```
+    pub fn from_value(value: JsonValue) -> Result<Self> {
+        if value.get("document").is_some() {
+            Ok(serde_json::from_value(value)?)
+        } else {
+            Ok(Self {
+                version: None,
+                file_type: None,
+                root: serde_json::from_value(value)?,
+            })
+        }
+    }
+
+    /// Convert back to JSON (enums are written as plain strings)
+    pub fn to_value(&self) -> Result<JsonValue> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    /// Visit every node depth-first, parents before children
+    ///
+    /// The visitor receives each node together with its [`Ancestors`].
+    pub fn walk<'n, F>(&'n self, visitor: F)
+    where
+        F: FnMut(&'n Node, Ancestors<'_, 'n>),
+    {
+        self.root.walk(visitor);
+    }
+}
+
+impl Node {
+    /// Visit this node and its descendants depth-first, parents before children
+    pub fn walk<'n, F>(&'n self, mut visitor: F)
+    where
+        F: FnMut(&'n Node, Ancestors<'_, 'n>),
+    {
+        walk::walk_node(self, &mut Vec::new(), &mut visitor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn raw_text() -> JsonValue {
+        json!({
+            "guid": {"sessionID": 1, "localID": 3},
+            "type": {"__enum__": "NodeType", "value": "TEXT"},
+            "name": "Title",
+            "fontSize": 16.0,
+            "fontName": {"family": "Inter", "style": "Bold", "postscript": ""},
+            "lineHeight": {"value": 150.0, "units": {"__enum__": "NumberUnits", "value": "PERCENT"}},
+            "textData": {
+                "characters": "Hello",
+                "characterStyleIDs": [0, 0, 1, 1, 1],
+                "styleOverrideTable": [{"styleID": 1, "fontSize": 20.0}],
+                "lines": [{"lineType": "PLAIN"}]
+            }
+        })
+    }
+
+    fn raw_frame() -> JsonValue {
+        json!({
+            "guid": {"sessionID": 1, "localID": 2},
+            "type": {"__enum__": "NodeType", "value": "FRAME"},
+            "name": "Card",
+            "size": {"x": 200.0, "y": 100.0},
+            "transform": {"m00": 1.0, "m01": 0.0, "m02": 10.0, "m10": 0.0, "m11": 1.0, "m12": 20.0},
+            "stackMode": {"__enum__": "StackMode", "value": "VERTICAL"},
+            "stackSpacing": 8.0,
+            "fillPaints": [{
+                "type": {"__enum__": "PaintType", "value": "SOLID"},
+                "color": {"r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0},
+                "opacity": 1.0
+            }],
+            "effects": [{
+                "type": {"__enum__": "EffectType", "value": "DROP_SHADOW"},
+                "color": {"r": 0.0, "g": 0.0, "b": 0.0, "a": 0.25},
+                "offset": {"x": 0.0, "y": 4.0},
+                "radius": 8.0
+            }],
+            "frameMaskDisabled": true,
+            "children": [raw_text()]
+        })
+    }
+
+    fn raw_document() -> JsonValue {
+        json!({
+            "version": 101,
+            "fileType": "figma",
+            "blobs": [],
+            "document": {
+                "guid": {"sessionID": 0, "localID": 0},
+                "type": {"__enum__": "NodeType", "value": "DOCUMENT"},
+                "name": "Document",
+                "children": [{
+                    "guid": {"sessionID": 0, "localID": 1},
+                    "type": {"__enum__": "NodeType", "value": "CANVAS"},
+                    "name": "Page 1",
+                    "children": [raw_frame()]
+                }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_from_raw_output() {
+        let document = Document::from_value(raw_document()).unwrap();
+        assert_eq!(document.version, Some(101));
+        assert_eq!(document.file_type.as_deref(), Some("figma"));
+
+        let frame = &document.root.children[0].children[0];
+        assert_eq!(frame.node_type, NodeType::Frame);
+        assert_eq!(frame.guid.unwrap().to_string(), "1:2");
+        assert_eq!(frame.layout.stack_mode, Some(StackMode::Vertical));
+        assert_eq!(frame.layout.stack_spacing, Some(8.0));
+        assert_eq!(frame.transform.unwrap().translation(), Vector { x: 10.0, y: 20.0 });
+        assert_eq!(frame.fill_paints[0].paint_type, PaintType::Solid);
+        assert_eq!(frame.effects[0].effect_type, EffectType::DropShadow);
+        assert_eq!(frame.extra.get("frameMaskDisabled"), Some(&json!(true)));
+    }
+
+    #[test]
+    fn test_text_fields() {
+        let document = Document::from_value(raw_document()).unwrap();
+        let text = &document.root.children[0].children[0].children[0];
+
+        assert_eq!(text.characters(), Some("Hello"));
+        assert_eq!(text.text_style.font_size, Some(16.0));
+        assert_eq!(text.text_style.font_name.as_ref().unwrap().family, "Inter");
+        assert_eq!(text.text_style.line_height.as_ref().unwrap().units, NumberUnits::Percent);
+
+        let text_data = text.text_data.as_ref().unwrap();
+        assert_eq!(text_data.character_style_ids, vec![0, 0, 1, 1, 1]);
+        assert_eq!(text_data.style_override_table[0].style.font_size, Some(20.0));
+        assert!(text_data.extra.contains_key("lines"));
+        // Typed fields are not duplicated in extra
+        assert!(!text.extra.contains_key("fontSize"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let document = Document::from_value(raw_document()).unwrap();
+        let value = document.to_value().unwrap();
+
+        assert_eq!(value["document"]["type"], json!("DOCUMENT"));
+        assert_eq!(value["document"]["children"][0]["children"][0]["frameMaskDisabled"], json!(true));
+        assert_eq!(Document::from_value(value).unwrap(), document);
+    }
+
+    #[test]
+    fn test_walk_passes_ancestors() {
+        let document = Document::from_value(raw_document()).unwrap();
+        let mut visited = Vec::new();
+
+        document.walk(|node, ancestors| {
+            visited.push((
+                node.name().to_string(),
+                ancestors.depth(),
+                ancestors.parent().map(|parent| parent.name().to_string()),
+                ancestors.page().map(|page| page.name().to_string()),
+            ));
+        });
+
+        assert_eq!(
+            visited,
+            vec![
+                ("Document".to_string(), 0, None, None),
+                ("Page 1".to_string(), 1, Some("Document".to_string()), None),
+                ("Card".to_string(), 2, Some("Page 1".to_string()), Some("Page 1".to_string())),
+                ("Title".to_string(), 3, Some("Card".to_string()), Some("Page 1".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_ancestor_names() {
+        let document = Document::from_value(raw_document()).unwrap();
+        let mut title_path = Vec::new();
+
+        document.walk(|node, ancestors| {
+            if node.node_type == NodeType::Text {
+                title_path = ancestors.names();
+            }
+        });
+
+        assert_eq!(title_path, vec!["Document", "Page 1", "Card"]);
+    }
+
+    #[test]
+    fn test_null_floats_are_read_as_nan() {
+        let mut frame = raw_frame();
+        frame["transform"]["m02"] = JsonValue::Null;
+        frame["size"]["x"] = JsonValue::Null;
+        frame["fillPaints"][0]["color"]["r"] = JsonValue::Null;
+
+        let node: Node = serde_json::from_value(frame).unwrap();
+        assert!(node.transform.unwrap().m02.is_nan());
+        assert!(node.size.unwrap().x.is_nan());
+        assert!(node.fill_paints[0].color.unwrap().r.is_nan());
+        assert_eq!(node.size.unwrap().y, 100.0);
+
+        let value = serde_json::to_value(&node).unwrap();
+        assert_eq!(value["transform"]["m02"], JsonValue::Null);
+    }
+}
@@ -0,0 +1,293 @@
+use super::enums::{
+    BlendMode, NodeType, StackAlign, StackMode, StrokeAlign, TextAlignHorizontal, TextAlignVertical,
+    TextAutoResize,
+};
+use super::lenient_f64;
+use super::paint::{Effect, Paint};
+use super::text::{TextData, TextStyle};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::fmt;
+
+/// Unique identifier of a node within a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Guid {
+    #[serde(rename = "sessionID")]
+    pub session_id: u32,
+    #[serde(rename = "localID")]
+    pub local_id: u32,
+}
+
+impl fmt::Display for Guid {
+    /// Formats as `sessionID:localID`, the notation used by Figma URLs and `build_tree`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.session_id, self.local_id)
+    }
+}
+
+/// 2D vector (sizes, offsets)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Vector {
+    #[serde(deserialize_with = "lenient_f64")]
+    pub x: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub y: f64,
+}
+
+/// 2D affine transformation matrix
+///
+/// Maps `(x, y)` to `(m00 * x + m01 * y + m02, m10 * x + m11 * y + m12)`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Matrix {
+    #[serde(deserialize_with = "lenient_f64")]
+    pub m00: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub m01: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub m02: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub m10: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub m11: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub m12: f64,
+}
+
+impl Matrix {
+    /// Identity matrix
+    pub const IDENTITY: Matrix = Matrix {
+        m00: 1.0,
+        m01: 0.0,
+        m02: 0.0,
+        m10: 0.0,
+        m11: 1.0,
+        m12: 0.0,
+    };
+
+    /// Translation part of the matrix
+    pub fn translation(&self) -> Vector {
+        Vector {
+            x: self.m02,
+            y: self.m12,
+        }
+    }
+
+    /// Rotation angle in degrees
+    pub fn rotation_degrees(&self) -> f64 {
+        self.m10.atan2(self.m00).to_degrees()
+    }
+
+    /// Matrix of a raw `transform`, or of one decomposed by `matrix_to_css`
+    /// (`x`, `y`, `rotation`, `scaleX`, `scaleY`, `skewX`)
+    pub(crate) fn from_transform(transform: &JsonValue) -> Option<Matrix> {
+        let field = |key: &str| transform.get(key).and_then(|v| v.as_f64());
+        if let Some(m00) = field("m00") {
+            return Some(Matrix {
+                m00,
+                m01: field("m01")?,
+                m02: field("m02")?,
+                m10: field("m10")?,
+                m11: field("m11")?,
+                m12: field("m12")?,
+            });
+        }
+
+        let rotation = field("rotation").unwrap_or(0.0).to_radians();
+        let skew = field("skewX").unwrap_or(0.0).to_radians();
+        let scale_x = field("scaleX").unwrap_or(1.0);
+        let scale_y = field("scaleY").unwrap_or(1.0);
+        let (sin, cos) = rotation.sin_cos();
+        Some(Matrix {
+            m00: scale_x * cos,
+            m01: scale_x * skew.tan() * cos - scale_y * sin,
+            m02: field("x")?,
+            m10: scale_x * sin,
+            m11: scale_x * skew.tan() * sin + scale_y * cos,
+            m12: field("y")?,
+        })
+    }
+
+    /// Matrix applying `inner` first, then `self`
+    pub fn multiply(&self, inner: &Matrix) -> Matrix {
+        Matrix {
+            m00: self.m00 * inner.m00 + self.m01 * inner.m10,
+            m01: self.m00 * inner.m01 + self.m01 * inner.m11,
+            m02: self.m00 * inner.m02 + self.m01 * inner.m12 + self.m02,
+            m10: self.m10 * inner.m00 + self.m11 * inner.m10,
+            m11: self.m10 * inner.m01 + self.m11 * inner.m11,
+            m12: self.m10 * inner.m02 + self.m11 * inner.m12 + self.m12,
+        }
+    }
+
+    /// Inverse of the matrix, if it is not singular
+    pub fn invert(&self) -> Option<Matrix> {
+        let determinant = self.m00 * self.m11 - self.m01 * self.m10;
+        if determinant.abs() < 1e-12 {
+            return None;
+        }
+        Some(Matrix {
+            m00: self.m11 / determinant,
+            m01: -self.m01 / determinant,
+            m02: (self.m01 * self.m12 - self.m11 * self.m02) / determinant,
+            m10: -self.m10 / determinant,
+            m11: self.m00 / determinant,
+            m12: (self.m10 * self.m02 - self.m00 * self.m12) / determinant,
+        })
+    }
+
+    /// Map the point `(x, y)` through the matrix
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.m00 * x + self.m01 * y + self.m02,
+            self.m10 * x + self.m11 * y + self.m12,
+        )
+    }
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Matrix::IDENTITY
+    }
+}
+
+/// Auto-layout properties of a frame
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoLayout {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_mode: Option<StackMode>,
+    /// Gap between children
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_spacing: Option<f64>,
+    /// Left padding (also right padding unless `stack_padding_right` is set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_horizontal_padding: Option<f64>,
+    /// Top padding (also bottom padding unless `stack_padding_bottom` is set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_vertical_padding: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_padding_right: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_padding_bottom: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_primary_align_items: Option<StackAlign>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_counter_align_items: Option<StackAlign>,
+}
+
+/// Component reference of an INSTANCE node
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolData {
+    /// GUID of the SYMBOL node this instance was created from
+    #[serde(rename = "symbolID", default, skip_serializing_if = "Option::is_none")]
+    pub symbol_id: Option<Guid>,
+    /// Property overrides applied on top of the component, keyed by `guidPath`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symbol_overrides: Vec<JsonValue>,
+    #[serde(flatten)]
+    pub extra: Map<String, JsonValue>,
+}
+
+/// A node of the document tree
+///
+/// Only the most commonly used fields are typed; everything else is kept in
+/// [`Node::extra`] so that no information is lost.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Node {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guid: Option<Guid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visible: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blend_mode: Option<BlendMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Vector>,
+    /// Transform relative to the parent node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<Matrix>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fill_paints: Vec<Paint>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stroke_paints: Vec<Paint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke_weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke_align: Option<StrokeAlign>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effects: Vec<Effect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corner_radius: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_data: Option<TextData>,
+    #[serde(flatten)]
+    pub text_style: TextStyle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_align_horizontal: Option<TextAlignHorizontal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_align_vertical: Option<TextAlignVertical>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_auto_resize: Option<TextAutoResize>,
+    #[serde(flatten)]
+    pub layout: AutoLayout,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_data: Option<SymbolData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Node>,
+    /// Fields not modelled above
+    #[serde(flatten)]
+    pub extra: Map<String, JsonValue>,
+}
+
+impl Node {
+    /// Create a node of the given type with every optional field unset
+    pub fn new(node_type: NodeType) -> Self {
+        Self {
+            guid: None,
+            name: None,
+            node_type,
+            visible: None,
+            opacity: None,
+            blend_mode: None,
+            size: None,
+            transform: None,
+            fill_paints: Vec::new(),
+            stroke_paints: Vec::new(),
+            stroke_weight: None,
+            stroke_align: None,
+            effects: Vec::new(),
+            corner_radius: None,
+            text_data: None,
+            text_style: TextStyle::default(),
+            text_align_horizontal: None,
+            text_align_vertical: None,
+            text_auto_resize: None,
+            layout: AutoLayout::default(),
+            symbol_data: None,
+            children: Vec::new(),
+            extra: Map::new(),
+        }
+    }
+
+    /// Name of the node, or an empty string
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("")
+    }
+
+    /// Check if the node is rendered (`visible` defaults to true)
+    pub fn is_visible(&self) -> bool {
+        self.visible.unwrap_or(true)
+    }
+
+    /// Text content of a TEXT node
+    pub fn characters(&self) -> Option<&str> {
+        self.text_data.as_ref().map(|data| data.characters.as_str())
+    }
+}
@@ -0,0 +1,110 @@
+use super::enums::{BlendMode, EffectType, ImageScaleMode, PaintType};
+use super::lenient_f64;
+use super::node::{Matrix, Vector};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+/// RGBA color with components in the 0.0-1.0 range
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Color {
+    #[serde(deserialize_with = "lenient_f64")]
+    pub r: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub g: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub b: f64,
+    #[serde(default = "opaque", deserialize_with = "lenient_f64")]
+    pub a: f64,
+}
+
+fn opaque() -> f64 {
+    1.0
+}
+
+/// Color at a given position of a gradient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorStop {
+    pub color: Color,
+    /// Position along the gradient (0.0-1.0)
+    #[serde(deserialize_with = "lenient_f64")]
+    pub position: f64,
+}
+
+/// Reference to an image stored next to the canvas file
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ImageRef {
+    /// SHA-1 hash of the image file, as bytes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hash: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, JsonValue>,
+}
+
+/// Fill or stroke paint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Paint {
+    #[serde(rename = "type")]
+    pub paint_type: PaintType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visible: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blend_mode: Option<BlendMode>,
+    /// Gradient stops (gradient paints only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stops: Vec<ColorStop>,
+    /// Gradient or image transform in the node's unit square
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<Matrix>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<ImageRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_scale_mode: Option<ImageScaleMode>,
+    #[serde(flatten)]
+    pub extra: Map<String, JsonValue>,
+}
+
+impl Paint {
+    /// Check if the paint is rendered (`visible` defaults to true)
+    pub fn is_visible(&self) -> bool {
+        self.visible.unwrap_or(true)
+    }
+}
+
+/// Shadow or blur effect
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Effect {
+    #[serde(rename = "type")]
+    pub effect_type: EffectType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<Vector>,
+    /// Blur radius
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub radius: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spread: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visible: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blend_mode: Option<BlendMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show_shadow_behind_node: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Map<String, JsonValue>,
+}
+
+impl Effect {
+    /// Check if the effect is rendered (`visible` defaults to true)
+    pub fn is_visible(&self) -> bool {
+        self.visible.unwrap_or(true)
+    }
+}
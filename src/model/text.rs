@@ -0,0 +1,72 @@
+use super::enums::{NumberUnits, TextCase, TextDecoration};
+use super::lenient_f64;
+use super::paint::Paint;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+/// Font family and style
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FontName {
+    pub family: String,
+    pub style: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postscript: Option<String>,
+}
+
+/// Number with a unit, used for letter spacing and line height
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnitValue {
+    #[serde(deserialize_with = "lenient_f64")]
+    pub value: f64,
+    pub units: NumberUnits,
+}
+
+/// Character-level text style
+///
+/// Flattened into [`Node`](super::Node) for the node-wide style and into
+/// [`StyleOverride`] for styled runs.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_name: Option<FontName>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub letter_spacing: Option<UnitValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_height: Option<UnitValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_case: Option<TextCase>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_decoration: Option<TextDecoration>,
+}
+
+/// Style applied to the characters whose style ID is `style_id`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StyleOverride {
+    #[serde(rename = "styleID")]
+    pub style_id: u32,
+    #[serde(flatten)]
+    pub style: TextStyle,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fill_paints: Vec<Paint>,
+    #[serde(flatten)]
+    pub extra: Map<String, JsonValue>,
+}
+
+/// Content of a TEXT node
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextData {
+    #[serde(default)]
+    pub characters: String,
+    /// Style ID of each character (0 = node style); empty when the text has a single style
+    #[serde(rename = "characterStyleIDs", default, skip_serializing_if = "Vec::is_empty")]
+    pub character_style_ids: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub style_override_table: Vec<StyleOverride>,
+    #[serde(flatten)]
+    pub extra: Map<String, JsonValue>,
+}
@@ -0,0 +1,66 @@
+use super::enums::NodeType;
+use super::node::Node;
+
+/// Chain of nodes above the visited node, from the root down to its parent
+#[derive(Debug, Clone, Copy)]
+pub struct Ancestors<'a, 'n> {
+    nodes: &'a [&'n Node],
+}
+
+impl<'a, 'n> Ancestors<'a, 'n> {
+    /// All ancestors, root first
+    pub fn as_slice(&self) -> &'a [&'n Node] {
+        self.nodes
+    }
+
+    /// Direct parent of the visited node (`None` for the root)
+    pub fn parent(&self) -> Option<&'n Node> {
+        self.nodes.last().copied()
+    }
+
+    /// Depth of the visited node (0 for the root)
+    pub fn depth(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Page (CANVAS node) containing the visited node
+    pub fn page(&self) -> Option<&'n Node> {
+        self.nodes
+            .iter()
+            .copied()
+            .find(|node| node.node_type == NodeType::Canvas)
+    }
+
+    /// Nearest ancestor of the given type
+    pub fn nearest(&self, node_type: &NodeType) -> Option<&'n Node> {
+        self.nodes
+            .iter()
+            .rev()
+            .copied()
+            .find(|node| &node.node_type == node_type)
+    }
+
+    /// Check if every ancestor is visible
+    pub fn all_visible(&self) -> bool {
+        self.nodes.iter().all(|node| node.is_visible())
+    }
+
+    /// Names of the ancestors, root first
+    pub fn names(&self) -> Vec<&'n str> {
+        self.nodes.iter().map(|node| node.name()).collect()
+    }
+}
+
+/// Depth-first, pre-order traversal of `node` and its descendants
+pub(crate) fn walk_node<'n, F>(node: &'n Node, stack: &mut Vec<&'n Node>, visitor: &mut F)
+where
+    F: FnMut(&'n Node, Ancestors<'_, 'n>),
+{
+    visitor(node, Ancestors { nodes: stack });
+
+    stack.push(node);
+    for child in &node.children {
+        walk_node(child, stack, visitor);
+    }
+    stack.pop();
+}
@@ -0,0 +1,378 @@
+//! Outline output
+//!
+//! [`to_outline`] renders the transformed tree as indented text, one line per
+//! node with its important properties inline:
+//!
+//! ```text
+//! Page "Home"
+//!   Frame "Header" 1440×80 flex-row gap 24 pad 16 32 align center bg #ffffff
+//!     Text "Title" 200×40 color #111111 font 700 32 Inter "Welcome back"
+//!     Instance "Sign in" of Button [Size=Small] 96×40 at 1312,20
+//! ```
+//!
+//! Properties come from the same CSS declarations as the HTML output
+//! ([`node_declarations`]), shortened: `flex-row`/`flex-col`, `gap`, `pad`,
+//! `justify`, `align`, `bg`, `border`, `radius`, `font <weight> <size>[/<line
+//! height>] <family>`, `color`, and `at x,y` for absolutely positioned nodes.
+//! Other declarations are kept as `property value`. Lengths are in pixels,
+//! without unit.
+//!
+//! It takes a small fraction of the tokens of the JSON output.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::outline::{pipeline, to_outline};
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let json = fig2json::convert_with_pipeline(&bytes, None, &pipeline()).unwrap();
+//!
+//! print!("{}", to_outline(&json, Some(3)));
+//! ```
+
+use crate::css::{format_number, Declaration};
+use crate::html::css::{node_declarations, Placement};
+use crate::pipeline::Pipeline;
+use serde_json::{Map, Value as JsonValue};
+use std::fmt::Write;
+
+/// Passes of the standard pipeline removing fields shown in the outline
+const DISABLED_PASSES: &[&str] = &[
+    "type_removal",
+    "redundant_padding_removal",
+    "stack_align_items_removal",
+];
+
+/// Declarations that are implied by other tokens or irrelevant in an outline
+const SKIPPED_DECLARATIONS: &[&str] = &[
+    "position",
+    "width",
+    "height",
+    "flex-shrink",
+    "transform-origin",
+    "background-size",
+    "background-position",
+    "background-repeat",
+    "font-style",
+];
+
+/// Standard pipeline keeping the node types and auto-layout properties, with
+/// component instances expanded and gradients and effects written as CSS
+pub fn pipeline() -> Pipeline {
+    let mut pipeline = Pipeline::standard_without(DISABLED_PASSES);
+    for name in ["instance_expansion", "gradient_to_css", "effects_to_css"] {
+        pipeline.enable(name).expect("pass of the standard pipeline");
+    }
+    pipeline
+}
+
+/// Render a transformed tree as an indented outline
+///
+/// Pages are at the first level, their top-level frames at the second, and
+/// so on. With a depth limit, nodes deeper than `depth` levels below the
+/// pages are left out, and the last level shown tells how many nodes it
+/// hides (`+12 nodes`).
+///
+/// # Arguments
+/// * `output` - Output of [`convert`](crate::convert) (with [`pipeline`] to
+///   keep node types), or its `document` node
+/// * `depth` - Number of levels shown below the pages, `None` for all
+///
+/// # Returns
+/// One line per node, each ending with a newline
+///
+/// # Examples
+/// ```
+/// use fig2json::outline::to_outline;
+/// use serde_json::json;
+///
+/// let output = json!({"document": {"children": [{"type": "CANVAS", "name": "Home", "children": [{
+///     "type": "FRAME",
+///     "name": "Header",
+///     "size": {"x": 1440.0, "y": 80.0},
+///     "stackMode": "HORIZONTAL",
+///     "stackSpacing": 24.0,
+///     "fillPaints": [{"type": "SOLID", "color": "#ffffff"}]
+/// }]}]}});
+///
+/// assert_eq!(
+///     to_outline(&output, None),
+///     "Page \"Home\"\n  Frame \"Header\" 1440×80 flex-row gap 24 bg #ffffff\n"
+/// );
+/// ```
+pub fn to_outline(output: &JsonValue, depth: Option<usize>) -> String {
+    let document = output.get("document").unwrap_or(output).as_object();
+    let mut outline = String::new();
+    for page in document.into_iter().flat_map(children) {
+        write_node(&mut outline, page, Placement::Root, 0, depth);
+    }
+    outline
+}
+
+fn write_node(
+    outline: &mut String,
+    node: &Map<String, JsonValue>,
+    placement: Placement,
+    level: usize,
+    depth: Option<usize>,
+) {
+    let indent = "  ".repeat(level);
+    let _ = write!(
+        outline,
+        "{}{} {}",
+        indent,
+        type_name(node),
+        quote(name(node))
+    );
+
+    if let Some(component) = node.get("componentName").and_then(|c| c.as_str()) {
+        let _ = write!(outline, " of {}", component);
+        if let Some(variant) = node.get("variant").and_then(|v| v.as_str()) {
+            let _ = write!(outline, " [{}]", variant);
+        }
+    }
+    if level > 0 {
+        for token in tokens(node, placement) {
+            let _ = write!(outline, " {}", token);
+        }
+    }
+    if node.get("visible").and_then(|v| v.as_bool()) == Some(false) {
+        outline.push_str(" hidden");
+    }
+    if let Some(text) = node
+        .get("textData")
+        .and_then(|t| t.get("characters"))
+        .and_then(|c| c.as_str())
+    {
+        let _ = write!(outline, " {}", quote(text));
+    }
+
+    if depth.is_some_and(|depth| level >= depth) {
+        let hidden = count_descendants(node);
+        if hidden > 0 {
+            let _ = write!(outline, " +{} nodes", hidden);
+        }
+        outline.push('\n');
+        return;
+    }
+    outline.push('\n');
+
+    let child_placement = match level {
+        0 => Placement::Root,
+        _ => Placement::of_children(node),
+    };
+    for child in children(node) {
+        write_node(
+            outline,
+            child,
+            child_placement.for_child(child),
+            level + 1,
+            depth,
+        );
+    }
+}
+
+/// Inline properties of a node
+fn tokens(node: &Map<String, JsonValue>, placement: Placement) -> Vec<String> {
+    let mut tokens = Vec::new();
+    if let Some(size) = node.get("size") {
+        let dimension = |key: &str| size.get(key).and_then(|v| v.as_f64()).map(format_number);
+        if let (Some(width), Some(height)) = (dimension("x"), dimension("y")) {
+            tokens.push(format!("{}×{}", width, height));
+        }
+    }
+
+    let declarations = node_declarations(node, placement);
+    let value = |property: &str| {
+        declarations
+            .iter()
+            .find(|(p, _)| *p == property)
+            .map(|(_, v)| v.as_str())
+    };
+    if let (Some(left), Some(top)) = (value("left"), value("top")) {
+        if left != "0" || top != "0" {
+            tokens.push(format!("at {},{}", bare(left), bare(top)));
+        }
+    }
+
+    let mut font_written = false;
+    for (property, value) in &declarations {
+        match *property {
+            "left" | "top" => {}
+            "display" => {}
+            "flex-direction" => tokens.push(match value.as_str() {
+                "row" => "flex-row".to_string(),
+                _ => "flex-col".to_string(),
+            }),
+            "gap" => tokens.push(format!("gap {}", bare(value))),
+            "padding" => tokens.push(format!("pad {}", bare(value))),
+            "justify-content" => tokens.push(format!("justify {}", value)),
+            "align-items" => tokens.push(format!("align {}", value)),
+            "background-color" => tokens.push(format!("bg {}", value)),
+            "background-image" => tokens.push(format!("bg {}", value)),
+            "border" => tokens.push(format!("border {}", bare(&value.replace(" solid", "")))),
+            "border-radius" => tokens.push(format!("radius {}", bare(value))),
+            "transform" => tokens.push(
+                value
+                    .trim_start_matches("rotate(")
+                    .trim_end_matches("deg)")
+                    .parse::<f64>()
+                    .map(|angle| format!("rotate {}", format_number(angle)))
+                    .unwrap_or_else(|_| format!("transform {}", value)),
+            ),
+            "font-family" | "font-weight" | "font-size" | "line-height" => {
+                if !font_written {
+                    tokens.push(font(&declarations));
+                    font_written = true;
+                }
+            }
+            "letter-spacing" => tokens.push(format!("tracking {}", bare(value))),
+            "text-align" => tokens.push(format!("text-{}", value)),
+            "text-transform" | "text-decoration" => tokens.push(value.clone()),
+            property if SKIPPED_DECLARATIONS.contains(&property) => {}
+            property => tokens.push(format!("{} {}", property, value)),
+        }
+    }
+    tokens
+}
+
+/// `font <weight> <size>[/<line height>] <family>` token
+fn font(declarations: &[Declaration]) -> String {
+    let value = |property: &str| {
+        declarations
+            .iter()
+            .find(|(p, _)| *p == property)
+            .map(|(_, v)| v.as_str())
+    };
+    let mut parts = vec!["font".to_string()];
+    if let Some(weight) = value("font-weight") {
+        parts.push(weight.to_string());
+    }
+    if let Some(size) = value("font-size") {
+        match value("line-height") {
+            Some(line_height) => parts.push(format!("{}/{}", bare(size), bare(line_height))),
+            None => parts.push(bare(size)),
+        }
+    }
+    if let Some(family) = value("font-family") {
+        let family = family.trim_end_matches(", sans-serif").trim_matches('"');
+        parts.push(family.to_string());
+    }
+    parts.join(" ")
+}
+
+/// `FRAME` → `Frame`, `ROUNDED_RECTANGLE` → `RoundedRectangle`, `CANVAS` → `Page`
+fn type_name(node: &Map<String, JsonValue>) -> String {
+    let node_type = node.get("type").and_then(|t| t.as_str()).unwrap_or("NODE");
+    if node_type == "CANVAS" {
+        return "Page".to_string();
+    }
+    node_type
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn count_descendants(node: &Map<String, JsonValue>) -> usize {
+    children(node)
+        .map(|child| 1 + count_descendants(child))
+        .sum()
+}
+
+/// Object children of a node
+fn children(node: &Map<String, JsonValue>) -> impl Iterator<Item = &Map<String, JsonValue>> {
+    node.get("children")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|child| child.as_object())
+}
+
+fn name(node: &Map<String, JsonValue>) -> &str {
+    node.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+/// Text as a JSON string literal, so that quotes and newlines stay on one line
+fn quote(text: &str) -> String {
+    JsonValue::from(text).to_string()
+}
+
+/// Lengths without their `px` unit
+fn bare(value: &str) -> String {
+    value.replace("px", "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn output() -> JsonValue {
+        json!({"document": {"children": [{"type": "CANVAS", "name": "Home", "children": [{
+            "type": "FRAME",
+            "name": "Card",
+            "size": {"x": 320.0, "y": 200.0},
+            "cornerRadius": 8.0,
+            "children": [
+                {
+                    "type": "TEXT",
+                    "name": "Title",
+                    "size": {"x": 200.0, "y": 40.0},
+                    "transform": {"x": 16.0, "y": 24.0},
+                    "fontName": {"family": "Inter", "style": "Bold"},
+                    "fontSize": 32.0,
+                    "lineHeight": "40px",
+                    "fillPaints": [{"type": "SOLID", "color": "#111111"}],
+                    "textData": {"characters": "Say \"hi\"\nthere"}
+                },
+                {
+                    "type": "INSTANCE",
+                    "name": "Action",
+                    "componentName": "Button",
+                    "variant": "Size=Small",
+                    "visible": false,
+                    "children": [{"type": "TEXT", "name": "Label"}]
+                }
+            ]
+        }]}]}})
+    }
+
+    #[test]
+    fn test_to_outline() {
+        assert_eq!(
+            to_outline(&output(), None),
+            "Page \"Home\"\n  \
+             Frame \"Card\" 320×200 radius 8\n    \
+             Text \"Title\" 200×40 at 16,24 color #111111 font 700 32/40 Inter \"Say \\\"hi\\\"\\nthere\"\n    \
+             Instance \"Action\" of Button [Size=Small] hidden\n      \
+             Text \"Label\"\n"
+        );
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        assert_eq!(
+            to_outline(&output(), Some(1)),
+            "Page \"Home\"\n  Frame \"Card\" 320×200 radius 8 +3 nodes\n"
+        );
+        assert_eq!(to_outline(&output(), Some(0)), "Page \"Home\" +4 nodes\n");
+    }
+
+    #[test]
+    fn test_type_name() {
+        let node = |node_type: &str| json!({"type": node_type}).as_object().unwrap().clone();
+
+        assert_eq!(type_name(&node("ROUNDED_RECTANGLE")), "RoundedRectangle");
+        assert_eq!(type_name(&node("CANVAS")), "Page");
+        assert_eq!(type_name(&Map::new()), "Node");
+    }
+}
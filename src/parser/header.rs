@@ -76,6 +76,22 @@ pub fn is_zip_container(bytes: &[u8]) -> bool {
     bytes.len() >= 2 && &bytes[0..2] == ZIP_MAGIC
 }
 
+/// Magic header written at the start of a file of the given type
+///
+/// # Examples
+/// ```
+/// use fig2json::parser::magic_header;
+/// use fig2json::FileType;
+///
+/// assert_eq!(magic_header(FileType::FigJam), b"fig-jam.");
+/// ```
+pub fn magic_header(file_type: FileType) -> &'static [u8; 8] {
+    match file_type {
+        FileType::Figma => FIGMA_MAGIC,
+        FileType::FigJam => FIGJAM_MAGIC,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,4 +5,4 @@ pub mod header;
 // Re-export commonly used items
 pub use chunks::{extract_chunks, extract_from_zip, extract_zip_to_directory};
 pub use compression::decompress_chunk;
-pub use header::{detect_file_type, is_zip_container};
+pub use header::{detect_file_type, is_zip_container, magic_header};
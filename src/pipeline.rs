@@ -0,0 +1,1333 @@
+//! Configurable transformation pipeline
+//!
+//! Every pass in [`crate::schema::transformations`] is registered here under a
+//! stable name (the name of its module, e.g. `guid_removal`). A [`Pipeline`]
+//! is an ordered list of passes that can be individually enabled, disabled,
+//! reordered or extended with third-party passes implementing [`Transform`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::pipeline::Pipeline;
+//!
+//! let mut pipeline = Pipeline::standard();
+//! pipeline.disable("stroke_properties_removal").unwrap();
+//! pipeline.disable("layout_aids_removal").unwrap();
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let json = fig2json::convert_with_pipeline(&bytes, None, &pipeline).unwrap();
+//! ```
+
+use crate::engine::{self, Fusion, VisitContext};
+use crate::error::{FigError, Result};
+use crate::schema;
+use crate::schema::transformations as t;
+use crate::tailwind::Theme;
+use serde_json::{Map, Value as JsonValue};
+use std::path::{Path, PathBuf};
+
+/// Part of the output a pass is applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassTarget {
+    /// The `document` node tree
+    Document,
+    /// The whole output object (`version`, `fileType`, `document`, `blobs`)
+    Root,
+}
+
+/// Context shared by all passes of a pipeline run
+#[derive(Debug, Clone)]
+pub struct PassContext {
+    /// Directory where image files are located (used to detect and rename image files)
+    pub base_dir: PathBuf,
+    /// Directory to export vector nodes to as SVG files (`svg_export` pass)
+    pub svg_dir: Option<PathBuf>,
+    /// Theme to generate Tailwind classes with (`tailwind` pass)
+    pub tailwind: Theme,
+}
+
+impl PassContext {
+    /// Create a new PassContext
+    pub fn new(base_dir: &Path) -> Self {
+        Self {
+            base_dir: base_dir.to_path_buf(),
+            svg_dir: None,
+            tailwind: Theme::default(),
+        }
+    }
+
+    /// Enable SVG export of vector nodes to `svg_dir`
+    pub fn with_svg_dir(mut self, svg_dir: &Path) -> Self {
+        self.svg_dir = Some(svg_dir.to_path_buf());
+        self
+    }
+
+    /// Generate Tailwind classes with `theme` instead of the default theme
+    pub fn with_tailwind(mut self, theme: Theme) -> Self {
+        self.tailwind = theme;
+        self
+    }
+}
+
+impl Default for PassContext {
+    fn default() -> Self {
+        Self::new(Path::new("."))
+    }
+}
+
+/// A transformation pass that can be registered in a [`Pipeline`]
+///
+/// # Examples
+/// ```
+/// use fig2json::pipeline::{PassContext, Pipeline, Transform};
+/// use serde_json::Value as JsonValue;
+///
+/// struct RemoveLocks;
+///
+/// impl Transform for RemoveLocks {
+///     fn name(&self) -> &str {
+///         "locked_removal"
+///     }
+///
+///     fn apply(&self, tree: &mut JsonValue, _ctx: &PassContext) -> fig2json::Result<()> {
+///         if let Some(obj) = tree.as_object_mut() {
+///             obj.remove("locked");
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let mut pipeline = Pipeline::standard();
+/// pipeline.push(RemoveLocks).unwrap();
+/// ```
+pub trait Transform {
+    /// Unique name of the pass
+    fn name(&self) -> &str;
+
+    /// One-line description shown by `--list-passes`
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Part of the output this pass is applied to
+    fn target(&self) -> PassTarget {
+        PassTarget::Document
+    }
+
+    /// Names of passes that must run before this one when they are enabled
+    fn runs_after(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Apply the pass to the targeted JSON value
+    fn apply(&self, tree: &mut JsonValue, ctx: &PassContext) -> Result<()>;
+
+    /// How the pass can share a traversal with its neighbours
+    ///
+    /// Passes returning anything but [`Fusion::None`] must implement the hooks
+    /// below so that a fused traversal produces the same result as [`Transform::apply`].
+    fn fusion(&self) -> Fusion {
+        Fusion::None
+    }
+
+    /// Called on every object before its values are visited
+    fn enter_object(&self, _map: &mut Map<String, JsonValue>, _cx: &VisitContext) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called on every array before its elements are visited
+    fn enter_array(&self, _arr: &mut Vec<JsonValue>, _cx: &VisitContext) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called on every object after its values have been visited
+    fn leave_object(&self, _map: &mut Map<String, JsonValue>, _cx: &VisitContext) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called on every array after its elements have been visited
+    fn leave_array(&self, _arr: &mut Vec<JsonValue>, _cx: &VisitContext) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Per-node hook of a built-in pass on objects
+type ObjectHook = fn(&mut Map<String, JsonValue>, &VisitContext);
+
+/// Per-node hook of a built-in pass on arrays
+type ArrayHook = fn(&mut Vec<JsonValue>, &VisitContext);
+
+/// A built-in pass wrapping one of the `schema::transformations` functions
+struct BuiltinPass {
+    name: &'static str,
+    description: &'static str,
+    target: PassTarget,
+    runs_after: &'static [&'static str],
+    run: fn(&mut JsonValue, &PassContext) -> Result<()>,
+    enabled: bool,
+    fusion: Fusion,
+    enter_object: Option<ObjectHook>,
+    enter_array: Option<ArrayHook>,
+    leave_object: Option<ObjectHook>,
+    leave_array: Option<ArrayHook>,
+}
+
+impl BuiltinPass {
+    /// Leave the pass disabled in [`Pipeline::standard`] until it is enabled
+    const fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+
+    /// Fuse the pass through an object hook that only touches the visited object
+    const fn local(mut self, enter_object: ObjectHook) -> Self {
+        self.fusion = Fusion::Local;
+        self.enter_object = Some(enter_object);
+        self
+    }
+
+    /// Fuse the pass through an object hook that inspects nested values
+    const fn barrier(mut self, enter_object: ObjectHook) -> Self {
+        self.fusion = Fusion::Barrier;
+        self.enter_object = Some(enter_object);
+        self
+    }
+
+    /// Fuse the pass with the given strategy, without object hook
+    const fn fused(mut self, fusion: Fusion) -> Self {
+        self.fusion = fusion;
+        self
+    }
+
+    /// Add an array hook run before the elements are visited
+    const fn array(mut self, enter_array: ArrayHook) -> Self {
+        self.enter_array = Some(enter_array);
+        self
+    }
+
+    /// Fuse the pass through hooks run after the children have been visited
+    const fn post_order(mut self, leave_object: ObjectHook, leave_array: ArrayHook) -> Self {
+        self.fusion = Fusion::PostOrder;
+        self.leave_object = Some(leave_object);
+        self.leave_array = Some(leave_array);
+        self
+    }
+}
+
+impl Transform for BuiltinPass {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    fn target(&self) -> PassTarget {
+        self.target
+    }
+
+    fn runs_after(&self) -> &[&str] {
+        self.runs_after
+    }
+
+    fn apply(&self, tree: &mut JsonValue, ctx: &PassContext) -> Result<()> {
+        (self.run)(tree, ctx)
+    }
+
+    fn fusion(&self) -> Fusion {
+        self.fusion
+    }
+
+    fn enter_object(&self, map: &mut Map<String, JsonValue>, cx: &VisitContext) -> Result<()> {
+        if let Some(hook) = self.enter_object {
+            hook(map, cx);
+        }
+        Ok(())
+    }
+
+    fn enter_array(&self, arr: &mut Vec<JsonValue>, cx: &VisitContext) -> Result<()> {
+        if let Some(hook) = self.enter_array {
+            hook(arr, cx);
+        }
+        Ok(())
+    }
+
+    fn leave_object(&self, map: &mut Map<String, JsonValue>, cx: &VisitContext) -> Result<()> {
+        if let Some(hook) = self.leave_object {
+            hook(map, cx);
+        }
+        Ok(())
+    }
+
+    fn leave_array(&self, arr: &mut Vec<JsonValue>, cx: &VisitContext) -> Result<()> {
+        if let Some(hook) = self.leave_array {
+            hook(arr, cx);
+        }
+        Ok(())
+    }
+}
+
+/// Shorthand to declare a built-in pass
+const fn builtin(
+    name: &'static str,
+    description: &'static str,
+    target: PassTarget,
+    runs_after: &'static [&'static str],
+    run: fn(&mut JsonValue, &PassContext) -> Result<()>,
+) -> BuiltinPass {
+    BuiltinPass {
+        name,
+        description,
+        target,
+        runs_after,
+        run,
+        enabled: true,
+        fusion: Fusion::None,
+        enter_object: None,
+        enter_array: None,
+        leave_object: None,
+        leave_array: None,
+    }
+}
+
+/// Built-in passes in the order used by [`crate::convert`]
+fn standard_passes() -> Vec<BuiltinPass> {
+    use PassTarget::{Document, Root};
+
+    vec![
+        builtin("svg_export", "Write vector nodes to SVG files when an SVG directory is set", Document, &[],
+            |tree, ctx| match &ctx.svg_dir {
+                Some(dir) => crate::svg::export_svgs(tree, dir).map(|_| ()),
+                None => Ok(()),
+            }),
+        builtin("instance_expansion", "Expand component instances into the subtree of their main component", Document, &[],
+            |tree, _| schema::expand_instances(tree))
+            .disabled(),
+        builtin("absolute_bounds", "Add absolute bounding boxes, render bounds and frame positions", Document,
+            &["instance_expansion"],
+            |tree, _| schema::add_absolute_bounds(tree))
+            .disabled(),
+        builtin("image_hash", "Convert image hash arrays to filename strings", Document, &[],
+            |tree, ctx| schema::transform_image_hashes(tree, &ctx.base_dir))
+            .local(|map, cx| t::image_hash::enter_object(map, &cx.pass.base_dir)),
+        builtin("matrix_to_css", "Convert 2D affine transformation matrices to CSS positioning properties", Document, &["absolute_bounds"],
+            |tree, _| schema::transform_matrix_to_css(tree))
+            .local(|map, _| t::matrix_to_css::enter_object(map)),
+        builtin("color_to_css", "Convert RGBA color objects to CSS hex color strings", Document, &[],
+            |tree, _| schema::transform_colors_to_css(tree))
+            .local(|map, _| t::color_to_css::enter_object(map)),
+        builtin("text_glyphs_removal", "Remove glyph vector data from text objects", Document, &[],
+            |tree, _| schema::remove_text_glyphs(tree))
+            .local(|map, _| t::text_glyphs_removal::enter_object(map)),
+        builtin("enum_simplification", "Simplify verbose enum objects to simple strings", Document, &[],
+            |tree, _| schema::simplify_enums(tree))
+            .local(|map, _| t::enum_simplification::enter_object(map)),
+        builtin("default_blend_mode_removal", "Remove default blendMode values", Document, &["enum_simplification"],
+            |tree, _| schema::remove_default_blend_mode(tree))
+            .local(|map, _| t::default_blend_mode_removal::enter_object(map)),
+        builtin("edit_info_removal", "Remove version control edit info metadata", Document, &[],
+            |tree, _| schema::remove_edit_info_fields(tree))
+            .local(|map, _| t::edit_info_removal::enter_object(map)),
+        builtin("phase_removal", "Remove Figma internal phase state", Document, &[],
+            |tree, _| schema::remove_phase_fields(tree))
+            .local(|map, _| t::phase_removal::enter_object(map)),
+        builtin("text_runs", "Combine characterStyleIDs and styleOverrideTable into styled text runs", Document,
+            &["color_to_css", "enum_simplification"],
+            |tree, _| schema::build_text_runs(tree))
+            .barrier(|map, _| t::text_runs::enter_object(map))
+            .disabled(),
+        builtin("geometry_removal", "Remove detailed geometry path commands", Document, &["svg_export", "text_runs"],
+            |tree, _| schema::remove_geometry_fields(tree))
+            .barrier(|map, _| t::geometry_removal::enter_object(map)),
+        builtin("text_layout_removal", "Remove detailed text layout data", Document, &[],
+            |tree, _| schema::remove_text_layout_fields(tree))
+            .local(|map, _| t::text_layout_removal::enter_object(map)),
+        builtin("derived_text_layout_size_removal", "Remove redundant layoutSize from derivedTextData", Document, &[],
+            |tree, _| schema::remove_derived_text_layout_size(tree))
+            .local(|map, _| t::derived_text_layout_size_removal::enter_object(map)),
+        builtin("empty_derived_text_data_removal", "Remove empty derivedTextData objects", Document,
+            &["text_glyphs_removal", "text_layout_removal", "derived_text_layout_size_removal"],
+            |tree, _| schema::remove_empty_derived_text_data(tree))
+            .barrier(|map, _| t::empty_derived_text_data_removal::enter_object(map)),
+        builtin("text_metadata_removal", "Remove text configuration metadata", Document, &[],
+            |tree, _| schema::remove_text_metadata_fields(tree))
+            .local(|map, _| t::text_metadata_removal::enter_object(map)),
+        builtin("text_line_defaults_removal", "Remove default text line properties from lines arrays", Document, &["enum_simplification"],
+            |tree, _| schema::remove_default_text_line_properties(tree))
+            .barrier(|map, _| t::text_line_defaults_removal::enter_object(map)),
+        builtin("default_text_properties_removal", "Remove default text property values", Document, &["enum_simplification", "text_runs"],
+            |tree, _| schema::remove_default_text_properties(tree))
+            .barrier(|map, _| t::default_text_properties_removal::enter_object(map)),
+        builtin("text_properties_simplification", "Simplify verbose letterSpacing/lineHeight structures to CSS-ready strings", Document,
+            &["enum_simplification", "text_runs", "default_text_properties_removal"],
+            |tree, _| schema::simplify_text_properties(tree))
+            .barrier(|map, _| t::text_properties_simplification::enter_object(map)),
+        builtin("gradient_to_css", "Add CSS linear/radial/conic gradient strings to gradient paints", Document,
+            &["matrix_to_css", "color_to_css", "enum_simplification", "text_runs"],
+            |tree, _| schema::transform_gradients_to_css(tree))
+            .disabled(),
+        builtin("effects_to_css", "Replace effects with CSS box-shadow, text-shadow, filter and backdrop-filter values", Document,
+            &["color_to_css", "enum_simplification"],
+            |tree, _| schema::transform_effects_to_css(tree))
+            .barrier(|map, _| t::effects_to_css::enter_object(map))
+            .disabled(),
+        builtin("style_resolution", "Resolve shared style references to style names and a root styles table", Root,
+            &["color_to_css", "enum_simplification", "text_properties_simplification"],
+            |tree, _| schema::resolve_style_references(tree)),
+        builtin("tailwind", "Add Tailwind CSS classes to nodes", Document,
+            &["matrix_to_css", "color_to_css", "enum_simplification", "text_properties_simplification"],
+            |tree, ctx| crate::tailwind::add_tailwind_classes(tree, &ctx.tailwind))
+            .barrier(|map, cx| crate::tailwind::enter_object(map, cx.depth, &cx.pass.tailwind))
+            .disabled(),
+        builtin("layout", "Translate auto-layout properties to a flexbox or grid css block", Document,
+            &["enum_simplification", "tailwind"],
+            |tree, _| schema::transform_layout_to_css(tree))
+            .barrier(|map, _| t::layout::enter_object(map))
+            .disabled(),
+        builtin("guid_removal", "Remove internal Figma guid identifiers", Document, &["instance_expansion", "style_resolution", "gradient_to_css"],
+            |tree, _| schema::remove_guid_fields(tree))
+            .local(|map, _| t::guid_removal::enter_object(map)),
+        builtin("empty_font_postscript_removal", "Remove empty postscript from fontName", Document, &[],
+            |tree, _| schema::remove_empty_font_postscript(tree))
+            .local(|map, _| t::empty_font_postscript_removal::enter_object(map)),
+        builtin("stroke_properties_removal", "Remove CSS-incompatible stroke properties", Document, &["svg_export", "tailwind"],
+            |tree, _| schema::remove_stroke_properties(tree))
+            .local(|map, _| t::stroke_properties_removal::enter_object(map)),
+        builtin("border_weights_removal", "Remove individual border weight fields", Document, &[],
+            |tree, _| schema::remove_border_weights(tree))
+            .local(|map, _| t::border_weights_removal::enter_object(map)),
+        builtin("frame_properties_removal", "Remove frame-specific metadata", Document, &[],
+            |tree, _| schema::remove_frame_properties(tree))
+            .local(|map, _| t::frame_properties_removal::enter_object(map)),
+        builtin("background_properties_removal", "Remove background metadata fields", Document, &[],
+            |tree, _| schema::remove_background_properties(tree))
+            .local(|map, _| t::background_properties_removal::enter_object(map)),
+        builtin("image_metadata_removal", "Remove image metadata fields", Document, &[],
+            |tree, _| schema::remove_image_metadata_fields(tree))
+            .local(|map, _| t::image_metadata_removal::enter_object(map)),
+        builtin("internal_only_nodes_removal", "Filter out internal-only nodes", Document, &["style_resolution"],
+            |tree, _| schema::remove_internal_only_nodes(tree))
+            .local(|map, _| t::internal_only_nodes_removal::enter_object(map))
+            .array(|arr, _| t::internal_only_nodes_removal::enter_array(arr)),
+        builtin("default_opacity_removal", "Remove default opacity values (1.0)", Document, &[],
+            |tree, _| schema::remove_default_opacity(tree))
+            .local(|map, _| t::default_opacity_removal::enter_object(map)),
+        builtin("default_visible_removal", "Remove default visible values (true)", Document, &[],
+            |tree, _| schema::remove_default_visible(tree))
+            .local(|map, _| t::default_visible_removal::enter_object(map)),
+        builtin("default_rotation_removal", "Remove default rotation values (0.0)", Document, &[],
+            |tree, _| schema::remove_default_rotation(tree))
+            .local(|map, _| t::default_rotation_removal::enter_object(map)),
+        builtin("uniform_scale_factor_removal", "Remove default uniformScaleFactor values (1.0)", Document, &[],
+            |tree, _| schema::remove_default_uniform_scale_factor(tree))
+            .local(|map, _| t::uniform_scale_factor_removal::enter_object(map)),
+        builtin("document_properties_removal", "Remove document-level properties", Root, &[],
+            |tree, _| schema::remove_document_properties(tree))
+            .local(|map, _| t::document_properties_removal::enter_object(map)),
+        builtin("root_metadata_removal", "Remove root-level version and fileType fields", Root, &[],
+            |tree, _| schema::remove_root_metadata(tree))
+            .local(|map, cx| if cx.depth == 0 { t::root_metadata_removal::enter_object(map) }),
+        builtin("blobs_removal", "Remove the root-level blobs array from final output", Root, &[],
+            |tree, _| schema::remove_root_blobs(tree))
+            .local(|map, cx| if cx.depth == 0 { t::blobs_removal::enter_object(map) }),
+        builtin("guid_path_removal", "Remove internal Figma guidPath references", Root, &["instance_expansion", "gradient_to_css"],
+            |tree, _| schema::remove_guid_paths(tree))
+            .local(|map, _| t::guid_path_removal::enter_object(map)),
+        builtin("user_facing_version_removal", "Remove Figma version strings", Root, &[],
+            |tree, _| schema::remove_user_facing_versions(tree))
+            .local(|map, _| t::user_facing_version_removal::enter_object(map)),
+        builtin("style_id_removal", "Remove unresolved Figma shared style references", Root, &["style_resolution"],
+            |tree, _| schema::remove_style_ids(tree))
+            .local(|map, _| t::style_id_removal::enter_object(map)),
+        builtin("export_settings_removal", "Remove asset export configurations", Root, &["geometry_removal"],
+            |tree, _| schema::remove_export_settings(tree))
+            .local(|map, _| t::export_settings_removal::enter_object(map)),
+        builtin("plugin_data_removal", "Remove Figma plugin storage data", Root, &[],
+            |tree, _| schema::remove_plugin_data(tree))
+            .local(|map, _| t::plugin_data_removal::enter_object(map)),
+        builtin("rectangle_corner_radii_independent_removal", "Remove corner radii independent flag", Root, &[],
+            |tree, _| schema::remove_rectangle_corner_radii_independent(tree))
+            .local(|map, _| t::rectangle_corner_radii_independent_removal::enter_object(map)),
+        builtin("constraint_properties_removal", "Remove Figma auto-layout constraint properties", Root, &[],
+            |tree, _| schema::remove_constraint_properties(tree))
+            .local(|map, _| t::constraint_properties_removal::enter_object(map)),
+        builtin("scroll_resize_properties_removal", "Remove Figma scroll and resize behavior properties", Root, &[],
+            |tree, _| schema::remove_scroll_resize_properties(tree))
+            .local(|map, _| t::scroll_resize_properties_removal::enter_object(map)),
+        builtin("layout_aids_removal", "Remove design-time layout aids (guides, layoutGrids)", Root, &[],
+            |tree, _| schema::remove_layout_aids(tree))
+            .local(|map, _| t::layout_aids_removal::enter_object(map)),
+        builtin("detached_symbol_id_removal", "Remove Figma component instance metadata", Root, &[],
+            |tree, _| schema::remove_detached_symbol_id(tree))
+            .local(|map, _| t::detached_symbol_id_removal::enter_object(map)),
+        builtin("overridden_symbol_id_removal", "Remove standalone overriddenSymbolID objects from arrays", Root, &["instance_expansion"],
+            |tree, _| schema::remove_overridden_symbol_id(tree))
+            .fused(Fusion::Barrier)
+            .array(|arr, _| t::overridden_symbol_id_removal::enter_array(arr)),
+        builtin("redundant_corner_radii_removal", "Remove individual corner radius fields when general cornerRadius exists", Root, &[],
+            |tree, _| schema::remove_redundant_corner_radii(tree))
+            .local(|map, _| t::redundant_corner_radii_removal::enter_object(map)),
+        builtin("corner_smoothing_removal", "Remove Figma's corner smoothing property", Root, &[],
+            |tree, _| schema::remove_corner_smoothing(tree))
+            .local(|map, _| t::corner_smoothing_removal::enter_object(map)),
+        builtin("invisible_paints_removal", "Remove invisible paints from fillPaints and strokePaints arrays", Root, &[],
+            |tree, _| schema::remove_invisible_paints(tree))
+            .barrier(|map, _| t::invisible_paints_removal::enter_object(map)),
+        builtin("empty_paint_arrays_removal", "Remove empty fillPaints and strokePaints arrays", Root, &["invisible_paints_removal"],
+            |tree, _| schema::remove_empty_paint_arrays(tree))
+            .barrier(|map, _| t::empty_paint_arrays_removal::enter_object(map)),
+        builtin("redundant_padding_removal", "Remove redundant padding properties when general axis-based padding exists", Root, &["layout"],
+            |tree, _| schema::remove_redundant_padding(tree))
+            .local(|map, _| t::redundant_padding_removal::enter_object(map)),
+        builtin("stack_child_properties_removal", "Remove Figma auto-layout child properties (stackChildAlignSelf, stackChildPrimaryGrow)", Root, &["layout"],
+            |tree, _| schema::remove_stack_child_properties(tree))
+            .local(|map, _| t::stack_child_properties_removal::enter_object(map)),
+        builtin("stack_sizing_properties_removal", "Remove Figma auto-layout sizing properties (stackCounterSizing, stackPrimarySizing)", Root, &["layout"],
+            |tree, _| schema::remove_stack_sizing_properties(tree))
+            .local(|map, _| t::stack_sizing_properties_removal::enter_object(map)),
+        builtin("stack_align_items_removal", "Remove Figma auto-layout alignment properties (stackCounterAlignItems, stackPrimaryAlignItems)", Root, &["tailwind", "layout"],
+            |tree, _| schema::remove_stack_align_items(tree))
+            .local(|map, _| t::stack_align_items_removal::enter_object(map)),
+        builtin("symbol_id_removal", "Remove symbolID objects containing only localID and/or sessionID", Root, &["instance_expansion"],
+            |tree, _| schema::remove_symbol_id_fields(tree))
+            .barrier(|map, _| t::symbol_id_removal::enter_object(map)),
+        builtin("type_removal", "Remove type field from all nodes", Root, &["svg_export", "instance_expansion", "image_metadata_removal"],
+            |tree, _| schema::remove_type(tree))
+            .local(|map, _| t::type_removal::enter_object(map)),
+        builtin("visible_only_objects_removal", "Remove objects that only contain a visible property", Root, &[],
+            |tree, _| schema::remove_visible_only_objects(tree))
+            .post_order(
+                |map, _| t::visible_only_objects_removal::leave_object(map),
+                |arr, _| t::visible_only_objects_removal::leave_array(arr),
+            ),
+        builtin("empty_objects_removal", "Remove empty objects {} from the JSON tree", Root, &[],
+            |tree, _| schema::remove_empty_objects(tree))
+            .post_order(
+                |map, _| t::empty_objects_removal::leave_object(map),
+                |arr, _| t::empty_objects_removal::leave_array(arr),
+            ),
+    ]
+}
+
+/// A registered pass and whether it is enabled
+struct PassEntry {
+    pass: Box<dyn Transform>,
+    enabled: bool,
+}
+
+/// Ordered, configurable list of transformation passes
+///
+/// # Examples
+/// ```
+/// use fig2json::pipeline::Pipeline;
+///
+/// let mut pipeline = Pipeline::standard();
+/// pipeline.disable("geometry_removal").unwrap();
+/// assert!(!pipeline.is_enabled("geometry_removal"));
+/// ```
+#[derive(Default)]
+pub struct Pipeline {
+    passes: Vec<PassEntry>,
+}
+
+impl Pipeline {
+    /// Create an empty pipeline (produces the same output as [`crate::convert_raw`])
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the pipeline used by [`crate::convert`]
+    ///
+    /// Every built-in pass is registered; optional passes adding data to the
+    /// output or changing its shape (`instance_expansion`, `absolute_bounds`,
+    /// `text_runs`, `gradient_to_css`, `effects_to_css`, `tailwind`, `layout`)
+    /// are disabled until [`Pipeline::enable`]d.
+    ///
+    /// # Examples
+    /// ```
+    /// use fig2json::pipeline::Pipeline;
+    ///
+    /// let mut pipeline = Pipeline::standard();
+    /// assert!(pipeline.is_enabled("guid_removal"));
+    /// assert!(!pipeline.is_enabled("absolute_bounds"));
+    ///
+    /// pipeline.enable("absolute_bounds").unwrap();
+    /// assert!(pipeline.is_enabled("absolute_bounds"));
+    /// ```
+    pub fn standard() -> Self {
+        Self {
+            passes: standard_passes()
+                .into_iter()
+                .map(|pass| PassEntry {
+                    enabled: pass.enabled,
+                    pass: Box::new(pass),
+                })
+                .collect(),
+        }
+    }
+
+    /// Create the standard pipeline with the given built-in passes disabled
+    ///
+    /// # Panics
+    /// If a name is not the name of a built-in pass
+    ///
+    /// # Examples
+    /// ```
+    /// use fig2json::pipeline::Pipeline;
+    ///
+    /// let pipeline = Pipeline::standard_without(&["guid_removal", "type_removal"]);
+    /// assert!(!pipeline.is_enabled("type_removal"));
+    /// assert!(pipeline.is_enabled("geometry_removal"));
+    /// ```
+    pub fn standard_without(names: &[&str]) -> Self {
+        let mut pipeline = Self::standard();
+        for name in names {
+            pipeline
+                .disable(name)
+                .expect("pass of the standard pipeline");
+        }
+        pipeline
+    }
+
+    /// Names of all registered passes, in execution order
+    pub fn names(&self) -> Vec<&str> {
+        self.passes.iter().map(|entry| entry.pass.name()).collect()
+    }
+
+    /// Iterate over registered passes with their enabled state, in execution order
+    pub fn passes(&self) -> impl Iterator<Item = (&dyn Transform, bool)> {
+        self.passes
+            .iter()
+            .map(|entry| (entry.pass.as_ref(), entry.enabled))
+    }
+
+    /// Check if a pass is registered and enabled
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.passes
+            .iter()
+            .any(|entry| entry.enabled && entry.pass.name() == name)
+    }
+
+    /// Enable a registered pass
+    pub fn enable(&mut self, name: &str) -> Result<&mut Self> {
+        let index = self.position(name)?;
+        self.passes[index].enabled = true;
+        Ok(self)
+    }
+
+    /// Disable a registered pass
+    pub fn disable(&mut self, name: &str) -> Result<&mut Self> {
+        let index = self.position(name)?;
+        self.passes[index].enabled = false;
+        Ok(self)
+    }
+
+    /// Enable only the given passes and disable all others
+    pub fn only<S: AsRef<str>>(&mut self, names: &[S]) -> Result<&mut Self> {
+        for name in names {
+            self.position(name.as_ref())?;
+        }
+        for entry in &mut self.passes {
+            entry.enabled = names.iter().any(|name| name.as_ref() == entry.pass.name());
+        }
+        Ok(self)
+    }
+
+    /// Append a pass at the end of the pipeline
+    pub fn push<T: Transform + 'static>(&mut self, pass: T) -> Result<&mut Self> {
+        let index = self.passes.len();
+        self.insert_at(index, Box::new(pass))
+    }
+
+    /// Insert a pass right before an existing pass
+    pub fn insert_before<T: Transform + 'static>(&mut self, anchor: &str, pass: T) -> Result<&mut Self> {
+        let index = self.position(anchor)?;
+        self.insert_at(index, Box::new(pass))
+    }
+
+    /// Insert a pass right after an existing pass
+    pub fn insert_after<T: Transform + 'static>(&mut self, anchor: &str, pass: T) -> Result<&mut Self> {
+        let index = self.position(anchor)?;
+        self.insert_at(index + 1, Box::new(pass))
+    }
+
+    /// Move an existing pass right before another one
+    ///
+    /// Fails (leaving the pipeline unchanged) if the new order would run a pass
+    /// before one of its [`Transform::runs_after`] dependencies.
+    pub fn move_before(&mut self, name: &str, anchor: &str) -> Result<&mut Self> {
+        self.move_relative(name, anchor, 0)
+    }
+
+    /// Move an existing pass right after another one
+    ///
+    /// Fails (leaving the pipeline unchanged) if the new order would run a pass
+    /// before one of its [`Transform::runs_after`] dependencies.
+    pub fn move_after(&mut self, name: &str, anchor: &str) -> Result<&mut Self> {
+        self.move_relative(name, anchor, 1)
+    }
+
+    /// Check that every pass runs after the enabled passes it depends on
+    pub fn validate(&self) -> Result<()> {
+        for (index, entry) in self.passes.iter().enumerate() {
+            if !entry.enabled {
+                continue;
+            }
+            for dependency in entry.pass.runs_after() {
+                let misplaced = self.passes[index + 1..]
+                    .iter()
+                    .any(|later| later.enabled && later.pass.name() == *dependency);
+                if misplaced {
+                    return Err(FigError::PassOrder {
+                        pass: entry.pass.name().to_string(),
+                        dependency: dependency.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run all enabled passes on a conversion output
+    ///
+    /// `output` is the root object built by the conversion (`version`,
+    /// `fileType`, `document`, `blobs`). Passes targeting
+    /// [`PassTarget::Document`] are applied to `output["document"]`.
+    ///
+    /// Consecutive passes that support it share a single traversal of the
+    /// tree (see [`crate::engine`]); the result is the same as [`Pipeline::run_unfused`].
+    pub fn run(&self, output: &mut JsonValue, ctx: &PassContext) -> Result<()> {
+        self.validate()?;
+
+        for group in engine::plan(&self.enabled()) {
+            if let Some(tree) = engine::target_value(output, group[0].target()) {
+                engine::run_group(&group, tree, ctx)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run all enabled passes one after another, each with its own tree walk
+    ///
+    /// Slower reference implementation of [`Pipeline::run`].
+    pub fn run_unfused(&self, output: &mut JsonValue, ctx: &PassContext) -> Result<()> {
+        self.validate()?;
+
+        for pass in self.enabled() {
+            if let Some(tree) = engine::target_value(output, pass.target()) {
+                pass.apply(tree, ctx)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Names of enabled passes grouped by shared traversal, in execution order
+    ///
+    /// # Examples
+    /// ```
+    /// use fig2json::pipeline::Pipeline;
+    ///
+    /// let pipeline = Pipeline::standard();
+    /// let groups = pipeline.groups();
+    /// assert_eq!(groups[1][0], "image_hash");
+    /// ```
+    pub fn groups(&self) -> Vec<Vec<&str>> {
+        engine::plan(&self.enabled())
+            .into_iter()
+            .map(|group| group.into_iter().map(|pass| pass.name()).collect())
+            .collect()
+    }
+
+    /// Enabled passes in execution order
+    fn enabled(&self) -> Vec<&dyn Transform> {
+        self.passes
+            .iter()
+            .filter(|entry| entry.enabled)
+            .map(|entry| entry.pass.as_ref())
+            .collect()
+    }
+
+    /// Find the index of a registered pass
+    fn position(&self, name: &str) -> Result<usize> {
+        self.passes
+            .iter()
+            .position(|entry| entry.pass.name() == name)
+            .ok_or_else(|| FigError::UnknownPass(name.to_string()))
+    }
+
+    /// Insert a new pass, rejecting duplicate names and invalid orders
+    fn insert_at(&mut self, index: usize, pass: Box<dyn Transform>) -> Result<&mut Self> {
+        if self.position(pass.name()).is_ok() {
+            return Err(FigError::DuplicatePass(pass.name().to_string()));
+        }
+
+        self.passes.insert(index, PassEntry { pass, enabled: true });
+        if let Err(e) = self.check_order() {
+            self.passes.remove(index);
+            return Err(e);
+        }
+
+        Ok(self)
+    }
+
+    /// Move a pass next to an anchor pass (`offset` 0 = before, 1 = after)
+    fn move_relative(&mut self, name: &str, anchor: &str, offset: usize) -> Result<&mut Self> {
+        let from = self.position(name)?;
+        self.position(anchor)?;
+        if name == anchor {
+            return Ok(self);
+        }
+
+        let entry = self.passes.remove(from);
+        let to = self.position(anchor)? + offset;
+        self.passes.insert(to, entry);
+
+        if let Err(e) = self.check_order() {
+            let entry = self.passes.remove(to);
+            self.passes.insert(from, entry);
+            return Err(e);
+        }
+
+        Ok(self)
+    }
+
+    /// Check ordering constraints regardless of enabled state
+    fn check_order(&self) -> Result<()> {
+        for (index, entry) in self.passes.iter().enumerate() {
+            for dependency in entry.pass.runs_after() {
+                if self.passes[index + 1..]
+                    .iter()
+                    .any(|later| later.pass.name() == *dependency)
+                {
+                    return Err(FigError::PassOrder {
+                        pass: entry.pass.name().to_string(),
+                        dependency: dependency.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct RenamePass;
+
+    impl Transform for RenamePass {
+        fn name(&self) -> &str {
+            "rename"
+        }
+
+        fn runs_after(&self) -> &[&str] {
+            &["guid_removal"]
+        }
+
+        fn apply(&self, tree: &mut JsonValue, _ctx: &PassContext) -> Result<()> {
+            if let Some(obj) = tree.as_object_mut() {
+                obj.insert("name".to_string(), json!("Renamed"));
+            }
+            Ok(())
+        }
+    }
+
+    fn sample_output() -> JsonValue {
+        json!({
+            "version": 101,
+            "fileType": "figma",
+            "document": {
+                "name": "Document",
+                "guid": {"localID": 0, "sessionID": 0},
+                "type": {"__enum__": "NodeType", "value": "DOCUMENT"},
+                "opacity": 1.0,
+                "strokeWeight": 2.0,
+                "children": [{
+                    "name": "Frame",
+                    "guid": {"localID": 1, "sessionID": 0},
+                    "blendMode": {"__enum__": "BlendMode", "value": "NORMAL"},
+                    "strokeWeight": 1.0,
+                    "fillPaints": [{"color": {"r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0}, "visible": false}]
+                }]
+            },
+            "blobs": []
+        })
+    }
+
+    #[test]
+    fn test_standard_pass_names_are_unique() {
+        let pipeline = Pipeline::standard();
+        let names = pipeline.names();
+        let mut unique = names.clone();
+        unique.sort();
+        unique.dedup();
+
+        assert_eq!(names.len(), 63);
+        assert_eq!(unique.len(), names.len());
+        assert_eq!(names[0], "svg_export");
+        assert_eq!(names[1], "instance_expansion");
+        assert_eq!(names[2], "absolute_bounds");
+        assert_eq!(names[3], "image_hash");
+        assert_eq!(names[names.len() - 1], "empty_objects_removal");
+    }
+
+    #[test]
+    fn test_standard_order_is_valid() {
+        assert!(Pipeline::standard().validate().is_ok());
+    }
+
+    #[test]
+    fn test_empty_pipeline_is_noop() {
+        let mut output = sample_output();
+        Pipeline::new().run(&mut output, &PassContext::default()).unwrap();
+        assert_eq!(output, sample_output());
+    }
+
+    #[test]
+    fn test_standard_pipeline() {
+        let mut output = sample_output();
+        Pipeline::standard().run(&mut output, &PassContext::default()).unwrap();
+
+        assert_eq!(
+            output,
+            json!({
+                "document": {
+                    "name": "Document",
+                    "children": [{"name": "Frame"}]
+                }
+            })
+        );
+    }
+
+    fn auto_layout_output() -> JsonValue {
+        json!({
+            "document": {
+                "name": "Document",
+                "children": [{
+                    "name": "Row",
+                    "size": {"x": 200.0, "y": 100.0},
+                    "stackMode": {"__enum__": "StackMode", "value": "HORIZONTAL"},
+                    "stackSpacing": 8.0,
+                    "stackHorizontalPadding": 16.0,
+                    "stackPaddingRight": 16.0,
+                    "stackPrimarySizing": {"__enum__": "StackSize", "value": "FIXED"},
+                    "stackPrimaryAlignItems": {"__enum__": "StackJustify", "value": "CENTER"},
+                    "fillPaints": [{
+                        "type": {"__enum__": "PaintType", "value": "GRADIENT_LINEAR"},
+                        "stops": [
+                            {"color": {"r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0}, "position": 0.0},
+                            {"color": {"r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0}, "position": 1.0}
+                        ],
+                        "transform": {"m00": 1.0, "m01": 0.0, "m02": 0.0, "m10": 0.0, "m11": 1.0, "m12": 0.0}
+                    }],
+                    "children": [{"name": "Label", "stackChildPrimaryGrow": 1.0}]
+                }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_standard_pipeline_keeps_auto_layout_direction() {
+        let mut output = auto_layout_output();
+        Pipeline::standard().run(&mut output, &PassContext::default()).unwrap();
+
+        let row = &output["document"]["children"][0];
+        assert_eq!(row["stackMode"], json!("HORIZONTAL"));
+        assert_eq!(row["stackSpacing"], json!(8.0));
+        assert_eq!(row["stackHorizontalPadding"], json!(16.0));
+        assert!(row.get("stackPaddingRight").is_none());
+        assert!(row.get("stackPrimarySizing").is_none());
+        assert!(row.get("stackPrimaryAlignItems").is_none());
+        assert!(row.get("css").is_none());
+        assert!(row["fillPaints"][0].get("css").is_none());
+        assert!(row["children"][0].get("stackChildPrimaryGrow").is_none());
+    }
+
+    #[test]
+    fn test_layout_keeps_gradient_css() {
+        let mut output = auto_layout_output();
+        let mut pipeline = Pipeline::standard();
+        pipeline.enable("layout").unwrap();
+        pipeline.enable("gradient_to_css").unwrap();
+        pipeline.run(&mut output, &PassContext::default()).unwrap();
+
+        let row = &output["document"]["children"][0];
+        assert_eq!(
+            row["fillPaints"][0]["css"],
+            json!("linear-gradient(90deg, #ffffff 0%, #000000 100%)")
+        );
+        assert_eq!(row["css"]["display"], json!("flex"));
+        assert_eq!(row["css"]["gap"], json!("8px"));
+        assert_eq!(row["css"]["justify-content"], json!("center"));
+        assert_eq!(row["children"][0]["css"]["flex"], json!("1 0 0"));
+        assert!(row.get("stackMode").is_none());
+        assert!(row.get("stackHorizontalPadding").is_none());
+    }
+
+    fn styled_text_output() -> JsonValue {
+        json!({
+            "document": {
+                "name": "Link",
+                "size": {"x": 100.0, "y": 20.0},
+                "fontSize": 16.0,
+                "textData": {
+                    "characters": "Go home",
+                    "characterStyleIDs": [0, 0, 0, 4, 4, 4, 4],
+                    "styleOverrideTable": [{
+                        "styleID": 4,
+                        "textDecoration": {"__enum__": "TextDecoration", "value": "UNDERLINE"},
+                        "fillPaints": [{
+                            "type": {"__enum__": "PaintType", "value": "GRADIENT_LINEAR"},
+                            "stops": [
+                                {"color": {"r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0}, "position": 0.0},
+                                {"color": {"r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0}, "position": 1.0}
+                            ],
+                            "transform": {"m00": 1.0, "m01": 0.0, "m02": 0.0, "m10": 0.0, "m11": 1.0, "m12": 0.0}
+                        }]
+                    }]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_text_runs_read_style_override_ids() {
+        let mut output = styled_text_output();
+        let mut pipeline = Pipeline::standard();
+        pipeline.enable("text_runs").unwrap();
+        pipeline.enable("gradient_to_css").unwrap();
+        pipeline.run(&mut output, &PassContext::default()).unwrap();
+
+        let runs = &output["document"]["textData"]["runs"];
+        assert_eq!(runs[0], json!({"text": "Go ", "fontSize": 16.0}));
+        assert_eq!(runs[1]["text"], json!("home"));
+        assert_eq!(runs[1]["textDecoration"], json!("UNDERLINE"));
+        assert_eq!(
+            runs[1]["fillPaints"][0]["css"],
+            json!("linear-gradient(90deg, #ffffff 0%, #000000 100%)")
+        );
+    }
+
+    #[test]
+    fn test_style_override_ids_removed_without_text_runs() {
+        let mut output = styled_text_output();
+        Pipeline::standard().run(&mut output, &PassContext::default()).unwrap();
+
+        let text_data = &output["document"]["textData"];
+        assert!(text_data.get("runs").is_none());
+        assert!(text_data["styleOverrideTable"][0].get("styleID").is_none());
+    }
+
+    #[test]
+    fn test_absolute_bounds_when_enabled() {
+        let output = json!({
+            "document": {"children": [{
+                "name": "Page",
+                "children": [{
+                    "name": "Frame",
+                    "size": {"x": 100.0, "y": 50.0},
+                    "transform": {"m00": 1.0, "m01": 0.0, "m02": 10.0, "m10": 0.0, "m11": 1.0, "m12": 20.0}
+                }]
+            }]}
+        });
+
+        let mut default = output.clone();
+        Pipeline::standard().run(&mut default, &PassContext::default()).unwrap();
+        assert!(default["document"]["children"][0]["children"][0].get("absoluteBoundingBox").is_none());
+
+        let mut enabled = output;
+        let mut pipeline = Pipeline::standard();
+        pipeline.enable("absolute_bounds").unwrap();
+        pipeline.run(&mut enabled, &PassContext::default()).unwrap();
+        assert_eq!(
+            enabled["document"]["children"][0]["children"][0]["absoluteBoundingBox"],
+            json!({"x": 10.0, "y": 20.0, "width": 100.0, "height": 50.0})
+        );
+    }
+
+    #[test]
+    fn test_effects_css_when_enabled() {
+        let output = json!({
+            "document": {
+                "name": "Card",
+                "effects": [
+                    {"type": "DROP_SHADOW", "visible": true, "color": {"r": 0.0, "g": 0.0, "b": 0.0, "a": 0.25},
+                     "offset": {"x": 0.0, "y": 4.0}, "radius": 8.0},
+                    {"type": "LAYER_BLUR", "visible": false, "radius": 4.0}
+                ]
+            }
+        });
+
+        let mut default = output.clone();
+        Pipeline::standard().run(&mut default, &PassContext::default()).unwrap();
+        let effects = default["document"]["effects"].as_array().unwrap();
+        assert_eq!(effects.len(), 2);
+        assert_eq!(effects[0]["radius"], json!(8.0));
+
+        let mut enabled = output;
+        let mut pipeline = Pipeline::standard();
+        pipeline.enable("effects_to_css").unwrap();
+        pipeline.run(&mut enabled, &PassContext::default()).unwrap();
+        assert_eq!(enabled["document"]["effects"], json!({"boxShadow": "0 4px 8px 0 #00000040"}));
+    }
+
+    #[test]
+    fn test_disable_pass() {
+        let mut pipeline = Pipeline::standard();
+        pipeline.disable("stroke_properties_removal").unwrap();
+        assert!(!pipeline.is_enabled("stroke_properties_removal"));
+
+        let mut output = sample_output();
+        pipeline.run(&mut output, &PassContext::default()).unwrap();
+
+        assert_eq!(output["document"]["strokeWeight"], json!(2.0));
+        assert_eq!(output["document"]["children"][0]["strokeWeight"], json!(1.0));
+    }
+
+    #[test]
+    fn test_enable_pass() {
+        let mut pipeline = Pipeline::standard();
+        pipeline.disable("guid_removal").unwrap();
+        pipeline.enable("guid_removal").unwrap();
+        assert!(pipeline.is_enabled("guid_removal"));
+    }
+
+    #[test]
+    fn test_only_passes() {
+        let mut pipeline = Pipeline::standard();
+        pipeline.only(&["guid_removal", "root_metadata_removal"]).unwrap();
+
+        let mut output = sample_output();
+        pipeline.run(&mut output, &PassContext::default()).unwrap();
+
+        assert!(output.get("version").is_none());
+        assert!(output.get("blobs").is_some());
+        assert!(output["document"].get("guid").is_none());
+        assert!(output["document"].get("opacity").is_some());
+    }
+
+    #[test]
+    fn test_unknown_pass() {
+        let mut pipeline = Pipeline::standard();
+        assert!(matches!(
+            pipeline.disable("does_not_exist"),
+            Err(FigError::UnknownPass(_))
+        ));
+        assert!(matches!(
+            pipeline.only(&["guid_removal", "does_not_exist"]),
+            Err(FigError::UnknownPass(_))
+        ));
+        // A failed only() leaves the pipeline untouched
+        assert!(pipeline.is_enabled("type_removal"));
+    }
+
+    #[test]
+    fn test_move_pass() {
+        let mut pipeline = Pipeline::standard();
+        pipeline.move_before("corner_smoothing_removal", "guid_removal").unwrap();
+
+        let names = pipeline.names();
+        let moved_index = names.iter().position(|n| *n == "corner_smoothing_removal").unwrap();
+        let guid_index = names.iter().position(|n| *n == "guid_removal").unwrap();
+        assert_eq!(moved_index + 1, guid_index);
+
+        pipeline.move_after("corner_smoothing_removal", "empty_objects_removal").unwrap();
+        assert_eq!(pipeline.names().last(), Some(&"corner_smoothing_removal"));
+    }
+
+    #[test]
+    fn test_move_pass_violating_order() {
+        let mut pipeline = Pipeline::standard();
+        let before = pipeline.names().iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let result = pipeline.move_before("default_blend_mode_removal", "enum_simplification");
+        assert!(matches!(result, Err(FigError::PassOrder { .. })));
+
+        let after = pipeline.names().iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_push_custom_pass() {
+        let mut pipeline = Pipeline::new();
+        pipeline.push(RenamePass).unwrap();
+        assert_eq!(pipeline.names(), vec!["rename"]);
+
+        let mut output = sample_output();
+        pipeline.run(&mut output, &PassContext::default()).unwrap();
+        assert_eq!(output["document"]["name"], json!("Renamed"));
+        assert_eq!(output["document"]["children"][0]["name"], json!("Frame"));
+    }
+
+    #[test]
+    fn test_insert_custom_pass_order() {
+        let mut pipeline = Pipeline::standard();
+        assert!(matches!(
+            pipeline.insert_before("guid_removal", RenamePass),
+            Err(FigError::PassOrder { .. })
+        ));
+        assert!(!pipeline.names().contains(&"rename"));
+
+        pipeline.insert_after("guid_removal", RenamePass).unwrap();
+        assert!(matches!(
+            pipeline.push(RenamePass),
+            Err(FigError::DuplicatePass(_))
+        ));
+    }
+
+    /// Small deterministic generator for differential tests
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> usize {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (self.0 >> 33) as usize
+        }
+
+        fn number(&mut self) -> f64 {
+            [0.0, 1.0, 0.5, 100.0][self.next() % 4]
+        }
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[self.next() % items.len()]
+        }
+    }
+
+    const KEYS: &[&str] = &[
+        "name", "children", "type", "visible", "opacity", "rotation", "blendMode", "guid", "guidPath",
+        "phase", "editInfo", "transform", "color", "backgroundColor", "fillPaints", "strokePaints",
+        "strokeWeight", "strokeAlign", "strokeJoin", "borderTopWeight", "borderStrokeWeightsIndependent",
+        "fillGeometry", "strokeGeometry", "windingRule", "styleID", "derivedTextData", "textData", "lines",
+        "glyphs", "baselines", "layoutSize", "fontMetaData", "derivedLines", "fontName", "postscript",
+        "family", "letterSpacing", "lineHeight", "fontSize", "textTracking", "lineType", "indentationLevel",
+        "isFirstLineOfList", "listStartOffset", "sourceDirectionality", "textAutoResize", "image",
+        "imageThumbnail", "hash", "imageType", "imageScaleMode", "originalImageWidth", "thumbHash",
+        "internalOnly", "symbolData", "symbolID", "overriddenSymbolID", "symbolOverrides", "localID",
+        "sessionID", "detachedSymbolId", "userFacingVersion", "exportSettings", "pluginData",
+        "rectangleCornerRadiiIndependent", "cornerRadius", "rectangleTopLeftCornerRadius",
+        "cornerSmoothing", "horizontalConstraint", "scrollBehavior", "resizeToFit", "layoutGrids", "guides",
+        "stackMode", "stackPaddingRight", "stackHorizontalPadding", "stackChildAlignSelf",
+        "stackPrimarySizing", "stackPrimaryAlignItems", "frameMaskDisabled", "backgroundOpacity",
+        "backgroundEnabled", "uniformScaleFactor", "documentColorProfile", "value", "units", "commands",
+        "styleIdForFill", "version", "fileType", "blobs",
+    ];
+
+    const STRINGS: &[&str] = &[
+        "NORMAL", "MULTIPLY", "IMAGE", "SOLID", "TEXT", "FRAME", "VECTOR", "INSTANCE", "PLAIN", "PIXELS",
+        "PERCENT", "AUTO", "CREATED", "", "Inter", "Frame",
+    ];
+
+    fn random_value(rng: &mut Lcg, depth: usize) -> JsonValue {
+        let choice = rng.next() % if depth > 4 { 9 } else { 16 };
+        match choice {
+            0 => json!(true),
+            1 => json!(false),
+            2 => json!(rng.next() % 3),
+            3 => json!(rng.number()),
+            4 => json!(rng.pick(STRINGS)),
+            5 => json!({"__enum__": "BlendMode", "value": rng.pick(STRINGS)}),
+            6 => json!({"r": 1.0, "g": 0.5, "b": 0.0, "a": rng.number()}),
+            7 => json!({"units": rng.pick(&["PIXELS", "PERCENT", "RAW"]), "value": rng.number()}),
+            8 => json!({"localID": 1, "sessionID": 2}),
+            9 => json!({"m00": 1.0, "m01": 0.0, "m02": 10.0, "m10": 0.0, "m11": 1.0, "m12": 20.0}),
+            10 => json!({"hash": [1, 2, 3, 255]}),
+            11 | 12 => {
+                let len = rng.next() % 4;
+                JsonValue::Array((0..len).map(|_| random_value(rng, depth + 1)).collect())
+            }
+            _ => random_object(rng, depth + 1),
+        }
+    }
+
+    /// Random value, biased towards the shape Figma uses for `key`
+    fn random_field(rng: &mut Lcg, key: &str, depth: usize) -> JsonValue {
+        if depth > 6 || rng.next().is_multiple_of(3) {
+            return random_value(rng, depth);
+        }
+        match key {
+            "guid" | "symbolID" | "overriddenSymbolID" | "guidPath" | "styleID" => json!({"localID": 1, "sessionID": 2}),
+            "children" | "fillPaints" | "strokePaints" | "lines" | "symbolOverrides" | "exportSettings" => {
+                let len = rng.next() % 4;
+                JsonValue::Array((0..len).map(|_| random_object(rng, depth + 1)).collect())
+            }
+            "internalOnly" | "visible" => json!(rng.next().is_multiple_of(2)),
+            _ => random_object(rng, depth + 1),
+        }
+    }
+
+    fn random_object(rng: &mut Lcg, depth: usize) -> JsonValue {
+        let mut map = Map::new();
+        for _ in 0..rng.next() % 5 {
+            let key = rng.pick(KEYS);
+            map.insert(key.to_string(), random_field(rng, key, depth));
+        }
+        JsonValue::Object(map)
+    }
+
+    #[test]
+    fn test_standard_groups() {
+        let pipeline = Pipeline::standard();
+        let groups = pipeline.groups();
+
+        assert_eq!(groups.len(), 15);
+        assert_eq!(groups.iter().map(|group| group.len()).sum::<usize>(), 56);
+        assert_eq!(groups[0], vec!["svg_export"]);
+        assert_eq!(groups[1][0], "image_hash");
+        assert_eq!(groups[2], vec!["geometry_removal", "text_layout_removal", "derived_text_layout_size_removal"]);
+        assert_eq!(groups[groups.len() - 1], vec!["empty_objects_removal"]);
+    }
+
+    #[test]
+    fn test_custom_pass_runs_alone() {
+        let mut pipeline = Pipeline::standard();
+        pipeline.insert_after("guid_removal", RenamePass).unwrap();
+
+        assert!(pipeline.groups().contains(&vec!["rename"]));
+    }
+
+    #[test]
+    fn test_fused_run_matches_unfused_run() {
+        let ctx = PassContext::new(Path::new("/nonexistent/fig2json"));
+        let mut rng = Lcg(0x5eed);
+
+        for round in 0..1000usize {
+            let mut pipeline = Pipeline::standard();
+            if round.is_multiple_of(2) {
+                // Also cover the passes that are disabled by default
+                for name in pipeline.names().iter().map(|s| s.to_string()).collect::<Vec<_>>() {
+                    pipeline.enable(&name).unwrap();
+                }
+            }
+            if !round.is_multiple_of(3) {
+                // Disable a few passes to exercise other groupings
+                for _ in 0..rng.next() % 12 {
+                    let names = pipeline.names().iter().map(|s| s.to_string()).collect::<Vec<_>>();
+                    pipeline.disable(&names[rng.next() % names.len()]).unwrap();
+                }
+            }
+
+            let mut output = json!({
+                "version": 101,
+                "fileType": "figma",
+                "document": random_object(&mut rng, 0),
+                "blobs": random_value(&mut rng, 3),
+            });
+            let mut expected = output.clone();
+
+            pipeline.run_unfused(&mut expected, &ctx).unwrap();
+            pipeline.run(&mut output, &ctx).unwrap();
+
+            assert_eq!(
+                serde_json::to_string(&output).unwrap(),
+                serde_json::to_string(&expected).unwrap(),
+                "round {round}, groups {:?}",
+                pipeline.groups()
+            );
+        }
+    }
+}
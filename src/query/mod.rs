@@ -0,0 +1,479 @@
+//! Node queries
+//!
+//! [`Query`] finds nodes of a document with CSS-like selectors:
+//!
+//! | Selector                         | Matches                                          |
+//! | -------------------------------- | ------------------------------------------------ |
+//! | `TEXT`, `*`                      | nodes of a type (case-insensitive), any node     |
+//! | `#1:23`                          | the node with GUID `sessionID:localID`           |
+//! | `[visible]`                      | nodes with the property                          |
+//! | `[name=Checkout]`                | property equal to a value (`!=` for different)   |
+//! | `[name^=Btn]`, `$=`, `*=`        | string property starting with, ending with, containing |
+//! | `[fontSize>20]`, `>=`, `<`, `<=` | numeric comparisons                              |
+//! | `[textData.characters*="Pay"]`   | nested properties                                |
+//! | `INSTANCE[componentName=Button]` | instances of a component (or component set)      |
+//! | `A B`, `A > B`                   | B nodes inside an A node, B children of an A node |
+//! | `A, B`                           | nodes matching A or B                            |
+//!
+//! Queries run on the raw output of [`convert_raw`](crate::convert_raw) or on
+//! a transformed tree; [`pipeline`] keeps the `type`, `guid` and
+//! `symbolData.symbolID` fields that the standard pipeline removes. It does
+//! not expand component instances, so that every GUID selects a single node.
+//! Instead, `componentName` is resolved from `symbolData.symbolID` for
+//! instances that have no such field.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::query::{pipeline, Query};
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let json = fig2json::convert_with_pipeline(&bytes, None, &pipeline()).unwrap();
+//!
+//! let query = Query::parse("FRAME[name=Checkout] TEXT[fontSize>20]").unwrap();
+//! for result in query.find(&json) {
+//!     println!("{}", result.path.join(" / "));
+//! }
+//! ```
+
+mod parser;
+
+use crate::error::Result;
+use crate::pipeline::Pipeline;
+use crate::schema::transformations::instance_expansion::component_names;
+use crate::schema::tree::{guid_key, node_type};
+use parser::{Combinator, Complex, Compound, Operator, Predicate};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Passes of the standard pipeline removing fields that queries match on, or
+/// renaming the image files next to the queried file
+const DISABLED_PASSES: &[&str] = &[
+    "image_hash",
+    "guid_removal",
+    "type_removal",
+    "redundant_padding_removal",
+    "stack_child_properties_removal",
+    "stack_sizing_properties_removal",
+    "stack_align_items_removal",
+    "symbol_id_removal",
+];
+
+/// Standard pipeline keeping the node types, GUIDs, component references and
+/// auto-layout properties, without touching the image files
+pub fn pipeline() -> Pipeline {
+    Pipeline::standard_without(DISABLED_PASSES)
+}
+
+/// A parsed selector list
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    selectors: Vec<Complex>,
+}
+
+/// A node matching a query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryMatch {
+    /// Names of the node's ancestors (from its page) and of the node
+    pub path: Vec<String>,
+    /// JSON Pointer to the node in the queried tree
+    pub pointer: String,
+    pub node: JsonValue,
+}
+
+impl Query {
+    /// Parse a selector list
+    ///
+    /// # Arguments
+    /// * `selector` - Selectors, e.g. `FRAME[name=Checkout] > TEXT`
+    ///
+    /// # Returns
+    /// * `Ok(Query)` - The parsed query
+    /// * `Err(FigError::InvalidQuery)` - If the selector is malformed
+    pub fn parse(selector: &str) -> Result<Self> {
+        Ok(Self {
+            selectors: parser::parse(selector)?,
+        })
+    }
+
+    /// Find the nodes matching the query
+    ///
+    /// # Arguments
+    /// * `output` - Conversion output (raw or transformed), or a node
+    ///
+    /// # Returns
+    /// Matching nodes in document order
+    ///
+    /// # Examples
+    /// ```
+    /// use fig2json::query::Query;
+    /// use serde_json::json;
+    ///
+    /// let output = json!({"document": {"type": "DOCUMENT", "children": [
+    ///     {"type": "CANVAS", "name": "Page", "children": [
+    ///         {"type": "TEXT", "name": "Title", "fontSize": 32.0},
+    ///         {"type": "TEXT", "name": "Body", "fontSize": 16.0}
+    ///     ]}
+    /// ]}});
+    /// let results = Query::parse("CANVAS > TEXT[fontSize>20]").unwrap().find(&output);
+    ///
+    /// assert_eq!(results.len(), 1);
+    /// assert_eq!(results[0].path, ["Page", "Title"]);
+    /// assert_eq!(results[0].pointer, "/document/children/0/children/0");
+    /// ```
+    pub fn find(&self, output: &JsonValue) -> Vec<QueryMatch> {
+        let (root, pointer) = match output.get("document") {
+            Some(document) => (document, "/document".to_string()),
+            None => (output, String::new()),
+        };
+        let mut results = Vec::new();
+        if let Some(root_map) = root.as_object() {
+            let components = component_names(root);
+            let mut ancestors = Vec::new();
+            self.visit(root_map, pointer, &mut ancestors, &components, &mut results);
+        }
+        results
+    }
+
+    fn visit<'a>(
+        &self,
+        node: &'a Map<String, JsonValue>,
+        pointer: String,
+        ancestors: &mut Vec<&'a Map<String, JsonValue>>,
+        components: &Components,
+        results: &mut Vec<QueryMatch>,
+    ) {
+        if self
+            .selectors
+            .iter()
+            .any(|selector| matches_complex(&selector.parts, node, ancestors, components))
+        {
+            // The root (document) node itself has no place in the path
+            let path = match ancestors.split_first() {
+                Some((_, ancestors)) => ancestors
+                    .iter()
+                    .chain(std::iter::once(&node))
+                    .map(|n| {
+                        n.get("name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("")
+                            .to_string()
+                    })
+                    .collect(),
+                None => Vec::new(),
+            };
+            results.push(QueryMatch {
+                path,
+                pointer: pointer.clone(),
+                node: JsonValue::Object(node.clone()),
+            });
+        }
+
+        if let Some(JsonValue::Array(children)) = node.get("children") {
+            ancestors.push(node);
+            for (i, child) in children.iter().enumerate() {
+                if let Some(child) = child.as_object() {
+                    let pointer = format!("{}/children/{}", pointer, i);
+                    self.visit(child, pointer, ancestors, components, results);
+                }
+            }
+            ancestors.pop();
+        }
+    }
+}
+
+/// Names of the main components by SYMBOL GUID
+type Components = HashMap<String, String>;
+
+/// Whether a node and its ancestors match the compounds of a selector
+fn matches_complex(
+    parts: &[(Combinator, Compound)],
+    node: &Map<String, JsonValue>,
+    ancestors: &[&Map<String, JsonValue>],
+    components: &Components,
+) -> bool {
+    let Some(((combinator, compound), rest)) = parts.split_last() else {
+        return true;
+    };
+    if !matches_compound(compound, node, components) {
+        return false;
+    }
+    if rest.is_empty() {
+        return true;
+    }
+
+    let Some((parent, above)) = ancestors.split_last() else {
+        return false;
+    };
+    match combinator {
+        Combinator::Child => matches_complex(rest, parent, above, components),
+        Combinator::Descendant => (0..ancestors.len())
+            .rev()
+            .any(|i| matches_complex(rest, ancestors[i], &ancestors[..i], components)),
+    }
+}
+
+fn matches_compound(compound: &Compound, node: &Map<String, JsonValue>, components: &Components) -> bool {
+    if let Some(expected) = &compound.node_type {
+        if !node_type(node).is_some_and(|actual| actual.eq_ignore_ascii_case(expected)) {
+            return false;
+        }
+    }
+    if let Some(guid) = &compound.guid {
+        if node.get("guid").and_then(guid_key).as_ref() != Some(guid) {
+            return false;
+        }
+    }
+    compound
+        .predicates
+        .iter()
+        .all(|predicate| matches_predicate(predicate, node, components))
+}
+
+/// Name of the main component of an instance, from `symbolData.symbolID`
+fn component_name(node: &Map<String, JsonValue>, components: &Components) -> Option<JsonValue> {
+    let id = node.get("symbolData")?.get("symbolID").and_then(guid_key)?;
+    components.get(&id).map(|name| JsonValue::String(name.clone()))
+}
+
+fn matches_predicate(predicate: &Predicate, node: &Map<String, JsonValue>, components: &Components) -> bool {
+    let resolved = match predicate.path.first() {
+        Some(first) if first == "componentName" && !node.contains_key(first) => {
+            component_name(node, components)
+        }
+        _ => None,
+    };
+    let mut path = predicate.path.iter();
+    let Some(value) = path
+        .next()
+        .and_then(|first| node.get(first).or(resolved.as_ref()))
+        .and_then(|value| path.try_fold(value, |value, key| value.get(key)))
+    else {
+        return false;
+    };
+    // Raw enums are compared by their value
+    let value = match value.get("__enum__") {
+        Some(_) => value.get("value").unwrap_or(value),
+        None => value,
+    };
+    let Some((operator, expected)) = &predicate.comparison else {
+        return true;
+    };
+
+    let text = match value {
+        JsonValue::String(text) => text.clone(),
+        JsonValue::Number(_) | JsonValue::Bool(_) => value.to_string(),
+        _ => return *operator == Operator::NotEqual,
+    };
+    let numbers = value.as_f64().zip(expected.parse::<f64>().ok());
+    match operator {
+        Operator::Equal => numbers.map_or(text == *expected, |(a, b)| a == b),
+        Operator::NotEqual => numbers.map_or(text != *expected, |(a, b)| a != b),
+        Operator::StartsWith => text.starts_with(expected.as_str()),
+        Operator::EndsWith => text.ends_with(expected.as_str()),
+        Operator::Contains => text.contains(expected.as_str()),
+        Operator::Greater => numbers.is_some_and(|(a, b)| a > b),
+        Operator::GreaterOrEqual => numbers.is_some_and(|(a, b)| a >= b),
+        Operator::Less => numbers.is_some_and(|(a, b)| a < b),
+        Operator::LessOrEqual => numbers.is_some_and(|(a, b)| a <= b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn output() -> JsonValue {
+        json!({"document": {
+            "type": {"__enum__": "NodeType", "value": "DOCUMENT"},
+            "children": [{
+                "type": {"__enum__": "NodeType", "value": "CANVAS"},
+                "name": "Page",
+                "children": [
+                    {
+                        "type": {"__enum__": "NodeType", "value": "FRAME"},
+                        "name": "Checkout",
+                        "guid": {"sessionID": 1, "localID": 2},
+                        "children": [
+                            {
+                                "type": {"__enum__": "NodeType", "value": "FRAME"},
+                                "name": "Summary",
+                                "children": [
+                                    {"type": {"__enum__": "NodeType", "value": "TEXT"}, "name": "Total", "fontSize": 24.0, "textData": {"characters": "Total: 42 €"}}
+                                ]
+                            },
+                            {"type": {"__enum__": "NodeType", "value": "TEXT"}, "name": "Title", "fontSize": 32.0, "textData": {"characters": "Checkout"}},
+                            {"type": {"__enum__": "NodeType", "value": "TEXT"}, "name": "Note", "fontSize": 12.0}
+                        ]
+                    },
+                    {"type": {"__enum__": "NodeType", "value": "INSTANCE"}, "name": "Button", "visible": false}
+                ]
+            }]
+        }})
+    }
+
+    fn names(query: &str) -> Vec<String> {
+        Query::parse(query)
+            .unwrap()
+            .find(&output())
+            .into_iter()
+            .map(|result| result.path.join("/"))
+            .collect()
+    }
+
+    #[test]
+    fn test_descendant_and_child() {
+        assert_eq!(
+            names("FRAME[name=Checkout] TEXT[fontSize>20]"),
+            ["Page/Checkout/Summary/Total", "Page/Checkout/Title"]
+        );
+        assert_eq!(
+            names("frame[name=Checkout] > text[fontSize>20]"),
+            ["Page/Checkout/Title"]
+        );
+        assert_eq!(names("#1:2 > FRAME TEXT"), ["Page/Checkout/Summary/Total"]);
+    }
+
+    #[test]
+    fn test_predicates() {
+        assert_eq!(
+            names("[textData.characters*=\"42\"]"),
+            ["Page/Checkout/Summary/Total"]
+        );
+        assert_eq!(
+            names("TEXT[name^=T][fontSize<=24]"),
+            ["Page/Checkout/Summary/Total"]
+        );
+        assert_eq!(names("INSTANCE[visible=false]"), ["Page/Button"]);
+        assert_eq!(
+            names("TEXT[textData]"),
+            ["Page/Checkout/Summary/Total", "Page/Checkout/Title"]
+        );
+        assert_eq!(names("CANVAS[fontSize>0]"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_match_pointer() {
+        let output = output();
+        let results = Query::parse("TEXT[name=Title], DOCUMENT")
+            .unwrap()
+            .find(&output);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].pointer, "/document");
+        assert_eq!(results[0].path, Vec::<String>::new());
+        assert_eq!(
+            results[1].pointer,
+            "/document/children/0/children/0/children/1"
+        );
+        assert_eq!(output.pointer(&results[1].pointer), Some(&results[1].node));
+    }
+
+    #[test]
+    fn test_pipeline_keeps_guids_unique() {
+        let mut output = json!({"document": {"children": [{
+            "type": {"__enum__": "NodeType", "value": "CANVAS"},
+            "name": "Page",
+            "children": [
+                {
+                    "type": {"__enum__": "NodeType", "value": "SYMBOL"},
+                    "name": "Button",
+                    "guid": {"sessionID": 1, "localID": 1},
+                    "children": [{"type": {"__enum__": "NodeType", "value": "TEXT"}, "name": "Label", "guid": {"sessionID": 1, "localID": 2}}]
+                },
+                {
+                    "type": {"__enum__": "NodeType", "value": "INSTANCE"},
+                    "name": "Button",
+                    "guid": {"sessionID": 1, "localID": 3},
+                    "symbolData": {"symbolID": {"sessionID": 1, "localID": 1}}
+                }
+            ]
+        }]}});
+        pipeline().run(&mut output, &crate::PassContext::default()).unwrap();
+
+        let results = Query::parse("#1:2").unwrap().find(&output);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, ["Page", "Button", "Label"]);
+    }
+
+    #[test]
+    fn test_component_name_without_expansion() {
+        let mut output = json!({"document": {
+            "type": {"__enum__": "NodeType", "value": "DOCUMENT"},
+            "children": [{
+                "type": {"__enum__": "NodeType", "value": "CANVAS"},
+                "name": "Page",
+                "children": [
+                    {
+                        "type": {"__enum__": "NodeType", "value": "SYMBOL"},
+                        "name": "Button",
+                        "guid": {"sessionID": 1, "localID": 1},
+                        "children": [{"type": {"__enum__": "NodeType", "value": "TEXT"}, "name": "Label", "guid": {"sessionID": 1, "localID": 2}}]
+                    },
+                    {
+                        "type": {"__enum__": "NodeType", "value": "FRAME"},
+                        "name": "Toggle",
+                        "isStateGroup": true,
+                        "guid": {"sessionID": 1, "localID": 3},
+                        "children": [{"type": {"__enum__": "NodeType", "value": "SYMBOL"}, "name": "State=On", "guid": {"sessionID": 1, "localID": 4}}]
+                    },
+                    {
+                        "type": {"__enum__": "NodeType", "value": "FRAME"},
+                        "name": "Form",
+                        "guid": {"sessionID": 1, "localID": 5},
+                        "children": [
+                            {
+                                "type": {"__enum__": "NodeType", "value": "INSTANCE"},
+                                "name": "Submit",
+                                "guid": {"sessionID": 1, "localID": 6},
+                                "symbolData": {"symbolID": {"sessionID": 1, "localID": 1}}
+                            },
+                            {
+                                "type": {"__enum__": "NodeType", "value": "INSTANCE"},
+                                "name": "Remember me",
+                                "guid": {"sessionID": 1, "localID": 7},
+                                "symbolData": {"symbolID": {"sessionID": 1, "localID": 4}}
+                            }
+                        ]
+                    }
+                ]
+            }]
+        }});
+        pipeline().run(&mut output, &crate::PassContext::default()).unwrap();
+
+        let find = |query: &str| -> Vec<String> {
+            Query::parse(query)
+                .unwrap()
+                .find(&output)
+                .into_iter()
+                .map(|result| result.path.join("/"))
+                .collect()
+        };
+        assert_eq!(find("INSTANCE[componentName=Button]"), ["Page/Form/Submit"]);
+        assert_eq!(find("INSTANCE[componentName=Toggle]"), ["Page/Form/Remember me"]);
+        assert_eq!(find("[componentName]"), ["Page/Form/Submit", "Page/Form/Remember me"]);
+        // Instances are not expanded
+        assert_eq!(find("INSTANCE TEXT"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_pipeline_leaves_image_files_alone() {
+        let temp_dir = std::env::temp_dir().join("fig2json_test_query_images");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let image = temp_dir.join("images").join("6049a17a");
+        std::fs::create_dir_all(image.parent().unwrap()).unwrap();
+        std::fs::write(&image, [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]).unwrap();
+
+        let mut output = json!({"document": {"children": [{
+            "name": "Page",
+            "children": [{"name": "Photo", "fillPaints": [{"image": {"hash": [96, 73, 161, 122]}}]}]
+        }]}});
+        pipeline().run(&mut output, &crate::PassContext::new(&temp_dir)).unwrap();
+
+        assert!(image.exists());
+        assert!(!temp_dir.join("images/6049a17a.png").exists());
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+}
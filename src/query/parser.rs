@@ -0,0 +1,313 @@
+//! Query selector parser
+//!
+//! ```text
+//! list      := complex ("," complex)*
+//! complex   := compound ((" " | ">") compound)*
+//! compound  := (TYPE | "*")? ("#" GUID | "[" path (op value)? "]")*
+//! path      := IDENT ("." IDENT)*
+//! op        := "=" | "!=" | "^=" | "$=" | "*=" | ">" | ">=" | "<" | "<="
+//! value     := '"' ... '"' | "'" ... "'" | bare word
+//! ```
+
+use crate::error::{FigError, Result};
+
+/// Selector matching a node and, through its combinators, its ancestors
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Complex {
+    /// Compounds from the outermost ancestor to the node, each with the
+    /// combinator linking it to the previous one
+    pub parts: Vec<(Combinator, Compound)>,
+}
+
+/// How a compound relates to the previous one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Combinator {
+    /// Any ancestor (whitespace)
+    Descendant,
+    /// Parent (`>`)
+    Child,
+}
+
+/// Conditions on a single node
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Compound {
+    /// Node type, uppercase (`None` for `*` or no type)
+    pub node_type: Option<String>,
+    /// `sessionID:localID` GUID
+    pub guid: Option<String>,
+    pub predicates: Vec<Predicate>,
+}
+
+/// `[path op value]` condition on a node property
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Predicate {
+    /// Property path, e.g. `["textData", "characters"]`
+    pub path: Vec<String>,
+    /// Comparison, `None` to only test that the property exists
+    pub comparison: Option<(Operator, String)>,
+}
+
+/// Comparison operator of a predicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operator {
+    Equal,
+    NotEqual,
+    StartsWith,
+    EndsWith,
+    Contains,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+/// Parse a comma-separated list of selectors
+pub(crate) fn parse(input: &str) -> Result<Vec<Complex>> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+    };
+    let mut list = vec![parser.complex()?];
+    while parser.eat(',') {
+        list.push(parser.complex()?);
+    }
+    parser.skip_whitespace();
+    if let Some(c) = parser.peek() {
+        return Err(parser.error(&format!("unexpected '{}'", c)));
+    }
+    Ok(list)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn complex(&mut self) -> Result<Complex> {
+        self.skip_whitespace();
+        let mut parts = vec![(Combinator::Descendant, self.compound()?)];
+        loop {
+            let had_whitespace = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                Some(',') | None => break,
+                Some(_) if had_whitespace => Combinator::Descendant,
+                Some(c) => return Err(self.error(&format!("unexpected '{}'", c))),
+            };
+            parts.push((combinator, self.compound()?));
+        }
+        Ok(Complex { parts })
+    }
+
+    fn compound(&mut self) -> Result<Compound> {
+        let start = self.pos;
+        let mut compound = Compound::default();
+        if self.eat('*') {
+            // Any type
+        } else if self.peek().is_some_and(is_ident_start) {
+            compound.node_type = Some(self.ident().to_ascii_uppercase());
+        }
+
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.pos += 1;
+                    let guid = self.take_while(|c| c.is_ascii_digit() || c == ':');
+                    if !guid.contains(':') {
+                        return Err(self.error("expected a GUID like #1:23"));
+                    }
+                    compound.guid = Some(guid);
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    compound.predicates.push(self.predicate()?);
+                }
+                _ => break,
+            }
+        }
+
+        if self.pos == start {
+            return Err(self.error("expected a node type, '*', '#' or '['"));
+        }
+        Ok(compound)
+    }
+
+    fn predicate(&mut self) -> Result<Predicate> {
+        self.skip_whitespace();
+        let mut path = Vec::new();
+        loop {
+            if !self.peek().is_some_and(is_ident_start) {
+                return Err(self.error("expected a property name"));
+            }
+            path.push(self.ident());
+            if !self.eat('.') {
+                break;
+            }
+        }
+
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(Predicate {
+                path,
+                comparison: None,
+            });
+        }
+
+        let operator = self.operator()?;
+        self.skip_whitespace();
+        let value = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let value = self.take_while(|c| c != quote);
+                if !self.eat(quote) {
+                    return Err(self.error("unterminated string"));
+                }
+                value
+            }
+            _ => self.take_while(|c| c != ']').trim_end().to_string(),
+        };
+        self.skip_whitespace();
+        if !self.eat(']') {
+            return Err(self.error("expected ']'"));
+        }
+
+        Ok(Predicate {
+            path,
+            comparison: Some((operator, value)),
+        })
+    }
+
+    fn operator(&mut self) -> Result<Operator> {
+        let operators = [
+            ("!=", Operator::NotEqual),
+            ("^=", Operator::StartsWith),
+            ("$=", Operator::EndsWith),
+            ("*=", Operator::Contains),
+            (">=", Operator::GreaterOrEqual),
+            ("<=", Operator::LessOrEqual),
+            ("=", Operator::Equal),
+            (">", Operator::Greater),
+            ("<", Operator::Less),
+        ];
+        for (symbol, operator) in operators {
+            if self.chars[self.pos..].starts_with(&symbol.chars().collect::<Vec<_>>()) {
+                self.pos += symbol.len();
+                return Ok(operator);
+            }
+        }
+        Err(self.error("expected an operator or ']'"))
+    }
+
+    fn ident(&mut self) -> String {
+        self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(&predicate) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Skip whitespace, returning whether there was any
+    fn skip_whitespace(&mut self) -> bool {
+        !self.take_while(char::is_whitespace).is_empty()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> FigError {
+        FigError::InvalidQuery(format!("{} at position {}", message, self.pos + 1))
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn predicate(path: &[&str], operator: Operator, value: &str) -> Predicate {
+        Predicate {
+            path: path.iter().map(|s| s.to_string()).collect(),
+            comparison: Some((operator, value.to_string())),
+        }
+    }
+
+    #[test]
+    fn test_parse_compound() {
+        let list = parse(r#"text[fontSize>=20][textData.characters*="Pay now"]"#).unwrap();
+
+        assert_eq!(
+            list,
+            [Complex {
+                parts: vec![(
+                    Combinator::Descendant,
+                    Compound {
+                        node_type: Some("TEXT".to_string()),
+                        guid: None,
+                        predicates: vec![
+                            predicate(&["fontSize"], Operator::GreaterOrEqual, "20"),
+                            predicate(&["textData", "characters"], Operator::Contains, "Pay now"),
+                        ],
+                    }
+                )]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_combinators() {
+        let list = parse("FRAME[name=Checkout] > * #1:23, INSTANCE[visible]").unwrap();
+
+        assert_eq!(list.len(), 2);
+        let combinators: Vec<Combinator> = list[0].parts.iter().map(|(c, _)| *c).collect();
+        assert_eq!(
+            combinators,
+            [
+                Combinator::Descendant,
+                Combinator::Child,
+                Combinator::Descendant
+            ]
+        );
+        assert_eq!(list[0].parts[2].1.guid.as_deref(), Some("1:23"));
+        assert_eq!(list[1].parts[0].1.predicates[0].comparison, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for input in [
+            "",
+            "FRAME >",
+            "TEXT[fontSize~=2]",
+            "[name=\"Checkout]",
+            "#12",
+            "TEXT,",
+        ] {
+            assert!(
+                matches!(parse(input), Err(FigError::InvalidQuery(_))),
+                "{:?} should not parse",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,965 @@
+//! React components from SYMBOL nodes
+//!
+//! [`render_components`] turns every main component of a document into a
+//! TypeScript React component:
+//!
+//! - a standalone SYMBOL node becomes one component
+//! - a component set (a frame with `isStateGroup`) becomes one component whose
+//!   variant properties ("Size=Large, State=Hover") are string-literal props,
+//!   each variant being rendered under its own condition
+//! - component property definitions (`componentPropDefs`) become props, bound
+//!   to the text, visibility or swapped instance of the nodes referencing them
+//! - INSTANCE nodes become imports of the components they instantiate
+//!
+//! Styles are the inline equivalent of the [`html`](crate::html) stylesheet.
+//! The output is a compilable skeleton to refine, not a finished component.
+//!
+//! The input is the output of [`pipeline`], which keeps the node types and
+//! GUIDs that the standard pipeline removes.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::react::{pipeline, render_components};
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let json = fig2json::convert_with_pipeline(&bytes, None, &pipeline()).unwrap();
+//!
+//! for component in render_components(&json) {
+//!     std::fs::write(&component.file_name, &component.source).unwrap();
+//! }
+//! ```
+
+use crate::components::parse_variant_name;
+use crate::css::is_text;
+use crate::html::css::{node_declarations, Placement};
+use crate::schema::tree::{guid_key, node_type};
+use crate::Pipeline;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// Passes of the standard pipeline that drop what components are built from
+const DISABLED_PASSES: &[&str] = &[
+    "guid_removal",
+    "symbol_id_removal",
+    "type_removal",
+    "redundant_padding_removal",
+    "stack_align_items_removal",
+];
+
+/// Generated source of a component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactComponent {
+    /// Component name (PascalCase, unique in the document)
+    pub name: String,
+    /// File name (`<name>.tsx`)
+    pub file_name: String,
+    /// TypeScript source
+    pub source: String,
+}
+
+/// Standard pipeline without the passes removing GUIDs, symbol IDs, node types
+/// and auto-layout properties, with gradients and effects written as CSS
+pub fn pipeline() -> Pipeline {
+    let mut pipeline = Pipeline::standard_without(DISABLED_PASSES);
+    for name in ["gradient_to_css", "effects_to_css"] {
+        pipeline.enable(name).expect("pass of the standard pipeline");
+    }
+    pipeline
+}
+
+/// Render every main component of a document
+///
+/// # Arguments
+/// * `output` - Output of [`convert_with_pipeline`](crate::convert_with_pipeline)
+///   with [`pipeline`], or its `document` node
+///
+/// # Returns
+/// Components in document order
+///
+/// # Examples
+/// ```
+/// use fig2json::react::render_components;
+/// use serde_json::json;
+///
+/// let document = json!({"type": "DOCUMENT", "children": [{"type": "CANVAS", "children": [{
+///     "type": "SYMBOL",
+///     "name": "Badge",
+///     "guid": {"sessionID": 1, "localID": 2},
+///     "children": [{"type": "TEXT", "name": "Label", "textData": {"characters": "New"}}]
+/// }]}]});
+///
+/// let components = render_components(&document);
+/// assert_eq!(components[0].file_name, "Badge.tsx");
+/// assert!(components[0].source.contains("export function Badge() {"));
+/// ```
+pub fn render_components(output: &JsonValue) -> Vec<ReactComponent> {
+    let document = output.get("document").unwrap_or(output);
+    let library = Library::collect(document);
+
+    library
+        .components
+        .iter()
+        .map(|component| ReactComponent {
+            name: component.name.clone(),
+            file_name: format!("{}.tsx", component.name),
+            source: library.render(component),
+        })
+        .collect()
+}
+
+/// `index.ts` re-exporting every component
+pub fn index_source(components: &[ReactComponent]) -> String {
+    components
+        .iter()
+        .map(|c| format!("export {{ {} }} from \"./{}\";\n", c.name, c.name))
+        .collect()
+}
+
+/// Kind of a component property
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PropKind {
+    Bool,
+    Text,
+    Number,
+    InstanceSwap,
+}
+
+impl PropKind {
+    fn typescript(self) -> &'static str {
+        match self {
+            PropKind::Bool => "boolean",
+            PropKind::Text => "string",
+            PropKind::Number => "number",
+            PropKind::InstanceSwap => "ReactNode",
+        }
+    }
+}
+
+/// A prop from a component property definition
+#[derive(Debug, Clone)]
+struct Prop {
+    name: String,
+    kind: PropKind,
+    /// TypeScript literal of the initial value
+    default: Option<String>,
+}
+
+/// A prop from a variant property of a component set
+#[derive(Debug, Clone)]
+struct VariantProp {
+    name: String,
+    property: String,
+    values: Vec<String>,
+}
+
+/// A SYMBOL node and its variant values (aligned with the component's variant props)
+struct Variant<'a> {
+    node: &'a Map<String, JsonValue>,
+    values: Vec<String>,
+}
+
+struct Component<'a> {
+    name: String,
+    variant_props: Vec<VariantProp>,
+    props: Vec<Prop>,
+    variants: Vec<Variant<'a>>,
+}
+
+/// Components of a document and the lookups needed to render instances
+#[derive(Default)]
+struct Library<'a> {
+    components: Vec<Component<'a>>,
+    /// Symbol GUID → component index and variant index
+    symbols: HashMap<String, (usize, usize)>,
+    /// Property definition GUID → component index and prop index
+    prop_defs: HashMap<String, (usize, usize)>,
+    /// Component names already given
+    names: BTreeSet<String>,
+}
+
+impl<'a> Library<'a> {
+    fn collect(document: &'a JsonValue) -> Self {
+        let mut library = Library::default();
+        library.visit(document);
+        library
+    }
+
+    fn visit(&mut self, node: &'a JsonValue) {
+        let Some(map) = node.as_object() else {
+            return;
+        };
+
+        if map.get("isStateGroup").and_then(|v| v.as_bool()) == Some(true) {
+            self.add_component_set(map);
+        } else if node_type(map) == Some("SYMBOL") {
+            self.add_symbol(map);
+        } else {
+            for child in children(map) {
+                self.visit(child);
+            }
+        }
+    }
+
+    fn add_symbol(&mut self, symbol: &'a Map<String, JsonValue>) {
+        let index = self.components.len();
+        let props = self.register_props(index, [symbol]);
+        if let Some(guid) = symbol.get("guid").and_then(guid_key) {
+            self.symbols.insert(guid, (index, 0));
+        }
+
+        let name = self.unique_name(node_name(symbol));
+        self.components.push(Component {
+            name,
+            variant_props: Vec::new(),
+            props,
+            variants: vec![Variant {
+                node: symbol,
+                values: Vec::new(),
+            }],
+        });
+    }
+
+    fn add_component_set(&mut self, set: &'a Map<String, JsonValue>) {
+        let index = self.components.len();
+        let symbols: Vec<&Map<String, JsonValue>> = children(set)
+            .iter()
+            .filter_map(|c| c.as_object())
+            .filter(|c| node_type(c) == Some("SYMBOL"))
+            .collect();
+        if symbols.is_empty() {
+            return;
+        }
+        let parsed: Vec<Vec<(String, String)>> = symbols
+            .iter()
+            .map(|symbol| parse_variant_name(node_name(symbol)))
+            .collect();
+
+        // Property order and values from Figma when available, else by appearance
+        let mut variant_props: Vec<VariantProp> = Vec::new();
+        let orders = set
+            .get("stateGroupPropertyValueOrders")
+            .and_then(|o| o.as_array())
+            .into_iter()
+            .flatten();
+        for order in orders {
+            let Some(property) = order.get("property").and_then(|p| p.as_str()) else {
+                continue;
+            };
+            let values = order
+                .get("values")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
+            variant_props.push(VariantProp {
+                name: String::new(),
+                property: property.to_string(),
+                values,
+            });
+        }
+        for (property, value) in parsed.iter().flatten() {
+            let prop = match variant_props.iter_mut().find(|p| &p.property == property) {
+                Some(prop) => prop,
+                None => {
+                    variant_props.push(VariantProp {
+                        name: String::new(),
+                        property: property.clone(),
+                        values: Vec::new(),
+                    });
+                    variant_props.last_mut().unwrap()
+                }
+            };
+            if !prop.values.contains(value) {
+                prop.values.push(value.clone());
+            }
+        }
+        // A property without values has no type to declare
+        variant_props.retain(|prop| !prop.values.is_empty());
+
+        let mut taken = BTreeSet::new();
+        for prop in &mut variant_props {
+            prop.name = unique_identifier(&camel_case(&prop.property, "variant"), &mut taken);
+        }
+
+        let mut props = self.register_props(index, std::iter::once(set).chain(symbols.iter().copied()));
+        for prop in &mut props {
+            prop.name = unique_identifier(&prop.name, &mut taken);
+        }
+
+        let variants = symbols
+            .iter()
+            .zip(parsed)
+            .enumerate()
+            .map(|(variant_index, (symbol, pairs))| {
+                if let Some(guid) = symbol.get("guid").and_then(guid_key) {
+                    self.symbols.insert(guid, (index, variant_index));
+                }
+                let values = variant_props
+                    .iter()
+                    .map(|prop| {
+                        pairs
+                            .iter()
+                            .find(|(property, _)| *property == prop.property)
+                            .map(|(_, value)| value.clone())
+                            .unwrap_or_else(|| prop.values.first().cloned().unwrap_or_default())
+                    })
+                    .collect();
+                Variant {
+                    node: symbol,
+                    values,
+                }
+            })
+            .collect();
+
+        let name = self.unique_name(node_name(set));
+        self.components.push(Component {
+            name,
+            variant_props,
+            props,
+            variants,
+        });
+    }
+
+    /// Props from the `componentPropDefs` of `nodes`, deduplicated by name and kind
+    ///
+    /// Definitions sharing a name but not a kind become separate props.
+    fn register_props(
+        &mut self,
+        component: usize,
+        nodes: impl IntoIterator<Item = &'a Map<String, JsonValue>>,
+    ) -> Vec<Prop> {
+        let mut props: Vec<Prop> = Vec::new();
+        // Name before deduplication and kind of each prop
+        let mut keys: Vec<(String, PropKind)> = Vec::new();
+        let mut taken = BTreeSet::new();
+
+        for def in nodes
+            .into_iter()
+            .filter_map(|node| node.get("componentPropDefs")?.as_array())
+            .flatten()
+        {
+            let kind = match def.get("type").and_then(|t| t.as_str()) {
+                Some("BOOL") => PropKind::Bool,
+                Some("TEXT") => PropKind::Text,
+                Some("NUMBER") => PropKind::Number,
+                Some("INSTANCE_SWAP") => PropKind::InstanceSwap,
+                _ => continue,
+            };
+            let label = def.get("name").and_then(|n| n.as_str()).unwrap_or("");
+            let label = label.split('#').next().unwrap_or(label);
+            let name = camel_case(label, "prop");
+
+            let index = match keys.iter().position(|key| key.0 == name && key.1 == kind) {
+                Some(index) => index,
+                None => {
+                    props.push(Prop {
+                        name: unique_identifier(&name, &mut taken),
+                        kind,
+                        default: def.get("initialValue").and_then(|v| prop_literal(kind, v)),
+                    });
+                    keys.push((name, kind));
+                    props.len() - 1
+                }
+            };
+            if let Some(id) = def.get("id").and_then(guid_key) {
+                self.prop_defs.insert(id, (component, index));
+            }
+        }
+
+        props
+    }
+
+    fn unique_name(&mut self, name: &str) -> String {
+        unique_identifier(&pascal_case(name), &mut self.names)
+    }
+
+    fn render(&self, component: &Component) -> String {
+        let mut renderer = Renderer {
+            library: self,
+            component,
+            imports: BTreeSet::new(),
+        };
+
+        let (default, others) = component.variants.split_first().expect("at least one variant");
+        let mut body = String::new();
+        for variant in others {
+            let condition: Vec<String> = component
+                .variant_props
+                .iter()
+                .zip(&variant.values)
+                .map(|(prop, value)| format!("{} === {}", prop.name, string_literal(value)))
+                .collect();
+            let _ = writeln!(body, "  if ({}) {{", condition.join(" && "));
+            renderer.return_statement(variant.node, 2, &mut body);
+            body.push_str("  }\n");
+        }
+        renderer.return_statement(default.node, 1, &mut body);
+
+        let mut source = String::new();
+        let uses_react_node = component
+            .props
+            .iter()
+            .any(|p| p.kind == PropKind::InstanceSwap);
+        if uses_react_node {
+            source.push_str("import type { ReactNode } from \"react\";\n");
+        }
+        for import in &renderer.imports {
+            let _ = writeln!(source, "import {{ {} }} from \"./{}\";", import, import);
+        }
+        if !source.is_empty() {
+            source.push('\n');
+        }
+
+        let has_props = !component.variant_props.is_empty() || !component.props.is_empty();
+        if has_props {
+            let _ = writeln!(source, "export interface {}Props {{", component.name);
+            for prop in &component.variant_props {
+                let values: Vec<String> = prop.values.iter().map(|v| string_literal(v)).collect();
+                let _ = writeln!(source, "  {}?: {};", prop.name, values.join(" | "));
+            }
+            for prop in &component.props {
+                let _ = writeln!(source, "  {}?: {};", prop.name, prop.kind.typescript());
+            }
+            source.push_str("}\n\n");
+
+            let mut bindings: Vec<String> = component
+                .variant_props
+                .iter()
+                .zip(&default.values)
+                .map(|(prop, value)| format!("{} = {}", prop.name, string_literal(value)))
+                .collect();
+            bindings.extend(component.props.iter().map(|prop| match &prop.default {
+                Some(default) => format!("{} = {}", prop.name, default),
+                None => prop.name.clone(),
+            }));
+            let _ = writeln!(
+                source,
+                "export function {}({{ {} }}: {}Props) {{",
+                component.name,
+                bindings.join(", "),
+                component.name
+            );
+        } else {
+            let _ = writeln!(source, "export function {}() {{", component.name);
+        }
+        source.push_str(&body);
+        source.push_str("}\n");
+        source
+    }
+}
+
+/// Writes the JSX of a component and records the components it imports
+struct Renderer<'l, 'a> {
+    library: &'l Library<'a>,
+    component: &'l Component<'a>,
+    imports: BTreeSet<String>,
+}
+
+impl Renderer<'_, '_> {
+    fn return_statement(&mut self, symbol: &Map<String, JsonValue>, depth: usize, out: &mut String) {
+        let indent = "  ".repeat(depth);
+        let _ = writeln!(out, "{}return (", indent);
+        self.element(symbol, Placement::Root, depth + 1, out);
+        let _ = writeln!(out, "{});", indent);
+    }
+
+    fn element(&mut self, node: &Map<String, JsonValue>, placement: Placement, depth: usize, out: &mut String) {
+        let indent = "  ".repeat(depth);
+        let visible_prop = self.bound_prop(node, "VISIBLE");
+        if visible_prop.is_none() && node.get("visible").and_then(|v| v.as_bool()) == Some(false) {
+            return;
+        }
+
+        if let Some(prop) = visible_prop {
+            let _ = writeln!(out, "{}{{{} && (", indent, prop);
+            self.element_body(node, placement, depth + 1, out);
+            let _ = writeln!(out, "{})}}", indent);
+        } else {
+            self.element_body(node, placement, depth, out);
+        }
+    }
+
+    fn element_body(&mut self, node: &Map<String, JsonValue>, placement: Placement, depth: usize, out: &mut String) {
+        let indent = "  ".repeat(depth);
+
+        if node_type(node) == Some("INSTANCE") {
+            let element = self.instance(node);
+            if placement == Placement::Absolute {
+                let style = style_attribute(node, placement, &["position", "left", "top"]);
+                let _ = writeln!(out, "{}<div{}>", indent, style);
+                let _ = writeln!(out, "{}  {}", indent, element);
+                let _ = writeln!(out, "{}</div>", indent);
+            } else {
+                let _ = writeln!(out, "{}{}", indent, element);
+            }
+            return;
+        }
+
+        let style = style_attribute(node, placement, &[]);
+        if is_text(node) {
+            let text = match self.bound_prop(node, "TEXT_DATA") {
+                Some(prop) => format!("{{{}}}", prop),
+                None => jsx_text(
+                    node.get("textData")
+                        .and_then(|t| t.get("characters"))
+                        .and_then(|c| c.as_str())
+                        .unwrap_or(""),
+                ),
+            };
+            let _ = writeln!(out, "{}<p{}>{}</p>", indent, style, text);
+            return;
+        }
+
+        let children: Vec<&Map<String, JsonValue>> =
+            children(node).iter().filter_map(|c| c.as_object()).collect();
+        if children.is_empty() {
+            let _ = writeln!(out, "{}<div{} />", indent, style);
+            return;
+        }
+
+        let _ = writeln!(out, "{}<div{}>", indent, style);
+        let child_placement = Placement::of_children(node);
+        for child in children {
+            self.element(child, child_placement.for_child(child), depth + 1, out);
+        }
+        let _ = writeln!(out, "{}</div>", indent);
+    }
+
+    /// JSX of an instance: the instantiated component with its variant and property values
+    fn instance(&mut self, node: &Map<String, JsonValue>) -> String {
+        if let Some(prop) = self.bound_prop(node, "OVERRIDDEN_SYMBOL_ID") {
+            return format!("{{{}}}", prop);
+        }
+
+        let symbol_id = node
+            .get("symbolData")
+            .and_then(|s| s.get("symbolID"))
+            .and_then(guid_key);
+        let Some(&(index, variant)) = symbol_id.and_then(|id| self.library.symbols.get(&id)) else {
+            return format!("{{/* {} */}}", node_name(node).replace("*/", "* /"));
+        };
+        let target = &self.library.components[index];
+        if !std::ptr::eq(target, self.component) {
+            self.imports.insert(target.name.clone());
+        }
+
+        let mut attributes = String::new();
+        let defaults = &target.variants[0].values;
+        for ((prop, value), default) in target
+            .variant_props
+            .iter()
+            .zip(&target.variants[variant].values)
+            .zip(defaults)
+        {
+            if value != default {
+                let _ = write!(attributes, " {}={}", prop.name, string_literal(value));
+            }
+        }
+
+        let assignments = node
+            .get("componentPropAssignments")
+            .and_then(|a| a.as_array())
+            .into_iter()
+            .flatten();
+        for assignment in assignments {
+            let Some(&(component, prop)) = assignment.get("defID").and_then(guid_key)
+                .and_then(|id| self.library.prop_defs.get(&id))
+                .filter(|(component, _)| *component == index)
+            else {
+                continue;
+            };
+            let prop = &self.library.components[component].props[prop];
+            let value = assignment
+                .get("value")
+                .and_then(|v| prop_literal(prop.kind, v));
+            if let Some(value) = value {
+                let _ = write!(attributes, " {}={{{}}}", prop.name, value);
+            }
+        }
+
+        format!("<{}{} />", target.name, attributes)
+    }
+
+    /// Prop bound to a field of the node through `componentPropRefs`
+    fn bound_prop(&self, node: &Map<String, JsonValue>, field: &str) -> Option<&str> {
+        node.get("componentPropRefs")?
+            .as_array()?
+            .iter()
+            .filter(|r| r.get("isDeleted").and_then(|d| d.as_bool()) != Some(true))
+            .filter(|r| r.get("componentPropNodeField").and_then(|f| f.as_str()) == Some(field))
+            .find_map(|r| {
+                let (component, prop) = self.library.prop_defs.get(&r.get("defID").and_then(guid_key)?)?;
+                let component = &self.library.components[*component];
+                std::ptr::eq(component, self.component).then(|| component.props[*prop].name.as_str())
+            })
+    }
+}
+
+/// ` style={{ width: "24px", ... }}`, keeping only `only` properties when not empty
+fn style_attribute(node: &Map<String, JsonValue>, placement: Placement, only: &[&str]) -> String {
+    let entries: Vec<String> = node_declarations(node, placement)
+        .into_iter()
+        .filter(|(property, _)| only.is_empty() || only.contains(property))
+        .map(|(property, value)| format!("{}: {}", camel_case(property, property), string_literal(&value)))
+        .collect();
+    if entries.is_empty() {
+        String::new()
+    } else {
+        format!(" style={{{{ {} }}}}", entries.join(", "))
+    }
+}
+
+/// TypeScript literal of a `componentPropDefs` initial value or an assignment value
+fn prop_literal(kind: PropKind, value: &JsonValue) -> Option<String> {
+    match kind {
+        PropKind::Bool => value.get("boolValue")?.as_bool().map(|b| b.to_string()),
+        PropKind::Text => value
+            .get("textValue")?
+            .get("characters")?
+            .as_str()
+            .map(string_literal),
+        PropKind::Number => value.get("floatValue")?.as_f64().map(|n| n.to_string()),
+        PropKind::InstanceSwap => None,
+    }
+}
+
+/// Text content for JSX, as an expression when it contains JSX syntax
+fn jsx_text(text: &str) -> String {
+    let plain = !text.is_empty()
+        && text.trim() == text
+        && !text.contains(['{', '}', '<', '>', '&', '\n', '"', '\'']);
+    if plain {
+        text.to_string()
+    } else {
+        format!("{{{}}}", string_literal(text))
+    }
+}
+
+fn string_literal(text: &str) -> String {
+    JsonValue::String(text.to_string()).to_string()
+}
+
+fn words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// "primary button" → "PrimaryButton", prefixed when empty or starting with a digit
+fn pascal_case(name: &str) -> String {
+    let name: String = words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    if name.is_empty() {
+        "Component".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("Component{}", name)
+    } else {
+        name
+    }
+}
+
+/// Words that cannot be used as a binding name in a module
+const RESERVED_WORDS: &[&str] = &[
+    "arguments", "await", "break", "case", "catch", "class", "const", "continue", "debugger",
+    "default", "delete", "do", "else", "enum", "eval", "export", "extends", "false", "finally",
+    "for", "function", "if", "implements", "import", "in", "instanceof", "interface", "let",
+    "new", "null", "package", "private", "protected", "public", "return", "static", "super",
+    "switch", "this", "throw", "true", "try", "typeof", "undefined", "var", "void", "while",
+    "with", "yield",
+];
+
+/// "Show icon" → "showIcon", `fallback` when empty or starting with a digit,
+/// `Prop` suffix for reserved words ("default" → "defaultProp")
+fn camel_case(name: &str, fallback: &str) -> String {
+    let mut result = String::new();
+    for (i, word) in words(name).iter().enumerate() {
+        if i == 0 {
+            result.push_str(&word.to_ascii_lowercase());
+        } else {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                result.push(first.to_ascii_uppercase());
+                result.push_str(chars.as_str());
+            }
+        }
+    }
+    if result.is_empty() || result.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("{}{}", fallback, result)
+    } else if RESERVED_WORDS.contains(&result.as_str()) {
+        format!("{}Prop", result)
+    } else {
+        result
+    }
+}
+
+fn unique_identifier(name: &str, taken: &mut BTreeSet<String>) -> String {
+    let mut candidate = name.to_string();
+    let mut suffix = 2;
+    while !taken.insert(candidate.clone()) {
+        candidate = format!("{}{}", name, suffix);
+        suffix += 1;
+    }
+    candidate
+}
+
+fn node_name(node: &Map<String, JsonValue>) -> &str {
+    node.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+fn children(node: &Map<String, JsonValue>) -> &[JsonValue] {
+    node.get("children")
+        .and_then(|c| c.as_array())
+        .map(|c| c.as_slice())
+        .unwrap_or(&[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn guid(local: u64) -> JsonValue {
+        json!({"sessionID": 1, "localID": local})
+    }
+
+    fn sample_document() -> JsonValue {
+        json!({"type": "DOCUMENT", "children": [{"type": "CANVAS", "name": "Components", "children": [
+            {
+                "type": "SYMBOL",
+                "name": "icon/check",
+                "guid": guid(10),
+                "size": {"x": 16.0, "y": 16.0}
+            },
+            {
+                "type": "FRAME",
+                "name": "Button",
+                "isStateGroup": true,
+                "guid": guid(20),
+                "componentPropDefs": [
+                    {"id": guid(90), "name": "Label#12:0", "type": "TEXT", "initialValue": {"textValue": {"characters": "Submit"}}},
+                    {"id": guid(91), "name": "Show icon", "type": "BOOL", "initialValue": {"boolValue": true}}
+                ],
+                "children": [
+                    {
+                        "type": "SYMBOL",
+                        "name": "Size=Small, State=Default",
+                        "guid": guid(21),
+                        "stackMode": "HORIZONTAL",
+                        "stackSpacing": 4.0,
+                        "children": [
+                            {
+                                "type": "INSTANCE",
+                                "name": "icon",
+                                "symbolData": {"symbolID": guid(10)},
+                                "componentPropRefs": [{"defID": guid(91), "componentPropNodeField": "VISIBLE"}]
+                            },
+                            {
+                                "type": "TEXT",
+                                "name": "Label",
+                                "textData": {"characters": "Submit"},
+                                "componentPropRefs": [{"defID": guid(90), "componentPropNodeField": "TEXT_DATA"}]
+                            }
+                        ]
+                    },
+                    {"type": "SYMBOL", "name": "Size=Large, State=Hover", "guid": guid(22)}
+                ]
+            },
+            {
+                "type": "FRAME",
+                "name": "Form",
+                "children": [{
+                    "type": "INSTANCE",
+                    "name": "Button",
+                    "transform": {"x": 10.0, "y": 20.0},
+                    "symbolData": {"symbolID": guid(22)},
+                    "componentPropAssignments": [
+                        {"defID": guid(90), "value": {"textValue": {"characters": "Send \"now\""}}},
+                        {"defID": guid(91), "value": {"boolValue": false}}
+                    ]
+                }]
+            }
+        ]}]})
+    }
+
+    #[test]
+    fn test_one_component_per_symbol_and_set() {
+        let components = render_components(&sample_document());
+
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["IconCheck", "Button"]);
+        assert_eq!(
+            components[0].source,
+            "export function IconCheck() {\n  return (\n    <div style={{ width: \"16px\", height: \"16px\" }} />\n  );\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_component_set_props_and_variants() {
+        let button = &render_components(&sample_document())[1];
+
+        assert_eq!(
+            button.source,
+            r#"import { IconCheck } from "./IconCheck";
+
+export interface ButtonProps {
+  size?: "Small" | "Large";
+  state?: "Default" | "Hover";
+  label?: string;
+  showIcon?: boolean;
+}
+
+export function Button({ size = "Small", state = "Default", label = "Submit", showIcon = true }: ButtonProps) {
+  if (size === "Large" && state === "Hover") {
+    return (
+      <div />
+    );
+  }
+  return (
+    <div style={{ position: "relative", display: "flex", flexDirection: "row", gap: "4px" }}>
+      {showIcon && (
+        <IconCheck />
+      )}
+      <p style={{ flexShrink: "0" }}>{label}</p>
+    </div>
+  );
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_instance_props() {
+        let document = sample_document();
+        let library = Library::collect(&document);
+        let form = document["children"][0]["children"][2]["children"][0]
+            .as_object()
+            .unwrap();
+        let mut renderer = Renderer {
+            library: &library,
+            component: &library.components[0],
+            imports: BTreeSet::new(),
+        };
+
+        let mut out = String::new();
+        renderer.element(form, Placement::Absolute, 0, &mut out);
+
+        assert_eq!(
+            out,
+            "<div style={{ position: \"absolute\", left: \"10px\", top: \"20px\" }}>\n  <Button size=\"Large\" state=\"Hover\" label={\"Send \\\"now\\\"\"} showIcon={false} />\n</div>\n"
+        );
+        assert_eq!(renderer.imports.into_iter().collect::<Vec<_>>(), ["Button"]);
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(pascal_case("icon / arrow-right"), "IconArrowRight");
+        assert_eq!(pascal_case("2 col"), "Component2Col");
+        assert_eq!(camel_case("Show icon", "prop"), "showIcon");
+        assert_eq!(camel_case("font-size", "prop"), "fontSize");
+        assert_eq!(camel_case("1st", "prop"), "prop1st");
+        assert_eq!(camel_case("Default", "prop"), "defaultProp");
+        assert_eq!(camel_case("class", "variant"), "classProp");
+        assert_eq!(camel_case("New item", "prop"), "newItem");
+        assert_eq!(jsx_text("Hello world"), "Hello world");
+        assert_eq!(jsx_text("a {b}"), "{\"a {b}\"}");
+        assert_eq!(
+            parse_variant_name("Size=Large, Dark"),
+            [("Size".to_string(), "Large".to_string()), ("Variant".to_string(), "Dark".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_unique_component_names() {
+        let symbol = |name: &str, id: u64| json!({"type": "SYMBOL", "name": name, "guid": guid(id)});
+        let document = json!({"children": [{"type": "CANVAS", "children": [
+            symbol("Button", 1),
+            symbol("Button", 2),
+            symbol("Button 2", 3)
+        ]}]});
+
+        let names: Vec<String> = render_components(&document).into_iter().map(|c| c.name).collect();
+        assert_eq!(names, ["Button", "Button2", "Button22"]);
+    }
+
+    #[test]
+    fn test_index() {
+        let components = render_components(&sample_document());
+
+        assert_eq!(
+            index_source(&components),
+            "export { IconCheck } from \"./IconCheck\";\nexport { Button } from \"./Button\";\n"
+        );
+    }
+
+    #[test]
+    fn test_reserved_and_mismatched_props() {
+        let document = json!({"children": [{"type": "CANVAS", "children": [{
+            "type": "SYMBOL",
+            "name": "Card",
+            "guid": guid(1),
+            "componentPropDefs": [
+                {"id": guid(2), "name": "default", "type": "BOOL", "initialValue": {"boolValue": true}},
+                {"id": guid(3), "name": "new", "type": "TEXT"},
+                {"id": guid(4), "name": "Size#1:0", "type": "TEXT", "initialValue": {"textValue": {"characters": "M"}}},
+                {"id": guid(5), "name": "Size#1:1", "type": "NUMBER", "initialValue": {"floatValue": 2.0}},
+                {"id": guid(6), "name": "Size#1:2", "type": "TEXT"}
+            ]
+        }]}]});
+        let library = Library::collect(&document);
+
+        let props: Vec<(&str, PropKind)> = library.components[0]
+            .props
+            .iter()
+            .map(|p| (p.name.as_str(), p.kind))
+            .collect();
+        assert_eq!(
+            props,
+            [
+                ("defaultProp", PropKind::Bool),
+                ("newProp", PropKind::Text),
+                ("size", PropKind::Text),
+                ("size2", PropKind::Number)
+            ]
+        );
+        assert_eq!(library.prop_defs[&guid_key(&guid(5)).unwrap()], (0, 3));
+        assert_eq!(library.prop_defs[&guid_key(&guid(6)).unwrap()], (0, 2));
+
+        let source = &render_components(&document)[0].source;
+        assert!(source.contains(
+            "export function Card({ defaultProp = true, newProp, size = \"M\", size2 = 2 }: CardProps) {"
+        ));
+    }
+
+    #[test]
+    fn test_variant_prop_without_values() {
+        let document = json!({"children": [{"type": "CANVAS", "children": [{
+            "type": "FRAME",
+            "name": "Badge",
+            "isStateGroup": true,
+            "stateGroupPropertyValueOrders": [
+                {"property": "Theme", "values": []},
+                {"property": "Tone", "values": ["Neutral", "Danger"]}
+            ],
+            "children": [
+                {"type": "SYMBOL", "name": "Tone=Neutral", "guid": guid(1)},
+                {"type": "SYMBOL", "name": "Tone=Danger", "guid": guid(2)}
+            ]
+        }]}]});
+
+        let source = &render_components(&document)[0].source;
+        assert!(source.contains("export interface BadgeProps {\n  tone?: \"Neutral\" | \"Danger\";\n}"));
+        assert!(source.contains("export function Badge({ tone = \"Neutral\" }: BadgeProps) {"));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::error::{FigError, Result};
-use kiwi_schema::{Schema, Value};
+use kiwi_schema::{Def, Schema, Value};
 use serde_json::Value as JsonValue;
 
 /// Decode .fig file data to JSON
@@ -24,19 +24,10 @@ use serde_json::Value as JsonValue;
 /// ```
 pub fn decode_fig_to_json(schema_bytes: &[u8], data_bytes: &[u8]) -> Result<JsonValue> {
     // 1. Decode the binary schema
-    let schema = Schema::decode(schema_bytes)
-        .map_err(|_| FigError::ZipError("Failed to decode Kiwi binary schema".to_string()))?;
+    let schema = decode_schema(schema_bytes)?;
 
     // 2. Find the root message type
-    // In Figma .fig files, the root message is named "Message" and contains nodeChanges and blobs
-    let root_type_id = schema
-        .defs
-        .iter()
-        .find(|def| {
-            def.name == "Message"
-                && def.fields.iter().any(|f| f.name == "nodeChanges")
-                && def.fields.iter().any(|f| f.name == "blobs")
-        })
+    let root_type_id = root_message(&schema)
         .map(|def| def.index)
         .ok_or_else(|| {
             FigError::ZipError("No root Message definition found in schema".to_string())
@@ -52,6 +43,30 @@ pub fn decode_fig_to_json(schema_bytes: &[u8], data_bytes: &[u8]) -> Result<Json
     Ok(json)
 }
 
+/// Decode a binary Kiwi schema
+///
+/// # Arguments
+/// * `schema_bytes` - Decompressed schema chunk (chunk 0)
+///
+/// # Returns
+/// * `Ok(Schema)` - Decoded schema
+/// * `Err(FigError)` - If the schema is invalid
+pub fn decode_schema(schema_bytes: &[u8]) -> Result<Schema> {
+    Schema::decode(schema_bytes)
+        .map_err(|_| FigError::ZipError("Failed to decode Kiwi binary schema".to_string()))
+}
+
+/// Find the root message definition of a Figma schema
+///
+/// In Figma .fig files, the root message is named "Message" and contains nodeChanges and blobs
+pub fn root_message(schema: &Schema) -> Option<&Def> {
+    schema.defs.iter().find(|def| {
+        def.name == "Message"
+            && def.fields.iter().any(|f| f.name == "nodeChanges")
+            && def.fields.iter().any(|f| f.name == "blobs")
+    })
+}
+
 /// Convert a Kiwi Value to serde_json Value
 fn kiwi_value_to_json(value: &Value) -> JsonValue {
     match value {
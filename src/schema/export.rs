@@ -0,0 +1,801 @@
+use super::decoder::{decode_schema, root_message};
+use crate::error::{FigError, Result};
+use crate::parser;
+use kiwi_schema::{Def, DefKind, Field, Schema};
+use serde_json::{json, Map, Value as JsonValue};
+use std::fmt::Write;
+
+/// Output format of [`export_schema`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaFormat {
+    /// Canonical textual Kiwi schema (`.kiwi`)
+    Kiwi,
+    /// JSON Schema (draft 2020-12) of the [`crate::convert_raw`] output
+    JsonSchema,
+    /// TypeScript declarations of the [`crate::convert_raw`] output (`.d.ts`)
+    TypeScript,
+}
+
+/// Name of the tree node type added on top of the schema definitions
+const DOCUMENT_NODE: &str = "DocumentNode";
+
+/// Read the Kiwi schema embedded in a .fig file
+///
+/// # Arguments
+/// * `bytes` - Raw bytes from the .fig file (ZIP containers are supported)
+///
+/// # Returns
+/// * `Ok(Schema)` - Schema decoded from chunk 0
+/// * `Err(FigError)` - If the file or its schema chunk is invalid
+///
+/// # Examples
+/// ```no_run
+/// use fig2json::schema::{export_schema, read_schema, SchemaFormat};
+///
+/// let bytes = std::fs::read("example.fig").unwrap();
+/// let schema = read_schema(&bytes).unwrap();
+/// println!("{}", export_schema(&schema, SchemaFormat::Kiwi));
+/// ```
+pub fn read_schema(bytes: &[u8]) -> Result<Schema> {
+    let bytes = if parser::is_zip_container(bytes) {
+        parser::extract_from_zip(bytes)?
+    } else {
+        bytes.to_vec()
+    };
+
+    parser::detect_file_type(&bytes)?;
+    let parsed = parser::extract_chunks(&bytes)?;
+    let schema_bytes = parser::decompress_chunk(parsed.schema_chunk().ok_or(
+        FigError::NotEnoughChunks {
+            expected: 1,
+            actual: 0,
+        },
+    )?)?;
+
+    decode_schema(&schema_bytes)
+}
+
+/// Export a Kiwi schema in the given format
+///
+/// # Arguments
+/// * `schema` - Decoded Kiwi schema
+/// * `format` - Output format
+///
+/// # Returns
+/// * The exported schema as text
+pub fn export_schema(schema: &Schema, format: SchemaFormat) -> String {
+    match format {
+        SchemaFormat::Kiwi => to_kiwi(schema),
+        SchemaFormat::JsonSchema => {
+            // Serializing a JSON value cannot fail
+            serde_json::to_string_pretty(&to_json_schema(schema)).unwrap_or_default() + "\n"
+        }
+        SchemaFormat::TypeScript => to_typescript(schema),
+    }
+}
+
+/// Render a schema as canonical `.kiwi` text
+///
+/// Definitions are written in schema order, fields in declaration order.
+pub fn to_kiwi(schema: &Schema) -> String {
+    let mut out = String::new();
+
+    for (index, def) in schema.defs.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+
+        let keyword = match def.kind {
+            DefKind::Enum => "enum",
+            DefKind::Struct => "struct",
+            DefKind::Message => "message",
+        };
+        let _ = writeln!(out, "{} {} {{", keyword, def.name);
+
+        for field in &def.fields {
+            let _ = match def.kind {
+                DefKind::Enum => writeln!(out, "  {} = {};", field.name, field.value),
+                DefKind::Struct => writeln!(out, "  {} {};", kiwi_type(schema, field), field.name),
+                DefKind::Message => writeln!(
+                    out,
+                    "  {} {} = {};",
+                    kiwi_type(schema, field),
+                    field.name,
+                    field.value
+                ),
+            };
+        }
+
+        out.push_str("}\n");
+    }
+
+    out
+}
+
+/// Build a JSON Schema describing the [`crate::convert_raw`] output
+///
+/// Every Kiwi definition becomes an entry of `$defs`. Enums are described in
+/// their decoded form (`{"__enum__": ..., "value": ...}`), struct fields are
+/// required and message fields optional. Non-finite floats are decoded as
+/// `null`, so float fields also accept `null`. Blob fields are described
+/// under the name and shape they have after blob substitution
+/// (`commandsBlob` becomes a `commands` array).
+pub fn to_json_schema(schema: &Schema) -> JsonValue {
+    let mut defs = Map::new();
+
+    for def in &schema.defs {
+        defs.insert(def.name.clone(), def_json_schema(schema, def));
+    }
+
+    let document = match node_change_def(schema) {
+        Some(node_change) => {
+            defs.insert(
+                DOCUMENT_NODE.to_string(),
+                json!({
+                    "description": "Node with its children, as rebuilt from nodeChanges",
+                    "allOf": [{"$ref": format!("#/$defs/{}", node_change.name)}],
+                    "properties": {
+                        "children": {
+                            "type": "array",
+                            "items": {"$ref": format!("#/$defs/{}", DOCUMENT_NODE)}
+                        }
+                    }
+                }),
+            );
+            json!({"$ref": format!("#/$defs/{}", DOCUMENT_NODE)})
+        }
+        None => json!({"type": "object"}),
+    };
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "fig2json raw output",
+        "type": "object",
+        "properties": {
+            "version": {"type": "integer", "minimum": 0},
+            "fileType": {"enum": ["figma", "figjam"]},
+            "document": document,
+            "blobs": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "bytes": {"type": "string", "contentEncoding": "base64"}
+                    }
+                }
+            }
+        },
+        "required": ["version", "fileType", "document", "blobs"],
+        "$defs": defs,
+    })
+}
+
+/// Render TypeScript declarations of the [`crate::convert_raw`] output
+///
+/// Every Kiwi definition becomes an exported type, and `RawOutput` describes
+/// the whole conversion output. As in [`to_json_schema`], blob fields are
+/// declared under their substituted name and shape.
+pub fn to_typescript(schema: &Schema) -> String {
+    let mut out = String::from(
+        "// Generated by fig2json from the Kiwi schema embedded in a .fig file.\n\
+         // Non-finite float values are decoded as null.\n",
+    );
+
+    for def in &schema.defs {
+        out.push('\n');
+        match def.kind {
+            DefKind::Enum => {
+                let values = def
+                    .fields
+                    .iter()
+                    .map(|field| format!("\"{}\"", field.name))
+                    .collect::<Vec<_>>();
+                let values = if values.is_empty() {
+                    "never".to_string()
+                } else {
+                    values.join(" | ")
+                };
+                let _ = writeln!(out, "export type {}Value = {};", def.name, values);
+                let _ = writeln!(
+                    out,
+                    "export interface {} {{\n  __enum__: \"{}\";\n  value: {}Value;\n}}",
+                    def.name, def.name, def.name
+                );
+            }
+            DefKind::Struct | DefKind::Message => {
+                let optional = if def.kind == DefKind::Message { "?" } else { "" };
+                let _ = writeln!(out, "export interface {} {{", def.name);
+                for field in &def.fields {
+                    let _ = writeln!(
+                        out,
+                        "  {}{}: {};",
+                        output_name(field),
+                        optional,
+                        typescript_type(schema, field)
+                    );
+                }
+                out.push_str("}\n");
+            }
+        }
+    }
+
+    out.push('\n');
+    let document = match node_change_def(schema) {
+        Some(node_change) => {
+            let _ = writeln!(
+                out,
+                "export type {} = Omit<{}, \"parentIndex\"> & {{\n  children?: {}[];\n}};\n",
+                DOCUMENT_NODE, node_change.name, DOCUMENT_NODE
+            );
+            DOCUMENT_NODE
+        }
+        None => "Record<string, unknown>",
+    };
+
+    let _ = writeln!(
+        out,
+        "export interface RawOutput {{\n  \
+         version: number;\n  \
+         fileType: \"figma\" | \"figjam\";\n  \
+         document: {};\n  \
+         blobs: Array<{{ bytes?: string }}>;\n\
+         }}",
+        document
+    );
+
+    out
+}
+
+/// Name of a built-in type, or None for user-defined types
+fn builtin_type(type_id: i32) -> Option<&'static str> {
+    match type_id {
+        kiwi_schema::TYPE_BOOL => Some("bool"),
+        kiwi_schema::TYPE_BYTE => Some("byte"),
+        kiwi_schema::TYPE_INT => Some("int"),
+        kiwi_schema::TYPE_UINT => Some("uint"),
+        kiwi_schema::TYPE_FLOAT => Some("float"),
+        kiwi_schema::TYPE_STRING => Some("string"),
+        kiwi_schema::TYPE_INT64 => Some("int64"),
+        kiwi_schema::TYPE_UINT64 => Some("uint64"),
+        _ => None,
+    }
+}
+
+/// Name of a field type as written in `.kiwi` text
+fn type_name(schema: &Schema, type_id: i32) -> &str {
+    builtin_type(type_id)
+        .or_else(|| schema.defs.get(type_id as usize).map(|def| def.name.as_str()))
+        .unwrap_or("unknown")
+}
+
+/// Field type in `.kiwi` syntax (`Paint[]`, `float`, ...)
+fn kiwi_type(schema: &Schema, field: &Field) -> String {
+    let name = type_name(schema, field.type_id);
+    if field.is_array {
+        format!("{}[]", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// JSON Schema of a single (non-array) value of the given type
+fn type_json_schema(schema: &Schema, type_id: i32) -> JsonValue {
+    match builtin_type(type_id) {
+        Some("bool") => json!({"type": "boolean"}),
+        Some("byte") => json!({"type": "integer", "minimum": 0, "maximum": 255}),
+        Some("uint") | Some("uint64") => json!({"type": "integer", "minimum": 0}),
+        Some("int") | Some("int64") => json!({"type": "integer"}),
+        Some("float") => json!({"type": ["number", "null"]}),
+        Some(_) => json!({"type": "string"}),
+        None => json!({"$ref": format!("#/$defs/{}", type_name(schema, type_id))}),
+    }
+}
+
+/// Blob type of a field replaced by its parsed content during blob
+/// substitution (see [`crate::blobs::substitute_blobs`])
+///
+/// `commandsBlob: uint` is decoded as `commands`, `vectorNetworkBlob: uint`
+/// as `vectorNetwork`. Other blob fields are kept as indices.
+fn substituted_blob(field: &Field) -> Option<&str> {
+    if field.is_array || field.type_id != kiwi_schema::TYPE_UINT {
+        return None;
+    }
+    match field.name.strip_suffix("Blob")? {
+        blob_type @ ("commands" | "vectorNetwork") => Some(blob_type),
+        _ => None,
+    }
+}
+
+/// Name of a field in the decoded output
+fn output_name(field: &Field) -> &str {
+    substituted_blob(field).unwrap_or(&field.name)
+}
+
+/// JSON Schema of the parsed content of a blob
+fn blob_json_schema(blob_type: &str) -> JsonValue {
+    let number = json!({"type": ["number", "null"]});
+    match blob_type {
+        "commands" => json!({
+            "type": "array",
+            "items": {"anyOf": [{"enum": ["M", "L", "Q", "C", "Z"]}, number]}
+        }),
+        _ => {
+            let index = json!({"type": "integer", "minimum": 0});
+            let end = json!({
+                "type": "object",
+                "properties": {"vertex": index, "dx": number, "dy": number},
+                "required": ["vertex", "dx", "dy"]
+            });
+            json!({
+                "type": "object",
+                "properties": {
+                    "vertices": {"type": "array", "items": {
+                        "type": "object",
+                        "properties": {"styleID": index, "x": number, "y": number},
+                        "required": ["styleID", "x", "y"]
+                    }},
+                    "segments": {"type": "array", "items": {
+                        "type": "object",
+                        "properties": {"styleID": index, "start": end, "end": end},
+                        "required": ["styleID", "start", "end"]
+                    }},
+                    "regions": {"type": "array", "items": {
+                        "type": "object",
+                        "properties": {
+                            "styleID": index,
+                            "windingRule": {"enum": ["NONZERO", "ODD"]},
+                            "loops": {"type": "array", "items": {
+                                "type": "object",
+                                "properties": {"segments": {"type": "array", "items": index}},
+                                "required": ["segments"]
+                            }}
+                        },
+                        "required": ["styleID", "windingRule", "loops"]
+                    }}
+                },
+                "required": ["vertices", "segments", "regions"]
+            })
+        }
+    }
+}
+
+/// TypeScript type of the parsed content of a blob
+fn blob_typescript_type(blob_type: &str) -> &'static str {
+    match blob_type {
+        "commands" => "Array<\"M\" | \"L\" | \"Q\" | \"C\" | \"Z\" | number | null>",
+        _ => {
+            "{\n    \
+             vertices: Array<{ styleID: number; x: number | null; y: number | null }>;\n    \
+             segments: Array<{\n      \
+             styleID: number;\n      \
+             start: { vertex: number; dx: number | null; dy: number | null };\n      \
+             end: { vertex: number; dx: number | null; dy: number | null };\n    \
+             }>;\n    \
+             regions: Array<{\n      \
+             styleID: number;\n      \
+             windingRule: \"NONZERO\" | \"ODD\";\n      \
+             loops: Array<{ segments: number[] }>;\n    \
+             }>;\n  \
+             }"
+        }
+    }
+}
+
+/// JSON Schema of a field value (array or single value)
+fn field_json_schema(schema: &Schema, field: &Field) -> JsonValue {
+    if let Some(blob_type) = substituted_blob(field) {
+        return blob_json_schema(blob_type);
+    }
+    let item = type_json_schema(schema, field.type_id);
+    if field.is_array {
+        json!({"type": "array", "items": item})
+    } else {
+        item
+    }
+}
+
+/// JSON Schema of a definition
+fn def_json_schema(schema: &Schema, def: &Def) -> JsonValue {
+    match def.kind {
+        DefKind::Enum => {
+            let values: Vec<&str> = def.fields.iter().map(|field| field.name.as_str()).collect();
+            json!({
+                "type": "object",
+                "properties": {
+                    "__enum__": {"const": def.name},
+                    "value": {"enum": values}
+                },
+                "required": ["__enum__", "value"]
+            })
+        }
+        DefKind::Struct | DefKind::Message => {
+            let properties: Map<String, JsonValue> = def
+                .fields
+                .iter()
+                .map(|field| (output_name(field).to_string(), field_json_schema(schema, field)))
+                .collect();
+
+            let mut object = json!({"type": "object", "properties": properties});
+            if def.kind == DefKind::Struct {
+                let required: Vec<&str> = def.fields.iter().map(output_name).collect();
+                object["required"] = json!(required);
+            }
+            object
+        }
+    }
+}
+
+/// TypeScript type of a field
+fn typescript_type(schema: &Schema, field: &Field) -> String {
+    if let Some(blob_type) = substituted_blob(field) {
+        return blob_typescript_type(blob_type).to_string();
+    }
+    let item = match builtin_type(field.type_id) {
+        Some("bool") => "boolean",
+        Some("string") => "string",
+        Some(_) => "number",
+        None => type_name(schema, field.type_id),
+    };
+    if field.is_array {
+        format!("{}[]", item)
+    } else {
+        item.to_string()
+    }
+}
+
+/// Definition of the elements of `Message.nodeChanges`
+fn node_change_def(schema: &Schema) -> Option<&Def> {
+    let field = root_message(schema)?.field("nodeChanges")?;
+    schema.defs.get(usize::try_from(field.type_id).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, type_id: i32, is_array: bool, value: u32) -> Field {
+        Field {
+            name: name.to_string(),
+            type_id,
+            is_array,
+            value,
+        }
+    }
+
+    /// Small schema shaped like Figma's: enum, struct, node message and root message
+    fn sample_schema() -> Schema {
+        Schema::new(vec![
+            Def::new(
+                "NodeType".to_string(),
+                DefKind::Enum,
+                vec![field("DOCUMENT", 0, false, 1), field("FRAME", 0, false, 4)],
+            ),
+            Def::new(
+                "Vector".to_string(),
+                DefKind::Struct,
+                vec![
+                    field("x", kiwi_schema::TYPE_FLOAT, false, 0),
+                    field("y", kiwi_schema::TYPE_FLOAT, false, 0),
+                ],
+            ),
+            Def::new(
+                "NodeChange".to_string(),
+                DefKind::Message,
+                vec![
+                    field("type", 0, false, 1),
+                    field("name", kiwi_schema::TYPE_STRING, false, 2),
+                    field("size", 1, false, 3),
+                    field("visible", kiwi_schema::TYPE_BOOL, false, 4),
+                ],
+            ),
+            Def::new(
+                "Blob".to_string(),
+                DefKind::Message,
+                vec![field("bytes", kiwi_schema::TYPE_BYTE, true, 1)],
+            ),
+            Def::new(
+                "Message".to_string(),
+                DefKind::Message,
+                vec![field("nodeChanges", 2, true, 1), field("blobs", 3, true, 2)],
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_to_kiwi() {
+        let text = to_kiwi(&sample_schema());
+
+        assert!(text.starts_with("enum NodeType {\n  DOCUMENT = 1;\n  FRAME = 4;\n}\n"));
+        assert!(text.contains("struct Vector {\n  float x;\n  float y;\n}\n"));
+        assert!(text.contains("message NodeChange {\n  NodeType type = 1;\n  string name = 2;\n  Vector size = 3;\n  bool visible = 4;\n}\n"));
+        assert!(text.contains("  byte[] bytes = 1;\n"));
+        assert!(text.contains("  NodeChange[] nodeChanges = 1;\n"));
+    }
+
+    #[test]
+    fn test_to_kiwi_survives_binary_round_trip() {
+        let schema = sample_schema();
+        let decoded = decode_schema(&schema.encode()).unwrap();
+        assert_eq!(to_kiwi(&decoded), to_kiwi(&schema));
+    }
+
+    #[test]
+    fn test_to_json_schema() {
+        let json_schema = to_json_schema(&sample_schema());
+        let defs = &json_schema["$defs"];
+
+        assert_eq!(defs["NodeType"]["properties"]["value"]["enum"], json!(["DOCUMENT", "FRAME"]));
+        assert_eq!(defs["Vector"]["required"], json!(["x", "y"]));
+        assert_eq!(defs["Vector"]["properties"]["x"]["type"], json!(["number", "null"]));
+        assert!(defs["NodeChange"].get("required").is_none());
+        assert_eq!(defs["NodeChange"]["properties"]["size"]["$ref"], json!("#/$defs/Vector"));
+        assert_eq!(defs["Blob"]["properties"]["bytes"]["type"], json!("array"));
+        assert_eq!(json_schema["properties"]["document"]["$ref"], json!("#/$defs/DocumentNode"));
+        assert_eq!(defs["DocumentNode"]["allOf"][0]["$ref"], json!("#/$defs/NodeChange"));
+    }
+
+    #[test]
+    fn test_to_typescript() {
+        let ts = to_typescript(&sample_schema());
+
+        assert!(ts.contains("export type NodeTypeValue = \"DOCUMENT\" | \"FRAME\";\n"));
+        assert!(ts.contains("export interface NodeType {\n  __enum__: \"NodeType\";\n  value: NodeTypeValue;\n}\n"));
+        assert!(ts.contains("export interface Vector {\n  x: number;\n  y: number;\n}\n"));
+        assert!(ts.contains("  size?: Vector;\n"));
+        assert!(ts.contains("  visible?: boolean;\n"));
+        assert!(ts.contains("export type DocumentNode = Omit<NodeChange, \"parentIndex\"> & {\n  children?: DocumentNode[];\n};\n"));
+        assert!(ts.contains("  document: DocumentNode;\n"));
+    }
+
+    #[test]
+    fn test_schema_without_root_message() {
+        let schema = Schema::new(vec![Def::new(
+            "Vector".to_string(),
+            DefKind::Struct,
+            vec![field("x", kiwi_schema::TYPE_FLOAT, false, 0)],
+        )]);
+
+        assert_eq!(to_json_schema(&schema)["properties"]["document"], json!({"type": "object"}));
+        assert!(to_typescript(&schema).contains("  document: Record<string, unknown>;\n"));
+    }
+
+    /// Schema with GUIDs, parent indices and blob fields, enough to write a file
+    fn vector_schema() -> Schema {
+        Schema::new(vec![
+            Def::new(
+                "NodeType".to_string(),
+                DefKind::Enum,
+                vec![
+                    field("DOCUMENT", 0, false, 1),
+                    field("CANVAS", 0, false, 2),
+                    field("VECTOR", 0, false, 6),
+                ],
+            ),
+            Def::new(
+                "GUID".to_string(),
+                DefKind::Struct,
+                vec![
+                    field("sessionID", kiwi_schema::TYPE_UINT, false, 0),
+                    field("localID", kiwi_schema::TYPE_UINT, false, 0),
+                ],
+            ),
+            Def::new(
+                "ParentIndex".to_string(),
+                DefKind::Struct,
+                vec![
+                    field("guid", 1, false, 0),
+                    field("position", kiwi_schema::TYPE_STRING, false, 0),
+                ],
+            ),
+            Def::new(
+                "VectorData".to_string(),
+                DefKind::Message,
+                vec![field("vectorNetworkBlob", kiwi_schema::TYPE_UINT, false, 1)],
+            ),
+            Def::new(
+                "Path".to_string(),
+                DefKind::Message,
+                vec![field("commandsBlob", kiwi_schema::TYPE_UINT, false, 1)],
+            ),
+            Def::new(
+                "NodeChange".to_string(),
+                DefKind::Message,
+                vec![
+                    field("guid", 1, false, 1),
+                    field("parentIndex", 2, false, 2),
+                    field("type", 0, false, 3),
+                    field("name", kiwi_schema::TYPE_STRING, false, 4),
+                    field("opacity", kiwi_schema::TYPE_FLOAT, false, 5),
+                    field("vectorData", 3, false, 6),
+                    field("fillGeometry", 4, true, 7),
+                ],
+            ),
+            Def::new(
+                "Blob".to_string(),
+                DefKind::Message,
+                vec![field("bytes", kiwi_schema::TYPE_BYTE, true, 1)],
+            ),
+            Def::new(
+                "Message".to_string(),
+                DefKind::Message,
+                vec![field("nodeChanges", 5, true, 1), field("blobs", 6, true, 2)],
+            ),
+        ])
+    }
+
+    /// Errors of `value` against the subset of JSON Schema emitted by [`to_json_schema`]
+    ///
+    /// Unlike a standard validator, properties that the schema does not declare
+    /// are reported, so that fields missing from the schema are caught.
+    fn validate(root: &JsonValue, schema: &JsonValue, value: &JsonValue, path: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        check(root, schema, value, path, true, &mut errors);
+        errors
+    }
+
+    fn resolve<'a>(root: &'a JsonValue, schema: &'a JsonValue) -> &'a JsonValue {
+        match schema.get("$ref").and_then(|r| r.as_str()) {
+            Some(reference) => {
+                let name = reference.trim_start_matches("#/$defs/");
+                resolve(root, &root["$defs"][name])
+            }
+            None => schema,
+        }
+    }
+
+    fn declared_properties(root: &JsonValue, schema: &JsonValue, names: &mut Vec<String>) {
+        let schema = resolve(root, schema);
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            names.extend(properties.keys().cloned());
+        }
+        for sub in schema.get("allOf").and_then(|a| a.as_array()).into_iter().flatten() {
+            declared_properties(root, sub, names);
+        }
+    }
+
+    fn check(
+        root: &JsonValue,
+        schema: &JsonValue,
+        value: &JsonValue,
+        path: &str,
+        strict: bool,
+        errors: &mut Vec<String>,
+    ) {
+        let schema = resolve(root, schema);
+
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                JsonValue::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+                other => other.as_str().into_iter().collect(),
+            };
+            let matches = types.iter().any(|t| match *t {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_u64() || value.is_i64(),
+                "number" => value.is_number(),
+                "null" => value.is_null(),
+                _ => false,
+            });
+            if !matches {
+                errors.push(format!("{}: expected {:?}, got {}", path, types, value));
+                return;
+            }
+        }
+        if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+            if !allowed.contains(value) {
+                errors.push(format!("{}: {} is not one of {:?}", path, value, allowed));
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if expected != value {
+                errors.push(format!("{}: expected {}", path, expected));
+            }
+        }
+        if let Some(options) = schema.get("anyOf").and_then(|a| a.as_array()) {
+            if !options.iter().any(|option| validate(root, option, value, path).is_empty()) {
+                errors.push(format!("{}: {} matches no anyOf option", path, value));
+            }
+        }
+        for sub in schema.get("allOf").and_then(|a| a.as_array()).into_iter().flatten() {
+            check(root, sub, value, path, false, errors);
+        }
+        if let Some(items) = schema.get("items") {
+            for (i, item) in value.as_array().into_iter().flatten().enumerate() {
+                check(root, items, item, &format!("{}[{}]", path, i), true, errors);
+            }
+        }
+
+        let Some(object) = value.as_object() else {
+            return;
+        };
+        for name in schema.get("required").and_then(|r| r.as_array()).into_iter().flatten() {
+            let name = name.as_str().unwrap_or_default();
+            if !object.contains_key(name) {
+                errors.push(format!("{}: missing {}", path, name));
+            }
+        }
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        for (key, field) in object {
+            if let Some(property) = properties.and_then(|p| p.get(key)) {
+                check(root, property, field, &format!("{}.{}", path, key), true, errors);
+            }
+        }
+        if strict && (properties.is_some() || schema.get("allOf").is_some()) {
+            let mut declared = Vec::new();
+            declared_properties(root, schema, &mut declared);
+            for key in object.keys().filter(|key| !declared.contains(key)) {
+                errors.push(format!("{}: undeclared property {}", path, key));
+            }
+        }
+    }
+
+    #[test]
+    fn test_blob_fields_use_substituted_names() {
+        let schema = vector_schema();
+        let json_schema = to_json_schema(&schema);
+        let defs = &json_schema["$defs"];
+
+        assert!(defs["Path"]["properties"].get("commandsBlob").is_none());
+        assert_eq!(defs["Path"]["properties"]["commands"]["type"], json!("array"));
+        assert_eq!(
+            defs["VectorData"]["properties"]["vectorNetwork"]["required"],
+            json!(["vertices", "segments", "regions"])
+        );
+
+        let ts = to_typescript(&schema);
+        assert!(!ts.contains("commandsBlob"));
+        assert!(ts.contains("  commands?: Array<\"M\" | \"L\" | \"Q\" | \"C\" | \"Z\" | number | null>;\n"));
+        assert!(ts.contains("  vectorNetwork?: {\n    vertices: Array<"));
+    }
+
+    #[test]
+    fn test_convert_raw_output_matches_json_schema() {
+        use crate::writer::{write_fig, WriteOptions};
+
+        let schema = vector_schema();
+        let raw = json!({
+            "version": 48,
+            "fileType": "figma",
+            "blobs": [],
+            "document": {
+                "guid": {"sessionID": 0, "localID": 0},
+                "type": {"__enum__": "NodeType", "value": "DOCUMENT"},
+                "children": [{
+                    "guid": {"sessionID": 0, "localID": 1},
+                    "type": {"__enum__": "NodeType", "value": "CANVAS"},
+                    "name": "Page 1",
+                    "children": [{
+                        "guid": {"sessionID": 1, "localID": 2},
+                        "type": {"__enum__": "NodeType", "value": "VECTOR"},
+                        "name": "Line",
+                        "opacity": null,
+                        "fillGeometry": [{"commands": ["M", 0.0, 0.0, "L", 10.0, 0.0, "Z"]}],
+                        "vectorData": {"vectorNetwork": {
+                            "vertices": [
+                                {"styleID": 0, "x": 0.0, "y": 0.0},
+                                {"styleID": 0, "x": 10.0, "y": 0.0}
+                            ],
+                            "segments": [{
+                                "styleID": 0,
+                                "start": {"vertex": 0, "dx": 0.0, "dy": 0.0},
+                                "end": {"vertex": 1, "dx": 0.0, "dy": 0.0}
+                            }],
+                            "regions": []
+                        }}
+                    }]
+                }]
+            }
+        });
+        let fig = write_fig(&raw, &schema, &WriteOptions::default()).unwrap();
+        let output = crate::convert_raw(&fig).unwrap();
+
+        let vector = &output["document"]["children"][0]["children"][0];
+        assert!(vector["fillGeometry"][0]["commands"].is_array());
+        assert!(vector["vectorData"]["vectorNetwork"].is_object());
+
+        let json_schema = to_json_schema(&schema);
+        assert_eq!(validate(&json_schema, &json_schema, &output, "$"), Vec::<String>::new());
+    }
+}
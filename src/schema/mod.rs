@@ -1,11 +1,13 @@
 pub mod decoder;
+pub mod export;
 pub mod transformations;
 pub mod tree;
 
 // Re-export commonly used items
-pub use decoder::decode_fig_to_json;
+pub use decoder::{decode_fig_to_json, decode_schema, root_message};
+pub use export::{export_schema, read_schema, SchemaFormat};
 pub use transformations::{
-    remove_background_properties, remove_border_weights,
+    add_absolute_bounds, build_text_runs, expand_instances, remove_background_properties, remove_border_weights,
     remove_constraint_properties, remove_corner_smoothing, remove_default_blend_mode,
     remove_default_opacity, remove_default_rotation, remove_default_text_properties,
     remove_default_visible, remove_derived_text_layout_size,
@@ -19,9 +21,9 @@ pub use transformations::{
     remove_redundant_padding, remove_root_blobs, remove_root_metadata,
     remove_scroll_resize_properties, remove_stack_align_items, remove_stack_child_properties,
     remove_stack_sizing_properties, remove_stroke_properties, remove_style_ids,
-    remove_symbol_id_fields, remove_text_glyphs,
+    remove_symbol_id_fields, remove_text_glyphs, resolve_style_references,
     remove_text_layout_fields, remove_default_text_line_properties, remove_text_metadata_fields, remove_type,
     remove_default_uniform_scale_factor, remove_user_facing_versions, remove_visible_only_objects, simplify_enums, simplify_text_properties,
-    transform_colors_to_css, transform_image_hashes, transform_matrix_to_css,
+    transform_colors_to_css, transform_effects_to_css, transform_gradients_to_css, transform_image_hashes, transform_layout_to_css, transform_matrix_to_css,
 };
 pub use tree::build_tree;
@@ -0,0 +1,342 @@
+use crate::model::Matrix;
+use crate::error::Result;
+use crate::schema::tree::enum_str;
+use serde_json::{json, Map, Value as JsonValue};
+
+/// Axis-aligned box: left, top, right, bottom
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Bounds {
+    left: f64,
+    top: f64,
+    right: f64,
+    bottom: f64,
+}
+
+impl Bounds {
+    /// Box of a local rectangle once transformed by `matrix`
+    fn transformed(matrix: &Matrix, left: f64, top: f64, right: f64, bottom: f64) -> Bounds {
+        let corners = [
+            matrix.apply(left, top),
+            matrix.apply(right, top),
+            matrix.apply(left, bottom),
+            matrix.apply(right, bottom),
+        ];
+        corners.iter().skip(1).fold(
+            Bounds {
+                left: corners[0].0,
+                top: corners[0].1,
+                right: corners[0].0,
+                bottom: corners[0].1,
+            },
+            |bounds, &(x, y)| Bounds {
+                left: bounds.left.min(x),
+                top: bounds.top.min(y),
+                right: bounds.right.max(x),
+                bottom: bounds.bottom.max(y),
+            },
+        )
+    }
+
+    fn union(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    fn offset(&self, dx: f64, dy: f64) -> Bounds {
+        Bounds {
+            left: self.left + dx,
+            top: self.top + dy,
+            right: self.right + dx,
+            bottom: self.bottom + dy,
+        }
+    }
+
+    fn expand(&self, by: f64) -> Bounds {
+        Bounds {
+            left: self.left - by,
+            top: self.top - by,
+            right: self.right + by,
+            bottom: self.bottom + by,
+        }
+    }
+
+    fn to_json(self) -> JsonValue {
+        json!({
+            "x": round(self.left),
+            "y": round(self.top),
+            "width": round(self.right - self.left),
+            "height": round(self.bottom - self.top),
+        })
+    }
+}
+
+/// Add absolute bounding boxes to the nodes of each page
+///
+/// Node transforms are relative to the parent. This pass composes them from
+/// each page down and adds to every node with a `size`:
+///
+/// - `absoluteBoundingBox`: the `{x, y, width, height}` box of the node in
+///   page coordinates (the box around the rotated node for rotated nodes)
+/// - `absoluteRenderBounds`: the box of what is drawn, extended by the
+///   outside part of visible strokes (`strokeAlign`), drop shadows (offset,
+///   blur radius and spread) and layer blurs; omitted for hidden nodes and
+///   their descendants
+/// - `framePosition`: the `{x, y}` position of `absoluteBoundingBox` relative
+///   to the top-level frame containing the node (`{0, 0}` for the frame)
+///
+/// It runs before `matrix_to_css`, on the matrix form of `transform`, and
+/// accepts raw enums. Values are rounded to two decimals.
+///
+/// # Arguments
+/// * `tree` - The document node, whose children are the pages
+///
+/// # Returns
+/// * `Ok(())` - Successfully added the boxes of all nodes
+///
+/// # Examples
+/// ```
+/// use fig2json::schema::add_absolute_bounds;
+/// use serde_json::json;
+///
+/// let translate = |x: f64, y: f64| json!({"m00": 1.0, "m01": 0.0, "m02": x, "m10": 0.0, "m11": 1.0, "m12": y});
+/// let mut document = json!({"children": [{"type": "CANVAS", "children": [{
+///     "name": "Screen",
+///     "transform": translate(100.0, 200.0),
+///     "size": {"x": 375.0, "y": 812.0},
+///     "children": [{
+///         "name": "Card",
+///         "transform": translate(16.0, 24.0),
+///         "size": {"x": 343.0, "y": 120.0},
+///         "strokePaints": [{"type": "SOLID", "color": {"r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0}}],
+///         "strokeWeight": 2.0,
+///         "strokeAlign": "OUTSIDE"
+///     }]
+/// }]}]});
+/// add_absolute_bounds(&mut document).unwrap();
+///
+/// let card = &document["children"][0]["children"][0]["children"][0];
+/// assert_eq!(card["absoluteBoundingBox"], json!({"x": 116.0, "y": 224.0, "width": 343.0, "height": 120.0}));
+/// assert_eq!(card["absoluteRenderBounds"], json!({"x": 114.0, "y": 222.0, "width": 347.0, "height": 124.0}));
+/// assert_eq!(card["framePosition"], json!({"x": 16.0, "y": 24.0}));
+/// ```
+pub fn add_absolute_bounds(tree: &mut JsonValue) -> Result<()> {
+    for page in children_mut(tree) {
+        for node in children_mut(page) {
+            if let Some(node) = node.as_object_mut() {
+                add_bounds(node, &Matrix::IDENTITY, None, true);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Add the boxes of a node and its descendants
+///
+/// # Arguments
+/// * `parent` - Matrix from the parent's coordinates to page coordinates
+/// * `frame` - Position of the top-level frame, `None` for top-level nodes
+/// * `rendered` - Whether the ancestors are visible
+fn add_bounds(
+    node: &mut Map<String, JsonValue>,
+    parent: &Matrix,
+    frame: Option<(f64, f64)>,
+    rendered: bool,
+) {
+    let transform = node
+        .get("transform")
+        .and_then(Matrix::from_transform)
+        .unwrap_or(Matrix::IDENTITY);
+    let matrix = parent.multiply(&transform);
+    let rendered = rendered && node.get("visible").and_then(|v| v.as_bool()) != Some(false);
+
+    let size = node
+        .get("size")
+        .and_then(|size| Some((size.get("x")?.as_f64()?, size.get("y")?.as_f64()?)));
+    let mut frame = frame;
+    if let Some((width, height)) = size {
+        let bounds = Bounds::transformed(&matrix, 0.0, 0.0, width, height);
+        let (frame_x, frame_y) = *frame.get_or_insert((bounds.left, bounds.top));
+
+        node.insert("absoluteBoundingBox".to_string(), bounds.to_json());
+        if rendered {
+            let render_bounds = render_bounds(node, &matrix, width, height);
+            node.insert("absoluteRenderBounds".to_string(), render_bounds.to_json());
+        }
+        node.insert(
+            "framePosition".to_string(),
+            json!({"x": round(bounds.left - frame_x), "y": round(bounds.top - frame_y)}),
+        );
+    }
+
+    if let Some(JsonValue::Array(children)) = node.get_mut("children") {
+        for child in children.iter_mut().filter_map(|c| c.as_object_mut()) {
+            add_bounds(child, &matrix, frame, rendered);
+        }
+    }
+}
+
+/// Box of what a node draws: its box with strokes, then shadows and blurs
+fn render_bounds(
+    node: &Map<String, JsonValue>,
+    matrix: &Matrix,
+    width: f64,
+    height: f64,
+) -> Bounds {
+    let outset = stroke_outset(node);
+    let stroked = Bounds::transformed(matrix, -outset, -outset, width + outset, height + outset);
+
+    let mut bounds = stroked;
+    let mut blur: f64 = 0.0;
+    let effects = node.get("effects").and_then(|e| e.as_array());
+    for effect in effects.into_iter().flatten().filter(|e| is_visible(e)) {
+        let number = |value: Option<&JsonValue>| value.and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let radius = number(effect.get("radius"));
+        match effect.get("type").and_then(enum_str) {
+            Some("DROP_SHADOW") => {
+                let offset = effect.get("offset");
+                let shadow = stroked
+                    .offset(
+                        number(offset.and_then(|o| o.get("x"))),
+                        number(offset.and_then(|o| o.get("y"))),
+                    )
+                    .expand(radius + number(effect.get("spread")));
+                bounds = bounds.union(&shadow);
+            }
+            Some("FOREGROUND_BLUR") => blur = blur.max(radius),
+            _ => {}
+        }
+    }
+    bounds.expand(blur)
+}
+
+/// Width of the stroke drawn outside the node's box
+fn stroke_outset(node: &Map<String, JsonValue>) -> f64 {
+    let has_stroke = node
+        .get("strokePaints")
+        .and_then(|p| p.as_array())
+        .is_some_and(|paints| paints.iter().any(is_visible));
+    let weight = node
+        .get("strokeWeight")
+        .and_then(|w| w.as_f64())
+        .unwrap_or(0.0);
+    if !has_stroke || weight <= 0.0 {
+        return 0.0;
+    }
+    match node.get("strokeAlign").and_then(enum_str) {
+        Some("INSIDE") => 0.0,
+        Some("OUTSIDE") => weight,
+        // Figma centers strokes by default
+        _ => weight / 2.0,
+    }
+}
+
+fn is_visible(value: &JsonValue) -> bool {
+    value.get("visible").and_then(|v| v.as_bool()) != Some(false)
+}
+
+/// Object children of a node
+fn children_mut(node: &mut JsonValue) -> impl Iterator<Item = &mut JsonValue> {
+    node.get_mut("children")
+        .and_then(|c| c.as_array_mut())
+        .into_iter()
+        .flatten()
+}
+
+fn round(value: f64) -> f64 {
+    let rounded = (value * 100.0).round() / 100.0;
+    // Avoid -0.0
+    if rounded == 0.0 {
+        0.0
+    } else {
+        rounded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(m00: f64, m01: f64, m02: f64, m10: f64, m11: f64, m12: f64) -> JsonValue {
+        json!({"m00": m00, "m01": m01, "m02": m02, "m10": m10, "m11": m11, "m12": m12})
+    }
+
+    fn page(nodes: JsonValue) -> JsonValue {
+        json!({"type": "DOCUMENT", "children": [{"type": "CANVAS", "children": nodes}]})
+    }
+
+    #[test]
+    fn test_rotated_child() {
+        // A 100×20 bar rotated by 90° around its top-left corner, placed at
+        // (50, 10) in a frame at (-200, 300)
+        let mut document = page(json!([{
+            "transform": matrix(1.0, 0.0, -200.0, 0.0, 1.0, 300.0),
+            "size": {"x": 400.0, "y": 300.0},
+            "children": [{
+                "transform": matrix(0.0, -1.0, 50.0, 1.0, 0.0, 10.0),
+                "size": {"x": 100.0, "y": 20.0}
+            }]
+        }]));
+
+        add_absolute_bounds(&mut document).unwrap();
+
+        let frame = &document["children"][0]["children"][0];
+        assert_eq!(
+            frame["absoluteBoundingBox"],
+            json!({"x": -200.0, "y": 300.0, "width": 400.0, "height": 300.0})
+        );
+        assert_eq!(frame["framePosition"], json!({"x": 0.0, "y": 0.0}));
+
+        let bar = &frame["children"][0];
+        assert_eq!(
+            bar["absoluteBoundingBox"],
+            json!({"x": -170.0, "y": 310.0, "width": 20.0, "height": 100.0})
+        );
+        assert_eq!(bar["framePosition"], json!({"x": 30.0, "y": 10.0}));
+    }
+
+    #[test]
+    fn test_render_bounds_with_effects() {
+        let mut document = page(json!([{
+            "transform": matrix(1.0, 0.0, 0.0, 0.0, 1.0, 0.0),
+            "size": {"x": 100.0, "y": 50.0},
+            "strokePaints": [{"type": "SOLID"}],
+            "strokeWeight": 4.0,
+            "strokeAlign": {"__enum__": "StrokeAlign", "value": "CENTER"},
+            "effects": [
+                {"type": {"__enum__": "EffectType", "value": "DROP_SHADOW"}, "offset": {"x": 0.0, "y": 8.0}, "radius": 12.0, "spread": 2.0},
+                {"type": {"__enum__": "EffectType", "value": "INNER_SHADOW"}, "offset": {"x": 0.0, "y": 100.0}, "radius": 50.0},
+                {"type": {"__enum__": "EffectType", "value": "DROP_SHADOW"}, "offset": {"x": 500.0, "y": 0.0}, "visible": false}
+            ]
+        }]));
+
+        add_absolute_bounds(&mut document).unwrap();
+
+        // Stroke: 2px outside; shadow: 14px around the stroked box, 8px lower
+        assert_eq!(
+            document["children"][0]["children"][0]["absoluteRenderBounds"],
+            json!({"x": -16.0, "y": -8.0, "width": 132.0, "height": 82.0})
+        );
+    }
+
+    #[test]
+    fn test_hidden_nodes_have_no_render_bounds() {
+        let mut document = page(json!([{
+            "size": {"x": 10.0, "y": 10.0},
+            "visible": false,
+            "children": [{"size": {"x": 5.0, "y": 5.0}}, {"name": "Group"}]
+        }]));
+
+        add_absolute_bounds(&mut document).unwrap();
+
+        let frame = &document["children"][0]["children"][0];
+        assert!(frame.get("absoluteRenderBounds").is_none());
+        assert!(frame["children"][0].get("absoluteRenderBounds").is_none());
+        assert!(frame["children"][0].get("absoluteBoundingBox").is_some());
+        // Nodes without size get no boxes
+        assert!(frame["children"][1].get("absoluteBoundingBox").is_none());
+    }
+}
@@ -34,20 +34,22 @@ pub fn remove_background_properties(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove background property fields from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove background property fields if they exist
+    map.remove("backgroundEnabled");
+    map.remove("backgroundOpacity");
+}
+
 /// Recursively remove background property fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove background property fields if they exist
-            map.remove("backgroundEnabled");
-            map.remove("backgroundOpacity");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
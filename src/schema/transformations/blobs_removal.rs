@@ -29,11 +29,16 @@ use serde_json::Value as JsonValue;
 /// ```
 pub fn remove_root_blobs(json: &mut JsonValue) -> Result<()> {
     if let Some(obj) = json.as_object_mut() {
-        obj.remove("blobs");
+        enter_object(obj);
     }
     Ok(())
 }
 
+/// Remove the blobs array from the root object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    map.remove("blobs");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,23 +41,25 @@ pub fn remove_border_weights(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove border weight fields from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove all border weight fields if they exist
+    map.remove("borderTopWeight");
+    map.remove("borderBottomWeight");
+    map.remove("borderLeftWeight");
+    map.remove("borderRightWeight");
+    map.remove("borderStrokeWeightsIndependent");
+}
+
 /// Recursively remove border weight fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove all border weight fields if they exist
-            map.remove("borderTopWeight");
-            map.remove("borderBottomWeight");
-            map.remove("borderLeftWeight");
-            map.remove("borderRightWeight");
-            map.remove("borderStrokeWeightsIndependent");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -36,31 +36,29 @@ pub fn transform_colors_to_css(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Replace the color objects held directly by a single object with CSS hex strings
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    for val in map.values_mut() {
+        let css_color = val
+            .as_object()
+            .filter(|obj| is_color_object(obj))
+            .and_then(convert_color_to_css);
+
+        if let Some(css_color) = css_color {
+            *val = JsonValue::String(css_color);
+        }
+    }
+}
+
 /// Recursively transform color objects in a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Collect keys to avoid borrow checker issues
-            let keys: Vec<String> = map.keys().cloned().collect();
-
-            for key in keys {
-                if let Some(val) = map.get(&key) {
-                    // Check if this value is a color object
-                    if let Some(obj) = val.as_object() {
-                        if is_color_object(obj) {
-                            // Convert color object to CSS hex string
-                            if let Some(css_color) = convert_color_to_css(obj) {
-                                map.insert(key.clone(), JsonValue::String(css_color));
-                                continue; // Skip recursion since we replaced the object
-                            }
-                        }
-                    }
-                }
+            enter_object(map);
 
-                // Recurse into the value if it wasn't replaced
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
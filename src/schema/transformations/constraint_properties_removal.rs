@@ -34,19 +34,21 @@ pub fn remove_constraint_properties(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove constraint properties from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove constraint properties
+    map.remove("horizontalConstraint");
+    map.remove("verticalConstraint");
+}
+
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove constraint properties
-            map.remove("horizontalConstraint");
-            map.remove("verticalConstraint");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
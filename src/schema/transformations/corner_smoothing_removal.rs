@@ -34,18 +34,20 @@ pub fn remove_corner_smoothing(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove cornerSmoothing from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove corner smoothing property
+    map.remove("cornerSmoothing");
+}
+
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove corner smoothing property
-            map.remove("cornerSmoothing");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
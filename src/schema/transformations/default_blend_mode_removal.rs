@@ -34,25 +34,27 @@ pub fn remove_default_blend_mode(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove a NORMAL blendMode from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if blendMode exists and is "NORMAL"
+    if let Some(blend_mode) = map.get("blendMode") {
+        if let Some(s) = blend_mode.as_str() {
+            if s == "NORMAL" {
+                map.remove("blendMode");
+            }
+        }
+    }
+}
+
 /// Recursively remove default blendMode fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if blendMode exists and is "NORMAL"
-            if let Some(blend_mode) = map.get("blendMode") {
-                if let Some(s) = blend_mode.as_str() {
-                    if s == "NORMAL" {
-                        map.remove("blendMode");
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -30,26 +30,28 @@ pub fn remove_default_opacity(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove a default opacity (1.0) from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if opacity exists and is 1.0
+    if let Some(opacity) = map.get("opacity") {
+        if let Some(n) = opacity.as_f64() {
+            // Use epsilon comparison for floating point
+            if (n - 1.0).abs() < f64::EPSILON {
+                map.remove("opacity");
+            }
+        }
+    }
+}
+
 /// Recursively remove default opacity fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if opacity exists and is 1.0
-            if let Some(opacity) = map.get("opacity") {
-                if let Some(n) = opacity.as_f64() {
-                    // Use epsilon comparison for floating point
-                    if (n - 1.0).abs() < f64::EPSILON {
-                        map.remove("opacity");
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -33,26 +33,28 @@ pub fn remove_default_rotation(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove a default rotation (0.0) from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if rotation exists and is 0.0
+    if let Some(rotation) = map.get("rotation") {
+        if let Some(n) = rotation.as_f64() {
+            // Use epsilon comparison for floating point
+            if n.abs() < f64::EPSILON {
+                map.remove("rotation");
+            }
+        }
+    }
+}
+
 /// Recursively remove default rotation fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if rotation exists and is 0.0
-            if let Some(rotation) = map.get("rotation") {
-                if let Some(n) = rotation.as_f64() {
-                    // Use epsilon comparison for floating point
-                    if n.abs() < f64::EPSILON {
-                        map.remove("rotation");
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
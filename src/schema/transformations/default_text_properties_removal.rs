@@ -35,30 +35,32 @@ pub fn remove_default_text_properties(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove default letterSpacing/lineHeight values from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check and remove letterSpacing if it's default (0 PERCENT)
+    if let Some(letter_spacing) = map.get("letterSpacing") {
+        if is_default_letter_spacing(letter_spacing) {
+            map.remove("letterSpacing");
+        }
+    }
+
+    // Check and remove lineHeight if it's default (100 PERCENT)
+    if let Some(line_height) = map.get("lineHeight") {
+        if is_default_line_height(line_height) {
+            map.remove("lineHeight");
+        }
+    }
+}
+
 /// Recursively remove default text properties from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check and remove letterSpacing if it's default (0 PERCENT)
-            if let Some(letter_spacing) = map.get("letterSpacing") {
-                if is_default_letter_spacing(letter_spacing) {
-                    map.remove("letterSpacing");
-                }
-            }
-
-            // Check and remove lineHeight if it's default (100 PERCENT)
-            if let Some(line_height) = map.get("lineHeight") {
-                if is_default_line_height(line_height) {
-                    map.remove("lineHeight");
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
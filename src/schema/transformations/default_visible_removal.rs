@@ -30,25 +30,27 @@ pub fn remove_default_visible(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove `visible: true` from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if visible exists and is true
+    if let Some(visible) = map.get("visible") {
+        if let Some(b) = visible.as_bool() {
+            if b {
+                map.remove("visible");
+            }
+        }
+    }
+}
+
 /// Recursively remove default visible fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if visible exists and is true
-            if let Some(visible) = map.get("visible") {
-                if let Some(b) = visible.as_bool() {
-                    if b {
-                        map.remove("visible");
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
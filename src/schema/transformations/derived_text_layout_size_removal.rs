@@ -33,28 +33,25 @@ pub fn remove_derived_text_layout_size(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove layoutSize from a single object's derivedTextData
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    if let Some(derived_text_data) = map.get_mut("derivedTextData") {
+        if let Some(data_obj) = derived_text_data.as_object_mut() {
+            // Remove the layoutSize field
+            data_obj.remove("layoutSize");
+        }
+    }
+}
+
 /// Recursively remove layoutSize from derivedTextData objects
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if this object has a "derivedTextData" field
-            let keys: Vec<String> = map.keys().cloned().collect();
-
-            for key in keys {
-                if key == "derivedTextData" {
-                    // This might be a derivedTextData object with layoutSize
-                    if let Some(derived_text_data) = map.get_mut(&key) {
-                        if let Some(data_obj) = derived_text_data.as_object_mut() {
-                            // Remove the layoutSize field
-                            data_obj.remove("layoutSize");
-                        }
-                    }
-                }
+            enter_object(map);
 
-                // Recurse into the value regardless
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -0,0 +1,225 @@
+use crate::css::{is_text, px};
+use crate::error::Result;
+use crate::schema::tree::enum_str;
+use serde_json::{Map, Value as JsonValue};
+
+/// Replace node effects with their CSS values
+///
+/// Figma stores shadows and blurs in the `effects` array of a node, each with
+/// its `type`, `color`, `offset`, `radius`, `spread` and `visible` flag. This
+/// pass replaces the array with an object of CSS values, omitting invisible
+/// effects:
+///
+/// - DROP_SHADOW and INNER_SHADOW → `boxShadow` (`inset` for inner shadows),
+///   or `textShadow` on TEXT nodes, where inner shadows and the spread have no
+///   CSS equivalent and are dropped
+/// - FOREGROUND_BLUR → `filter: blur()`
+/// - BACKGROUND_BLUR → `backdropFilter: blur()`
+///
+/// Figma blur radii are twice the CSS blur radius, as in Figma's own CSS
+/// export. Shadows keep the order of the effects. Nodes left without visible
+/// effects lose their `effects` field.
+///
+/// Colors must be CSS strings (`color_to_css`); effect types may be raw or
+/// simplified enums.
+///
+/// # Arguments
+/// * `tree` - The JSON tree to modify (usually the document root)
+///
+/// # Returns
+/// * `Ok(())` - Successfully converted the effects of all nodes
+///
+/// # Examples
+/// ```
+/// use fig2json::schema::transform_effects_to_css;
+/// use serde_json::json;
+///
+/// let mut tree = json!({
+///     "type": "FRAME",
+///     "effects": [
+///         {"type": "DROP_SHADOW", "color": "#00000040", "offset": {"x": 0.0, "y": 4.0}, "radius": 8.0},
+///         {"type": "INNER_SHADOW", "color": "#ffffff", "offset": {"x": 0.0, "y": 1.0}, "radius": 0.0},
+///         {"type": "BACKGROUND_BLUR", "radius": 20.0},
+///         {"type": "FOREGROUND_BLUR", "radius": 4.0, "visible": false}
+///     ]
+/// });
+/// transform_effects_to_css(&mut tree).unwrap();
+///
+/// assert_eq!(
+///     tree["effects"],
+///     json!({
+///         "boxShadow": "0 4px 8px 0 #00000040, inset 0 1px 0 0 #ffffff",
+///         "backdropFilter": "blur(10px)"
+///     })
+/// );
+/// ```
+pub fn transform_effects_to_css(tree: &mut JsonValue) -> Result<()> {
+    transform_recursive(tree)
+}
+
+/// Replace the effects array of a single node with its CSS values
+pub(crate) fn enter_object(map: &mut Map<String, JsonValue>) {
+    let Some(JsonValue::Array(effects)) = map.get("effects") else {
+        return;
+    };
+
+    let text = is_text(map);
+    let mut shadows = Vec::new();
+    let mut filters = Vec::new();
+    let mut backdrop_filters = Vec::new();
+    for effect in effects {
+        if effect.get("visible").and_then(|v| v.as_bool()) == Some(false) {
+            continue;
+        }
+        let number = |key: &str| effect.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let blur = || format!("blur({})", px(number("radius") / 2.0));
+
+        match effect.get("type").and_then(enum_str) {
+            Some("DROP_SHADOW") => shadows.push(shadow(effect, text, false)),
+            Some("INNER_SHADOW") if !text => shadows.push(shadow(effect, text, true)),
+            Some("FOREGROUND_BLUR") => filters.push(blur()),
+            Some("BACKGROUND_BLUR") => backdrop_filters.push(blur()),
+            _ => {}
+        }
+    }
+
+    let mut css = Map::new();
+    let shadow_property = if text { "textShadow" } else { "boxShadow" };
+    for (property, values, separator) in [
+        (shadow_property, shadows, ", "),
+        ("filter", filters, " "),
+        ("backdropFilter", backdrop_filters, " "),
+    ] {
+        if !values.is_empty() {
+            css.insert(
+                property.to_string(),
+                JsonValue::String(values.join(separator)),
+            );
+        }
+    }
+
+    if css.is_empty() {
+        map.remove("effects");
+    } else {
+        map.insert("effects".to_string(), JsonValue::Object(css));
+    }
+}
+
+/// CSS of a shadow: `box-shadow` syntax, or `text-shadow` syntax (no spread)
+fn shadow(effect: &JsonValue, text: bool, inset: bool) -> String {
+    let number = |value: Option<&JsonValue>| value.and_then(|v| v.as_f64()).unwrap_or(0.0);
+    let offset = effect.get("offset");
+    let mut lengths = vec![
+        px(number(offset.and_then(|o| o.get("x")))),
+        px(number(offset.and_then(|o| o.get("y")))),
+        px(number(effect.get("radius"))),
+    ];
+    if !text {
+        lengths.push(px(number(effect.get("spread"))));
+    }
+    let color = effect
+        .get("color")
+        .and_then(|c| c.as_str())
+        .unwrap_or("#000000");
+
+    let inset = if inset { "inset " } else { "" };
+    format!("{}{} {}", inset, lengths.join(" "), color)
+}
+
+/// Recursively convert the effects of a JSON value
+fn transform_recursive(value: &mut JsonValue) -> Result<()> {
+    match value {
+        JsonValue::Object(map) => {
+            enter_object(map);
+
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val)?;
+            }
+        }
+        JsonValue::Array(arr) => {
+            // Recurse into array elements
+            for val in arr.iter_mut() {
+                transform_recursive(val)?;
+            }
+        }
+        _ => {
+            // Primitives - nothing to do
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_shadows_and_blurs() {
+        let mut tree = json!({
+            "children": [{
+                "type": "RECTANGLE",
+                "effects": [
+                    {"type": "DROP_SHADOW", "color": "#0000001a", "offset": {"x": 2.0, "y": 2.0}, "radius": 4.0, "spread": 1.5},
+                    {"type": "DROP_SHADOW", "color": "#00000033", "offset": {"x": 0.0, "y": 8.0}, "radius": 24.0},
+                    {"type": "FOREGROUND_BLUR", "radius": 8.0}
+                ]
+            }]
+        });
+
+        transform_effects_to_css(&mut tree).unwrap();
+
+        assert_eq!(
+            tree["children"][0]["effects"],
+            json!({
+                "boxShadow": "2px 2px 4px 1.5px #0000001a, 0 8px 24px 0 #00000033",
+                "filter": "blur(4px)"
+            })
+        );
+    }
+
+    #[test]
+    fn test_text_shadow() {
+        let mut tree = json!({
+            "type": "TEXT",
+            "effects": [
+                {"type": "INNER_SHADOW", "color": "#ffffff", "offset": {"x": 0.0, "y": 1.0}},
+                {"type": "DROP_SHADOW", "color": "#000000", "offset": {"x": 1.0, "y": 1.0}, "radius": 2.0, "spread": 3.0}
+            ]
+        });
+
+        transform_effects_to_css(&mut tree).unwrap();
+
+        assert_eq!(
+            tree["effects"],
+            json!({"textShadow": "1px 1px 2px #000000"})
+        );
+    }
+
+    #[test]
+    fn test_invisible_effects_removed() {
+        let mut tree = json!({
+            "effects": [
+                {"type": {"__enum__": "EffectType", "value": "DROP_SHADOW"}, "color": "#000000", "visible": false},
+                {"type": {"__enum__": "EffectType", "value": "BACKGROUND_BLUR"}, "radius": 10.0, "visible": false}
+            ]
+        });
+
+        transform_effects_to_css(&mut tree).unwrap();
+
+        assert!(tree.get("effects").is_none());
+    }
+
+    #[test]
+    fn test_raw_enum_types() {
+        let mut tree = json!({
+            "effects": [{"type": {"__enum__": "EffectType", "value": "INNER_SHADOW"}, "offset": {"x": -1.0, "y": 0.0}, "radius": 3.0}]
+        });
+
+        transform_effects_to_css(&mut tree).unwrap();
+
+        assert_eq!(tree["effects"]["boxShadow"], "inset -1px 0 3px 0 #000000");
+    }
+}
@@ -32,25 +32,27 @@ pub fn remove_empty_derived_text_data(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove an empty derivedTextData from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if derivedTextData exists and is an empty object
+    if let Some(derived_text_data) = map.get("derivedTextData") {
+        if let Some(obj) = derived_text_data.as_object() {
+            if obj.is_empty() {
+                map.remove("derivedTextData");
+            }
+        }
+    }
+}
+
 /// Recursively remove empty derivedTextData fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if derivedTextData exists and is an empty object
-            if let Some(derived_text_data) = map.get("derivedTextData") {
-                if let Some(obj) = derived_text_data.as_object() {
-                    if obj.is_empty() {
-                        map.remove("derivedTextData");
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -33,34 +33,31 @@ pub fn remove_empty_font_postscript(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove an empty postscript from a single object's fontName
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    if let Some(font_name) = map.get_mut("fontName") {
+        if let Some(font_obj) = font_name.as_object_mut() {
+            // Check if postscript exists and is empty
+            if let Some(postscript) = font_obj.get("postscript") {
+                if let Some(s) = postscript.as_str() {
+                    if s.is_empty() {
+                        font_obj.remove("postscript");
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Recursively remove empty postscript fields from fontName objects
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if this object has a "fontName" field
-            let keys: Vec<String> = map.keys().cloned().collect();
+            enter_object(map);
 
-            for key in keys {
-                if key == "fontName" {
-                    // This might be a fontName object with postscript field
-                    if let Some(font_name) = map.get_mut(&key) {
-                        if let Some(font_obj) = font_name.as_object_mut() {
-                            // Check if postscript exists and is empty
-                            if let Some(postscript) = font_obj.get("postscript") {
-                                if let Some(s) = postscript.as_str() {
-                                    if s.is_empty() {
-                                        font_obj.remove("postscript");
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-
-                // Recurse into the value regardless
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
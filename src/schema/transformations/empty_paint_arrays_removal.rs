@@ -36,29 +36,31 @@ pub fn remove_empty_paint_arrays(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove empty fillPaints/strokePaints arrays from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check and remove empty fillPaints array
+    if let Some(JsonValue::Array(paints)) = map.get("fillPaints") {
+        if paints.is_empty() {
+            map.remove("fillPaints");
+        }
+    }
+
+    // Check and remove empty strokePaints array
+    if let Some(JsonValue::Array(paints)) = map.get("strokePaints") {
+        if paints.is_empty() {
+            map.remove("strokePaints");
+        }
+    }
+}
+
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check and remove empty fillPaints array
-            if let Some(JsonValue::Array(paints)) = map.get("fillPaints") {
-                if paints.is_empty() {
-                    map.remove("fillPaints");
-                }
-            }
-
-            // Check and remove empty strokePaints array
-            if let Some(JsonValue::Array(paints)) = map.get("strokePaints") {
-                if paints.is_empty() {
-                    map.remove("strokePaints");
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -41,31 +41,29 @@ pub fn simplify_enums(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Replace the enum objects held directly by a single object with their value
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    for val in map.values_mut() {
+        let enum_value = val
+            .as_object()
+            .filter(|obj| is_enum_object(obj))
+            .and_then(extract_enum_value);
+
+        if let Some(enum_value) = enum_value {
+            *val = JsonValue::String(enum_value);
+        }
+    }
+}
+
 /// Recursively simplify enum objects in a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Collect keys to avoid borrow checker issues
-            let keys: Vec<String> = map.keys().cloned().collect();
-
-            for key in keys {
-                if let Some(val) = map.get(&key) {
-                    // Check if this value is an enum object
-                    if let Some(obj) = val.as_object() {
-                        if is_enum_object(obj) {
-                            // Extract the value and replace the enum object
-                            if let Some(enum_value) = extract_enum_value(obj) {
-                                map.insert(key.clone(), JsonValue::String(enum_value));
-                                continue; // Skip recursion since we replaced the object
-                            }
-                        }
-                    }
-                }
+            enter_object(map);
 
-                // Recurse into the value if it wasn't replaced
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
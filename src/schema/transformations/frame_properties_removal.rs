@@ -39,20 +39,22 @@ pub fn remove_frame_properties(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove frame properties from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove frame property fields if they exist
+    map.remove("frameMaskDisabled");
+    map.remove("targetAspectRatio");
+}
+
 /// Recursively remove frame property fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove frame property fields if they exist
-            map.remove("frameMaskDisabled");
-            map.remove("targetAspectRatio");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -58,33 +58,31 @@ pub fn remove_geometry_fields(tree: &mut JsonValue) -> Result<()> {
 /// child components.
 ///
 /// # Arguments
-/// * `value` - The JSON node to check
+/// * `obj` - The JSON node to check
 ///
 /// # Returns
 /// * `true` if geometry should be preserved, `false` if it should be removed
-fn should_preserve_geometry(value: &JsonValue) -> bool {
-    if let Some(obj) = value.as_object() {
-        // Check 1: Look for icon/image name patterns (THIS node's name, not children)
-        if let Some(name) = obj.get("name") {
-            if let Some(name_str) = name.as_str() {
-                if name_str.starts_with("icon/") || name_str.starts_with("arrows/") {
-                    return true; // Icon or arrow, preserve geometry
-                }
+fn should_preserve_geometry(obj: &serde_json::Map<String, JsonValue>) -> bool {
+    // Check 1: Look for icon/image name patterns (THIS node's name, not children)
+    if let Some(name) = obj.get("name") {
+        if let Some(name_str) = name.as_str() {
+            if name_str.starts_with("icon/") || name_str.starts_with("arrows/") {
+                return true; // Icon or arrow, preserve geometry
             }
         }
+    }
 
-        // Check 2: Look for exportSettings with imageType in symbolData.symbolOverrides
-        // This checks if THIS specific node has export settings (not child overrides)
-        if let Some(symbol_data) = obj.get("symbolData") {
-            if let Some(overrides) = symbol_data.get("symbolOverrides") {
-                if let Some(overrides_array) = overrides.as_array() {
-                    for override_item in overrides_array {
-                        if let Some(export_settings) = override_item.get("exportSettings") {
-                            if let Some(settings_array) = export_settings.as_array() {
-                                for setting in settings_array {
-                                    if setting.get("imageType").is_some() {
-                                        return true; // Has imageType, preserve geometry
-                                    }
+    // Check 2: Look for exportSettings with imageType in symbolData.symbolOverrides
+    // This checks if THIS specific node has export settings (not child overrides)
+    if let Some(symbol_data) = obj.get("symbolData") {
+        if let Some(overrides) = symbol_data.get("symbolOverrides") {
+            if let Some(overrides_array) = overrides.as_array() {
+                for override_item in overrides_array {
+                    if let Some(export_settings) = override_item.get("exportSettings") {
+                        if let Some(settings_array) = export_settings.as_array() {
+                            for setting in settings_array {
+                                if setting.get("imageType").is_some() {
+                                    return true; // Has imageType, preserve geometry
                                 }
                             }
                         }
@@ -97,27 +95,25 @@ fn should_preserve_geometry(value: &JsonValue) -> bool {
     false // Not an icon/image, remove geometry
 }
 
+/// Remove geometry fields from a single object unless it is an icon/image node
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    if !should_preserve_geometry(map) {
+        map.remove("fillGeometry");
+        map.remove("strokeGeometry");
+        map.remove("windingRule");
+        map.remove("styleID");
+    }
+}
+
 /// Recursively remove geometry fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
-    // Check if we should preserve geometry BEFORE matching (to avoid borrow checker issues)
-    let preserve = should_preserve_geometry(value);
-
     match value {
         JsonValue::Object(map) => {
-            // Only remove geometry if this is not an icon/image node
-            if !preserve {
-                map.remove("fillGeometry");
-                map.remove("strokeGeometry");
-                map.remove("windingRule");
-                map.remove("styleID");
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -288,6 +284,21 @@ mod tests {
         assert_eq!(tree.get("type").unwrap().as_str(), Some("SHAPE"));
     }
 
+    #[test]
+    fn test_remove_style_id_of_styled_nodes() {
+        let mut tree = json!({
+            "name": "Title",
+            "styleID": 7,
+            "fontSize": 24.0,
+            "fillPaints": [{"type": "SOLID"}]
+        });
+
+        remove_geometry_fields(&mut tree).unwrap();
+
+        assert!(tree.get("styleID").is_none());
+        assert_eq!(tree["fontSize"], 24.0);
+    }
+
     #[test]
     fn test_remove_all_geometry_fields() {
         let mut tree = json!({
@@ -0,0 +1,420 @@
+use crate::model::Matrix;
+use crate::css::{color_with_opacity, format_number};
+use crate::error::Result;
+use crate::schema::tree::{enum_str, guid_key};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Paint arrays whose gradients get a CSS string
+const PAINT_ARRAYS: &[&str] = &["fillPaints", "strokePaints", "backgroundPaints"];
+
+/// Add a CSS gradient string to gradient paints
+///
+/// Figma stores gradients as color `stops` and a paint `transform` mapping
+/// the node's unit square (0..1 on both axes) to gradient space, where a
+/// linear gradient runs from (0, 0.5) to (1, 0.5) and radial, angular and
+/// diamond gradients are centered on (0.5, 0.5) with a radius of 0.5.
+///
+/// This pass maps those handles back to the node, using its `size`, and adds
+/// a `css` field to each gradient paint of `fillPaints`, `strokePaints` and
+/// `backgroundPaints` (and of the text runs and style overrides of the node).
+/// Symbol overrides are sized by the node their `guidPath` targets; paints of
+/// other objects without a `size` get no `css`, their box being unknown.
+///
+///
+/// - GRADIENT_LINEAR → `linear-gradient(<angle>deg, <stops>)`, with the stop
+///   positions projected onto the CSS gradient line
+/// - GRADIENT_RADIAL → `radial-gradient(ellipse <rx>% <ry>% at <x>% <y>%, <stops>)`
+/// - GRADIENT_ANGULAR → `conic-gradient(from <angle>deg at <x>% <y>%, <stops>)`
+/// - GRADIENT_DIAMOND → the radial gradient with the same center and radii,
+///   CSS having no diamond gradient
+///
+/// The paint opacity is folded into the stop colors. Rotated radial
+/// gradients lose their rotation, which CSS cannot express.
+///
+/// Both the matrix form of `transform` (`m00`..`m12`) and the decomposed form
+/// of `matrix_to_css` are supported; colors must be CSS strings
+/// (`color_to_css`) and paint types plain strings (`enum_simplification`).
+///
+/// # Arguments
+/// * `tree` - The JSON tree to modify (usually the document root)
+///
+/// # Returns
+/// * `Ok(())` - Successfully added the CSS of all gradient paints
+///
+/// # Examples
+/// ```
+/// use fig2json::schema::transform_gradients_to_css;
+/// use serde_json::json;
+///
+/// let mut tree = json!({
+///     "size": {"x": 200.0, "y": 200.0},
+///     "fillPaints": [{
+///         "type": "GRADIENT_LINEAR",
+///         "opacity": 0.5,
+///         "stops": [
+///             {"color": "#ff0000", "position": 0.0},
+///             {"color": "#0000ff", "position": 1.0}
+///         ],
+///         "transform": {"m00": 0.5, "m01": 0.5, "m02": 0.0, "m10": -0.5, "m11": 0.5, "m12": 0.5}
+///     }]
+/// });
+/// transform_gradients_to_css(&mut tree).unwrap();
+///
+/// assert_eq!(
+///     tree["fillPaints"][0]["css"],
+///     "linear-gradient(135deg, #ff000080 0%, #0000ff80 100%)"
+/// );
+/// ```
+pub fn transform_gradients_to_css(tree: &mut JsonValue) -> Result<()> {
+    let mut sizes = HashMap::new();
+    index_sizes(tree, &mut sizes);
+    transform_recursive(tree, &sizes)
+}
+
+/// Index the sizes of the nodes of a tree by GUID
+fn index_sizes(value: &JsonValue, sizes: &mut HashMap<String, (f64, f64)>) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+    if let (Some(guid), Some(size)) = (map.get("guid").and_then(guid_key), node_size(map)) {
+        sizes.insert(guid, size);
+    }
+    if let Some(JsonValue::Array(children)) = map.get("children") {
+        for child in children {
+            index_sizes(child, sizes);
+        }
+    }
+}
+
+/// Add the CSS of the gradient paints of a node or symbol override
+fn enter_object(map: &mut Map<String, JsonValue>, sizes: &HashMap<String, (f64, f64)>) {
+    let Some(size) = node_size(map).or_else(|| override_target_size(map, sizes)) else {
+        return;
+    };
+    add_css(map, size);
+
+    // Text runs and style overrides are sized by their node
+    let Some(JsonValue::Object(text_data)) = map.get_mut("textData") else {
+        return;
+    };
+    for key in ["runs", "styleOverrideTable"] {
+        let Some(JsonValue::Array(entries)) = text_data.get_mut(key) else {
+            continue;
+        };
+        for entry in entries.iter_mut().filter_map(|e| e.as_object_mut()) {
+            add_css(entry, size);
+        }
+    }
+}
+
+/// Add the CSS of the gradient paints of an object, for a node of `size`
+fn add_css(map: &mut Map<String, JsonValue>, size: (f64, f64)) {
+    for key in PAINT_ARRAYS {
+        let Some(JsonValue::Array(paints)) = map.get_mut(*key) else {
+            continue;
+        };
+        for paint in paints.iter_mut().filter_map(|p| p.as_object_mut()) {
+            if paint.contains_key("css") {
+                continue;
+            }
+            if let Some(css) = gradient_css(paint, size) {
+                paint.insert("css".to_string(), JsonValue::String(css));
+            }
+        }
+    }
+}
+
+/// Width and height of a node
+fn node_size(map: &Map<String, JsonValue>) -> Option<(f64, f64)> {
+    let dimension = |key: &str| {
+        map.get("size")
+            .and_then(|s| s.get(key))
+            .and_then(|v| v.as_f64())
+            .filter(|v| *v > 0.0)
+    };
+    Some((dimension("x")?, dimension("y")?))
+}
+
+/// Size of the node targeted by a symbol override (the last GUID of its `guidPath`)
+fn override_target_size(
+    map: &Map<String, JsonValue>,
+    sizes: &HashMap<String, (f64, f64)>,
+) -> Option<(f64, f64)> {
+    let target = map.get("guidPath")?.get("guids")?.as_array()?.last()?;
+    sizes.get(&guid_key(target)?).copied()
+}
+
+/// CSS of a gradient paint, `None` for other paints
+fn gradient_css(paint: &Map<String, JsonValue>, (width, height): (f64, f64)) -> Option<String> {
+    let paint_type = paint.get("type").and_then(enum_str)?;
+    if !paint_type.starts_with("GRADIENT_") {
+        return None;
+    }
+
+    let opacity = paint.get("opacity").and_then(|o| o.as_f64()).unwrap_or(1.0);
+    let stops: Vec<(String, f64)> = paint
+        .get("stops")
+        .or_else(|| paint.get("gradientStops"))?
+        .as_array()?
+        .iter()
+        .map(|stop| {
+            let color = stop.get("color")?.as_str()?;
+            let position = stop.get("position").and_then(|p| p.as_f64()).unwrap_or(0.0);
+            Some((color_with_opacity(color, opacity), position))
+        })
+        .collect::<Option<_>>()?;
+    if stops.is_empty() {
+        return None;
+    }
+
+    let matrix = match paint.get("transform") {
+        Some(transform) => Matrix::from_transform(transform)?,
+        None => Matrix::IDENTITY,
+    };
+    // Gradient handles in node pixels
+    let inverse = matrix.invert()?;
+    let handle = |x: f64, y: f64| {
+        let (x, y) = inverse.apply(x, y);
+        (x * width, y * height)
+    };
+    let stop_list = |positions: &dyn Fn(f64) -> f64| {
+        stops
+            .iter()
+            .map(|(color, position)| format!("{} {}%", color, format_number(positions(*position))))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match paint_type {
+        "GRADIENT_LINEAR" => {
+            let start = handle(0.0, 0.5);
+            let end = handle(1.0, 0.5);
+            let angle = css_angle(end.0 - start.0, end.1 - start.1)?;
+
+            // Positions of the handles along the CSS gradient line, which
+            // goes through the center and spans the box in that direction
+            let radians = angle.to_radians();
+            let (ux, uy) = (radians.sin(), -radians.cos());
+            let length = (width * ux).abs() + (height * uy).abs();
+            let along = |(x, y): (f64, f64)| {
+                ((x - width / 2.0) * ux + (y - height / 2.0) * uy) / length + 0.5
+            };
+            let (from, to) = (along(start), along(end));
+
+            Some(format!(
+                "linear-gradient({}deg, {})",
+                format_number(angle),
+                stop_list(&|position| (from + position * (to - from)) * 100.0)
+            ))
+        }
+        "GRADIENT_RADIAL" | "GRADIENT_DIAMOND" => {
+            let center = handle(0.5, 0.5);
+            let radius_x = distance(center, handle(1.0, 0.5)) / width * 100.0;
+            let radius_y = distance(center, handle(0.5, 1.0)) / height * 100.0;
+
+            Some(format!(
+                "radial-gradient(ellipse {}% {}% at {}% {}%, {})",
+                format_number(radius_x),
+                format_number(radius_y),
+                format_number(center.0 / width * 100.0),
+                format_number(center.1 / height * 100.0),
+                stop_list(&|position| position * 100.0)
+            ))
+        }
+        "GRADIENT_ANGULAR" => {
+            let center = handle(0.5, 0.5);
+            let start = handle(1.0, 0.5);
+            let angle = css_angle(start.0 - center.0, start.1 - center.1)?;
+
+            Some(format!(
+                "conic-gradient(from {}deg at {}% {}%, {})",
+                format_number(angle),
+                format_number(center.0 / width * 100.0),
+                format_number(center.1 / height * 100.0),
+                stop_list(&|position| position * 100.0)
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// CSS angle of a direction (0deg pointing up, clockwise), in [0, 360)
+fn css_angle(dx: f64, dy: f64) -> Option<f64> {
+    if dx.abs() < 1e-9 && dy.abs() < 1e-9 {
+        return None;
+    }
+    let angle = dx.atan2(-dy).to_degrees().rem_euclid(360.0);
+    // Rounding can turn 359.999 into 360
+    Some(if format_number(angle) == "360" {
+        0.0
+    } else {
+        angle
+    })
+}
+
+fn distance((x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> f64 {
+    ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt()
+}
+
+/// Recursively add the CSS of gradient paints in a JSON value
+fn transform_recursive(value: &mut JsonValue, sizes: &HashMap<String, (f64, f64)>) -> Result<()> {
+    match value {
+        JsonValue::Object(map) => {
+            enter_object(map, sizes);
+
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val, sizes)?;
+            }
+        }
+        JsonValue::Array(arr) => {
+            // Recurse into array elements
+            for val in arr.iter_mut() {
+                transform_recursive(val, sizes)?;
+            }
+        }
+        _ => {
+            // Primitives - nothing to do
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn css(node: JsonValue) -> String {
+        let mut tree = node;
+        transform_gradients_to_css(&mut tree).unwrap();
+        tree["fillPaints"][0]["css"]
+            .as_str()
+            .unwrap_or("")
+            .to_string()
+    }
+
+    fn gradient(paint_type: &str, transform: JsonValue) -> JsonValue {
+        json!({
+            "size": {"x": 200.0, "y": 100.0},
+            "fillPaints": [{
+                "type": paint_type,
+                "stops": [
+                    {"color": "#ffffff", "position": 0.0},
+                    {"color": "#000000", "position": 1.0}
+                ],
+                "transform": transform
+            }]
+        })
+    }
+
+    #[test]
+    fn test_linear_gradient() {
+        // Identity: left to right
+        let identity =
+            json!({"m00": 1.0, "m01": 0.0, "m02": 0.0, "m10": 0.0, "m11": 1.0, "m12": 0.0});
+        assert_eq!(
+            css(gradient("GRADIENT_LINEAR", identity)),
+            "linear-gradient(90deg, #ffffff 0%, #000000 100%)"
+        );
+
+        // Top to bottom, from 25% to 75% of the height
+        let vertical =
+            json!({"m00": 0.0, "m01": 2.0, "m02": -0.5, "m10": -1.0, "m11": 0.0, "m12": 1.0});
+        assert_eq!(
+            css(gradient("GRADIENT_LINEAR", vertical)),
+            "linear-gradient(180deg, #ffffff 25%, #000000 75%)"
+        );
+    }
+
+    #[test]
+    fn test_decomposed_transform() {
+        // The same vertical gradient after matrix_to_css
+        let raw = json!({"m00": 0.0, "m01": 2.0, "m02": -0.5, "m10": -1.0, "m11": 0.0, "m12": 1.0});
+        let mut decomposed = json!({"transform": raw.clone()});
+        crate::schema::transform_matrix_to_css(&mut decomposed).unwrap();
+
+        assert_eq!(
+            css(gradient("GRADIENT_LINEAR", decomposed["transform"].clone())),
+            css(gradient("GRADIENT_LINEAR", raw))
+        );
+    }
+
+    #[test]
+    fn test_radial_and_angular_gradients() {
+        let identity =
+            json!({"m00": 1.0, "m01": 0.0, "m02": 0.0, "m10": 0.0, "m11": 1.0, "m12": 0.0});
+        assert_eq!(
+            css(gradient("GRADIENT_RADIAL", identity.clone())),
+            "radial-gradient(ellipse 50% 50% at 50% 50%, #ffffff 0%, #000000 100%)"
+        );
+        assert_eq!(
+            css(gradient("GRADIENT_DIAMOND", identity.clone())),
+            "radial-gradient(ellipse 50% 50% at 50% 50%, #ffffff 0%, #000000 100%)"
+        );
+        assert_eq!(
+            css(gradient("GRADIENT_ANGULAR", identity)),
+            "conic-gradient(from 90deg at 50% 50%, #ffffff 0%, #000000 100%)"
+        );
+    }
+
+    #[test]
+    fn test_solid_paints_and_text_overrides() {
+        let mut tree = json!({
+            "size": {"x": 100.0, "y": 20.0},
+            "fillPaints": [{"type": "SOLID", "color": "#111111"}],
+            "textData": {"styleOverrideTable": [{
+                "styleID": 1,
+                "fillPaints": [{"type": "GRADIENT_LINEAR", "stops": [
+                    {"color": "#ff0000", "position": 0.0},
+                    {"color": "#0000ff", "position": 1.0}
+                ]}]
+            }]}
+        });
+
+        transform_gradients_to_css(&mut tree).unwrap();
+
+        assert!(tree["fillPaints"][0].get("css").is_none());
+        assert_eq!(
+            tree["textData"]["styleOverrideTable"][0]["fillPaints"][0]["css"],
+            "linear-gradient(90deg, #ff0000 0%, #0000ff 100%)"
+        );
+    }
+
+    #[test]
+    fn test_symbol_override_sized_by_target() {
+        let paint = json!({"type": "GRADIENT_RADIAL", "stops": [
+            {"color": "#ffffff", "position": 0.0},
+            {"color": "#000000", "position": 1.0}
+        ], "transform": {"m00": 2.0, "m01": 0.0, "m02": -0.5, "m10": 0.0, "m11": 1.0, "m12": 0.0}});
+        let mut tree = json!({"children": [
+            {
+                "type": "SYMBOL",
+                "guid": {"sessionID": 1, "localID": 1},
+                "size": {"x": 200.0, "y": 100.0},
+                "children": [{"guid": {"sessionID": 1, "localID": 2}, "size": {"x": 200.0, "y": 100.0}}]
+            },
+            {
+                "type": "INSTANCE",
+                "symbolData": {"symbolOverrides": [
+                    {"guidPath": {"guids": [{"sessionID": 1, "localID": 2}]}, "fillPaints": [paint.clone()]},
+                    {"guidPath": {"guids": [{"sessionID": 9, "localID": 9}]}, "fillPaints": [paint.clone()]}
+                ]}
+            },
+            {"name": "Without size", "fillPaints": [paint]}
+        ]});
+
+        transform_gradients_to_css(&mut tree).unwrap();
+
+        let overrides = &tree["children"][1]["symbolData"]["symbolOverrides"];
+        assert_eq!(
+            overrides[0]["fillPaints"][0]["css"],
+            "radial-gradient(ellipse 25% 50% at 50% 50%, #ffffff 0%, #000000 100%)"
+        );
+        // Unknown target or size: no css rather than a unit square guess
+        assert!(overrides[1]["fillPaints"][0].get("css").is_none());
+        assert!(tree["children"][2]["fillPaints"][0].get("css").is_none());
+    }
+}
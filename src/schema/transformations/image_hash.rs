@@ -41,56 +41,57 @@ pub fn transform_image_hashes(tree: &mut JsonValue, base_dir: &Path) -> Result<(
     transform_recursive(tree, base_dir)
 }
 
-/// Recursively transform image hashes in a JSON value
-fn transform_recursive(value: &mut JsonValue, base_dir: &Path) -> Result<()> {
-    match value {
-        JsonValue::Object(map) => {
-            // First, check if this object is in an "image" or "imageThumbnail" field
-            // We need to transform any such fields we find
-            let keys: Vec<String> = map.keys().cloned().collect();
-
-            for key in keys {
-                if key == "image" || key == "imageThumbnail" {
-                    // This field might need transformation
-                    if let Some(image_obj) = map.get_mut(&key) {
-                        if let Some(obj) = image_obj.as_object_mut() {
-                            // Check if it has a "hash" field
-                            if let Some(hash_value) = obj.get("hash") {
-                                if let Some(hash_array) = hash_value.as_array() {
-                                    // Convert hash array to filename
-                                    if let Some(mut filename) = hash_to_filename(hash_array) {
-                                        // Try to detect format and rename physical file
-                                        let file_path = base_dir.join(&filename);
-
-                                        if let Some(extension) = detect_image_format(&file_path) {
-                                            // Rename physical file with extension
-                                            let new_filename = format!("{}{}", filename, extension);
-                                            let new_file_path = base_dir.join(&new_filename);
-
-                                            // Attempt to rename the file
-                                            // If it fails, we'll still update the JSON with the extension
-                                            // (user may have already renamed files, or file may not exist yet)
-                                            let _ = fs::rename(&file_path, &new_file_path);
-
-                                            // Update filename to include extension
-                                            filename = new_filename;
-                                        }
-
-                                        // Remove hash field
-                                        obj.remove("hash");
-                                        // Add filename field (with or without extension)
-                                        obj.insert("filename".to_string(), JsonValue::String(filename));
-                                    }
-                                }
+/// Replace the image hashes held directly by a single object with filenames
+///
+/// Handles the "image" and "imageThumbnail" fields, renaming the matching
+/// physical file in `base_dir` when its format can be detected.
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>, base_dir: &Path) {
+    for key in ["image", "imageThumbnail"] {
+        if let Some(image_obj) = map.get_mut(key) {
+            if let Some(obj) = image_obj.as_object_mut() {
+                // Check if it has a "hash" field
+                if let Some(hash_value) = obj.get("hash") {
+                    if let Some(hash_array) = hash_value.as_array() {
+                        // Convert hash array to filename
+                        if let Some(mut filename) = hash_to_filename(hash_array) {
+                            // Try to detect format and rename physical file
+                            let file_path = base_dir.join(&filename);
+
+                            if let Some(extension) = detect_image_format(&file_path) {
+                                // Rename physical file with extension
+                                let new_filename = format!("{}{}", filename, extension);
+                                let new_file_path = base_dir.join(&new_filename);
+
+                                // Attempt to rename the file
+                                // If it fails, we'll still update the JSON with the extension
+                                // (user may have already renamed files, or file may not exist yet)
+                                let _ = fs::rename(&file_path, &new_file_path);
+
+                                // Update filename to include extension
+                                filename = new_filename;
                             }
+
+                            // Remove hash field
+                            obj.remove("hash");
+                            // Add filename field (with or without extension)
+                            obj.insert("filename".to_string(), JsonValue::String(filename));
                         }
                     }
                 }
+            }
+        }
+    }
+}
 
-                // Recurse into the value regardless
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val, base_dir)?;
-                }
+/// Recursively transform image hashes in a JSON value
+fn transform_recursive(value: &mut JsonValue, base_dir: &Path) -> Result<()> {
+    match value {
+        JsonValue::Object(map) => {
+            enter_object(map, base_dir);
+
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val, base_dir)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -47,42 +47,44 @@ pub fn remove_image_metadata_fields(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove image metadata fields from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove image metadata fields if they exist
+    map.remove("thumbHash");
+    map.remove("animationFrame");
+    map.remove("imageShouldColorManage");
+    map.remove("imageScaleMode");
+    map.remove("originalImageWidth");
+    map.remove("originalImageHeight");
+    map.remove("altText");
+    map.remove("imageThumbnail");
+
+    // Check if this is a paint object with image properties
+    // (rotation and scale should only be removed in certain contexts)
+    if map.contains_key("type") {
+        if let Some(type_val) = map.get("type") {
+            if let Some(type_obj) = type_val.as_object() {
+                if let Some(value_str) = type_obj.get("value").and_then(|v| v.as_str()) {
+                    if value_str == "IMAGE" {
+                        // This is an image paint object, remove rotation and scale
+                        map.remove("rotation");
+                        map.remove("scale");
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Recursively remove image metadata fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove image metadata fields if they exist
-            map.remove("thumbHash");
-            map.remove("animationFrame");
-            map.remove("imageShouldColorManage");
-            map.remove("imageScaleMode");
-            map.remove("originalImageWidth");
-            map.remove("originalImageHeight");
-            map.remove("altText");
-            map.remove("imageThumbnail");
-
-            // Check if this is a paint object with image properties
-            // (rotation and scale should only be removed in certain contexts)
-            if map.contains_key("type") {
-                if let Some(type_val) = map.get("type") {
-                    if let Some(type_obj) = type_val.as_object() {
-                        if let Some(value_str) = type_obj.get("value").and_then(|v| v.as_str()) {
-                            if value_str == "IMAGE" {
-                                // This is an image paint object, remove rotation and scale
-                                map.remove("rotation");
-                                map.remove("scale");
-                            }
-                        }
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -0,0 +1,461 @@
+use crate::error::Result;
+use crate::schema::tree::{guid_key, node_type};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Expand component instances into the subtree of their main component
+///
+/// INSTANCE nodes only reference their main component through
+/// `symbolData.symbolID` and describe their differences in
+/// `symbolData.symbolOverrides`. This pass looks up the SYMBOL node by GUID,
+/// copies its children under the instance, and applies the overrides:
+///
+/// - each override targets a node of the copy through `guidPath.guids`, one
+///   GUID per nesting level of instances
+/// - `overriddenSymbolID` swaps a nested instance to another component
+/// - all other fields (`textData`, `fillPaints`, `visible`, ...) replace the
+///   fields of the target node
+///
+/// Expanded instances get a `componentName` field with the name of their main
+/// component (the component set for variants, with the variant name in
+/// `variant`). Instances that already have children, reference a component
+/// missing from the file, or (indirectly) contain themselves are left as is.
+///
+/// # Arguments
+/// * `tree` - The JSON tree to modify (usually the document root)
+///
+/// # Returns
+/// * `Ok(())` - Successfully expanded all instances
+///
+/// # Examples
+/// ```
+/// use fig2json::schema::expand_instances;
+/// use serde_json::json;
+///
+/// let mut tree = json!({"children": [
+///     {
+///         "type": "SYMBOL",
+///         "name": "Button",
+///         "guid": {"sessionID": 1, "localID": 1},
+///         "children": [{"type": "TEXT", "guid": {"sessionID": 1, "localID": 2}, "textData": {"characters": "OK"}}]
+///     },
+///     {
+///         "type": "INSTANCE",
+///         "symbolData": {
+///             "symbolID": {"sessionID": 1, "localID": 1},
+///             "symbolOverrides": [{
+///                 "guidPath": {"guids": [{"sessionID": 1, "localID": 2}]},
+///                 "textData": {"characters": "Cancel"}
+///             }]
+///         }
+///     }
+/// ]});
+/// expand_instances(&mut tree).unwrap();
+///
+/// let instance = &tree["children"][1];
+/// assert_eq!(instance["componentName"], "Button");
+/// assert_eq!(instance["children"][0]["textData"]["characters"], "Cancel");
+/// ```
+pub fn expand_instances(tree: &mut JsonValue) -> Result<()> {
+    let mut symbols = HashMap::new();
+    collect_symbols(tree, None, &mut symbols);
+    if symbols.is_empty() {
+        return Ok(());
+    }
+
+    expand_recursive(tree, &symbols, &mut Vec::new());
+    Ok(())
+}
+
+/// A main component
+struct Symbol {
+    /// Name of the component, or of its component set for variants
+    component_name: String,
+    /// Variant name when the component belongs to a component set
+    variant: Option<String>,
+    children: JsonValue,
+}
+
+/// Index SYMBOL nodes by GUID
+fn collect_symbols(
+    value: &JsonValue,
+    set_name: Option<&str>,
+    symbols: &mut HashMap<String, Symbol>,
+) {
+    visit_symbols(value, set_name, &mut |guid, symbol, component_name, variant| {
+        let symbol = Symbol {
+            component_name,
+            variant,
+            children: symbol
+                .get("children")
+                .cloned()
+                .unwrap_or(JsonValue::Array(Vec::new())),
+        };
+        symbols.insert(guid, symbol);
+    });
+}
+
+/// Names of the main components by SYMBOL GUID (`sessionID:localID`)
+///
+/// Variants are named after their component set, as the `componentName` of
+/// expanded instances.
+pub(crate) fn component_names(tree: &JsonValue) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    visit_symbols(tree, None, &mut |guid, _, component_name, _| {
+        names.insert(guid, component_name);
+    });
+    names
+}
+
+/// Call `visit` with the GUID, node, component name and variant name of each SYMBOL node
+fn visit_symbols<'a>(
+    value: &'a JsonValue,
+    set_name: Option<&str>,
+    visit: &mut impl FnMut(String, &'a Map<String, JsonValue>, String, Option<String>),
+) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+
+    if node_type(map) == Some("SYMBOL") {
+        if let Some(guid) = map.get("guid").and_then(guid_key) {
+            let name = map
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("")
+                .to_string();
+            let (component_name, variant) = match set_name {
+                Some(set_name) => (set_name.to_string(), Some(name)),
+                None => (name, None),
+            };
+            visit(guid, map, component_name, variant);
+        }
+    }
+
+    let set_name = match map.get("isStateGroup").and_then(|v| v.as_bool()) {
+        Some(true) => map.get("name").and_then(|n| n.as_str()),
+        _ => None,
+    };
+    if let Some(JsonValue::Array(children)) = map.get("children") {
+        for child in children {
+            visit_symbols(child, set_name, visit);
+        }
+    }
+}
+
+/// Expand the instances of a subtree
+///
+/// `stack` holds the components being expanded, to stop on recursive instances.
+fn expand_recursive(
+    value: &mut JsonValue,
+    symbols: &HashMap<String, Symbol>,
+    stack: &mut Vec<String>,
+) {
+    let Some(map) = value.as_object_mut() else {
+        return;
+    };
+
+    if node_type(map) == Some("INSTANCE") && !has_children(map) {
+        expand_instance(map, symbols, stack);
+        return;
+    }
+
+    if let Some(JsonValue::Array(children)) = map.get_mut("children") {
+        for child in children {
+            expand_recursive(child, symbols, stack);
+        }
+    }
+}
+
+/// Copy the children of the main component under an instance and apply its overrides
+fn expand_instance(
+    instance: &mut Map<String, JsonValue>,
+    symbols: &HashMap<String, Symbol>,
+    stack: &mut Vec<String>,
+) {
+    let Some(id) = instance
+        .get("symbolData")
+        .and_then(|d| d.get("symbolID"))
+        .and_then(guid_key)
+    else {
+        return;
+    };
+    let Some(symbol) = symbols.get(&id) else {
+        return;
+    };
+    if stack.contains(&id) {
+        return;
+    }
+
+    stack.push(id);
+    let mut children = symbol.children.clone();
+    // Nested instances first, so that the overrides below apply on top of theirs
+    if let JsonValue::Array(nodes) = &mut children {
+        for child in nodes.iter_mut() {
+            expand_recursive(child, symbols, stack);
+        }
+    }
+    instance.insert("children".to_string(), children);
+    instance.insert(
+        "componentName".to_string(),
+        JsonValue::from(symbol.component_name.as_str()),
+    );
+    match &symbol.variant {
+        Some(variant) => instance.insert("variant".to_string(), JsonValue::from(variant.as_str())),
+        None => instance.remove("variant"),
+    };
+    apply_overrides(instance, symbols, stack);
+    stack.pop();
+}
+
+/// Apply `symbolData.symbolOverrides` to the expanded children of an instance
+fn apply_overrides(
+    instance: &mut Map<String, JsonValue>,
+    symbols: &HashMap<String, Symbol>,
+    stack: &mut Vec<String>,
+) {
+    let mut overrides: Vec<(Vec<String>, Map<String, JsonValue>)> = instance
+        .get("symbolData")
+        .and_then(|d| d.get("symbolOverrides"))
+        .and_then(|o| o.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let entry = entry.as_object()?;
+            let guids = entry
+                .get("guidPath")?
+                .get("guids")?
+                .as_array()?
+                .iter()
+                .map(guid_key)
+                .collect::<Option<Vec<_>>>()?;
+            Some((guids, entry.clone()))
+        })
+        .filter(|(guids, _)| !guids.is_empty())
+        .collect();
+    // Outer nodes first, so that swaps happen before the overrides of their content
+    overrides.sort_by_key(|(guids, _)| guids.len());
+
+    let Some(JsonValue::Array(children)) = instance.get_mut("children") else {
+        return;
+    };
+    for (guids, entry) in overrides {
+        let Some(target) = find_path(children, &guids) else {
+            continue;
+        };
+
+        if let Some(swap) = entry
+            .get("overriddenSymbolID")
+            .filter(|id| guid_key(id).is_some())
+        {
+            if let Some(JsonValue::Object(symbol_data)) = target.get_mut("symbolData") {
+                symbol_data.insert("symbolID".to_string(), swap.clone());
+                target.remove("children");
+                expand_instance(target, symbols, stack);
+            }
+        }
+        for (key, value) in entry {
+            if key != "guidPath" && key != "overriddenSymbolID" {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Node of an expanded subtree designated by a GUID path
+///
+/// The first GUID is looked up among the nodes of the subtree outside of nested
+/// instances, each following GUID inside the children of the previous node.
+fn find_path<'a>(
+    nodes: &'a mut [JsonValue],
+    guids: &[String],
+) -> Option<&'a mut Map<String, JsonValue>> {
+    let (first, rest) = guids.split_first()?;
+    let node = find_node(nodes, first)?;
+    if rest.is_empty() {
+        return Some(node);
+    }
+    match node.get_mut("children") {
+        Some(JsonValue::Array(children)) => find_path(children, rest),
+        _ => None,
+    }
+}
+
+fn find_node<'a>(nodes: &'a mut [JsonValue], guid: &str) -> Option<&'a mut Map<String, JsonValue>> {
+    for node in nodes {
+        let Some(map) = node.as_object_mut() else {
+            continue;
+        };
+        if map.get("guid").and_then(guid_key).as_deref() == Some(guid) {
+            return Some(map);
+        }
+        if node_type(map) == Some("INSTANCE") {
+            continue;
+        }
+        if let Some(JsonValue::Array(children)) = map.get_mut("children") {
+            if let Some(found) = find_node(children, guid) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+fn has_children(map: &Map<String, JsonValue>) -> bool {
+    map.get("children")
+        .and_then(|c| c.as_array())
+        .is_some_and(|c| !c.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn guid(local: u64) -> JsonValue {
+        json!({"sessionID": 1, "localID": local})
+    }
+
+    fn enum_type(value: &str) -> JsonValue {
+        json!({"__enum__": "NodeType", "value": value})
+    }
+
+    fn instance(symbol: u64, overrides: JsonValue) -> JsonValue {
+        json!({
+            "type": enum_type("INSTANCE"),
+            "name": "Instance",
+            "symbolData": {"symbolID": guid(symbol), "symbolOverrides": overrides}
+        })
+    }
+
+    /// Icon components 10 and 20, Button 1 containing a label and an icon instance
+    fn components() -> Vec<JsonValue> {
+        vec![
+            json!({
+                "type": enum_type("SYMBOL"),
+                "name": "Icon/Check",
+                "guid": guid(10),
+                "children": [{"type": enum_type("VECTOR"), "name": "Path", "guid": guid(11)}]
+            }),
+            json!({
+                "type": enum_type("SYMBOL"),
+                "name": "Icon/Close",
+                "guid": guid(20),
+                "children": [{"type": enum_type("VECTOR"), "name": "Cross", "guid": guid(21)}]
+            }),
+            json!({
+                "type": enum_type("SYMBOL"),
+                "name": "Button",
+                "guid": guid(1),
+                "children": [
+                    {"type": enum_type("TEXT"), "name": "Label", "guid": guid(2), "textData": {"characters": "OK"}},
+                    {
+                        "type": enum_type("INSTANCE"),
+                        "name": "Icon",
+                        "guid": guid(3),
+                        "symbolData": {"symbolID": guid(10)}
+                    }
+                ]
+            }),
+        ]
+    }
+
+    fn expand(instance: JsonValue) -> JsonValue {
+        let mut children = components();
+        children.push(instance);
+        let mut tree = json!({"children": children});
+        expand_instances(&mut tree).unwrap();
+        tree["children"][3].take()
+    }
+
+    #[test]
+    fn test_expand_instance() {
+        let expanded = expand(instance(1, json!([])));
+
+        assert_eq!(expanded["componentName"], json!("Button"));
+        assert_eq!(
+            expanded["children"][0]["textData"]["characters"],
+            json!("OK")
+        );
+        // Nested instances are expanded too
+        assert_eq!(
+            expanded["children"][1]["componentName"],
+            json!("Icon/Check")
+        );
+        assert_eq!(
+            expanded["children"][1]["children"][0]["name"],
+            json!("Path")
+        );
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let expanded = expand(instance(
+            1,
+            json!([
+                {"guidPath": {"guids": [guid(2)]}, "textData": {"characters": "Save"}, "fillPaints": []},
+                {"guidPath": {"guids": [guid(3), guid(11)]}, "visible": false}
+            ]),
+        ));
+
+        assert_eq!(
+            expanded["children"][0]["textData"]["characters"],
+            json!("Save")
+        );
+        assert_eq!(expanded["children"][0]["fillPaints"], json!([]));
+        assert_eq!(
+            expanded["children"][1]["children"][0]["visible"],
+            json!(false)
+        );
+    }
+
+    #[test]
+    fn test_nested_swap() {
+        let expanded = expand(instance(
+            1,
+            json!([
+                {"guidPath": {"guids": [guid(3), guid(21)]}, "name": "Swapped cross"},
+                {"guidPath": {"guids": [guid(3)]}, "overriddenSymbolID": guid(20)}
+            ]),
+        ));
+
+        let icon = &expanded["children"][1];
+        assert_eq!(icon["componentName"], json!("Icon/Close"));
+        assert_eq!(icon["symbolData"]["symbolID"], guid(20));
+        assert_eq!(icon["children"][0]["name"], json!("Swapped cross"));
+    }
+
+    #[test]
+    fn test_variant_and_missing_component() {
+        let mut tree = json!({"children": [
+            {
+                "name": "Button",
+                "isStateGroup": true,
+                "children": [{"type": "SYMBOL", "name": "Size=Large", "guid": guid(5), "children": []}]
+            },
+            {"type": "INSTANCE", "symbolData": {"symbolID": guid(5)}},
+            {"type": "INSTANCE", "symbolData": {"symbolID": guid(99)}}
+        ]});
+        expand_instances(&mut tree).unwrap();
+
+        assert_eq!(tree["children"][1]["componentName"], json!("Button"));
+        assert_eq!(tree["children"][1]["variant"], json!("Size=Large"));
+        assert!(tree["children"][2].get("componentName").is_none());
+    }
+
+    #[test]
+    fn test_recursive_instance() {
+        let mut tree = json!({"children": [{
+            "type": "SYMBOL",
+            "name": "Loop",
+            "guid": guid(1),
+            "children": [{"type": "INSTANCE", "guid": guid(2), "symbolData": {"symbolID": guid(1)}}]
+        }]});
+        expand_instances(&mut tree).unwrap();
+
+        // The instance of Loop inside Loop is expanded once, not forever
+        let inner = &tree["children"][0]["children"][0];
+        assert_eq!(inner["componentName"], json!("Loop"));
+        assert!(inner["children"][0].get("componentName").is_none());
+    }
+}
@@ -33,35 +33,43 @@ pub fn remove_internal_only_nodes(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Drop internalOnly nodes from a single array
+pub(crate) fn enter_array(arr: &mut Vec<JsonValue>) {
+    arr.retain(|node| {
+        if let Some(obj) = node.as_object() {
+            // Keep node if internalOnly is not true
+            !obj.get("internalOnly")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+        } else {
+            // Keep non-object values
+            true
+        }
+    });
+}
+
+/// Remove the internalOnly flag from a single object
+///
+/// The flag is only used for filtering by the enclosing array, which has
+/// already been visited when the object itself is reached.
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    map.remove("internalOnly");
+}
+
 /// Recursively remove internal-only nodes from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
+            enter_object(map);
+
             // Recurse into all values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
-
-            // After recursion, remove internalOnly field itself
-            // (it was only used for filtering, not needed in output)
-            map.remove("internalOnly");
         }
         JsonValue::Array(arr) => {
             // Filter out nodes with internalOnly: true FIRST (before recursing)
-            arr.retain(|node| {
-                if let Some(obj) = node.as_object() {
-                    // Keep node if internalOnly is not true
-                    !obj.get("internalOnly")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false)
-                } else {
-                    // Keep non-object values
-                    true
-                }
-            });
+            enter_array(arr);
 
             // Then recurse into remaining array elements
             for val in arr.iter_mut() {
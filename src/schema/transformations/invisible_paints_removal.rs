@@ -42,25 +42,27 @@ pub fn remove_invisible_paints(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Filter invisible paints out of a single object's paint arrays
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Filter fillPaints array
+    if let Some(JsonValue::Array(paints)) = map.get_mut("fillPaints") {
+        paints.retain(|paint| !is_invisible(paint));
+    }
+
+    // Filter strokePaints array
+    if let Some(JsonValue::Array(paints)) = map.get_mut("strokePaints") {
+        paints.retain(|paint| !is_invisible(paint));
+    }
+}
+
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Filter fillPaints array
-            if let Some(JsonValue::Array(paints)) = map.get_mut("fillPaints") {
-                paints.retain(|paint| !is_invisible(paint));
-            }
-
-            // Filter strokePaints array
-            if let Some(JsonValue::Array(paints)) = map.get_mut("strokePaints") {
-                paints.retain(|paint| !is_invisible(paint));
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
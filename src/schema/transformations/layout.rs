@@ -0,0 +1,540 @@
+use crate::css::{format_number, layout_declarations, padding, px, str_field, Declaration};
+use crate::error::Result;
+use serde_json::{Map, Value as JsonValue};
+
+/// CSS properties written to `css` blocks
+pub(crate) const CSS_PROPERTIES: &[&str] = &[
+    "display",
+    "flex-direction",
+    "flex-wrap",
+    "gap",
+    "row-gap",
+    "column-gap",
+    "padding",
+    "justify-content",
+    "align-items",
+    "align-content",
+    "grid-template-columns",
+    "grid-template-rows",
+    "width",
+    "height",
+    "min-width",
+    "min-height",
+    "max-width",
+    "max-height",
+    "position",
+    "flex",
+    "flex-shrink",
+    "align-self",
+    "justify-self",
+    "grid-row",
+    "grid-column",
+];
+
+/// Auto-layout fields replaced by the `css` block
+const LAYOUT_FIELDS: &[&str] = &[
+    "stackMode",
+    "stackSpacing",
+    "stackHorizontalPadding",
+    "stackVerticalPadding",
+    "stackPaddingRight",
+    "stackPaddingBottom",
+    "stackPrimarySizing",
+    "stackCounterSizing",
+    "stackPrimaryAlignItems",
+    "stackCounterAlignItems",
+    "stackWrap",
+    "stackCounterSpacing",
+    "stackCounterAlignContent",
+    "stackChildPrimaryGrow",
+    "stackChildAlignSelf",
+    "stackPositioning",
+    "minSize",
+    "maxSize",
+    "gridRowCount",
+    "gridColumnCount",
+    "gridRowGap",
+    "gridColumnGap",
+    "gridRowSizes",
+    "gridColumnSizes",
+    "gridRowAnchorIndex",
+    "gridColumnAnchorIndex",
+    "gridRowSpan",
+    "gridColumnSpan",
+    "gridChildHorizontalAlign",
+    "gridChildVerticalAlign",
+];
+
+/// Auto-layout of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    Row,
+    Column,
+    Grid,
+}
+
+impl Layout {
+    /// Size properties of the primary and counter axes
+    fn axes(self) -> (&'static str, &'static str) {
+        match self {
+            Layout::Column => ("height", "width"),
+            Layout::Row | Layout::Grid => ("width", "height"),
+        }
+    }
+}
+
+/// Translate auto-layout properties to CSS flexbox and grid declarations
+///
+/// Each node with auto-layout fields gets a `css` block of CSS declarations
+/// replacing them:
+///
+/// - frames: `stackMode` (`display: flex` and `flex-direction`, or
+///   `display: grid` for grid auto-layout), `stackSpacing`, paddings,
+///   primary and counter alignment, `stackWrap` with `stackCounterSpacing`
+///   and `stackCounterAlignContent`, and grid tracks and gaps
+///   (`gridColumnSizes`/`gridColumnCount`, `gridRowSizes`/`gridRowCount`,
+///   `gridColumnGap`, `gridRowGap`)
+/// - sizing modes: hug → `fit-content`, fixed → the `size` in pixels
+/// - `minSize` and `maxSize` → `min-width`, `max-height`, ...
+/// - children of flexbox frames: `stackChildPrimaryGrow` (fill on the main
+///   axis) → `flex: 1 0 0`, `stackChildAlignSelf: STRETCH` (fill on the cross
+///   axis) → `align-self: stretch`, other children `flex-shrink: 0`
+/// - children of grid frames: `grid-row`/`grid-column` from their anchor
+///   indices and spans, `justify-self`/`align-self` from their alignment
+/// - `stackPositioning: ABSOLUTE` → `position: absolute`
+///
+/// A fill replaces the size of its axis with `auto`. Enums must be plain
+/// strings (`enum_simplification`).
+///
+/// # Arguments
+/// * `tree` - The JSON tree to modify (usually the document root)
+///
+/// # Returns
+/// * `Ok(())` - Successfully translated the auto-layout of all nodes
+///
+/// # Examples
+/// ```
+/// use fig2json::schema::transform_layout_to_css;
+/// use serde_json::json;
+///
+/// let mut tree = json!({
+///     "stackMode": "HORIZONTAL",
+///     "stackSpacing": 8.0,
+///     "stackHorizontalPadding": 16.0,
+///     "stackVerticalPadding": 12.0,
+///     "stackCounterAlignItems": "CENTER",
+///     "stackPrimarySizing": "RESIZE_TO_FIT_WITH_IMPLICIT_SIZE",
+///     "size": {"x": 120.0, "y": 48.0},
+///     "children": [{"name": "Label", "stackChildPrimaryGrow": 1.0}]
+/// });
+/// transform_layout_to_css(&mut tree).unwrap();
+///
+/// assert_eq!(
+///     tree["css"],
+///     json!({
+///         "display": "flex",
+///         "flex-direction": "row",
+///         "gap": "8px",
+///         "padding": "12px 16px",
+///         "align-items": "center",
+///         "width": "fit-content",
+///         "height": "48px"
+///     })
+/// );
+/// assert_eq!(tree["children"][0]["css"], json!({"flex": "1 0 0", "width": "auto"}));
+/// assert!(tree.get("stackMode").is_none());
+/// ```
+pub fn transform_layout_to_css(tree: &mut JsonValue) -> Result<()> {
+    transform_recursive(tree)
+}
+
+/// Translate the auto-layout of a single node, and the child properties of
+/// its children
+pub(crate) fn enter_object(map: &mut Map<String, JsonValue>) {
+    // Other objects (paints, children of auto-layout frames) keep their `css`
+    if !LAYOUT_FIELDS.iter().any(|field| map.contains_key(*field)) {
+        return;
+    }
+
+    let layout = layout(map);
+    // Children already have the declarations of their parent's layout
+    let mut css = match map.get_mut("css") {
+        Some(JsonValue::Object(css)) => std::mem::take(css),
+        // Not a declaration block
+        Some(_) => return,
+        None => Map::new(),
+    };
+
+    let mut declarations = Vec::new();
+    match layout {
+        Some(Layout::Grid) => grid_declarations(map, &mut declarations),
+        Some(layout) => flex_declarations(map, layout, &mut declarations),
+        None => {}
+    }
+    for (property, value) in declarations {
+        css.insert(property.to_string(), JsonValue::String(value));
+    }
+    // Sizes set by the parent (fills) take precedence
+    for (property, value) in size_declarations(map, layout) {
+        css.entry(property)
+            .or_insert_with(|| JsonValue::String(value));
+    }
+
+    if let (Some(layout), Some(JsonValue::Array(children))) = (layout, map.get_mut("children")) {
+        for child in children.iter_mut().filter_map(|c| c.as_object_mut()) {
+            let declarations = child_declarations(child, layout);
+            if declarations.is_empty() {
+                continue;
+            }
+            let child_css = child
+                .entry("css")
+                .or_insert_with(|| JsonValue::Object(Map::new()));
+            if let Some(child_css) = child_css.as_object_mut() {
+                for (property, value) in declarations {
+                    child_css.insert(property.to_string(), JsonValue::String(value));
+                }
+            }
+        }
+    }
+
+    for field in LAYOUT_FIELDS {
+        map.remove(*field);
+    }
+    if css.is_empty() {
+        map.remove("css");
+    } else {
+        map.insert("css".to_string(), JsonValue::Object(css));
+    }
+}
+
+fn layout(map: &Map<String, JsonValue>) -> Option<Layout> {
+    match str_field(map, "stackMode")? {
+        "HORIZONTAL" => Some(Layout::Row),
+        "VERTICAL" => Some(Layout::Column),
+        "GRID" => Some(Layout::Grid),
+        _ => None,
+    }
+}
+
+/// Flexbox container declarations, with wrapping
+fn flex_declarations(map: &Map<String, JsonValue>, layout: Layout, css: &mut Vec<Declaration>) {
+    layout_declarations(map, css);
+    if str_field(map, "stackWrap") != Some("WRAP") {
+        return;
+    }
+
+    css.push(("flex-wrap", "wrap".to_string()));
+    if let Some(counter) = number(map, "stackCounterSpacing") {
+        let spacing = number(map, "stackSpacing").unwrap_or(0.0);
+        // Rows of a wrapped row are separated by the counter spacing
+        let (row_gap, column_gap) = match layout {
+            Layout::Column => (spacing, counter),
+            _ => (counter, spacing),
+        };
+        css.retain(|(property, _)| *property != "gap");
+        css.push(("gap", format!("{} {}", px(row_gap), px(column_gap))));
+    }
+    if str_field(map, "stackCounterAlignContent") == Some("SPACE_BETWEEN") {
+        css.push(("align-content", "space-between".to_string()));
+    }
+}
+
+/// Grid container declarations
+fn grid_declarations(map: &Map<String, JsonValue>, css: &mut Vec<Declaration>) {
+    css.push(("display", "grid".to_string()));
+    if let Some(columns) = tracks(map, "gridColumnSizes", "gridColumnCount") {
+        css.push(("grid-template-columns", columns));
+    }
+    if let Some(rows) = tracks(map, "gridRowSizes", "gridRowCount") {
+        css.push(("grid-template-rows", rows));
+    }
+
+    match (number(map, "gridRowGap"), number(map, "gridColumnGap")) {
+        (Some(row), Some(column)) if row == column => css.push(("gap", px(row))),
+        (row, column) => {
+            if let Some(row) = row {
+                css.push(("row-gap", px(row)));
+            }
+            if let Some(column) = column {
+                css.push(("column-gap", px(column)));
+            }
+        }
+    }
+    if let Some(padding) = padding(map) {
+        css.push(("padding", padding));
+    }
+}
+
+/// Grid track list from the track sizes, or equal tracks from the count
+fn tracks(map: &Map<String, JsonValue>, sizes: &str, count: &str) -> Option<String> {
+    if let Some(sizes) = map.get(sizes).and_then(|s| s.as_array()) {
+        let tracks: Vec<String> = sizes
+            .iter()
+            .map(|size| {
+                let value = size.get("value").and_then(|v| v.as_f64());
+                match size.get("type").and_then(|t| t.as_str()) {
+                    Some("FIXED") => px(value.unwrap_or(0.0)),
+                    Some("HUG") => "auto".to_string(),
+                    _ => format!("{}fr", format_number(value.unwrap_or(1.0))),
+                }
+            })
+            .collect();
+        if !tracks.is_empty() {
+            return Some(tracks.join(" "));
+        }
+    }
+    let count = map.get(count)?.as_u64().filter(|n| *n > 0)?;
+    Some(format!("repeat({}, 1fr)", count))
+}
+
+/// Width and height from the sizing modes, and min/max sizes
+fn size_declarations(
+    map: &Map<String, JsonValue>,
+    layout: Option<Layout>,
+) -> Vec<(String, String)> {
+    let mut css = Vec::new();
+    if let Some(layout) = layout {
+        let (primary, counter) = layout.axes();
+        for (field, property) in [
+            ("stackPrimarySizing", primary),
+            ("stackCounterSizing", counter),
+        ] {
+            let value = match str_field(map, field) {
+                Some("RESIZE_TO_FIT" | "RESIZE_TO_FIT_WITH_IMPLICIT_SIZE") => {
+                    Some("fit-content".to_string())
+                }
+                _ => size(map.get("size"), property).map(px),
+            };
+            if let Some(value) = value {
+                css.push((property.to_string(), value));
+            }
+        }
+    }
+
+    for (field, prefix) in [("minSize", "min"), ("maxSize", "max")] {
+        // Optional vectors are stored as {"value": {"x", "y"}}
+        let limits = map.get(field).map(|v| v.get("value").unwrap_or(v));
+        for property in ["width", "height"] {
+            if let Some(limit) = size(limits, property) {
+                css.push((format!("{}-{}", prefix, property), px(limit)));
+            }
+        }
+    }
+    css
+}
+
+/// Declarations of a child from the layout of its parent
+fn child_declarations(child: &Map<String, JsonValue>, parent: Layout) -> Vec<Declaration> {
+    let mut css = Vec::new();
+    if str_field(child, "stackPositioning") == Some("ABSOLUTE") {
+        css.push(("position", "absolute".to_string()));
+        return css;
+    }
+
+    if parent == Layout::Grid {
+        for (property, anchor, span) in [
+            ("grid-row", "gridRowAnchorIndex", "gridRowSpan"),
+            ("grid-column", "gridColumnAnchorIndex", "gridColumnSpan"),
+        ] {
+            if let Some(anchor) = child.get(anchor).and_then(|a| a.as_u64()) {
+                let span = child.get(span).and_then(|s| s.as_u64()).unwrap_or(1).max(1);
+                css.push((property, format!("{} / span {}", anchor + 1, span)));
+            }
+        }
+        for (property, field) in [
+            ("justify-self", "gridChildHorizontalAlign"),
+            ("align-self", "gridChildVerticalAlign"),
+        ] {
+            if let Some(align) = str_field(child, field).and_then(grid_align) {
+                css.push((property, align.to_string()));
+            }
+        }
+        return css;
+    }
+
+    let (primary, counter) = parent.axes();
+    if number(child, "stackChildPrimaryGrow").is_some_and(|grow| grow > 0.0) {
+        css.push(("flex", "1 0 0".to_string()));
+        css.push((primary, "auto".to_string()));
+    } else {
+        css.push(("flex-shrink", "0".to_string()));
+    }
+    if str_field(child, "stackChildAlignSelf") == Some("STRETCH") {
+        css.push(("align-self", "stretch".to_string()));
+        css.push((counter, "auto".to_string()));
+    }
+    css
+}
+
+fn grid_align(align: &str) -> Option<&'static str> {
+    match align {
+        "MIN" => Some("start"),
+        "CENTER" => Some("center"),
+        "MAX" => Some("end"),
+        _ => None,
+    }
+}
+
+/// Positive width (`x`) or height (`y`) of a size vector
+fn size(vector: Option<&JsonValue>, property: &str) -> Option<f64> {
+    let axis = if property == "width" { "x" } else { "y" };
+    vector?
+        .get(axis)?
+        .as_f64()
+        .filter(|v| v.is_finite() && *v > 0.0)
+}
+
+fn number(map: &Map<String, JsonValue>, key: &str) -> Option<f64> {
+    map.get(key)?.as_f64()
+}
+
+/// Recursively translate the auto-layout of a JSON value
+fn transform_recursive(value: &mut JsonValue) -> Result<()> {
+    match value {
+        JsonValue::Object(map) => {
+            enter_object(map);
+
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val)?;
+            }
+        }
+        JsonValue::Array(arr) => {
+            // Recurse into array elements
+            for val in arr.iter_mut() {
+                transform_recursive(val)?;
+            }
+        }
+        _ => {
+            // Primitives - nothing to do
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_vertical_stack_with_fill_children() {
+        let mut tree = json!({
+            "stackMode": "VERTICAL",
+            "stackSpacing": 12.0,
+            "stackPrimaryAlignItems": "SPACE_EVENLY",
+            "stackCounterSizing": "FIXED",
+            "stackPrimarySizing": "RESIZE_TO_FIT",
+            "size": {"x": 320.0, "y": 200.0},
+            "children": [
+                {"name": "Title", "stackChildAlignSelf": "STRETCH", "size": {"x": 320.0, "y": 24.0}},
+                {"name": "Body", "stackChildPrimaryGrow": 1.0},
+                {"name": "Badge", "stackPositioning": "ABSOLUTE"}
+            ]
+        });
+
+        transform_layout_to_css(&mut tree).unwrap();
+
+        assert_eq!(
+            tree["css"],
+            json!({
+                "display": "flex",
+                "flex-direction": "column",
+                "gap": "12px",
+                "justify-content": "space-evenly",
+                "width": "320px",
+                "height": "fit-content"
+            })
+        );
+        assert_eq!(
+            tree["children"][0],
+            json!({
+                "name": "Title",
+                "size": {"x": 320.0, "y": 24.0},
+                "css": {"flex-shrink": "0", "align-self": "stretch", "width": "auto"}
+            })
+        );
+        assert_eq!(
+            tree["children"][1]["css"],
+            json!({"flex": "1 0 0", "height": "auto"})
+        );
+        assert_eq!(tree["children"][2]["css"], json!({"position": "absolute"}));
+    }
+
+    #[test]
+    fn test_fill_wins_over_own_sizing() {
+        // A nested auto-layout frame filling its parent's width
+        let mut tree = json!({
+            "stackMode": "HORIZONTAL",
+            "children": [{
+                "stackMode": "HORIZONTAL",
+                "stackChildPrimaryGrow": 1.0,
+                "stackPrimarySizing": "FIXED",
+                "size": {"x": 100.0, "y": 40.0}
+            }]
+        });
+
+        transform_layout_to_css(&mut tree).unwrap();
+
+        let child = &tree["children"][0]["css"];
+        assert_eq!(child["width"], "auto");
+        assert_eq!(child["height"], "40px");
+        assert_eq!(child["flex-direction"], "row");
+    }
+
+    #[test]
+    fn test_wrap_and_min_max_sizes() {
+        let mut tree = json!({
+            "stackMode": "HORIZONTAL",
+            "stackSpacing": 8.0,
+            "stackWrap": "WRAP",
+            "stackCounterSpacing": 16.0,
+            "stackCounterAlignContent": "SPACE_BETWEEN",
+            "minSize": {"value": {"x": 200.0, "y": 0.0}},
+            "maxSize": {"value": {"x": 640.0, "y": 480.0}}
+        });
+
+        transform_layout_to_css(&mut tree).unwrap();
+
+        let css = &tree["css"];
+        assert_eq!(css["flex-wrap"], "wrap");
+        assert_eq!(css["gap"], "16px 8px");
+        assert_eq!(css["align-content"], "space-between");
+        assert_eq!(css["min-width"], "200px");
+        assert!(css.get("min-height").is_none());
+        assert_eq!(css["max-height"], "480px");
+        assert!(tree.get("minSize").is_none());
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut tree = json!({
+            "stackMode": "GRID",
+            "gridColumnSizes": [{"type": "FLEX", "value": 1.0}, {"type": "FIXED", "value": 240.0}, {"type": "HUG"}],
+            "gridRowCount": 2,
+            "gridRowGap": 16.0,
+            "gridColumnGap": 16.0,
+            "stackHorizontalPadding": 24.0,
+            "children": [{
+                "gridRowAnchorIndex": 1,
+                "gridColumnAnchorIndex": 0,
+                "gridColumnSpan": 2,
+                "gridChildHorizontalAlign": "CENTER",
+                "gridChildVerticalAlign": "AUTO"
+            }]
+        });
+
+        transform_layout_to_css(&mut tree).unwrap();
+
+        assert_eq!(tree["css"]["display"], "grid");
+        assert_eq!(tree["css"]["grid-template-columns"], "1fr 240px auto");
+        assert_eq!(tree["css"]["grid-template-rows"], "repeat(2, 1fr)");
+        assert_eq!(tree["css"]["gap"], "16px");
+        assert_eq!(tree["css"]["padding"], "0 24px");
+        assert_eq!(
+            tree["children"][0],
+            json!({"css": {"grid-row": "2 / span 1", "grid-column": "1 / span 2", "justify-self": "center"}})
+        );
+    }
+}
@@ -41,30 +41,32 @@ pub fn transform_matrix_to_css(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Replace a single object's matrix "transform" field with CSS properties
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if this is a "transform" object with matrix fields
+    if let Some(transform_value) = map.get("transform") {
+        if let Some(transform_obj) = transform_value.as_object() {
+            // Check if it has matrix fields
+            if has_matrix_fields(transform_obj) {
+                // Extract matrix values
+                if let Some(css_transform) = extract_and_decompose_matrix(transform_obj) {
+                    // Replace the transform object
+                    map.insert("transform".to_string(), css_transform);
+                }
+            }
+        }
+    }
+}
+
 /// Recursively transform matrix transforms in a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if this is a "transform" object with matrix fields
-            if let Some(transform_value) = map.get("transform") {
-                if let Some(transform_obj) = transform_value.as_object() {
-                    // Check if it has matrix fields
-                    if has_matrix_fields(transform_obj) {
-                        // Extract matrix values
-                        if let Some(css_transform) = extract_and_decompose_matrix(transform_obj) {
-                            // Replace the transform object
-                            map.insert("transform".to_string(), css_transform);
-                        }
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
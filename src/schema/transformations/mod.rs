@@ -3,6 +3,8 @@
 /// This module contains various transformation passes that are applied to the
 /// JSON document after initial parsing and blob substitution:
 ///
+/// - `instance_expansion`: Expand component instances into the subtree of their main component
+/// - `absolute_bounds`: Add absolute bounding boxes, render bounds and positions relative to the top-level frame (when enabled)
 /// - `image_hash`: Convert image hash arrays to filename strings
 /// - `blobs_removal`: Remove the root-level blobs array from final output
 /// - `matrix_to_css`: Convert 2D affine transformation matrices to CSS positioning properties
@@ -35,7 +37,8 @@
 /// - `root_metadata_removal`: Remove root-level version and fileType fields
 /// - `guid_path_removal`: Remove internal Figma guidPath references
 /// - `user_facing_version_removal`: Remove Figma version strings
-/// - `style_id_removal`: Remove Figma shared style references
+/// - `style_resolution`: Resolve shared style references to style names and a root `styles` table
+/// - `style_id_removal`: Remove unresolved Figma shared style references
 /// - `export_settings_removal`: Remove asset export configurations
 /// - `plugin_data_removal`: Remove Figma plugin storage data
 /// - `rectangle_corner_radii_independent_removal`: Remove corner radii independent flag
@@ -51,12 +54,17 @@
 /// - `stack_sizing_properties_removal`: Remove Figma auto-layout sizing properties (stackCounterSizing, stackPrimarySizing)
 /// - `stack_align_items_removal`: Remove Figma auto-layout alignment properties (stackCounterAlignItems, stackPrimaryAlignItems)
 /// - `text_properties_simplification`: Simplify verbose letterSpacing/lineHeight structures to CSS-ready strings
+/// - `effects_to_css`: Replace effects with CSS box-shadow, text-shadow, filter and backdrop-filter values
+/// - `gradient_to_css`: Add CSS linear/radial/conic gradient strings to gradient paints
+/// - `text_runs`: Combine characterStyleIDs and styleOverrideTable into styled text runs
+/// - `layout`: Translate auto-layout properties to a flexbox or grid `css` block (when enabled)
 /// - `type_removal`: Remove type field from all nodes
 /// - `empty_paint_arrays_removal`: Remove empty fillPaints and strokePaints arrays
 /// - `overridden_symbol_id_removal`: Remove standalone overriddenSymbolID objects from arrays
 /// - `symbol_id_removal`: Remove symbolID objects containing only localID and/or sessionID
 /// - `visible_only_objects_removal`: Remove objects that only contain a visible property
 /// - `uniform_scale_factor_removal`: Remove default uniformScaleFactor values (1.0)
+pub mod absolute_bounds;
 pub mod background_properties_removal;
 pub mod blobs_removal;
 pub mod border_weights_removal;
@@ -74,18 +82,22 @@ pub mod document_properties_removal;
 pub mod empty_derived_text_data_removal;
 pub mod empty_paint_arrays_removal;
 pub mod edit_info_removal;
+pub mod effects_to_css;
 pub mod empty_font_postscript_removal;
 pub mod empty_objects_removal;
 pub mod enum_simplification;
 pub mod export_settings_removal;
 pub mod frame_properties_removal;
 pub mod geometry_removal;
+pub mod gradient_to_css;
 pub mod guid_path_removal;
 pub mod guid_removal;
 pub mod image_hash;
 pub mod image_metadata_removal;
+pub mod instance_expansion;
 pub mod internal_only_nodes_removal;
 pub mod invisible_paints_removal;
+pub mod layout;
 pub mod layout_aids_removal;
 pub mod matrix_to_css;
 pub mod overridden_symbol_id_removal;
@@ -101,18 +113,21 @@ pub mod stack_child_properties_removal;
 pub mod stack_sizing_properties_removal;
 pub mod stroke_properties_removal;
 pub mod style_id_removal;
+pub mod style_resolution;
 pub mod symbol_id_removal;
 pub mod text_glyphs_removal;
 pub mod text_layout_removal;
 pub mod text_line_defaults_removal;
 pub mod text_metadata_removal;
 pub mod text_properties_simplification;
+pub mod text_runs;
 pub mod type_removal;
 pub mod uniform_scale_factor_removal;
 pub mod user_facing_version_removal;
 pub mod visible_only_objects_removal;
 
 // Re-export commonly used functions
+pub use absolute_bounds::add_absolute_bounds;
 pub use background_properties_removal::remove_background_properties;
 pub use blobs_removal::remove_root_blobs;
 pub use border_weights_removal::remove_border_weights;
@@ -130,18 +145,22 @@ pub use document_properties_removal::remove_document_properties;
 pub use empty_derived_text_data_removal::remove_empty_derived_text_data;
 pub use empty_paint_arrays_removal::remove_empty_paint_arrays;
 pub use edit_info_removal::remove_edit_info_fields;
+pub use effects_to_css::transform_effects_to_css;
 pub use empty_font_postscript_removal::remove_empty_font_postscript;
 pub use empty_objects_removal::remove_empty_objects;
 pub use enum_simplification::simplify_enums;
 pub use export_settings_removal::remove_export_settings;
 pub use frame_properties_removal::remove_frame_properties;
 pub use geometry_removal::remove_geometry_fields;
+pub use gradient_to_css::transform_gradients_to_css;
 pub use guid_path_removal::remove_guid_paths;
 pub use guid_removal::remove_guid_fields;
 pub use image_hash::transform_image_hashes;
 pub use image_metadata_removal::remove_image_metadata_fields;
+pub use instance_expansion::expand_instances;
 pub use internal_only_nodes_removal::remove_internal_only_nodes;
 pub use invisible_paints_removal::remove_invisible_paints;
+pub use layout::transform_layout_to_css;
 pub use layout_aids_removal::remove_layout_aids;
 pub use matrix_to_css::transform_matrix_to_css;
 pub use overridden_symbol_id_removal::remove_overridden_symbol_id;
@@ -157,12 +176,14 @@ pub use stack_child_properties_removal::remove_stack_child_properties;
 pub use stack_sizing_properties_removal::remove_stack_sizing_properties;
 pub use stroke_properties_removal::remove_stroke_properties;
 pub use style_id_removal::remove_style_ids;
+pub use style_resolution::resolve_style_references;
 pub use symbol_id_removal::remove_symbol_id_fields;
 pub use text_glyphs_removal::remove_text_glyphs;
 pub use text_layout_removal::remove_text_layout_fields;
 pub use text_line_defaults_removal::remove_default_text_line_properties;
 pub use text_metadata_removal::remove_text_metadata_fields;
 pub use text_properties_simplification::simplify_text_properties;
+pub use text_runs::build_text_runs;
 pub use type_removal::remove_type;
 pub use uniform_scale_factor_removal::remove_default_uniform_scale_factor;
 pub use user_facing_version_removal::remove_user_facing_versions;
@@ -73,29 +73,31 @@ fn is_standalone_overridden_symbol_id(obj: &serde_json::Map<String, JsonValue>)
     false
 }
 
+/// Drop standalone overriddenSymbolID objects from a single array
+pub(crate) fn enter_array(arr: &mut Vec<JsonValue>) {
+    arr.retain(|element| {
+        if let Some(obj) = element.as_object() {
+            // Keep element if it's NOT a standalone overriddenSymbolID
+            !is_standalone_overridden_symbol_id(obj)
+        } else {
+            // Keep non-object values
+            true
+        }
+    });
+}
+
 /// Recursively remove standalone overriddenSymbolID objects from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
             // Recurse into all values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
             // Filter out standalone overriddenSymbolID objects
-            arr.retain(|element| {
-                if let Some(obj) = element.as_object() {
-                    // Keep element if it's NOT a standalone overriddenSymbolID
-                    !is_standalone_overridden_symbol_id(obj)
-                } else {
-                    // Keep non-object values
-                    true
-                }
-            });
+            enter_array(arr);
 
             // Then recurse into remaining array elements
             for val in arr.iter_mut() {
@@ -33,19 +33,21 @@ pub fn remove_rectangle_corner_radii_independent(tree: &mut JsonValue) -> Result
     transform_recursive(tree)
 }
 
+/// Remove rectangleCornerRadiiIndependent from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove the "rectangleCornerRadiiIndependent" field if it exists
+    map.remove("rectangleCornerRadiiIndependent");
+}
+
 /// Recursively remove rectangleCornerRadiiIndependent fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove the "rectangleCornerRadiiIndependent" field if it exists
-            map.remove("rectangleCornerRadiiIndependent");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -44,23 +44,25 @@ pub fn remove_redundant_corner_radii(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove per-corner radii from a single object that has cornerRadius
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Only remove individual corner radii if cornerRadius exists
+    if map.contains_key("cornerRadius") {
+        map.remove("rectangleTopLeftCornerRadius");
+        map.remove("rectangleTopRightCornerRadius");
+        map.remove("rectangleBottomLeftCornerRadius");
+        map.remove("rectangleBottomRightCornerRadius");
+    }
+}
+
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Only remove individual corner radii if cornerRadius exists
-            if map.contains_key("cornerRadius") {
-                map.remove("rectangleTopLeftCornerRadius");
-                map.remove("rectangleTopRightCornerRadius");
-                map.remove("rectangleBottomLeftCornerRadius");
-                map.remove("rectangleBottomRightCornerRadius");
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
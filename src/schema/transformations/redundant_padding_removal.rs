@@ -38,25 +38,27 @@ pub fn remove_redundant_padding(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove redundant padding fields from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove stackPaddingRight if stackHorizontalPadding exists
+    if map.contains_key("stackHorizontalPadding") {
+        map.remove("stackPaddingRight");
+    }
+
+    // Remove stackPaddingBottom if stackVerticalPadding exists
+    if map.contains_key("stackVerticalPadding") {
+        map.remove("stackPaddingBottom");
+    }
+}
+
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove stackPaddingRight if stackHorizontalPadding exists
-            if map.contains_key("stackHorizontalPadding") {
-                map.remove("stackPaddingRight");
-            }
-
-            // Remove stackPaddingBottom if stackVerticalPadding exists
-            if map.contains_key("stackVerticalPadding") {
-                map.remove("stackPaddingBottom");
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -32,12 +32,17 @@ use serde_json::Value as JsonValue;
 /// ```
 pub fn remove_root_metadata(json: &mut JsonValue) -> Result<()> {
     if let Some(obj) = json.as_object_mut() {
-        obj.remove("version");
-        obj.remove("fileType");
+        enter_object(obj);
     }
     Ok(())
 }
 
+/// Remove version and fileType from the root object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    map.remove("version");
+    map.remove("fileType");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,21 +42,23 @@ pub fn remove_stroke_properties(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove stroke properties from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove stroke property fields if they exist
+    map.remove("strokeAlign");
+    map.remove("strokeJoin");
+    map.remove("strokeWeight");
+}
+
 /// Recursively remove stroke property fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove stroke property fields if they exist
-            map.remove("strokeAlign");
-            map.remove("strokeJoin");
-            map.remove("strokeWeight");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -1,7 +1,7 @@
 use crate::error::Result;
 use serde_json::Value as JsonValue;
 
-/// Remove style ID reference fields from all objects in the JSON tree
+/// Remove unresolved style ID reference fields from all objects in the JSON tree
 ///
 /// Recursively traverses the JSON tree and removes Figma shared style references:
 /// - "styleIdForFill" - Reference to fill paint style
@@ -10,7 +10,8 @@ use serde_json::Value as JsonValue;
 ///
 /// These fields reference Figma's shared style library. The actual style values
 /// are already inlined in the node properties, so these references are not needed
-/// for HTML/CSS rendering.
+/// for HTML/CSS rendering. References already replaced by a style name (a
+/// string) by `style_resolution` are kept.
 ///
 /// # Arguments
 /// * `tree` - The JSON tree to modify (usually the document root)
@@ -41,21 +42,27 @@ pub fn remove_style_ids(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Style ID reference fields removed when they are not resolved to a style name
+const STYLE_ID_FIELDS: &[&str] = &["styleIdForFill", "styleIdForText", "styleIdForStrokeFill"];
+
+/// Remove unresolved style ID references from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    for field in STYLE_ID_FIELDS {
+        if map.get(*field).is_some_and(|value| !value.is_string()) {
+            map.remove(*field);
+        }
+    }
+}
+
 /// Recursively remove style ID fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove all style ID fields if they exist
-            map.remove("styleIdForFill");
-            map.remove("styleIdForText");
-            map.remove("styleIdForStrokeFill");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -216,6 +223,34 @@ mod tests {
             .is_none());
     }
 
+    #[test]
+    fn test_keep_resolved_style_names() {
+        let mut tree = json!({
+            "name": "Card",
+            "styleIdForFill": "Brand/Primary",
+            "styleIdForText": {"assetRef": {"key": "a", "version": "1:1"}}
+        });
+
+        remove_style_ids(&mut tree).unwrap();
+
+        assert_eq!(tree["styleIdForFill"].as_str(), Some("Brand/Primary"));
+        assert!(tree.get("styleIdForText").is_none());
+    }
+
+    #[test]
+    fn test_keep_other_style_ids() {
+        let mut tree = json!({
+            "name": "Card",
+            "styleIdForEffect": {"assetRef": {"key": "a", "version": "1:1"}},
+            "styleIdForGrid": {"guid": {"sessionID": 1, "localID": 2}}
+        });
+
+        remove_style_ids(&mut tree).unwrap();
+
+        assert_eq!(tree["styleIdForEffect"]["assetRef"]["key"], json!("a"));
+        assert_eq!(tree["styleIdForGrid"]["guid"]["localID"], json!(2));
+    }
+
     #[test]
     fn test_remove_style_ids_missing() {
         let mut tree = json!({
@@ -0,0 +1,551 @@
+use crate::error::Result;
+use crate::schema::tree::{enum_str, guid_key};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Properties copied from a style definition node, by style type
+const STYLE_PROPERTIES: &[(&str, &[&str])] = &[
+    ("FILL", &["fillPaints"]),
+    (
+        "TEXT",
+        &[
+            "fontName",
+            "fontSize",
+            "lineHeight",
+            "letterSpacing",
+            "textCase",
+            "textDecoration",
+            "paragraphSpacing",
+            "paragraphIndent",
+        ],
+    ),
+    ("EFFECT", &["effects"]),
+    ("GRID", &["layoutGrids"]),
+];
+
+/// Resolve shared style references to style names
+///
+/// Figma stores shared styles (paint, text, effect and grid styles) as nodes
+/// with a `styleType`, usually on the internal-only canvas. Nodes reference
+/// them through `styleIdForFill`, `styleIdForStrokeFill`, `styleIdForText`,
+/// `styleIdForEffect` and `styleIdForGrid`, either by `guid` (local styles) or
+/// by `assetRef` key and version (library styles).
+///
+/// This pass collects the style definition nodes into a root-level `styles`
+/// table keyed by style name, and replaces every reference it can resolve with
+/// the key of the style. Unresolved references are left untouched.
+///
+/// A name shared by styles of different types (a FILL and a TEXT style both
+/// named "Heading") is suffixed with the style type in the keys of all of
+/// them (`Heading (FILL)`, `Heading (TEXT)`), whose definitions keep the
+/// plain `name`. Several styles of the same name and type (a local style and
+/// a library style both named "Brand/Primary") are further suffixed with
+/// their asset key, or their `sessionID:localID` GUID for local styles
+/// (`Brand/Primary (1:5)`, `Brand/Primary (k1)`). Versions of a library
+/// style sharing an asset key are kept apart and suffixed with
+/// `key@version` (`Brand/Primary (k1@1:2)`).
+///
+/// References are resolved by `guid` first, then by `assetRef`. An `assetRef`
+/// without a known version resolves to the highest version of its key.
+///
+/// # Arguments
+/// * `tree` - The whole conversion output (`styles` is added next to `document`)
+///
+/// # Returns
+/// * `Ok(())` - Successfully resolved style references
+///
+/// # Examples
+/// ```
+/// use fig2json::schema::resolve_style_references;
+/// use serde_json::json;
+///
+/// let mut output = json!({"document": {"children": [
+///     {"name": "Button", "styleIdForFill": {"assetRef": {"key": "k1", "version": "1:2"}}},
+///     {
+///         "name": "Brand/Primary",
+///         "styleType": "FILL",
+///         "key": "k1",
+///         "fillPaints": [{"type": "SOLID", "color": "#0a84ff"}]
+///     }
+/// ]}});
+/// resolve_style_references(&mut output).unwrap();
+///
+/// assert_eq!(output["document"]["children"][0]["styleIdForFill"], "Brand/Primary");
+/// assert_eq!(output["styles"]["Brand/Primary"]["styleType"], "FILL");
+/// ```
+pub fn resolve_style_references(tree: &mut JsonValue) -> Result<()> {
+    let mut styles = StyleIndex::default();
+    styles.collect(tree);
+    if styles.definitions.is_empty() {
+        return Ok(());
+    }
+
+    replace_references(tree, &styles);
+    if let Some(root) = tree.as_object_mut() {
+        root.insert("styles".to_string(), JsonValue::Object(styles.table()));
+    }
+    Ok(())
+}
+
+/// Name, type, asset key (or GUID for local styles) and version of a style
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StyleId {
+    name: String,
+    style_type: String,
+    identity: String,
+    version: Option<String>,
+}
+
+/// Style definitions and the lookup tables of their names
+#[derive(Default)]
+struct StyleIndex {
+    /// Definitions by style name and type
+    definitions: HashMap<StyleId, Map<String, JsonValue>>,
+    /// Number of style types using each name
+    type_counts: HashMap<String, usize>,
+    /// Number of styles of each name and type
+    style_counts: HashMap<(String, String), usize>,
+    /// Number of versions of each name, type and asset key
+    version_counts: HashMap<(String, String, String), usize>,
+    /// Styles by `sessionID:localID`
+    by_guid: HashMap<String, StyleId>,
+    /// Styles by `key@version`
+    by_version: HashMap<String, StyleId>,
+    /// Highest version of each asset key
+    latest: HashMap<String, StyleId>,
+}
+
+impl StyleIndex {
+    /// Collect the style definition nodes of a JSON value
+    fn collect(&mut self, value: &JsonValue) {
+        match value {
+            JsonValue::Object(map) => {
+                self.add(map);
+                for val in map.values() {
+                    self.collect(val);
+                }
+            }
+            JsonValue::Array(arr) => {
+                for val in arr {
+                    self.collect(val);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Register a node if it is a named style definition
+    fn add(&mut self, map: &Map<String, JsonValue>) {
+        let Some(style_type) = map.get("styleType").and_then(enum_str) else {
+            return;
+        };
+        let Some(name) = map.get("name").and_then(|n| n.as_str()) else {
+            return;
+        };
+
+        let guid = map.get("guid").and_then(guid_key);
+        let key = map.get("key").and_then(|k| k.as_str());
+        let version = key.and(map.get("version").and_then(|v| v.as_str()));
+        let id = StyleId {
+            name: name.to_string(),
+            style_type: style_type.to_string(),
+            identity: key
+                .map(str::to_string)
+                .or_else(|| guid.clone())
+                .unwrap_or_default(),
+            version: version.map(str::to_string),
+        };
+
+        if let Some(guid) = guid {
+            self.by_guid.insert(guid, id.clone());
+        }
+        if let Some(key) = key {
+            if let Some(version) = version {
+                self.by_version
+                    .insert(format!("{}@{}", key, version), id.clone());
+            }
+            let newer = self
+                .latest
+                .get(key)
+                .is_none_or(|latest| is_newer(version, latest.version.as_deref()));
+            if newer {
+                self.latest.insert(key.to_string(), id.clone());
+            }
+        }
+
+        if !self.definitions.contains_key(&id) {
+            let mut definition = Map::new();
+            definition.insert("styleType".to_string(), JsonValue::from(style_type));
+            let properties = STYLE_PROPERTIES
+                .iter()
+                .find(|(kind, _)| *kind == style_type)
+                .map_or(&[][..], |(_, properties)| properties);
+            for property in properties {
+                if let Some(value) = map.get(*property) {
+                    definition.insert(property.to_string(), value.clone());
+                }
+            }
+            let count = self
+                .style_counts
+                .entry((name.to_string(), style_type.to_string()))
+                .or_default();
+            if *count == 0 {
+                *self.type_counts.entry(name.to_string()).or_default() += 1;
+            }
+            *count += 1;
+            *self
+                .version_counts
+                .entry((id.name.clone(), id.style_type.clone(), id.identity.clone()))
+                .or_default() += 1;
+            self.definitions.insert(id, definition);
+        }
+    }
+
+    /// Key of a style in the `styles` table
+    fn key(&self, id: &StyleId) -> String {
+        let mut suffixes = Vec::new();
+        if self.type_counts.get(&id.name).copied().unwrap_or(0) > 1 {
+            suffixes.push(id.style_type.clone());
+        }
+        let same_type = (id.name.clone(), id.style_type.clone());
+        if self.style_counts.get(&same_type).copied().unwrap_or(0) > 1 {
+            let same_key = (id.name.clone(), id.style_type.clone(), id.identity.clone());
+            match &id.version {
+                Some(version) if self.version_counts.get(&same_key).copied().unwrap_or(0) > 1 => {
+                    suffixes.push(format!("{}@{}", id.identity, version))
+                }
+                _ => suffixes.push(id.identity.clone()),
+            }
+        }
+        if suffixes.is_empty() {
+            id.name.clone()
+        } else {
+            format!("{} ({})", id.name, suffixes.join(", "))
+        }
+    }
+
+    /// Key of the style a reference points to
+    fn resolve(&self, reference: &JsonValue) -> Option<String> {
+        let by_guid = reference
+            .get("guid")
+            .and_then(guid_key)
+            .and_then(|guid| self.by_guid.get(&guid));
+        let id = match by_guid {
+            Some(id) => id,
+            None => {
+                let asset = reference.get("assetRef")?;
+                let key = asset.get("key")?.as_str()?;
+                asset
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .and_then(|version| self.by_version.get(&format!("{}@{}", key, version)))
+                    .or_else(|| self.latest.get(key))?
+            }
+        };
+        Some(self.key(id))
+    }
+
+    /// The `styles` table, with the plain name of suffixed styles
+    fn table(&self) -> Map<String, JsonValue> {
+        self.definitions
+            .iter()
+            .map(|(id, definition)| {
+                let key = self.key(id);
+                let mut definition = definition.clone();
+                if key != id.name {
+                    definition.insert("name".to_string(), JsonValue::String(id.name.clone()));
+                }
+                (key, JsonValue::Object(definition))
+            })
+            .collect()
+    }
+}
+
+/// Check if `version` is newer than `current`
+///
+/// Versions are compared as `:`-separated numbers (`3:4` > `1:12`), falling
+/// back to string order when either is not numeric. A missing version is the
+/// oldest.
+fn is_newer(version: Option<&str>, current: Option<&str>) -> bool {
+    let numbers = |v: &str| {
+        v.split(':')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>()
+    };
+    match (version, current) {
+        (Some(version), Some(current)) => match (numbers(version), numbers(current)) {
+            (Some(version), Some(current)) => version > current,
+            _ => version > current,
+        },
+        (version, current) => version.is_some() && current.is_none(),
+    }
+}
+
+/// Replace resolvable `styleIdFor*` references with style names
+fn replace_references(value: &mut JsonValue, styles: &StyleIndex) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, val) in map.iter_mut() {
+                if key.starts_with("styleIdFor") {
+                    if let Some(key) = styles.resolve(val) {
+                        *val = JsonValue::String(key);
+                        continue;
+                    }
+                }
+                replace_references(val, styles);
+            }
+        }
+        JsonValue::Array(arr) => {
+            for val in arr.iter_mut() {
+                replace_references(val, styles);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn output() -> JsonValue {
+        json!({
+            "document": {
+                "children": [
+                    {
+                        "name": "Page",
+                        "children": [
+                            {
+                                "name": "Title",
+                                "styleIdForText": {"guid": {"sessionID": 1, "localID": 20}},
+                                "styleIdForFill": {"assetRef": {"key": "fill-key", "version": "3:4"}},
+                                "styleIdForEffect": {"assetRef": {"key": "missing", "version": "1:1"}}
+                            }
+                        ]
+                    },
+                    {
+                        "name": "Internal Only Canvas",
+                        "internalOnly": true,
+                        "children": [
+                            {
+                                "name": "Heading/H1",
+                                "guid": {"sessionID": 1, "localID": 20},
+                                "styleType": {"__enum__": "StyleType", "value": "TEXT"},
+                                "fontName": {"family": "Inter", "style": "Bold"},
+                                "fontSize": 32.0,
+                                "transform": {"x": 0.0, "y": 0.0}
+                            },
+                            {
+                                "name": "Brand/Old",
+                                "styleType": "FILL",
+                                "key": "fill-key",
+                                "version": "1:2",
+                                "fillPaints": [{"type": "SOLID", "color": "#000000"}]
+                            },
+                            {
+                                "name": "Brand/Primary",
+                                "styleType": "FILL",
+                                "key": "fill-key",
+                                "version": "3:4",
+                                "fillPaints": [{"type": "SOLID", "color": "#0a84ff"}]
+                            }
+                        ]
+                    }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn test_resolve_style_references() {
+        let mut output = output();
+        resolve_style_references(&mut output).unwrap();
+
+        let title = &output["document"]["children"][0]["children"][0];
+        assert_eq!(title["styleIdForText"], json!("Heading/H1"));
+        assert_eq!(title["styleIdForFill"], json!("Brand/Primary"));
+        // Unresolved references are kept as is
+        assert_eq!(
+            title["styleIdForEffect"]["assetRef"]["key"],
+            json!("missing")
+        );
+    }
+
+    #[test]
+    fn test_styles_table() {
+        let mut output = output();
+        resolve_style_references(&mut output).unwrap();
+
+        assert_eq!(
+            output["styles"]["Heading/H1"],
+            json!({"styleType": "TEXT", "fontName": {"family": "Inter", "style": "Bold"}, "fontSize": 32.0})
+        );
+        assert_eq!(
+            output["styles"]["Brand/Primary"]["fillPaints"][0]["color"],
+            json!("#0a84ff")
+        );
+        let names: Vec<&String> = output["styles"].as_object().unwrap().keys().collect();
+        assert_eq!(names, ["Brand/Old", "Brand/Primary", "Heading/H1"]);
+    }
+
+    #[test]
+    fn test_same_name_different_types() {
+        let mut output = json!({"document": {"children": [
+            {
+                "name": "Title",
+                "styleIdForText": {"guid": {"sessionID": 1, "localID": 2}},
+                "styleIdForFill": {"guid": {"sessionID": 1, "localID": 1}}
+            },
+            {
+                "name": "Heading",
+                "guid": {"sessionID": 1, "localID": 1},
+                "styleType": "FILL",
+                "fillPaints": [{"type": "SOLID", "color": "#111111"}]
+            },
+            {
+                "name": "Heading",
+                "guid": {"sessionID": 1, "localID": 2},
+                "styleType": "TEXT",
+                "fontSize": 24.0
+            }
+        ]}});
+
+        resolve_style_references(&mut output).unwrap();
+
+        let title = &output["document"]["children"][0];
+        assert_eq!(title["styleIdForFill"], json!("Heading (FILL)"));
+        assert_eq!(title["styleIdForText"], json!("Heading (TEXT)"));
+        assert_eq!(
+            output["styles"]["Heading (TEXT)"],
+            json!({"styleType": "TEXT", "name": "Heading", "fontSize": 24.0})
+        );
+        assert_eq!(output["styles"]["Heading (FILL)"]["styleType"], json!("FILL"));
+        assert!(output["styles"].get("Heading").is_none());
+    }
+
+    #[test]
+    fn test_same_name_same_type() {
+        let mut output = json!({"document": {"children": [
+            {
+                "name": "Button",
+                "styleIdForFill": {"guid": {"sessionID": 1, "localID": 5}},
+                "styleIdForStrokeFill": {"assetRef": {"key": "k1", "version": "1:2"}},
+                "styleIdForText": {"guid": {"sessionID": 1, "localID": 6}}
+            },
+            {
+                "name": "Brand/Primary",
+                "guid": {"sessionID": 1, "localID": 5},
+                "styleType": "FILL",
+                "fillPaints": [{"type": "SOLID", "color": "#111111"}]
+            },
+            {
+                "name": "Brand/Primary",
+                "styleType": "FILL",
+                "key": "k1",
+                "version": "1:2",
+                "fillPaints": [{"type": "SOLID", "color": "#0a84ff"}]
+            },
+            {
+                "name": "Brand/Primary",
+                "guid": {"sessionID": 1, "localID": 6},
+                "styleType": "TEXT",
+                "fontSize": 16.0
+            }
+        ]}});
+
+        resolve_style_references(&mut output).unwrap();
+
+        let button = &output["document"]["children"][0];
+        assert_eq!(button["styleIdForFill"], json!("Brand/Primary (FILL, 1:5)"));
+        assert_eq!(button["styleIdForStrokeFill"], json!("Brand/Primary (FILL, k1)"));
+        assert_eq!(button["styleIdForText"], json!("Brand/Primary (TEXT)"));
+        assert_eq!(
+            output["styles"]["Brand/Primary (FILL, k1)"]["fillPaints"][0]["color"],
+            json!("#0a84ff")
+        );
+        assert_eq!(
+            output["styles"]["Brand/Primary (FILL, 1:5)"]["name"],
+            json!("Brand/Primary")
+        );
+        assert_eq!(output["styles"].as_object().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_no_styles() {
+        let mut output = json!({"document": {"name": "Frame", "styleIdForFill": {"guid": {"sessionID": 1, "localID": 2}}}});
+        let expected = output.clone();
+
+        resolve_style_references(&mut output).unwrap();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_unknown_guid_falls_back_to_asset_ref() {
+        let mut output = json!({"document": {"children": [
+            {
+                "name": "Button",
+                "styleIdForFill": {
+                    "guid": {"sessionID": 9, "localID": 9},
+                    "assetRef": {"key": "k1", "version": "1:2"}
+                }
+            },
+            {
+                "name": "Brand/Primary",
+                "styleType": "FILL",
+                "key": "k1",
+                "version": "1:2",
+                "fillPaints": [{"type": "SOLID", "color": "#0a84ff"}]
+            }
+        ]}});
+
+        resolve_style_references(&mut output).unwrap();
+
+        assert_eq!(output["document"]["children"][0]["styleIdForFill"], json!("Brand/Primary"));
+    }
+
+    #[test]
+    fn test_versions_of_the_same_key() {
+        let mut output = json!({"document": {"children": [
+            {
+                "name": "Button",
+                "styleIdForFill": {"assetRef": {"key": "k1", "version": "2:1"}},
+                "styleIdForStrokeFill": {"assetRef": {"key": "k1"}}
+            },
+            {
+                "name": "Brand/Primary",
+                "styleType": "FILL",
+                "key": "k1",
+                "version": "2:1",
+                "fillPaints": [{"type": "SOLID", "color": "#111111"}]
+            },
+            {
+                "name": "Brand/Primary",
+                "styleType": "FILL",
+                "key": "k1",
+                "version": "10:3",
+                "fillPaints": [{"type": "SOLID", "color": "#0a84ff"}]
+            },
+            {
+                "name": "Brand/Primary",
+                "styleType": "FILL",
+                "key": "k1",
+                "version": "3:7",
+                "fillPaints": [{"type": "SOLID", "color": "#222222"}]
+            }
+        ]}});
+
+        resolve_style_references(&mut output).unwrap();
+
+        let button = &output["document"]["children"][0];
+        assert_eq!(button["styleIdForFill"], json!("Brand/Primary (k1@2:1)"));
+        // Key-only references resolve to the highest version
+        assert_eq!(button["styleIdForStrokeFill"], json!("Brand/Primary (k1@10:3)"));
+        assert_eq!(
+            output["styles"]["Brand/Primary (k1@10:3)"]["fillPaints"][0]["color"],
+            json!("#0a84ff")
+        );
+        assert_eq!(output["styles"].as_object().unwrap().len(), 3);
+    }
+}
@@ -33,22 +33,24 @@ pub fn remove_symbol_id_fields(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove a symbolID containing only localID/sessionID from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if this object has a "symbolID" field that should be removed
+    if let Some(symbol_id_value) = map.get("symbolID") {
+        if should_remove_symbol_id(symbol_id_value) {
+            map.remove("symbolID");
+        }
+    }
+}
+
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if this object has a "symbolID" field that should be removed
-            if let Some(symbol_id_value) = map.get("symbolID") {
-                if should_remove_symbol_id(symbol_id_value) {
-                    map.remove("symbolID");
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
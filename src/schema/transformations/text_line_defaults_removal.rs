@@ -51,42 +51,44 @@ pub fn remove_default_text_line_properties(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove default line properties from a single object's lines array
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if this object has a "lines" array
+    if let Some(lines_value) = map.get_mut("lines") {
+        if let Some(lines_array) = lines_value.as_array_mut() {
+            // Process each line object in the array
+            for line in lines_array.iter_mut() {
+                if let Some(line_obj) = line.as_object_mut() {
+                    remove_default_line_fields(line_obj);
+                }
+            }
+
+            // Check if all lines are now empty objects
+            // Only remove the lines array if it has elements and all of them are empty
+            let all_empty = !lines_array.is_empty()
+                && lines_array.iter().all(|line| {
+                    line.as_object()
+                        .map(|obj| obj.is_empty())
+                        .unwrap_or(false)
+                });
+
+            // If all lines are empty, remove the entire "lines" array
+            if all_empty {
+                map.remove("lines");
+            }
+        }
+    }
+}
+
 /// Recursively remove default text line properties from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if this object has a "lines" array
-            if let Some(lines_value) = map.get_mut("lines") {
-                if let Some(lines_array) = lines_value.as_array_mut() {
-                    // Process each line object in the array
-                    for line in lines_array.iter_mut() {
-                        if let Some(line_obj) = line.as_object_mut() {
-                            remove_default_line_fields(line_obj);
-                        }
-                    }
-
-                    // Check if all lines are now empty objects
-                    // Only remove the lines array if it has elements and all of them are empty
-                    let all_empty = !lines_array.is_empty()
-                        && lines_array.iter().all(|line| {
-                            line.as_object()
-                                .map(|obj| obj.is_empty())
-                                .unwrap_or(false)
-                        });
-
-                    // If all lines are empty, remove the entire "lines" array
-                    if all_empty {
-                        map.remove("lines");
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
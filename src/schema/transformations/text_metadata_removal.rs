@@ -48,33 +48,35 @@ pub fn remove_text_metadata_fields(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove text metadata fields from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Remove all text metadata fields if they exist
+    map.remove("textBidiVersion");
+    map.remove("textExplicitLayoutVersion");
+    map.remove("textUserLayoutVersion");
+    map.remove("textDecorationSkipInk");
+    map.remove("fontVariantCommonLigatures");
+    map.remove("fontVariantContextualLigatures");
+    map.remove("fontVariantNumericFigure");
+    map.remove("fontVariantNumericSpacing");
+    map.remove("fontVariations");
+    map.remove("fontVersion");
+    map.remove("emojiImageSet");
+    map.remove("autoRename");
+    map.remove("textTracking");
+    map.remove("textAlignVertical");
+    map.remove("textAutoResize");
+}
+
 /// Recursively remove text metadata fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Remove all text metadata fields if they exist
-            map.remove("textBidiVersion");
-            map.remove("textExplicitLayoutVersion");
-            map.remove("textUserLayoutVersion");
-            map.remove("textDecorationSkipInk");
-            map.remove("fontVariantCommonLigatures");
-            map.remove("fontVariantContextualLigatures");
-            map.remove("fontVariantNumericFigure");
-            map.remove("fontVariantNumericSpacing");
-            map.remove("fontVariations");
-            map.remove("fontVersion");
-            map.remove("emojiImageSet");
-            map.remove("autoRename");
-            map.remove("textTracking");
-            map.remove("textAlignVertical");
-            map.remove("textAutoResize");
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -43,33 +43,29 @@ pub fn simplify_text_properties(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Convert letterSpacing and lineHeight objects of a single object to CSS strings
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    for key in ["letterSpacing", "lineHeight"] {
+        let css_value = map
+            .get(key)
+            .and_then(|val| val.as_object())
+            .filter(|obj| is_text_property_object(obj))
+            .and_then(convert_to_css_string);
+
+        if let Some(css_value) = css_value {
+            map.insert(key.to_string(), JsonValue::String(css_value));
+        }
+    }
+}
+
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Collect keys to avoid borrow checker issues
-            let keys: Vec<String> = map.keys().cloned().collect();
-
-            for key in keys {
-                // Check if this is a letterSpacing or lineHeight property
-                if key == "letterSpacing" || key == "lineHeight" {
-                    if let Some(val) = map.get(&key) {
-                        // Check if this value is a units/value object
-                        if let Some(obj) = val.as_object() {
-                            if is_text_property_object(obj) {
-                                // Convert to CSS string
-                                if let Some(css_value) = convert_to_css_string(obj) {
-                                    map.insert(key.clone(), JsonValue::String(css_value));
-                                    continue; // Skip recursion since we replaced the object
-                                }
-                            }
-                        }
-                    }
-                }
+            enter_object(map);
 
-                // Recurse into the value if it wasn't replaced
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
@@ -0,0 +1,315 @@
+use crate::error::Result;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Style properties copied to each run, from the node or the style override
+const RUN_PROPERTIES: &[&str] = &[
+    "fontName",
+    "fontSize",
+    "fillPaints",
+    "textDecoration",
+    "hyperlink",
+    "letterSpacing",
+    "lineHeight",
+    "textCase",
+];
+
+/// Combine per-character text styles into an ordered array of runs
+///
+/// TEXT nodes with mixed styling store the style ID of each character in
+/// `textData.characterStyleIDs` (one per UTF-16 code unit, trailing zeros
+/// omitted) and the properties of each style in `textData.styleOverrideTable`,
+/// style 0 being the style of the node itself.
+///
+/// This pass replaces both fields with `textData.runs`: consecutive characters
+/// with the same style form a run, with its `text` and its complete style
+/// (`fontName`, `fontSize`, `fillPaints`, `textDecoration`, `hyperlink`,
+/// `letterSpacing`, `lineHeight`, `textCase`), the override properties taking
+/// precedence over the node's. Joining the run texts gives `characters`.
+///
+/// Texts whose characters all have the node style get no runs. The pass runs
+/// after `color_to_css` and `enum_simplification`, and before
+/// `geometry_removal`, which removes the `styleID` of the overrides. The text
+/// property and gradient passes that follow also apply to the runs.
+///
+/// # Arguments
+/// * `tree` - The JSON tree to modify (usually the document root)
+///
+/// # Returns
+/// * `Ok(())` - Successfully built the runs of all text nodes
+///
+/// # Examples
+/// ```
+/// use fig2json::schema::build_text_runs;
+/// use serde_json::json;
+///
+/// let mut tree = json!({
+///     "fontName": {"family": "Inter", "style": "Regular"},
+///     "fontSize": 16.0,
+///     "textData": {
+///         "characters": "Read the docs",
+///         "characterStyleIDs": [0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1],
+///         "styleOverrideTable": [{
+///             "styleID": 1,
+///             "textDecoration": "UNDERLINE",
+///             "hyperlink": {"url": "https://example.com/docs"}
+///         }]
+///     }
+/// });
+/// build_text_runs(&mut tree).unwrap();
+///
+/// let runs = &tree["textData"]["runs"];
+/// assert_eq!(runs[0]["text"], "Read the ");
+/// assert_eq!(runs[1]["text"], "docs");
+/// assert_eq!(runs[1]["fontSize"], 16.0);
+/// assert_eq!(runs[1]["hyperlink"]["url"], "https://example.com/docs");
+/// assert!(tree["textData"].get("styleOverrideTable").is_none());
+/// ```
+pub fn build_text_runs(tree: &mut JsonValue) -> Result<()> {
+    transform_recursive(tree)
+}
+
+/// Replace characterStyleIDs and styleOverrideTable of a single text node with runs
+pub(crate) fn enter_object(map: &mut Map<String, JsonValue>) {
+    let Some(text_data) = map.get("textData").and_then(|t| t.as_object()) else {
+        return;
+    };
+    let Some(style_ids) = text_data
+        .get("characterStyleIDs")
+        .and_then(|ids| ids.as_array())
+    else {
+        return;
+    };
+
+    let style_ids: Vec<u64> = style_ids
+        .iter()
+        .map(|id| id.as_u64().unwrap_or(0))
+        .collect();
+    let runs = build_runs(map, text_data, &style_ids);
+
+    if let Some(JsonValue::Object(text_data)) = map.get_mut("textData") {
+        text_data.remove("characterStyleIDs");
+        text_data.remove("styleOverrideTable");
+        if let Some(runs) = runs {
+            text_data.insert("runs".to_string(), JsonValue::Array(runs));
+        }
+    }
+}
+
+/// Runs of a text node, `None` when every character has the node style
+fn build_runs(
+    node: &Map<String, JsonValue>,
+    text_data: &Map<String, JsonValue>,
+    style_ids: &[u64],
+) -> Option<Vec<JsonValue>> {
+    let characters = text_data.get("characters")?.as_str()?;
+    let overrides: HashMap<u64, &Map<String, JsonValue>> = text_data
+        .get("styleOverrideTable")
+        .and_then(|t| t.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let entry = entry.as_object()?;
+            Some((entry.get("styleID")?.as_u64()?, entry))
+        })
+        .collect();
+
+    // Style ID of each character, by its first UTF-16 code unit
+    let mut index = 0;
+    let characters: Vec<(char, u64)> = characters
+        .chars()
+        .map(|c| {
+            let style_id = style_ids.get(index).copied().unwrap_or(0);
+            index += c.len_utf16();
+            (c, style_id)
+        })
+        .collect();
+
+    let base = style_of(node);
+    let mut styles: HashMap<u64, Map<String, JsonValue>> = HashMap::new();
+    for &(_, style_id) in &characters {
+        styles.entry(style_id).or_insert_with(|| {
+            let mut style = base.clone();
+            if let Some(entry) = overrides.get(&style_id) {
+                style.extend(style_of(entry));
+            }
+            style
+        });
+    }
+
+    let mut runs: Vec<(String, &Map<String, JsonValue>)> = Vec::new();
+    for (c, style_id) in characters {
+        let style = &styles[&style_id];
+        match runs.last_mut() {
+            Some((text, last)) if *last == style => text.push(c),
+            _ => runs.push((c.to_string(), style)),
+        }
+    }
+
+    if runs.is_empty() || (runs.len() == 1 && *runs[0].1 == base) {
+        return None;
+    }
+    Some(
+        runs.into_iter()
+            .map(|(text, style)| {
+                let mut run = Map::new();
+                run.insert("text".to_string(), JsonValue::String(text));
+                run.extend(style.clone());
+                JsonValue::Object(run)
+            })
+            .collect(),
+    )
+}
+
+/// Run properties present on a node or style override
+fn style_of(map: &Map<String, JsonValue>) -> Map<String, JsonValue> {
+    RUN_PROPERTIES
+        .iter()
+        .filter_map(|key| Some((key.to_string(), map.get(*key)?.clone())))
+        .collect()
+}
+
+/// Recursively build the text runs of a JSON value
+fn transform_recursive(value: &mut JsonValue) -> Result<()> {
+    match value {
+        JsonValue::Object(map) => {
+            enter_object(map);
+
+            // Recurse into all values
+            for val in map.values_mut() {
+                transform_recursive(val)?;
+            }
+        }
+        JsonValue::Array(arr) => {
+            // Recurse into array elements
+            for val in arr.iter_mut() {
+                transform_recursive(val)?;
+            }
+        }
+        _ => {
+            // Primitives - nothing to do
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn text_node(style_ids: JsonValue, table: JsonValue) -> JsonValue {
+        json!({
+            "type": "TEXT",
+            "fontName": {"family": "Inter", "style": "Regular"},
+            "fontSize": 14.0,
+            "fillPaints": [{"type": "SOLID", "color": "#111111"}],
+            "lineHeight": "20px",
+            "textData": {
+                "characters": "Sale 50% off",
+                "characterStyleIDs": style_ids,
+                "styleOverrideTable": table,
+                "lines": [{"lineType": "PLAIN"}]
+            }
+        })
+    }
+
+    #[test]
+    fn test_runs_merge_node_and_override_styles() {
+        let mut tree = text_node(
+            json!([0, 0, 0, 0, 0, 2, 2, 2, 0, 0, 0, 0]),
+            json!([{
+                "styleID": 2,
+                "fontName": {"family": "Inter", "style": "Bold"},
+                "fillPaints": [{"type": "SOLID", "color": "#e00000"}]
+            }]),
+        );
+
+        build_text_runs(&mut tree).unwrap();
+
+        assert_eq!(
+            tree["textData"],
+            json!({
+                "characters": "Sale 50% off",
+                "lines": [{"lineType": "PLAIN"}],
+                "runs": [
+                    {
+                        "text": "Sale ",
+                        "fontName": {"family": "Inter", "style": "Regular"},
+                        "fontSize": 14.0,
+                        "fillPaints": [{"type": "SOLID", "color": "#111111"}],
+                        "lineHeight": "20px"
+                    },
+                    {
+                        "text": "50%",
+                        "fontName": {"family": "Inter", "style": "Bold"},
+                        "fontSize": 14.0,
+                        "fillPaints": [{"type": "SOLID", "color": "#e00000"}],
+                        "lineHeight": "20px"
+                    },
+                    {
+                        "text": " off",
+                        "fontName": {"family": "Inter", "style": "Regular"},
+                        "fontSize": 14.0,
+                        "fillPaints": [{"type": "SOLID", "color": "#111111"}],
+                        "lineHeight": "20px"
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_trailing_characters_have_node_style() {
+        // Figma omits the trailing zeros of characterStyleIDs
+        let mut tree = text_node(
+            json!([1, 1, 1, 1]),
+            json!([{"styleID": 1, "fontSize": 20.0}]),
+        );
+
+        build_text_runs(&mut tree).unwrap();
+
+        let runs = tree["textData"]["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0]["text"], "Sale");
+        assert_eq!(runs[0]["fontSize"], 20.0);
+        assert_eq!(runs[1]["text"], " 50% off");
+        assert_eq!(runs[1]["fontSize"], 14.0);
+    }
+
+    #[test]
+    fn test_uniform_style_has_no_runs() {
+        // Style 3 only differed by properties that are not part of runs
+        let mut tree = text_node(
+            json!([0, 0, 3, 3]),
+            json!([{"styleID": 3, "fontVariantCommonLigatures": false}]),
+        );
+
+        build_text_runs(&mut tree).unwrap();
+
+        let text_data = tree["textData"].as_object().unwrap();
+        assert!(!text_data.contains_key("runs"));
+        assert!(!text_data.contains_key("characterStyleIDs"));
+        assert!(!text_data.contains_key("styleOverrideTable"));
+    }
+
+    #[test]
+    fn test_surrogate_pairs() {
+        let mut tree = json!({
+            "fontSize": 14.0,
+            "textData": {
+                "characters": "👍 ok",
+                // Two code units for the emoji
+                "characterStyleIDs": [0, 0, 0, 1, 1],
+                "styleOverrideTable": [{"styleID": 1, "textCase": "UPPER"}]
+            }
+        });
+
+        build_text_runs(&mut tree).unwrap();
+
+        assert_eq!(tree["textData"]["runs"][0]["text"], "👍 ");
+        assert_eq!(tree["textData"]["runs"][1]["text"], "ok");
+        assert_eq!(tree["textData"]["runs"][1]["textCase"], "UPPER");
+    }
+}
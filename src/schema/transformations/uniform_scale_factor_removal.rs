@@ -30,26 +30,28 @@ pub fn remove_default_uniform_scale_factor(tree: &mut JsonValue) -> Result<()> {
     transform_recursive(tree)
 }
 
+/// Remove a default uniformScaleFactor (1.0) from a single object
+pub(crate) fn enter_object(map: &mut serde_json::Map<String, JsonValue>) {
+    // Check if uniformScaleFactor exists and is 1.0
+    if let Some(scale_factor) = map.get("uniformScaleFactor") {
+        if let Some(num) = scale_factor.as_f64() {
+            // Remove if exactly 1.0
+            if (num - 1.0).abs() < f64::EPSILON {
+                map.remove("uniformScaleFactor");
+            }
+        }
+    }
+}
+
 /// Recursively remove default uniformScaleFactor fields from a JSON value
 fn transform_recursive(value: &mut JsonValue) -> Result<()> {
     match value {
         JsonValue::Object(map) => {
-            // Check if uniformScaleFactor exists and is 1.0
-            if let Some(scale_factor) = map.get("uniformScaleFactor") {
-                if let Some(num) = scale_factor.as_f64() {
-                    // Remove if exactly 1.0
-                    if (num - 1.0).abs() < f64::EPSILON {
-                        map.remove("uniformScaleFactor");
-                    }
-                }
-            }
+            enter_object(map);
 
             // Recurse into all remaining values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(val) = map.get_mut(&key) {
-                    transform_recursive(val)?;
-                }
+            for val in map.values_mut() {
+                transform_recursive(val)?;
             }
         }
         JsonValue::Array(arr) => {
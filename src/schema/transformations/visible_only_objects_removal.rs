@@ -37,20 +37,31 @@ pub fn remove_visible_only_objects(tree: &mut JsonValue) -> Result<()> {
     Ok(())
 }
 
+/// Remove the keys of a single object whose values are visible-only objects
+///
+/// Must run after the values themselves have been visited.
+pub(crate) fn leave_object(map: &mut serde_json::Map<String, JsonValue>) {
+    map.retain(|_, v| !is_visible_only_object(v));
+}
+
+/// Remove the visible-only objects from a single array
+///
+/// Must run after the elements themselves have been visited.
+pub(crate) fn leave_array(arr: &mut Vec<JsonValue>) {
+    arr.retain(|v| !is_visible_only_object(v));
+}
+
 /// Recursively remove visible-only objects from a JSON value
 fn transform_recursive(value: &mut JsonValue) {
     match value {
         JsonValue::Object(map) => {
             // First, recurse into all values
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in &keys {
-                if let Some(val) = map.get_mut(key) {
-                    transform_recursive(val);
-                }
+            for val in map.values_mut() {
+                transform_recursive(val);
             }
 
             // Then remove any keys whose values are visible-only objects
-            map.retain(|_, v| !is_visible_only_object(v));
+            leave_object(map);
         }
         JsonValue::Array(arr) => {
             // First, recurse into array elements
@@ -59,7 +70,7 @@ fn transform_recursive(value: &mut JsonValue) {
             }
 
             // Then filter out visible-only objects from the array
-            arr.retain(|v| !is_visible_only_object(v));
+            leave_array(arr);
         }
         _ => {
             // Primitives - nothing to do
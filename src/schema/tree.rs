@@ -1,5 +1,5 @@
 use crate::error::{FigError, Result};
-use serde_json::Value as JsonValue;
+use serde_json::{Map, Value as JsonValue};
 use std::collections::HashMap;
 
 /// Build a tree structure from flat nodeChanges array
@@ -95,7 +95,7 @@ fn build_node_tree(
 /// Format a GUID from a node's guid field
 ///
 /// Converts `{sessionID: X, localID: Y}` to string "X:Y"
-fn format_guid(node: &JsonValue) -> Result<String> {
+pub(crate) fn format_guid(node: &JsonValue) -> Result<String> {
     let guid_obj = node
         .get("guid")
         .and_then(|v| v.as_object())
@@ -114,6 +114,28 @@ fn format_guid(node: &JsonValue) -> Result<String> {
     Ok(format!("{}:{}", session_id, local_id))
 }
 
+/// Format a `{sessionID: X, localID: Y}` GUID value as "X:Y"
+///
+/// Unlike [`format_guid`], takes the GUID itself (of a node, a reference or
+/// an override path) and returns `None` when it is malformed.
+pub(crate) fn guid_key(guid: &JsonValue) -> Option<String> {
+    Some(format!(
+        "{}:{}",
+        guid.get("sessionID")?.as_u64()?,
+        guid.get("localID")?.as_u64()?
+    ))
+}
+
+/// String of a simplified enum or of a raw `{"__enum__", "value"}` object
+pub(crate) fn enum_str(value: &JsonValue) -> Option<&str> {
+    value.get("value").unwrap_or(value).as_str()
+}
+
+/// Type of a node, simplified or raw enum
+pub(crate) fn node_type(node: &Map<String, JsonValue>) -> Option<&str> {
+    node.get("type").and_then(enum_str)
+}
+
 /// Format a GUID from a parentIndex's guid field
 fn format_parent_guid(parent_index: &JsonValue) -> Result<String> {
     let guid_obj = parent_index
@@ -0,0 +1,536 @@
+//! Subtree selection
+//!
+//! A [`Selector`] narrows the raw conversion output down to a few pages or
+//! nodes before the transformation pipeline runs, so that the passes (and
+//! whoever reads the result) only see the parts of the file that matter.
+
+use crate::error::{FigError, Result};
+use crate::schema::tree::{enum_str, format_guid, guid_key, node_type};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::{HashMap, HashSet};
+
+/// Name of the canvas holding the definitions selected nodes depend on
+const DEFINITIONS_CANVAS: &str = "Selection Definitions";
+
+/// Criteria selecting the pages and nodes to convert
+///
+/// Pages are matched by exact name. Nodes are matched by GUID
+/// (`sessionID:localID`), name glob (`*` and `?` wildcards) and node type;
+/// a node must satisfy every kind of criteria given, and any value of each.
+/// An empty selector selects the whole document.
+#[derive(Debug, Clone, Default)]
+pub struct Selector {
+    /// Page names
+    pub pages: Vec<String>,
+    /// Node GUIDs, as `sessionID:localID`
+    pub nodes: Vec<String>,
+    /// Node name glob patterns
+    pub names: Vec<String>,
+    /// Node types (`FRAME`, `TEXT`, ...), case-insensitive
+    pub types: Vec<String>,
+}
+
+impl Selector {
+    /// Create an empty selector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the page with the given name
+    pub fn with_page(mut self, page: &str) -> Self {
+        self.pages.push(page.to_string());
+        self
+    }
+
+    /// Select the node with the given `sessionID:localID` GUID
+    pub fn with_node(mut self, guid: &str) -> Self {
+        self.nodes.push(guid.to_string());
+        self
+    }
+
+    /// Select the nodes whose name matches a glob pattern
+    pub fn with_name(mut self, pattern: &str) -> Self {
+        self.names.push(pattern.to_string());
+        self
+    }
+
+    /// Select the nodes of the given type
+    pub fn with_type(mut self, node_type: &str) -> Self {
+        self.types.push(node_type.to_string());
+        self
+    }
+
+    /// Whether the selector selects the whole document
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty() && !self.selects_nodes()
+    }
+
+    /// Whether the selector has node criteria (as opposed to whole pages)
+    fn selects_nodes(&self) -> bool {
+        !(self.nodes.is_empty() && self.names.is_empty() && self.types.is_empty())
+    }
+
+    fn matches_page(&self, page: &Map<String, JsonValue>) -> bool {
+        self.pages.is_empty()
+            || page
+                .get("name")
+                .and_then(|n| n.as_str())
+                .is_some_and(|name| self.pages.iter().any(|p| p == name))
+    }
+
+    fn matches_node(&self, node: &JsonValue) -> bool {
+        let guid_matches =
+            self.nodes.is_empty() || format_guid(node).is_ok_and(|guid| self.nodes.contains(&guid));
+        let name = node.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        let name_matches =
+            self.names.is_empty() || self.names.iter().any(|pattern| glob_match(pattern, name));
+        let type_matches = self.types.is_empty()
+            || node
+                .get("type")
+                .and_then(enum_str)
+                .is_some_and(|node_type| {
+                    self.types.iter().any(|t| t.eq_ignore_ascii_case(node_type))
+                });
+        guid_matches && name_matches && type_matches
+    }
+
+    /// Description of the criteria, for error messages
+    fn describe(&self) -> String {
+        let criteria = [
+            ("page", &self.pages),
+            ("node", &self.nodes),
+            ("name", &self.names),
+            ("type", &self.types),
+        ];
+        criteria
+            .iter()
+            .filter(|(_, values)| !values.is_empty())
+            .map(|(kind, values)| format!("{} {}", kind, values.join(",")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Keep only the selected subtrees of a raw conversion output
+///
+/// Runs on the output of [`convert_raw`](crate::convert_raw), before the
+/// transformation passes. Each selected page keeps, as children, the topmost
+/// nodes matching the selector (or all its children when only pages are
+/// selected); each of these nodes gets a `breadcrumbs` field with the names
+/// of the page and of its ancestors. Pages without matches are dropped.
+///
+/// Components used by the selected instances and the shared style
+/// definitions are kept on an internal-only canvas, so that instance
+/// expansion and style resolution still work; [`remove_definitions`] drops
+/// that canvas once the pipeline has run.
+///
+/// # Arguments
+/// * `output` - Raw conversion output (with a `document` field)
+/// * `selector` - Pages and nodes to keep
+///
+/// # Returns
+/// * `Ok(usize)` - Number of selected pages or nodes
+/// * `Err(FigError::EmptySelection)` - If nothing matches the selector
+///
+/// # Examples
+/// ```
+/// use fig2json::selector::{select, Selector};
+/// use serde_json::json;
+///
+/// let mut output = json!({"document": {"children": [
+///     {"name": "Home", "children": [
+///         {"name": "Hero", "children": [{"name": "Button/Primary"}]},
+///         {"name": "Footer"}
+///     ]},
+///     {"name": "About", "children": [{"name": "Button/Secondary"}]}
+/// ]}});
+/// let selector = Selector::new().with_page("Home").with_name("Button/*");
+///
+/// assert_eq!(select(&mut output, &selector).unwrap(), 1);
+/// let page = &output["document"]["children"][0];
+/// assert_eq!(page["children"][0]["name"], "Button/Primary");
+/// assert_eq!(page["children"][0]["breadcrumbs"], json!(["Home", "Hero"]));
+/// ```
+pub fn select(output: &mut JsonValue, selector: &Selector) -> Result<usize> {
+    if selector.is_empty() {
+        return Ok(0);
+    }
+    let Some(pages) = output
+        .get_mut("document")
+        .and_then(|d| d.get_mut("children"))
+        .and_then(|c| c.as_array_mut())
+    else {
+        return Err(FigError::EmptySelection(selector.describe()));
+    };
+
+    let definitions = Definitions::collect(pages);
+    let mut selected_pages = Vec::new();
+    let mut count = 0;
+    for page in pages.iter() {
+        let Some(page_map) = page.as_object() else {
+            continue;
+        };
+        if is_internal(page_map) || !selector.matches_page(page_map) {
+            continue;
+        }
+
+        if !selector.selects_nodes() {
+            selected_pages.push(page.clone());
+            count += 1;
+            continue;
+        }
+
+        let page_name = page_map.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        let mut matches = Vec::new();
+        let mut breadcrumbs = vec![page_name.to_string()];
+        if let Some(JsonValue::Array(children)) = page_map.get("children") {
+            for child in children {
+                collect_matches(child, selector, &mut breadcrumbs, &mut matches);
+            }
+        }
+        if matches.is_empty() {
+            continue;
+        }
+
+        count += matches.len();
+        let mut page = page_map.clone();
+        page.insert("children".to_string(), JsonValue::Array(matches));
+        selected_pages.push(JsonValue::Object(page));
+    }
+
+    if count == 0 {
+        return Err(FigError::EmptySelection(selector.describe()));
+    }
+
+    let canvas = definitions.canvas_for(&selected_pages);
+    *pages = selected_pages;
+    pages.extend(canvas);
+    Ok(count)
+}
+
+/// Remove the canvas of definitions added by [`select`]
+///
+/// `internal_only_nodes_removal` already drops it, but the pass may be
+/// disabled or left out of the pipeline.
+///
+/// # Arguments
+/// * `output` - Conversion output of a selection (with a `document` field)
+pub fn remove_definitions(output: &mut JsonValue) {
+    if let Some(pages) = output
+        .get_mut("document")
+        .and_then(|d| d.get_mut("children"))
+        .and_then(|c| c.as_array_mut())
+    {
+        pages.retain(|page| {
+            !page.as_object().is_some_and(|map| {
+                is_internal(map) && map.get("name").and_then(|n| n.as_str()) == Some(DEFINITIONS_CANVAS)
+            })
+        });
+    }
+}
+
+/// Collect the topmost nodes matching the selector, with their breadcrumbs
+fn collect_matches(
+    node: &JsonValue,
+    selector: &Selector,
+    breadcrumbs: &mut Vec<String>,
+    matches: &mut Vec<JsonValue>,
+) {
+    let Some(map) = node.as_object() else {
+        return;
+    };
+
+    if selector.matches_node(node) {
+        let mut selected = map.clone();
+        selected.insert(
+            "breadcrumbs".to_string(),
+            JsonValue::from(breadcrumbs.clone()),
+        );
+        matches.push(JsonValue::Object(selected));
+        return;
+    }
+
+    if let Some(JsonValue::Array(children)) = map.get("children") {
+        let name = map.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        breadcrumbs.push(name.to_string());
+        for child in children {
+            collect_matches(child, selector, breadcrumbs, matches);
+        }
+        breadcrumbs.pop();
+    }
+}
+
+/// Nodes the selected subtrees may reference from elsewhere in the file
+#[derive(Default)]
+struct Definitions {
+    /// Components by GUID: the SYMBOL node, or its component set for variants
+    symbols: HashMap<String, JsonValue>,
+    /// Shared style definition nodes
+    styles: Vec<JsonValue>,
+}
+
+impl Definitions {
+    fn collect(pages: &[JsonValue]) -> Self {
+        let mut definitions = Self::default();
+        for page in pages {
+            definitions.add(page, None);
+        }
+        definitions
+    }
+
+    fn add(&mut self, node: &JsonValue, state_group: Option<&JsonValue>) {
+        let Some(map) = node.as_object() else {
+            return;
+        };
+
+        if map.contains_key("styleType") {
+            self.styles.push(node.clone());
+        }
+        if node_type(map) == Some("SYMBOL") {
+            if let Ok(guid) = format_guid(node) {
+                self.symbols
+                    .insert(guid, state_group.unwrap_or(node).clone());
+            }
+        }
+
+        let is_state_group = map.get("isStateGroup").and_then(|v| v.as_bool()) == Some(true);
+        if let Some(JsonValue::Array(children)) = map.get("children") {
+            for child in children {
+                self.add(child, is_state_group.then_some(node));
+            }
+        }
+    }
+
+    /// Internal-only canvas with the styles and the components (transitively)
+    /// referenced by `selected`, if there is anything to keep
+    fn canvas_for(&self, selected: &[JsonValue]) -> Option<JsonValue> {
+        let mut pending = Vec::new();
+        for page in selected {
+            symbol_references(page, &mut pending);
+        }
+
+        let mut seen = HashSet::new();
+        let mut children = Vec::new();
+        while let Some(guid) = pending.pop() {
+            let Some(definition) = self.symbols.get(&guid) else {
+                continue;
+            };
+            // Variants of a set share one definition: only keep it once
+            let Ok(definition_guid) = format_guid(definition) else {
+                continue;
+            };
+            if !seen.insert(definition_guid) {
+                continue;
+            }
+            symbol_references(definition, &mut pending);
+            children.push(definition.clone());
+        }
+        children.extend(self.styles.iter().cloned());
+
+        if children.is_empty() {
+            return None;
+        }
+        Some(serde_json::json!({
+            "type": {"__enum__": "NodeType", "value": "CANVAS"},
+            "name": DEFINITIONS_CANVAS,
+            "internalOnly": true,
+            "children": children,
+        }))
+    }
+}
+
+/// GUIDs of the components referenced by `symbolID` / `overriddenSymbolID`
+fn symbol_references(value: &JsonValue, references: &mut Vec<String>) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, val) in map {
+                if key == "symbolID" || key == "overriddenSymbolID" {
+                    references.extend(guid_key(val));
+                } else {
+                    symbol_references(val, references);
+                }
+            }
+        }
+        JsonValue::Array(arr) => {
+            for val in arr {
+                symbol_references(val, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_internal(map: &Map<String, JsonValue>) -> bool {
+    map.get("internalOnly").and_then(|v| v.as_bool()) == Some(true)
+}
+
+/// Match a name against a glob pattern with `*` (any run) and `?` (any
+/// character) wildcards
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, t));
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node_type(value: &str) -> JsonValue {
+        json!({"__enum__": "NodeType", "value": value})
+    }
+
+    fn output() -> JsonValue {
+        json!({"document": {"children": [
+            {
+                "name": "Home",
+                "type": node_type("CANVAS"),
+                "children": [
+                    {
+                        "name": "Hero",
+                        "type": node_type("FRAME"),
+                        "guid": {"sessionID": 1, "localID": 10},
+                        "children": [
+                            {"name": "Title", "type": node_type("TEXT"), "guid": {"sessionID": 1, "localID": 11}},
+                            {
+                                "name": "CTA",
+                                "type": node_type("INSTANCE"),
+                                "guid": {"sessionID": 1, "localID": 12},
+                                "symbolData": {"symbolID": {"sessionID": 2, "localID": 1}}
+                            }
+                        ]
+                    }
+                ]
+            },
+            {
+                "name": "Components",
+                "type": node_type("CANVAS"),
+                "children": [
+                    {
+                        "name": "Button",
+                        "type": node_type("FRAME"),
+                        "isStateGroup": true,
+                        "guid": {"sessionID": 2, "localID": 0},
+                        "children": [
+                            {"name": "State=Default", "type": node_type("SYMBOL"), "guid": {"sessionID": 2, "localID": 1}},
+                            {"name": "State=Hover", "type": node_type("SYMBOL"), "guid": {"sessionID": 2, "localID": 2}}
+                        ]
+                    },
+                    {"name": "Card", "type": node_type("SYMBOL"), "guid": {"sessionID": 2, "localID": 3}}
+                ]
+            },
+            {
+                "name": "Internal Only Canvas",
+                "internalOnly": true,
+                "children": [
+                    {"name": "Brand/Primary", "styleType": {"__enum__": "StyleType", "value": "FILL"}}
+                ]
+            }
+        ]}})
+    }
+
+    #[test]
+    fn test_select_page() {
+        let mut output = output();
+        let count = select(&mut output, &Selector::new().with_page("Components")).unwrap();
+
+        assert_eq!(count, 1);
+        let pages = output["document"]["children"].as_array().unwrap();
+        assert_eq!(pages[0]["name"], json!("Components"));
+        assert_eq!(pages[0]["children"].as_array().unwrap().len(), 2);
+        // Only the style definitions are kept on the definitions canvas
+        assert_eq!(pages[1]["name"], json!(DEFINITIONS_CANVAS));
+        assert_eq!(pages[1]["children"][0]["name"], json!("Brand/Primary"));
+        assert_eq!(pages.len(), 2);
+    }
+
+    #[test]
+    fn test_select_nodes_by_type_and_name() {
+        let mut output = output();
+        let selector = Selector::new()
+            .with_type("text")
+            .with_type("FRAME")
+            .with_name("T*");
+        let count = select(&mut output, &selector).unwrap();
+
+        assert_eq!(count, 1);
+        let page = &output["document"]["children"][0];
+        assert_eq!(page["name"], json!("Home"));
+        assert_eq!(page["children"][0]["name"], json!("Title"));
+        assert_eq!(page["children"][0]["breadcrumbs"], json!(["Home", "Hero"]));
+    }
+
+    #[test]
+    fn test_select_node_keeps_referenced_components() {
+        let mut output = output();
+        let count = select(&mut output, &Selector::new().with_node("1:10")).unwrap();
+
+        assert_eq!(count, 1);
+        let pages = output["document"]["children"].as_array().unwrap();
+        assert_eq!(pages[0]["children"][0]["name"], json!("Hero"));
+        assert_eq!(pages[0]["children"][0]["breadcrumbs"], json!(["Home"]));
+        // The whole component set of the referenced variant, not the unused Card
+        let definitions = pages[1]["children"].as_array().unwrap();
+        let names: Vec<&JsonValue> = definitions.iter().map(|d| &d["name"]).collect();
+        assert_eq!(names, [&json!("Button"), &json!("Brand/Primary")]);
+        assert_eq!(pages[1]["internalOnly"], json!(true));
+    }
+
+    #[test]
+    fn test_remove_definitions() {
+        let mut output = output();
+        select(&mut output, &Selector::new().with_node("1:10")).unwrap();
+        assert_eq!(output["document"]["children"].as_array().unwrap().len(), 2);
+
+        remove_definitions(&mut output);
+        let pages = output["document"]["children"].as_array().unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0]["name"], json!("Home"));
+    }
+
+    #[test]
+    fn test_empty_selection() {
+        let mut output = output();
+        let result = select(
+            &mut output,
+            &Selector::new().with_page("Home").with_name("Card"),
+        );
+
+        assert!(matches!(result, Err(FigError::EmptySelection(_))));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("Button/*", "Button/Primary"));
+        assert!(glob_match("*card*", "Product card"));
+        assert!(glob_match("Icon ??", "Icon 24"));
+        assert!(!glob_match("Icon ??", "Icon 128"));
+        assert!(!glob_match("Button", "Button/Primary"));
+        assert!(glob_match("*", ""));
+    }
+}
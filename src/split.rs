@@ -0,0 +1,275 @@
+//! Split output
+//!
+//! [`split`] cuts a converted document into one JSON file per page or per
+//! top-level frame (the children of the pages), and describes them in an
+//! [`Index`] manifest:
+//!
+//! ```text
+//! index.json
+//! pages/home.json                  (SplitMode::Pages)
+//! pages/home/hero.json             (SplitMode::Frames)
+//! pages/home/pricing.json
+//! ```
+//!
+//! GUIDs are read from the `guid` field of the pages and frames, so the input
+//! should come from a pipeline with `guid_removal` disabled for the index to
+//! have them.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::split::{split, SplitMode};
+//! use fig2json::{convert_with_pipeline, Pipeline};
+//!
+//! let mut pipeline = Pipeline::standard();
+//! pipeline.disable("guid_removal").unwrap();
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let json = convert_with_pipeline(&bytes, None, &pipeline).unwrap();
+//!
+//! let output = split(&json, SplitMode::Frames);
+//! for file in &output.files {
+//!     println!("{}", file.path);
+//! }
+//! ```
+
+use crate::html::{slug, UniqueNames};
+use crate::schema::tree::guid_key;
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+
+/// Granularity of the split
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitMode {
+    /// One file per page
+    Pages,
+    /// One file per top-level frame, in a directory per page
+    Frames,
+}
+
+/// Result of [`split`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Split {
+    /// Manifest of the pages, frames and files
+    pub index: Index,
+    /// Files to write, in document order
+    pub files: Vec<SplitFile>,
+}
+
+/// A page or frame to write to its own file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitFile {
+    /// Path relative to the output directory, with `/` separators
+    pub path: String,
+    /// The page or frame node
+    pub json: JsonValue,
+}
+
+/// Manifest of a split output (`index.json`)
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct Index {
+    pub pages: Vec<PageEntry>,
+    /// Shared style definitions referenced by name from the nodes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub styles: Option<JsonValue>,
+}
+
+/// A page of the manifest
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageEntry {
+    pub name: String,
+    /// GUID (`sessionID:localID`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// File of the page, with [`SplitMode::Pages`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    pub frames: Vec<FrameEntry>,
+}
+
+/// A top-level frame of the manifest
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FrameEntry {
+    pub name: String,
+    /// GUID (`sessionID:localID`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+    /// File of the frame, with [`SplitMode::Frames`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// Split a converted document into one file per page or top-level frame
+///
+/// File names are slugs of the node names (`Sign up / Mobile` becomes
+/// `sign-up-mobile`), made unique with `-2`, `-3`, ... suffixes.
+///
+/// # Arguments
+/// * `output` - Output of [`convert`](crate::convert), or its `document` node
+/// * `mode` - One file per page or per top-level frame
+///
+/// # Returns
+/// The manifest and the files, in document order
+///
+/// # Examples
+/// ```
+/// use fig2json::split::{split, SplitMode};
+/// use serde_json::json;
+///
+/// let output = json!({"document": {"children": [
+///     {"name": "Home", "children": [
+///         {"name": "Hero", "guid": {"sessionID": 1, "localID": 2}, "size": {"x": 1440.0, "y": 800.0}}
+///     ]}
+/// ]}});
+/// let split = split(&output, SplitMode::Frames);
+///
+/// assert_eq!(split.files[0].path, "pages/home/hero.json");
+/// assert_eq!(split.index.pages[0].frames[0].id.as_deref(), Some("1:2"));
+/// assert_eq!(split.index.pages[0].frames[0].width, Some(1440.0));
+/// ```
+pub fn split(output: &JsonValue, mode: SplitMode) -> Split {
+    let document = output.get("document").unwrap_or(output).as_object();
+    let mut index = Index {
+        pages: Vec::new(),
+        styles: output.get("styles").cloned(),
+    };
+    let mut files = Vec::new();
+    let mut page_names = UniqueNames::default();
+
+    for page in document.into_iter().flat_map(children) {
+        let page_slug = page_names.next(&slug(node_name(page), "page"));
+        let mut frame_names = UniqueNames::default();
+
+        let frames = children(page)
+            .map(|frame| {
+                let file = (mode == SplitMode::Frames).then(|| {
+                    let frame_slug = frame_names.next(&slug(node_name(frame), "frame"));
+                    let path = format!("pages/{}/{}.json", page_slug, frame_slug);
+                    files.push(SplitFile {
+                        path: path.clone(),
+                        json: JsonValue::Object(frame.clone()),
+                    });
+                    path
+                });
+                let size = frame.get("size");
+                FrameEntry {
+                    name: node_name(frame).to_string(),
+                    id: frame.get("guid").and_then(guid_key),
+                    width: size.and_then(|s| s.get("x")).and_then(|x| x.as_f64()),
+                    height: size.and_then(|s| s.get("y")).and_then(|y| y.as_f64()),
+                    file,
+                }
+            })
+            .collect();
+
+        let file = (mode == SplitMode::Pages).then(|| {
+            let path = format!("pages/{}.json", page_slug);
+            files.push(SplitFile {
+                path: path.clone(),
+                json: JsonValue::Object(page.clone()),
+            });
+            path
+        });
+        index.pages.push(PageEntry {
+            name: node_name(page).to_string(),
+            id: page.get("guid").and_then(guid_key),
+            file,
+            frames,
+        });
+    }
+
+    Split { index, files }
+}
+
+/// Object children of a node
+fn children(node: &Map<String, JsonValue>) -> impl Iterator<Item = &Map<String, JsonValue>> {
+    node.get("children")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|child| child.as_object())
+}
+
+fn node_name(node: &Map<String, JsonValue>) -> &str {
+    node.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn output() -> JsonValue {
+        json!({
+            "document": {"children": [
+                {
+                    "name": "Home",
+                    "guid": {"sessionID": 0, "localID": 1},
+                    "children": [
+                        {"name": "Hero", "guid": {"sessionID": 1, "localID": 2}, "size": {"x": 1440.0, "y": 800.0}},
+                        {"name": "Hero", "guid": {"sessionID": 1, "localID": 3}},
+                        {"name": "Sign up / Mobile", "size": {"x": 390.0, "y": 844.0}}
+                    ]
+                },
+                {"name": "🎨", "children": []}
+            ]},
+            "styles": {"Brand/Primary": {"styleType": "FILL"}}
+        })
+    }
+
+    #[test]
+    fn test_split_frames() {
+        let split = split(&output(), SplitMode::Frames);
+
+        let paths: Vec<&str> = split.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "pages/home/hero.json",
+                "pages/home/hero-2.json",
+                "pages/home/sign-up-mobile.json"
+            ]
+        );
+        assert_eq!(split.files[1].json["guid"]["localID"], json!(3));
+        assert_eq!(split.index.pages[0].file, None);
+        assert_eq!(split.index.pages[1].frames, []);
+    }
+
+    #[test]
+    fn test_split_pages() {
+        let split = split(&output(), SplitMode::Pages);
+
+        let paths: Vec<&str> = split.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["pages/home.json", "pages/page.json"]);
+        assert_eq!(split.files[0].json["children"].as_array().unwrap().len(), 3);
+        assert_eq!(split.index.pages[0].frames[0].file, None);
+    }
+
+    #[test]
+    fn test_index() {
+        let split = split(&output(), SplitMode::Frames);
+
+        assert_eq!(
+            serde_json::to_value(&split.index).unwrap(),
+            json!({
+                "pages": [
+                    {
+                        "name": "Home",
+                        "id": "0:1",
+                        "frames": [
+                            {"name": "Hero", "id": "1:2", "width": 1440.0, "height": 800.0, "file": "pages/home/hero.json"},
+                            {"name": "Hero", "id": "1:3", "file": "pages/home/hero-2.json"},
+                            {"name": "Sign up / Mobile", "width": 390.0, "height": 844.0, "file": "pages/home/sign-up-mobile.json"}
+                        ]
+                    },
+                    {"name": "🎨", "frames": []}
+                ],
+                "styles": {"Brand/Primary": {"styleType": "FILL"}}
+            })
+        );
+    }
+}
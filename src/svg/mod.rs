@@ -0,0 +1,455 @@
+//! SVG export of vector nodes
+//!
+//! Vector-like nodes (VECTOR, BOOLEAN_OPERATION, STAR, REGULAR_POLYGON,
+//! ELLIPSE, LINE) are turned into standalone SVG documents from their
+//! decoded geometry:
+//!
+//! - fills come from `fillGeometry` commands, falling back to the regions of
+//!   `vectorData.vectorNetwork` or to `arcData` for ellipses
+//! - strokes follow the segments of the vector network (or the fill outline)
+//!   with the node's `strokeWeight`, `strokeCap` and `strokeJoin`
+//! - solid `fillPaints` and `strokePaints` give the colors
+//!
+//! Export needs the geometry and enums that the standard pipeline removes, so
+//! it runs on [`convert_raw`](crate::convert_raw) output, or as the
+//! `svg_export` pass when [`PassContext::svg_dir`](crate::PassContext) is set.
+
+pub mod path;
+
+use crate::css::format_decimals;
+use crate::error::Result;
+use crate::html::slug;
+use crate::schema::tree::enum_str;
+use path::{commands_to_path, ellipse_path, VectorNetwork, DECIMALS};
+use serde_json::{Map, Value as JsonValue};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Node types exported as SVG
+pub const VECTOR_NODE_TYPES: &[&str] = &[
+    "VECTOR",
+    "BOOLEAN_OPERATION",
+    "STAR",
+    "REGULAR_POLYGON",
+    "ELLIPSE",
+    "LINE",
+];
+
+/// Write one SVG file per vector node and reference it from the node
+///
+/// Each exported node gets an `svg` field holding the path of its file
+/// (`dir` joined with `<name>-<sessionID>-<localID>.svg`). Children of
+/// exported nodes (boolean operation operands) are not exported separately.
+///
+/// # Arguments
+/// * `tree` - Document tree from [`convert_raw`](crate::convert_raw)
+/// * `dir` - Directory to write the SVG files to (created if needed)
+///
+/// # Returns
+/// * `Ok(Vec<PathBuf>)` - Paths of the written files
+/// * `Err(FigError)` - If a file cannot be written
+///
+/// # Examples
+/// ```no_run
+/// use fig2json::svg::export_svgs;
+/// use std::path::Path;
+///
+/// let bytes = std::fs::read("example.fig").unwrap();
+/// let mut json = fig2json::convert_raw(&bytes).unwrap();
+/// let files = export_svgs(&mut json["document"], Path::new("icons")).unwrap();
+/// println!("{} icons exported", files.len());
+/// ```
+pub fn export_svgs(tree: &mut JsonValue, dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    export_recursive(tree, dir, &mut files)?;
+    Ok(files)
+}
+
+fn export_recursive(value: &mut JsonValue, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let Some(map) = value.as_object_mut() else {
+        return Ok(());
+    };
+
+    if is_vector_node(map) {
+        if let Some(svg) = node_to_svg(map) {
+            if files.is_empty() {
+                fs::create_dir_all(dir)?;
+            }
+            let path = dir.join(file_name(map));
+            fs::write(&path, svg)?;
+            map.insert(
+                "svg".to_string(),
+                JsonValue::String(path.to_string_lossy().replace('\\', "/")),
+            );
+            files.push(path);
+            return Ok(());
+        }
+    }
+
+    if let Some(JsonValue::Array(children)) = map.get_mut("children") {
+        for child in children {
+            export_recursive(child, dir, files)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// File name of the SVG of a node: slugified name and GUID
+fn file_name(node: &Map<String, JsonValue>) -> String {
+    let name = slug(node.get("name").and_then(|n| n.as_str()).unwrap_or(""), "vector");
+    let id = |key: &str| {
+        node.get("guid")
+            .and_then(|g| g.get(key))
+            .and_then(|v| v.as_u64())
+    };
+    match (id("sessionID"), id("localID")) {
+        (Some(session), Some(local)) => format!("{}-{}-{}.svg", name, session, local),
+        _ => format!("{}.svg", name),
+    }
+}
+
+/// Check if a node is one of [`VECTOR_NODE_TYPES`]
+pub fn is_vector_node(node: &Map<String, JsonValue>) -> bool {
+    node.get("type")
+        .and_then(enum_str)
+        .is_some_and(|t| VECTOR_NODE_TYPES.contains(&t))
+}
+
+/// Render a vector node as a standalone SVG document
+///
+/// The viewBox matches the node size, grown by half the stroke weight on
+/// each side so that strokes are not clipped.
+///
+/// # Returns
+/// * `Some(String)` - SVG document
+/// * `None` - If the node has no drawable geometry or no visible solid paint
+///
+/// # Examples
+/// ```
+/// use fig2json::svg::node_to_svg;
+/// use serde_json::json;
+///
+/// let node = json!({
+///     "type": "ELLIPSE",
+///     "size": {"x": 10.0, "y": 10.0},
+///     "fillPaints": [{"type": "SOLID", "color": {"r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0}}]
+/// });
+/// let svg = node_to_svg(node.as_object().unwrap()).unwrap();
+/// assert!(svg.contains("<path d=\"M10 5A5 5 0 1 1 0 5A5 5 0 1 1 10 5Z\" fill=\"#ff0000\"/>"));
+/// ```
+pub fn node_to_svg(node: &Map<String, JsonValue>) -> Option<String> {
+    let node_type = node.get("type").and_then(enum_str).unwrap_or("");
+    let size = node.get("size");
+    let width = size.and_then(|s| s.get("x")).and_then(|v| v.as_f64()).unwrap_or(0.0);
+    let height = size.and_then(|s| s.get("y")).and_then(|v| v.as_f64()).unwrap_or(0.0);
+
+    let fills = solid_paints(node.get("fillPaints"));
+    let strokes = solid_paints(node.get("strokePaints"));
+    let stroke_weight = node
+        .get("strokeWeight")
+        .and_then(|w| w.as_f64())
+        .unwrap_or(1.0);
+    let has_stroke = !strokes.is_empty() && stroke_weight > 0.0;
+
+    let network = vector_network(node, width, height);
+    let fill_shapes = fill_shapes(node, node_type, width, height, network.as_ref());
+
+    let mut body = String::new();
+    for (color, opacity) in &fills {
+        for (d, nonzero) in &fill_shapes {
+            let _ = write!(body, "  <path d=\"{}\" fill=\"{}\"", d, color);
+            if *opacity < 1.0 {
+                let _ = write!(body, " fill-opacity=\"{}\"", format_decimals(*opacity, DECIMALS));
+            }
+            if !nonzero {
+                body.push_str(" fill-rule=\"evenodd\"");
+            }
+            body.push_str("/>\n");
+        }
+    }
+
+    if has_stroke {
+        let outline = match &network {
+            Some(network) => network.stroke_path(),
+            None if node_type == "LINE" => format!("M0 0L{} 0", format_decimals(width, DECIMALS)),
+            None => fill_shapes.iter().map(|(d, _)| d.as_str()).collect(),
+        };
+        if !outline.is_empty() {
+            let cap = match node.get("strokeCap").and_then(enum_str) {
+                Some("ROUND") => Some("round"),
+                Some("SQUARE") => Some("square"),
+                _ => None,
+            };
+            let join = match node.get("strokeJoin").and_then(enum_str) {
+                Some("ROUND") => Some("round"),
+                Some("BEVEL") => Some("bevel"),
+                _ => None,
+            };
+
+            for (color, opacity) in &strokes {
+                let _ = write!(
+                    body,
+                    "  <path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"",
+                    outline,
+                    color,
+                    format_decimals(stroke_weight, DECIMALS)
+                );
+                if *opacity < 1.0 {
+                    let _ = write!(body, " stroke-opacity=\"{}\"", format_decimals(*opacity, DECIMALS));
+                }
+                if let Some(cap) = cap {
+                    let _ = write!(body, " stroke-linecap=\"{}\"", cap);
+                }
+                if let Some(join) = join {
+                    let _ = write!(body, " stroke-linejoin=\"{}\"", join);
+                }
+                body.push_str("/>\n");
+            }
+        }
+    }
+
+    if body.is_empty() {
+        return None;
+    }
+
+    let pad = if has_stroke { stroke_weight / 2.0 } else { 0.0 };
+    let (box_width, box_height) = (width + 2.0 * pad, height + 2.0 * pad);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\" fill=\"none\"",
+        format_decimals(box_width, DECIMALS),
+        format_decimals(box_height, DECIMALS),
+        format_decimals(-pad, DECIMALS),
+        format_decimals(-pad, DECIMALS),
+        format_decimals(box_width, DECIMALS),
+        format_decimals(box_height, DECIMALS),
+    );
+    if let Some(opacity) = node.get("opacity").and_then(|o| o.as_f64()).filter(|o| *o < 1.0) {
+        let _ = write!(svg, " opacity=\"{}\"", format_decimals(opacity, DECIMALS));
+    }
+    svg.push_str(">\n");
+    svg.push_str(&body);
+    svg.push_str("</svg>\n");
+    Some(svg)
+}
+
+/// Filled shapes of a node: path data and whether the fill rule is nonzero
+fn fill_shapes(
+    node: &Map<String, JsonValue>,
+    node_type: &str,
+    width: f64,
+    height: f64,
+    network: Option<&VectorNetwork>,
+) -> Vec<(String, bool)> {
+    let geometry: Vec<(String, bool)> = node
+        .get("fillGeometry")
+        .and_then(|g| g.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let d = commands_to_path(entry.get("commands")?)?;
+            let nonzero = entry.get("windingRule").and_then(enum_str) != Some("ODD");
+            Some((d, nonzero))
+        })
+        .collect();
+
+    if !geometry.is_empty() {
+        return geometry;
+    }
+    if node_type == "ELLIPSE" {
+        let arc_data = node.get("arcData");
+        let ring = arc_data
+            .and_then(|a| a.get("innerRadius"))
+            .and_then(|r| r.as_f64())
+            .is_some_and(|r| r > 0.0);
+        return vec![(ellipse_path(width, height, arc_data), !ring)];
+    }
+    network.map(|n| n.region_paths()).unwrap_or_default()
+}
+
+/// Vector network of the node, scaled from `normalizedSize` to the node size
+fn vector_network(node: &Map<String, JsonValue>, width: f64, height: f64) -> Option<VectorNetwork> {
+    let vector_data = node.get("vectorData")?;
+    let normalized = vector_data.get("normalizedSize");
+    let scale = |key: &str, size: f64| {
+        normalized
+            .and_then(|n| n.get(key))
+            .and_then(|v| v.as_f64())
+            .filter(|v| *v != 0.0)
+            .map_or(1.0, |v| size / v)
+    };
+    VectorNetwork::from_json(
+        vector_data.get("vectorNetwork")?,
+        (scale("x", width), scale("y", height)),
+    )
+}
+
+/// `#rrggbb` colors and opacities of the visible solid paints, bottom to top
+fn solid_paints(paints: Option<&JsonValue>) -> Vec<(String, f64)> {
+    paints
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|paint| paint.get("visible").and_then(|v| v.as_bool()) != Some(false))
+        .filter(|paint| paint.get("type").and_then(enum_str).is_none_or(|t| t == "SOLID"))
+        .filter_map(|paint| {
+            let (color, alpha) = color(paint.get("color")?)?;
+            let opacity = paint.get("opacity").and_then(|o| o.as_f64()).unwrap_or(1.0);
+            Some((color, alpha * opacity))
+        })
+        .collect()
+}
+
+/// `#rrggbb` and alpha of a raw `{r, g, b, a}` color or a CSS hex string
+fn color(value: &JsonValue) -> Option<(String, f64)> {
+    if let Some(hex) = value.as_str() {
+        let digits = hex.strip_prefix('#')?;
+        let alpha = match digits.len() {
+            6 => 1.0,
+            8 => u8::from_str_radix(&digits[6..], 16).ok()? as f64 / 255.0,
+            _ => return None,
+        };
+        return Some((format!("#{}", &digits[..6]), alpha));
+    }
+
+    let channel = |key: &str| -> Option<u8> {
+        Some((value.get(key)?.as_f64()?.clamp(0.0, 1.0) * 255.0).round() as u8)
+    };
+    let alpha = value.get("a").and_then(|a| a.as_f64()).unwrap_or(1.0);
+    Some((
+        format!("#{:02x}{:02x}{:02x}", channel("r")?, channel("g")?, channel("b")?),
+        alpha,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn solid(r: f64, g: f64, b: f64) -> JsonValue {
+        json!({
+            "type": {"__enum__": "PaintType", "value": "SOLID"},
+            "color": {"r": r, "g": g, "b": b, "a": 1.0},
+            "opacity": 1.0,
+            "visible": true
+        })
+    }
+
+    fn icon() -> JsonValue {
+        json!({
+            "guid": {"sessionID": 12, "localID": 7},
+            "type": {"__enum__": "NodeType", "value": "VECTOR"},
+            "name": "Arrow / Right",
+            "size": {"x": 24.0, "y": 24.0},
+            "fillPaints": [solid(0.0, 0.0, 0.0)],
+            "fillGeometry": [{
+                "windingRule": {"__enum__": "WindingRule", "value": "ODD"},
+                "commands": ["M", 0.0, 0.0, "L", 24.0, 12.0, "L", 0.0, 24.0, "Z"]
+            }]
+        })
+    }
+
+    #[test]
+    fn test_fill_geometry_with_winding_rule() {
+        let svg = node_to_svg(icon().as_object().unwrap()).unwrap();
+
+        assert_eq!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\" height=\"24\" viewBox=\"0 0 24 24\" fill=\"none\">\n  <path d=\"M0 0L24 12L0 24Z\" fill=\"#000000\" fill-rule=\"evenodd\"/>\n</svg>\n"
+        );
+    }
+
+    #[test]
+    fn test_line_stroke() {
+        let line = json!({
+            "type": "LINE",
+            "size": {"x": 100.0, "y": 0.0},
+            "strokePaints": [{"type": "SOLID", "color": "#ff000080"}],
+            "strokeWeight": 2.0,
+            "strokeCap": {"__enum__": "StrokeCap", "value": "ROUND"}
+        });
+        let svg = node_to_svg(line.as_object().unwrap()).unwrap();
+
+        assert!(svg.contains("width=\"102\" height=\"2\" viewBox=\"-1 -1 102 2\""));
+        assert!(svg.contains(
+            "<path d=\"M0 0L100 0\" fill=\"none\" stroke=\"#ff0000\" stroke-width=\"2\" stroke-opacity=\"0.502\" stroke-linecap=\"round\"/>"
+        ));
+    }
+
+    #[test]
+    fn test_vector_network_stroke_is_scaled() {
+        let node = json!({
+            "type": "VECTOR",
+            "size": {"x": 20.0, "y": 10.0},
+            "strokePaints": [solid(0.0, 0.0, 1.0)],
+            "strokeWeight": 0.0,
+            "vectorData": {
+                "normalizedSize": {"x": 2.0, "y": 1.0},
+                "vectorNetwork": {
+                    "vertices": [{"styleID": 0, "x": 0.0, "y": 0.0}, {"styleID": 0, "x": 2.0, "y": 1.0}],
+                    "segments": [{"styleID": 0, "start": {"vertex": 0, "dx": 0.0, "dy": 0.0}, "end": {"vertex": 1, "dx": 0.0, "dy": 0.0}}],
+                    "regions": []
+                }
+            }
+        });
+        // A zero stroke weight draws nothing
+        assert!(node_to_svg(node.as_object().unwrap()).is_none());
+
+        let mut node = node;
+        node["strokeWeight"] = json!(1.5);
+        let svg = node_to_svg(node.as_object().unwrap()).unwrap();
+        assert!(svg.contains("d=\"M0 0L20 10\""));
+        assert!(svg.contains("viewBox=\"-0.75 -0.75 21.5 11.5\""));
+    }
+
+    #[test]
+    fn test_invisible_and_gradient_paints_are_skipped() {
+        let mut node = icon();
+        node["fillPaints"] = json!([
+            {"type": "GRADIENT_LINEAR", "gradientStops": []},
+            {"type": "SOLID", "color": "#ffffff", "visible": false}
+        ]);
+        assert!(node_to_svg(node.as_object().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_export_writes_files_and_references_them() {
+        let dir = std::env::temp_dir().join(format!("fig2json-svg-{}", std::process::id()));
+        let mut tree = json!({
+            "type": {"__enum__": "NodeType", "value": "DOCUMENT"},
+            "children": [{
+                "type": {"__enum__": "NodeType", "value": "FRAME"},
+                "name": "Toolbar",
+                "children": [
+                    icon(),
+                    {
+                        "guid": {"sessionID": 12, "localID": 9},
+                        "type": {"__enum__": "NodeType", "value": "BOOLEAN_OPERATION"},
+                        "name": "Union",
+                        "size": {"x": 24.0, "y": 24.0},
+                        "fillPaints": [solid(1.0, 1.0, 1.0)],
+                        "fillGeometry": [{"commands": ["M", 0.0, 0.0, "L", 1.0, 1.0, "Z"]}],
+                        "children": [icon()]
+                    }
+                ]
+            }]
+        });
+
+        let files = export_svgs(&mut tree, &dir).unwrap();
+
+        assert_eq!(files, vec![dir.join("arrow-right-12-7.svg"), dir.join("union-12-9.svg")]);
+        assert!(fs::read_to_string(&files[0]).unwrap().starts_with("<svg"));
+        let toolbar = &tree["children"][0];
+        assert_eq!(
+            toolbar["children"][0]["svg"],
+            json!(dir.join("arrow-right-12-7.svg").to_string_lossy())
+        );
+        // Boolean operands are part of the union's SVG
+        assert!(toolbar["children"][1]["children"][0].get("svg").is_none());
+        assert!(toolbar.get("svg").is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,387 @@
+use crate::css::format_decimals;
+use serde_json::Value as JsonValue;
+use std::f64::consts::PI;
+
+/// SVG path data from a parsed `commands` blob
+///
+/// # Arguments
+/// * `commands` - Flat array produced by [`parse_commands`](crate::blobs::parse_commands)
+///
+/// # Returns
+/// * `Some(String)` - Path data (`d` attribute)
+/// * `None` - If the array is malformed or has missing (`null`) coordinates
+///
+/// # Examples
+/// ```
+/// use fig2json::svg::path::commands_to_path;
+/// use serde_json::json;
+///
+/// let d = commands_to_path(&json!(["M", 0.0, 0.0, "L", 10.0, 5.5, "Z"])).unwrap();
+/// assert_eq!(d, "M0 0L10 5.5Z");
+/// ```
+pub fn commands_to_path(commands: &JsonValue) -> Option<String> {
+    let items = commands.as_array()?;
+    let mut d = String::new();
+    let mut i = 0;
+
+    while i < items.len() {
+        let command = items[i].as_str()?;
+        let count = match command {
+            "Z" => 0,
+            "M" | "L" => 2,
+            "Q" => 4,
+            "C" => 6,
+            _ => return None,
+        };
+        let values = items
+            .get(i + 1..i + 1 + count)?
+            .iter()
+            .map(|v| v.as_f64())
+            .collect::<Option<Vec<f64>>>()?;
+
+        d.push_str(command);
+        d.push_str(&join_numbers(&values));
+        i += 1 + count;
+    }
+
+    Some(d)
+}
+
+/// A point of a vector network
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+/// A segment of a vector network, in node coordinates
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    start: usize,
+    end: usize,
+    from: Point,
+    control1: Point,
+    control2: Point,
+    to: Point,
+    curved: bool,
+}
+
+impl Segment {
+    fn reversed(self) -> Self {
+        Segment {
+            start: self.end,
+            end: self.start,
+            from: self.to,
+            control1: self.control2,
+            control2: self.control1,
+            to: self.from,
+            curved: self.curved,
+        }
+    }
+
+    /// Path data drawing the segment from its current point
+    fn draw(&self) -> String {
+        if self.curved {
+            format!(
+                "C{}",
+                join_numbers(&[
+                    self.control1.x,
+                    self.control1.y,
+                    self.control2.x,
+                    self.control2.y,
+                    self.to.x,
+                    self.to.y,
+                ])
+            )
+        } else {
+            format!("L{}", join_numbers(&[self.to.x, self.to.y]))
+        }
+    }
+}
+
+/// Decoded vector network, scaled to the node size
+pub struct VectorNetwork {
+    segments: Vec<Segment>,
+    regions: Vec<(Vec<Vec<usize>>, bool)>,
+}
+
+impl VectorNetwork {
+    /// Read a parsed vector network
+    ///
+    /// # Arguments
+    /// * `network` - Object produced by [`parse_vector_network`](crate::blobs::parse_vector_network)
+    /// * `scale` - Factors from network coordinates (`vectorData.normalizedSize`) to node coordinates
+    pub fn from_json(network: &JsonValue, scale: (f64, f64)) -> Option<Self> {
+        let vertices = network
+            .get("vertices")?
+            .as_array()?
+            .iter()
+            .map(|v| {
+                Some(Point {
+                    x: v.get("x")?.as_f64()? * scale.0,
+                    y: v.get("y")?.as_f64()? * scale.1,
+                })
+            })
+            .collect::<Option<Vec<Point>>>()?;
+
+        let mut segments = Vec::new();
+        for segment in network.get("segments")?.as_array()? {
+            let start = segment.get("start")?;
+            let end = segment.get("end")?;
+            let start_index = start.get("vertex")?.as_u64()? as usize;
+            let end_index = end.get("vertex")?.as_u64()? as usize;
+            let tangent = |end: &JsonValue| -> Option<Point> {
+                Some(Point {
+                    x: end.get("dx")?.as_f64()? * scale.0,
+                    y: end.get("dy")?.as_f64()? * scale.1,
+                })
+            };
+            let (t1, t2) = (tangent(start)?, tangent(end)?);
+            let from = *vertices.get(start_index)?;
+            let to = *vertices.get(end_index)?;
+
+            segments.push(Segment {
+                start: start_index,
+                end: end_index,
+                from,
+                control1: Point { x: from.x + t1.x, y: from.y + t1.y },
+                control2: Point { x: to.x + t2.x, y: to.y + t2.y },
+                to,
+                curved: t1 != Point { x: 0.0, y: 0.0 } || t2 != Point { x: 0.0, y: 0.0 },
+            });
+        }
+
+        let mut regions = Vec::new();
+        for region in network.get("regions")?.as_array()? {
+            let loops = region
+                .get("loops")?
+                .as_array()?
+                .iter()
+                .map(|l| {
+                    l.get("segments")?
+                        .as_array()?
+                        .iter()
+                        .map(|i| i.as_u64().map(|i| i as usize).filter(|i| *i < segments.len()))
+                        .collect::<Option<Vec<usize>>>()
+                })
+                .collect::<Option<Vec<Vec<usize>>>>()?;
+            let nonzero = region.get("windingRule").and_then(|w| w.as_str()) != Some("ODD");
+            regions.push((loops, nonzero));
+        }
+
+        Some(Self { segments, regions })
+    }
+
+    /// Path data and fill rule (`true` for nonzero) of each filled region
+    pub fn region_paths(&self) -> Vec<(String, bool)> {
+        self.regions
+            .iter()
+            .map(|(loops, nonzero)| {
+                let d: String = loops.iter().map(|l| self.loop_path(l)).collect();
+                (d, *nonzero)
+            })
+            .filter(|(d, _)| !d.is_empty())
+            .collect()
+    }
+
+    /// Path data of every segment, chaining segments that share a vertex
+    pub fn stroke_path(&self) -> String {
+        let indices: Vec<usize> = (0..self.segments.len()).collect();
+        self.chain(&indices, false)
+    }
+
+    fn loop_path(&self, indices: &[usize]) -> String {
+        self.chain(indices, true)
+    }
+
+    /// Draw segments in order, reversing them as needed to follow shared vertices
+    fn chain(&self, indices: &[usize], close: bool) -> String {
+        let mut d = String::new();
+        let mut current: Option<usize> = None;
+
+        for (n, index) in indices.iter().enumerate() {
+            let mut segment = self.segments[*index];
+
+            match current {
+                Some(vertex) if segment.start == vertex => {}
+                Some(vertex) if segment.end == vertex => segment = segment.reversed(),
+                _ => {
+                    // Start of a subpath: orient towards the next segment when possible
+                    let next = indices.get(n + 1).map(|i| self.segments[*i]);
+                    if let Some(next) = next {
+                        let joins_end = next.start == segment.end || next.end == segment.end;
+                        let joins_start = next.start == segment.start || next.end == segment.start;
+                        if !joins_end && joins_start {
+                            segment = segment.reversed();
+                        }
+                    }
+                    if close && current.is_some() {
+                        d.push('Z');
+                    }
+                    d.push_str(&format!("M{}", join_numbers(&[segment.from.x, segment.from.y])));
+                }
+            }
+
+            d.push_str(&segment.draw());
+            current = Some(segment.end);
+        }
+
+        if close && !d.is_empty() {
+            d.push('Z');
+        }
+        d
+    }
+}
+
+/// Path data of an ellipse filling a `width` x `height` box
+///
+/// `arc_data` (`startingAngle`, `endingAngle` in radians and `innerRadius`
+/// as a ratio of the radius) turns the ellipse into an arc, a pie slice or a
+/// ring. Rings need the `evenodd` fill rule.
+///
+/// # Examples
+/// ```
+/// use fig2json::svg::path::ellipse_path;
+///
+/// assert_eq!(ellipse_path(10.0, 10.0, None), "M10 5A5 5 0 1 1 0 5A5 5 0 1 1 10 5Z");
+/// ```
+pub fn ellipse_path(width: f64, height: f64, arc_data: Option<&JsonValue>) -> String {
+    let (rx, ry) = (width / 2.0, height / 2.0);
+    let angle = |key: &str| arc_data.and_then(|a| a.get(key)).and_then(|v| v.as_f64());
+    let start = angle("startingAngle").unwrap_or(0.0);
+    let end = angle("endingAngle").unwrap_or(2.0 * PI);
+    let inner = angle("innerRadius").unwrap_or(0.0).clamp(0.0, 1.0);
+
+    let sweep = end - start;
+    if sweep.abs() >= 2.0 * PI - 1e-6 {
+        let mut d = full_ellipse(rx, ry, rx, ry);
+        if inner > 0.0 {
+            d.push_str(&full_ellipse(rx, ry, rx * inner, ry * inner));
+        }
+        return d;
+    }
+
+    let point = |theta: f64, ratio: f64| {
+        [
+            rx + rx * ratio * theta.cos(),
+            ry + ry * ratio * theta.sin(),
+        ]
+    };
+    let large = if sweep.abs() > PI { 1 } else { 0 };
+    let positive = if sweep >= 0.0 { 1 } else { 0 };
+
+    let mut d = format!("M{}", join_numbers(&point(start, 1.0)));
+    d.push_str(&format!(
+        "A{} 0 {} {} {}",
+        join_numbers(&[rx, ry]),
+        large,
+        positive,
+        join_numbers(&point(end, 1.0))
+    ));
+    if inner > 0.0 {
+        d.push_str(&format!("L{}", join_numbers(&point(end, inner))));
+        d.push_str(&format!(
+            "A{} 0 {} {} {}",
+            join_numbers(&[rx * inner, ry * inner]),
+            large,
+            1 - positive,
+            join_numbers(&point(start, inner))
+        ));
+    } else {
+        d.push_str(&format!("L{}", join_numbers(&[rx, ry])));
+    }
+    d.push('Z');
+    d
+}
+
+/// Closed ellipse centered on `(cx, cy)` drawn as two half arcs
+fn full_ellipse(cx: f64, cy: f64, rx: f64, ry: f64) -> String {
+    let radii = join_numbers(&[rx, ry]);
+    format!(
+        "M{}A{} 0 1 1 {}A{} 0 1 1 {}Z",
+        join_numbers(&[cx + rx, cy]),
+        radii,
+        join_numbers(&[cx - rx, cy]),
+        radii,
+        join_numbers(&[cx + rx, cy]),
+    )
+}
+
+/// Decimals of SVG numbers, one more than CSS values so that small curves keep their shape
+pub const DECIMALS: usize = 3;
+
+fn join_numbers(values: &[f64]) -> String {
+    values
+        .iter()
+        .map(|v| format_decimals(*v, DECIMALS))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn triangle() -> JsonValue {
+        json!({
+            "vertices": [
+                {"styleID": 0, "x": 0.0, "y": 0.0},
+                {"styleID": 0, "x": 1.0, "y": 0.0},
+                {"styleID": 0, "x": 1.0, "y": 1.0}
+            ],
+            "segments": [
+                {"styleID": 0, "start": {"vertex": 0, "dx": 0.0, "dy": 0.0}, "end": {"vertex": 1, "dx": 0.0, "dy": 0.0}},
+                // Stored backwards: 1 -> 2 is drawn as 2 -> 1 in the file
+                {"styleID": 0, "start": {"vertex": 2, "dx": 0.0, "dy": 0.0}, "end": {"vertex": 1, "dx": 0.0, "dy": 0.5}},
+                {"styleID": 0, "start": {"vertex": 2, "dx": 0.0, "dy": 0.0}, "end": {"vertex": 0, "dx": 0.0, "dy": 0.0}}
+            ],
+            "regions": [
+                {"styleID": 0, "windingRule": "ODD", "loops": [{"segments": [0, 1, 2]}]}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_commands_to_path() {
+        let commands = json!(["M", 0.0, 0.0, "Q", 1.0, 2.0, 3.0, 4.0, "C", 1.0, 2.0, 3.0, 4.0, 5.25, 6.0, "Z"]);
+        assert_eq!(commands_to_path(&commands).unwrap(), "M0 0Q1 2 3 4C1 2 3 4 5.25 6Z");
+        assert!(commands_to_path(&json!(["M", null, 0.0])).is_none());
+        assert!(commands_to_path(&json!(["X"])).is_none());
+    }
+
+    #[test]
+    fn test_vector_network_region_follows_vertices() {
+        let network = VectorNetwork::from_json(&triangle(), (10.0, 20.0)).unwrap();
+        let regions = network.region_paths();
+
+        assert_eq!(regions.len(), 1);
+        // The second segment is reversed, so its tangents are swapped
+        assert_eq!(regions[0].0, "M0 0L10 0C10 10 10 20 10 20L0 0Z");
+        assert!(!regions[0].1);
+    }
+
+    #[test]
+    fn test_vector_network_stroke_path() {
+        let network = VectorNetwork::from_json(&triangle(), (1.0, 1.0)).unwrap();
+        assert_eq!(network.stroke_path(), "M0 0L1 0C1 0.5 1 1 1 1L0 0");
+    }
+
+    #[test]
+    fn test_ellipse_arcs() {
+        let half = json!({"startingAngle": 0.0, "endingAngle": PI, "innerRadius": 0.0});
+        assert_eq!(ellipse_path(10.0, 10.0, Some(&half)), "M10 5A5 5 0 0 1 0 5L5 5Z");
+
+        let ring = json!({"startingAngle": 0.0, "endingAngle": 2.0 * PI, "innerRadius": 0.5});
+        assert_eq!(
+            ellipse_path(10.0, 10.0, Some(&ring)),
+            "M10 5A5 5 0 1 1 0 5A5 5 0 1 1 10 5ZM7.5 5A2.5 2.5 0 1 1 2.5 5A2.5 2.5 0 1 1 7.5 5Z"
+        );
+
+        let arc = json!({"startingAngle": 0.0, "endingAngle": PI / 2.0, "innerRadius": 0.5});
+        assert_eq!(
+            ellipse_path(10.0, 10.0, Some(&arc)),
+            "M10 5A5 5 0 0 1 5 10L5 7.5A2.5 2.5 0 0 0 7.5 5Z"
+        );
+    }
+}
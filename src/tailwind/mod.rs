@@ -0,0 +1,645 @@
+//! Tailwind CSS classes from the transformed tree
+//!
+//! [`node_classes`] maps the CSS-ready fields produced by the earlier passes
+//! (`size`, the decomposed `transform`, hex colors, corner radii, auto-layout
+//! stack fields, opacity, simplified text properties) to Tailwind utilities.
+//! Values snap to a named token of the [`Theme`] when one matches and fall
+//! back to arbitrary values (`w-[312px]`, `bg-[#0a84ff]`) otherwise.
+//!
+//! The `tailwind` pass of the standard pipeline stores the classes of each
+//! node in a `tailwind` string field. It is disabled by default and uses the
+//! theme of [`PassContext::tailwind`](crate::PassContext).
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::tailwind::Theme;
+//! use fig2json::{convert_with_context, PassContext, Pipeline};
+//! use std::path::Path;
+//!
+//! let config = std::fs::read_to_string("tailwind.config.json").unwrap();
+//! let theme = Theme::from_config(&serde_json::from_str(&config).unwrap()).unwrap();
+//! let ctx = PassContext::new(Path::new(".")).with_tailwind(theme);
+//! let mut pipeline = Pipeline::standard();
+//! pipeline.enable("tailwind").unwrap();
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let json = convert_with_context(&bytes, &pipeline, &ctx).unwrap();
+//! ```
+
+pub mod theme;
+
+pub use theme::Theme;
+
+use crate::css::{color_with_opacity, format_number, is_text, padding_sides, str_field};
+use crate::error::Result;
+use crate::html::css::{font_weight, Placement};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::BTreeMap;
+
+/// Rotations with a default `rotate-*` utility
+const ROTATE_SCALE: &[f64] = &[0.0, 1.0, 2.0, 3.0, 6.0, 12.0, 45.0, 90.0, 180.0];
+
+/// Tolerance when matching a value against a theme scale
+const EPSILON: f64 = 0.005;
+
+/// Add a `tailwind` class string to every node under the pages of a document
+///
+/// Children of pages (CANVAS nodes) are placed as roots, children of
+/// auto-layout frames as flex items and other nodes absolutely, as in
+/// [`render_html`](crate::html::render_html). Nodes without any class are
+/// left untouched.
+///
+/// # Arguments
+/// * `document` - Document node, after `matrix_to_css`, `color_to_css`,
+///   `enum_simplification` and `text_properties_simplification`
+/// * `theme` - Scales to snap values to
+///
+/// # Returns
+/// * `Ok(())` - Classes were added
+///
+/// # Examples
+/// ```
+/// use fig2json::tailwind::{add_tailwind_classes, Theme};
+/// use serde_json::json;
+///
+/// let mut document = json!({"children": [{"children": [
+///     {"size": {"x": 312.0, "y": 48.0}, "cornerRadius": 8.0}
+/// ]}]});
+/// add_tailwind_classes(&mut document, &Theme::default()).unwrap();
+///
+/// assert_eq!(document["children"][0]["children"][0]["tailwind"], "w-[312px] h-12 rounded-lg");
+/// ```
+pub fn add_tailwind_classes(document: &mut JsonValue, theme: &Theme) -> Result<()> {
+    transform_recursive(document, 0, theme);
+    Ok(())
+}
+
+/// Add classes to the children of a single object
+///
+/// `depth` counts containers from the document node, so pages are at depth 2
+/// and their children are top-level frames. Classes are added from the parent
+/// because the placement of a node depends on its parent's layout.
+pub(crate) fn enter_object(map: &mut Map<String, JsonValue>, depth: usize, theme: &Theme) {
+    let placement = match depth {
+        0 | 1 => return,
+        2 => Placement::Root,
+        _ => Placement::of_children(map),
+    };
+
+    if let Some(JsonValue::Array(children)) = map.get_mut("children") {
+        for child in children.iter_mut().filter_map(|c| c.as_object_mut()) {
+            let child_placement = match depth {
+                2 => placement,
+                _ => placement.for_child(child),
+            };
+            let classes = node_classes(child, child_placement, theme);
+            if !classes.is_empty() {
+                child.insert("tailwind".to_string(), JsonValue::String(classes.join(" ")));
+            }
+        }
+    }
+}
+
+fn transform_recursive(value: &mut JsonValue, depth: usize, theme: &Theme) {
+    match value {
+        JsonValue::Object(map) => {
+            enter_object(map, depth, theme);
+            for val in map.values_mut() {
+                transform_recursive(val, depth + 1, theme);
+            }
+        }
+        JsonValue::Array(arr) => {
+            for val in arr.iter_mut() {
+                transform_recursive(val, depth + 1, theme);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Tailwind classes of a node
+///
+/// # Arguments
+/// * `node` - Node object with CSS-ready fields
+/// * `placement` - How the node is placed inside its parent
+/// * `theme` - Scales to snap values to
+///
+/// # Returns
+/// Classes in a fixed order: position, size, layout, box, fills, strokes, text
+///
+/// # Examples
+/// ```
+/// use fig2json::html::css::Placement;
+/// use fig2json::tailwind::{node_classes, Theme};
+/// use serde_json::json;
+///
+/// let node = json!({
+///     "transform": {"x": 16.0, "y": 10.0},
+///     "fillPaints": [{"type": "SOLID", "color": "#000000", "opacity": 0.5}]
+/// });
+/// let classes = node_classes(node.as_object().unwrap(), Placement::Absolute, &Theme::default());
+///
+/// assert_eq!(classes, ["absolute", "left-4", "top-2.5", "bg-black/50"]);
+/// ```
+pub fn node_classes(node: &Map<String, JsonValue>, placement: Placement, theme: &Theme) -> Vec<String> {
+    let mut classes = Vec::new();
+    let has_children = node
+        .get("children")
+        .and_then(|c| c.as_array())
+        .is_some_and(|c| !c.is_empty());
+
+    let transform = node.get("transform");
+    match placement {
+        Placement::Absolute => {
+            classes.push("absolute".to_string());
+            let x = transform.and_then(|t| number(t, "x")).unwrap_or(0.0);
+            let y = transform.and_then(|t| number(t, "y")).unwrap_or(0.0);
+            classes.push(spacing("left", x, theme));
+            classes.push(spacing("top", y, theme));
+        }
+        Placement::Root | Placement::Flow if has_children => classes.push("relative".to_string()),
+        _ => {}
+    }
+
+    if let Some(size) = node.get("size") {
+        if let Some(width) = number(size, "x") {
+            classes.push(spacing("w", width, theme));
+        }
+        if let Some(height) = number(size, "y") {
+            classes.push(spacing("h", height, theme));
+        }
+    }
+    if placement == Placement::Flow {
+        classes.push("shrink-0".to_string());
+    }
+
+    if placement != Placement::Root {
+        if let Some(rotation) = transform.and_then(|t| number(t, "rotation")).filter(|r| *r != 0.0) {
+            let class = match ROTATE_SCALE.iter().find(|step| close(**step, rotation.abs())) {
+                Some(step) => format!("rotate-{}", format_number(*step)),
+                None => format!("rotate-[{}deg]", format_number(rotation.abs())),
+            };
+            classes.push(signed(class, rotation));
+            classes.push("origin-top-left".to_string());
+        }
+    }
+
+    layout_classes(node, theme, &mut classes);
+
+    if let Some(opacity) = node.get("opacity").and_then(|o| o.as_f64()).filter(|o| *o < 1.0) {
+        classes.push(match lookup(&theme.opacity, opacity) {
+            Some(name) => format!("opacity-{}", name),
+            None => format!("opacity-[{}]", format_number(opacity)),
+        });
+    }
+    radius_classes(node, theme, &mut classes);
+
+    let text = is_text(node);
+    if let Some((color, opacity)) = top_solid_paint(node.get("fillPaints")) {
+        let prefix = if text { "text" } else { "bg" };
+        classes.push(color_class(prefix, color, opacity, theme));
+    }
+    if let Some((color, opacity)) = top_solid_paint(node.get("strokePaints")) {
+        let weight = node
+            .get("strokeWeight")
+            .and_then(|w| w.as_f64())
+            .unwrap_or(1.0);
+        classes.push(scaled("border", &theme.border_width, weight, "px"));
+        classes.push(color_class("border", color, opacity, theme));
+    }
+    if text {
+        text_classes(node, theme, &mut classes);
+    }
+
+    classes
+}
+
+/// Flexbox classes of an auto-layout frame
+fn layout_classes(node: &Map<String, JsonValue>, theme: &Theme, classes: &mut Vec<String>) {
+    match str_field(node, "stackMode") {
+        Some("HORIZONTAL") => classes.push("flex".to_string()),
+        Some("VERTICAL") => {
+            classes.push("flex".to_string());
+            classes.push("flex-col".to_string());
+        }
+        _ => return,
+    }
+
+    if let Some(gap) = node.get("stackSpacing").and_then(|s| s.as_f64()) {
+        classes.push(spacing("gap", gap, theme));
+    }
+
+    let (top, right, bottom, left) = padding_sides(node).unwrap_or_default();
+    let sides = if top == bottom && left == right {
+        if top == left {
+            vec![("p", top)]
+        } else {
+            vec![("px", left), ("py", top)]
+        }
+    } else {
+        vec![("pt", top), ("pr", right), ("pb", bottom), ("pl", left)]
+    };
+    for (prefix, value) in sides {
+        if value != 0.0 {
+            classes.push(spacing(prefix, value, theme));
+        }
+    }
+
+    match str_field(node, "stackPrimaryAlignItems") {
+        Some("CENTER") => classes.push("justify-center".to_string()),
+        Some("MAX") => classes.push("justify-end".to_string()),
+        Some("SPACE_BETWEEN") => classes.push("justify-between".to_string()),
+        Some("SPACE_EVENLY") => classes.push("justify-evenly".to_string()),
+        _ => {}
+    }
+    match str_field(node, "stackCounterAlignItems") {
+        Some("MIN") => classes.push("items-start".to_string()),
+        Some("CENTER") => classes.push("items-center".to_string()),
+        Some("MAX") => classes.push("items-end".to_string()),
+        Some("BASELINE") => classes.push("items-baseline".to_string()),
+        _ => {}
+    }
+}
+
+/// `rounded-*` classes from `cornerRadius` or the individual corner radii
+fn radius_classes(node: &Map<String, JsonValue>, theme: &Theme, classes: &mut Vec<String>) {
+    let corner = |key: &str| node.get(key).and_then(|r| r.as_f64());
+    let corners = [
+        ("rounded-tl", corner("rectangleTopLeftCornerRadius")),
+        ("rounded-tr", corner("rectangleTopRightCornerRadius")),
+        ("rounded-br", corner("rectangleBottomRightCornerRadius")),
+        ("rounded-bl", corner("rectangleBottomLeftCornerRadius")),
+    ];
+    let uniform = corners
+        .iter()
+        .all(|(_, radius)| radius.is_none() || *radius == corner("cornerRadius"));
+
+    match corner("cornerRadius") {
+        Some(radius) if uniform => {
+            if radius != 0.0 {
+                classes.push(scaled("rounded", &theme.border_radius, radius, "px"));
+            }
+        }
+        _ => {
+            for (prefix, radius) in corners {
+                if let Some(radius) = radius.filter(|r| *r != 0.0) {
+                    classes.push(scaled(prefix, &theme.border_radius, radius, "px"));
+                }
+            }
+        }
+    }
+}
+
+/// Font classes of a text node
+fn text_classes(node: &Map<String, JsonValue>, theme: &Theme, classes: &mut Vec<String>) {
+    if let Some(font_name) = node.get("fontName") {
+        if let Some(family) = font_name.get("family").and_then(|f| f.as_str()) {
+            let token = theme
+                .font_family
+                .iter()
+                .find(|(_, first)| first.eq_ignore_ascii_case(family))
+                .map(|(name, _)| name);
+            classes.push(match token {
+                Some(name) => prefixed("font", name),
+                None => format!("font-['{}']", family.replace(' ', "_")),
+            });
+        }
+        if let Some(style) = font_name.get("style").and_then(|s| s.as_str()) {
+            let weight = font_weight(style) as f64;
+            // 400 is the browser default
+            if weight != 400.0 {
+                classes.push(scaled("font", &theme.font_weight, weight, ""));
+            }
+            if style.to_lowercase().contains("italic") {
+                classes.push("italic".to_string());
+            }
+        }
+    }
+
+    let font_size = node.get("fontSize").and_then(|s| s.as_f64());
+    if let Some(size) = font_size {
+        classes.push(scaled("text", &theme.font_size, size, "px"));
+    }
+
+    if let Some(line_height) = str_field(node, "lineHeight") {
+        let class = if let Some(length) = parse_unit(line_height, "px") {
+            scaled("leading", &theme.line_height, length, "px")
+        } else if let Some(percent) = parse_unit(line_height, "%") {
+            match lookup(&theme.line_height_ratio, percent / 100.0) {
+                Some(name) => prefixed("leading", name),
+                None => format!("leading-[{}]", format_number(percent / 100.0)),
+            }
+        } else {
+            format!("leading-[{}]", line_height)
+        };
+        classes.push(class);
+    }
+
+    if let Some(spacing) = str_field(node, "letterSpacing") {
+        let em = if let Some(percent) = parse_unit(spacing, "%") {
+            Some(percent / 100.0)
+        } else {
+            parse_unit(spacing, "px").and_then(|px| Some(px / font_size.filter(|s| *s > 0.0)?))
+        };
+        let token = em.and_then(|em| lookup(&theme.letter_spacing, em));
+        let class = match (token, em) {
+            (Some(name), _) => prefixed("tracking", name),
+            (None, Some(em)) => format!("tracking-[{}em]", format_number(em)),
+            (None, None) => format!("tracking-[{}]", spacing),
+        };
+        classes.push(class);
+    }
+
+    match str_field(node, "textAlignHorizontal") {
+        Some("CENTER") => classes.push("text-center".to_string()),
+        Some("RIGHT") => classes.push("text-right".to_string()),
+        Some("JUSTIFIED") => classes.push("text-justify".to_string()),
+        _ => {}
+    }
+    match str_field(node, "textCase") {
+        Some("UPPER") => classes.push("uppercase".to_string()),
+        Some("LOWER") => classes.push("lowercase".to_string()),
+        Some("TITLE") => classes.push("capitalize".to_string()),
+        _ => {}
+    }
+    match str_field(node, "textDecoration") {
+        Some("UNDERLINE") => classes.push("underline".to_string()),
+        Some("STRIKETHROUGH") => classes.push("line-through".to_string()),
+        _ => {}
+    }
+}
+
+/// Color and opacity of the topmost visible solid paint
+fn top_solid_paint(paints: Option<&JsonValue>) -> Option<(&str, f64)> {
+    paints?.as_array()?.iter().rev().find_map(|paint| {
+        let paint = paint.as_object()?;
+        if paint.get("visible").and_then(|v| v.as_bool()) == Some(false)
+            || str_field(paint, "type").is_some_and(|t| t != "SOLID")
+        {
+            return None;
+        }
+        let color = str_field(paint, "color")?;
+        let opacity = paint.get("opacity").and_then(|o| o.as_f64()).unwrap_or(1.0);
+        Some((color, opacity))
+    })
+}
+
+/// Color class with a theme color name and opacity modifier when possible
+///
+/// `#0a84ff` at 50% becomes `bg-brand/50` when `brand` is `#0a84ff` in the
+/// theme, and `bg-[#0a84ff80]` otherwise.
+fn color_class(prefix: &str, color: &str, opacity: f64, theme: &Theme) -> String {
+    let Some(hex) = theme::parse_hex(color) else {
+        return format!("{}-[{}]", prefix, color);
+    };
+    let with_opacity = color_with_opacity(&hex, opacity);
+
+    if let Some(name) = find_color(theme, &with_opacity) {
+        return prefixed(prefix, name);
+    }
+
+    let base = &hex[..7];
+    let alpha = match hex.len() {
+        9 => u8::from_str_radix(&hex[7..], 16).map_or(1.0, |a| a as f64 / 255.0),
+        _ => 1.0,
+    } * opacity;
+    if let Some(name) = find_color(theme, base) {
+        let modifier = match lookup(&theme.opacity, alpha) {
+            Some(step) => step.to_string(),
+            None => format!("[{}]", format_number(alpha)),
+        };
+        return format!("{}/{}", prefixed(prefix, name), modifier);
+    }
+
+    format!("{}-[{}]", prefix, with_opacity)
+}
+
+fn find_color<'a>(theme: &'a Theme, hex: &str) -> Option<&'a str> {
+    theme
+        .colors
+        .iter()
+        .find(|(_, color)| color.as_str() == hex)
+        .map(|(name, _)| name.as_str())
+}
+
+/// Spacing class, negative values use the `-` prefix (`-top-2`, `top-[-3px]`)
+fn spacing(prefix: &str, value: f64, theme: &Theme) -> String {
+    match lookup(&theme.spacing, value.abs()) {
+        Some(name) if value != 0.0 => signed(prefixed(prefix, name), value),
+        Some(name) => prefixed(prefix, name),
+        None => format!("{}-[{}px]", prefix, format_number(value)),
+    }
+}
+
+/// Class from a theme scale, or an arbitrary value with `unit`
+fn scaled(prefix: &str, scale: &BTreeMap<String, f64>, value: f64, unit: &str) -> String {
+    match lookup(scale, value) {
+        Some(name) => prefixed(prefix, name),
+        None => format!("{}-[{}{}]", prefix, format_number(value), unit),
+    }
+}
+
+/// Name of the first scale entry matching `value`
+fn lookup(scale: &BTreeMap<String, f64>, value: f64) -> Option<&str> {
+    scale
+        .iter()
+        .find(|(_, step)| close(**step, value))
+        .map(|(name, _)| name.as_str())
+}
+
+/// `prefix-name`, or the bare utility for the `DEFAULT` entry
+fn prefixed(prefix: &str, name: &str) -> String {
+    if name.is_empty() {
+        prefix.to_string()
+    } else {
+        format!("{}-{}", prefix, name)
+    }
+}
+
+fn signed(class: String, value: f64) -> String {
+    if value < 0.0 {
+        format!("-{}", class)
+    } else {
+        class
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < EPSILON
+}
+
+fn parse_unit(value: &str, unit: &str) -> Option<f64> {
+    value.strip_suffix(unit)?.trim().parse().ok()
+}
+
+fn number(value: &JsonValue, key: &str) -> Option<f64> {
+    value.get(key)?.as_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn classes(node: JsonValue, placement: Placement, theme: &Theme) -> String {
+        node_classes(node.as_object().unwrap(), placement, theme).join(" ")
+    }
+
+    #[test]
+    fn test_auto_layout_frame() {
+        let node = json!({
+            "size": {"x": 320.0, "y": 200.0},
+            "stackMode": "VERTICAL",
+            "stackSpacing": 12.0,
+            "stackHorizontalPadding": 24.0,
+            "stackVerticalPadding": 16.0,
+            "stackPrimaryAlignItems": "SPACE_BETWEEN",
+            "stackCounterAlignItems": "CENTER",
+            "cornerRadius": 10.0,
+            "fillPaints": [{"type": "SOLID", "color": "#ffffff", "visible": true}],
+            "children": [{}]
+        });
+
+        assert_eq!(
+            classes(node, Placement::Root, &Theme::default()),
+            "relative w-80 h-[200px] flex flex-col gap-3 px-6 py-4 justify-between items-center rounded-[10px] bg-white"
+        );
+
+        let row = json!({"stackMode": "HORIZONTAL", "stackPrimaryAlignItems": "SPACE_EVENLY"});
+        assert_eq!(classes(row, Placement::Flow, &Theme::default()), "shrink-0 flex justify-evenly");
+    }
+
+    #[test]
+    fn test_position_and_rotation() {
+        let node = json!({"transform": {"x": -8.0, "y": 3.0, "rotation": -45.0}, "opacity": 0.37});
+
+        assert_eq!(
+            classes(node.clone(), Placement::Absolute, &Theme::default()),
+            "absolute -left-2 top-[3px] -rotate-45 origin-top-left opacity-[0.37]"
+        );
+        assert_eq!(
+            classes(node, Placement::Flow, &Theme::default()),
+            "shrink-0 -rotate-45 origin-top-left opacity-[0.37]"
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let node = json!({
+            "type": "TEXT",
+            "fontName": {"family": "Open Sans", "style": "Semi Bold Italic"},
+            "fontSize": 14.0,
+            "lineHeight": "20px",
+            "letterSpacing": "-2.5%",
+            "textAlignHorizontal": "CENTER",
+            "textCase": "UPPER",
+            "fillPaints": [
+                {"type": "SOLID", "color": "#333333"},
+                {"type": "SOLID", "color": "#ff0000", "visible": false}
+            ]
+        });
+
+        assert_eq!(
+            classes(node, Placement::Root, &Theme::default()),
+            "text-[#333333] font-['Open_Sans'] font-semibold italic text-sm leading-5 tracking-tight text-center uppercase"
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_text_values() {
+        let node = json!({
+            "textData": {"characters": "Hi"},
+            "fontName": {"family": "Inter", "style": "Regular"},
+            "fontSize": 15.0,
+            "lineHeight": "140%",
+            "letterSpacing": "1.2px"
+        });
+
+        assert_eq!(
+            classes(node, Placement::Root, &Theme::default()),
+            "font-['Inter'] text-[15px] leading-[1.4] tracking-[0.08em]"
+        );
+    }
+
+    #[test]
+    fn test_theme_snapping() {
+        let theme = Theme::from_config(&json!({"theme": {"extend": {
+            "colors": {"brand": "#0A84FF"},
+            "spacing": {"gutter": "18px"},
+            "borderRadius": {"card": "10px"},
+            "fontFamily": {"body": ["Inter", "sans-serif"]}
+        }}}))
+        .unwrap();
+        let node = json!({
+            "stackMode": "HORIZONTAL",
+            "stackSpacing": 18.0,
+            "rectangleTopLeftCornerRadius": 10.0,
+            "rectangleBottomRightCornerRadius": 4.0,
+            "fillPaints": [{"color": "#0a84ff", "opacity": 0.5}],
+            "strokePaints": [{"color": "#0a84ff80"}],
+            "strokeWeight": 2.0
+        });
+
+        assert_eq!(
+            classes(node, Placement::Root, &theme),
+            "flex gap-gutter rounded-tl-card rounded-br bg-brand/50 border-2 border-brand/50"
+        );
+
+        let text = json!({"textData": {}, "fontName": {"family": "Inter"}});
+        assert_eq!(classes(text, Placement::Root, &theme), "font-body");
+    }
+
+    #[test]
+    fn test_add_classes_to_tree() {
+        let mut document = json!({"children": [{
+            "backgroundColor": "#f5f5f5",
+            "children": [{
+                "stackMode": "HORIZONTAL",
+                "children": [
+                    {"size": {"x": 16.0, "y": 16.0}},
+                    {"stackPositioning": "ABSOLUTE", "transform": {"x": 0.0, "y": 0.0}}
+                ]
+            }]
+        }]});
+
+        add_tailwind_classes(&mut document, &Theme::default()).unwrap();
+
+        let page = &document["children"][0];
+        assert!(page.get("tailwind").is_none());
+        let frame = &page["children"][0];
+        assert_eq!(frame["tailwind"], "relative flex");
+        assert_eq!(frame["children"][0]["tailwind"], "w-4 h-4 shrink-0");
+        assert_eq!(frame["children"][1]["tailwind"], "absolute left-0 top-0");
+    }
+
+    #[test]
+    fn test_fused_pass_matches_standalone_run() {
+        use crate::{PassContext, Pipeline};
+
+        let mut output = json!({"document": {"children": [{"children": [{
+            "type": {"__enum__": "NodeType", "value": "FRAME"},
+            "stackMode": {"__enum__": "StackMode", "value": "VERTICAL"},
+            "strokePaints": [{"color": {"r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0}}],
+            "strokeWeight": 2.0,
+            "children": [{
+                "type": {"__enum__": "NodeType", "value": "TEXT"},
+                "transform": {"m00": 1.0, "m01": 0.0, "m02": 4.0, "m10": 0.0, "m11": 1.0, "m12": 8.0},
+                "letterSpacing": {"units": "PERCENT", "value": 5.0},
+                "stackPositioning": {"__enum__": "StackPositioning", "value": "ABSOLUTE"}
+            }]
+        }]}]}});
+        let mut unfused = output.clone();
+        let ctx = PassContext::default();
+        let mut pipeline = Pipeline::standard();
+        pipeline.enable("tailwind").unwrap();
+
+        pipeline.run(&mut output, &ctx).unwrap();
+        pipeline.run_unfused(&mut unfused, &ctx).unwrap();
+
+        assert_eq!(output, unfused);
+        let frame = &output["document"]["children"][0]["children"][0];
+        assert_eq!(frame["tailwind"], "relative flex flex-col border-2 border-[#ff0000]");
+        assert_eq!(frame["children"][0]["tailwind"], "absolute left-1 top-2 tracking-wider");
+    }
+}
@@ -0,0 +1,443 @@
+use crate::error::{FigError, Result};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::BTreeMap;
+
+/// Named scales that Tailwind classes can snap to
+///
+/// [`Theme::default`] holds the default Tailwind scales that matter for
+/// Figma values (spacing, radii, font sizes and weights, tracking, leading,
+/// opacity, border widths) with only `black` and `white` as colors, since
+/// design colors rarely match the default palette. Project colors and custom
+/// scales come from [`Theme::from_config`].
+///
+/// Lengths are stored in pixels (`1rem` = 16px), letter spacings in `em` and
+/// opacities between 0 and 1. A `DEFAULT` key is stored as an empty name, so
+/// that it produces the bare utility (`rounded`, `border`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Colors as lowercase `#rrggbb` or `#rrggbbaa`, nested names joined with `-`
+    pub colors: BTreeMap<String, String>,
+    /// Spacing scale (width, height, gap, padding, position)
+    pub spacing: BTreeMap<String, f64>,
+    /// Border radius scale
+    pub border_radius: BTreeMap<String, f64>,
+    /// Border width scale
+    pub border_width: BTreeMap<String, f64>,
+    /// Font families, by their first family name
+    pub font_family: BTreeMap<String, String>,
+    /// Font size scale
+    pub font_size: BTreeMap<String, f64>,
+    /// Font weight scale
+    pub font_weight: BTreeMap<String, f64>,
+    /// Letter spacing scale in `em`
+    pub letter_spacing: BTreeMap<String, f64>,
+    /// Line heights given as lengths
+    pub line_height: BTreeMap<String, f64>,
+    /// Line heights given as unitless ratios of the font size
+    pub line_height_ratio: BTreeMap<String, f64>,
+    /// Opacity scale
+    pub opacity: BTreeMap<String, f64>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let mut spacing = scale(&[("0", 0.0), ("px", 1.0)]);
+        for step in [
+            0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+            14.0, 16.0, 20.0, 24.0, 28.0, 32.0, 36.0, 40.0, 44.0, 48.0, 52.0, 56.0, 60.0, 64.0,
+            72.0, 80.0, 96.0,
+        ] {
+            spacing.insert(format_key(step), step * 4.0);
+        }
+
+        let mut line_height = BTreeMap::new();
+        for step in 3..=10 {
+            line_height.insert(step.to_string(), step as f64 * 4.0);
+        }
+
+        let opacity = (0..=20)
+            .map(|step| ((step * 5).to_string(), step as f64 * 0.05))
+            .collect();
+
+        Self {
+            colors: [("black", "#000000"), ("white", "#ffffff")]
+                .into_iter()
+                .map(|(name, color)| (name.to_string(), color.to_string()))
+                .collect(),
+            spacing,
+            border_radius: scale(&[
+                ("none", 0.0),
+                ("sm", 2.0),
+                ("", 4.0),
+                ("md", 6.0),
+                ("lg", 8.0),
+                ("xl", 12.0),
+                ("2xl", 16.0),
+                ("3xl", 24.0),
+                ("full", 9999.0),
+            ]),
+            border_width: scale(&[("0", 0.0), ("", 1.0), ("2", 2.0), ("4", 4.0), ("8", 8.0)]),
+            font_family: BTreeMap::new(),
+            font_size: scale(&[
+                ("xs", 12.0),
+                ("sm", 14.0),
+                ("base", 16.0),
+                ("lg", 18.0),
+                ("xl", 20.0),
+                ("2xl", 24.0),
+                ("3xl", 30.0),
+                ("4xl", 36.0),
+                ("5xl", 48.0),
+                ("6xl", 60.0),
+                ("7xl", 72.0),
+                ("8xl", 96.0),
+                ("9xl", 128.0),
+            ]),
+            font_weight: scale(&[
+                ("thin", 100.0),
+                ("extralight", 200.0),
+                ("light", 300.0),
+                ("normal", 400.0),
+                ("medium", 500.0),
+                ("semibold", 600.0),
+                ("bold", 700.0),
+                ("extrabold", 800.0),
+                ("black", 900.0),
+            ]),
+            letter_spacing: scale(&[
+                ("tighter", -0.05),
+                ("tight", -0.025),
+                ("normal", 0.0),
+                ("wide", 0.025),
+                ("wider", 0.05),
+                ("widest", 0.1),
+            ]),
+            line_height,
+            line_height_ratio: scale(&[
+                ("none", 1.0),
+                ("tight", 1.25),
+                ("snug", 1.375),
+                ("normal", 1.5),
+                ("relaxed", 1.625),
+                ("loose", 2.0),
+            ]),
+            opacity,
+        }
+    }
+}
+
+impl Theme {
+    /// Build a theme from a Tailwind config converted to JSON
+    ///
+    /// Accepts either the whole config (`{"theme": {...}}`) or its `theme`
+    /// object. As in Tailwind, a section under `theme` replaces the default
+    /// scale while a section under `theme.extend` is merged into it. Values
+    /// that cannot be resolved statically (`var(--x)`, `calc(...)`, functions)
+    /// are ignored.
+    ///
+    /// # Arguments
+    /// * `config` - Parsed `tailwind.config` JSON
+    ///
+    /// # Returns
+    /// * `Ok(Theme)` - Default theme with the config applied
+    /// * `Err(FigError)` - If the config or one of its sections is not an object
+    ///
+    /// # Examples
+    /// ```
+    /// use fig2json::tailwind::Theme;
+    /// use serde_json::json;
+    ///
+    /// let theme = Theme::from_config(&json!({
+    ///     "theme": {"extend": {"colors": {"brand": {"DEFAULT": "#0A84FF", "dark": "#0060df"}}}}
+    /// }))
+    /// .unwrap();
+    ///
+    /// assert_eq!(theme.colors["brand"], "#0a84ff");
+    /// assert_eq!(theme.colors["brand-dark"], "#0060df");
+    /// assert_eq!(theme.colors["white"], "#ffffff");
+    /// ```
+    pub fn from_config(config: &JsonValue) -> Result<Self> {
+        let root = object(config, "config")?;
+        let theme = match root.get("theme") {
+            Some(theme) => object(theme, "theme")?,
+            None => root,
+        };
+
+        let mut result = Self::default();
+        result.apply(theme, "theme", true)?;
+        if let Some(extend) = theme.get("extend") {
+            result.apply(object(extend, "theme.extend")?, "theme.extend", false)?;
+        }
+        Ok(result)
+    }
+
+    /// Apply the sections of a `theme` (replacing) or `theme.extend` (merging) object
+    fn apply(&mut self, sections: &Map<String, JsonValue>, path: &str, replace: bool) -> Result<()> {
+        for (key, value) in sections {
+            let section = || object(value, &format!("{}.{}", path, key));
+            match key.as_str() {
+                "colors" => {
+                    let mut colors = BTreeMap::new();
+                    flatten_colors(section()?, "", &mut colors);
+                    merge(&mut self.colors, colors, replace);
+                }
+                "fontFamily" => {
+                    let families = section()?
+                        .iter()
+                        .filter_map(|(name, value)| Some((token(name), first_family(value)?)))
+                        .collect();
+                    merge(&mut self.font_family, families, replace);
+                }
+                "lineHeight" => {
+                    let mut lengths = BTreeMap::new();
+                    let mut ratios = BTreeMap::new();
+                    for (name, value) in section()? {
+                        match value {
+                            JsonValue::Number(n) => {
+                                ratios.insert(token(name), n.as_f64().unwrap_or(0.0));
+                            }
+                            JsonValue::String(s) => match s.trim().parse::<f64>() {
+                                Ok(ratio) => {
+                                    ratios.insert(token(name), ratio);
+                                }
+                                Err(_) => {
+                                    if let Some(length) = parse_length(s) {
+                                        lengths.insert(token(name), length);
+                                    }
+                                }
+                            },
+                            _ => {}
+                        }
+                    }
+                    merge(&mut self.line_height, lengths, replace);
+                    merge(&mut self.line_height_ratio, ratios, replace);
+                }
+                "spacing" => merge(&mut self.spacing, lengths(section()?), replace),
+                "borderRadius" => merge(&mut self.border_radius, lengths(section()?), replace),
+                "borderWidth" => merge(&mut self.border_width, lengths(section()?), replace),
+                "fontSize" => merge(&mut self.font_size, lengths(section()?), replace),
+                "fontWeight" => merge(&mut self.font_weight, numbers(section()?), replace),
+                "opacity" => merge(&mut self.opacity, numbers(section()?), replace),
+                "letterSpacing" => {
+                    let spacings = section()?
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            let em = value.as_str()?.trim().strip_suffix("em")?;
+                            Some((token(name), em.parse().ok()?))
+                        })
+                        .collect();
+                    merge(&mut self.letter_spacing, spacings, replace);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replace or extend a scale
+fn merge<T>(target: &mut BTreeMap<String, T>, values: BTreeMap<String, T>, replace: bool) {
+    if replace {
+        *target = values;
+    } else {
+        target.extend(values);
+    }
+}
+
+fn scale(entries: &[(&str, f64)]) -> BTreeMap<String, f64> {
+    entries
+        .iter()
+        .map(|(name, value)| (name.to_string(), *value))
+        .collect()
+}
+
+fn object<'a>(value: &'a JsonValue, path: &str) -> Result<&'a Map<String, JsonValue>> {
+    value
+        .as_object()
+        .ok_or_else(|| FigError::InvalidTheme(format!("{} must be an object", path)))
+}
+
+/// Class name of a scale key (`DEFAULT` maps to the bare utility)
+fn token(name: &str) -> String {
+    if name == "DEFAULT" {
+        String::new()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Scale key of a default spacing step (0.5 → "0.5", 4.0 → "4")
+fn format_key(step: f64) -> String {
+    if step.fract() == 0.0 {
+        format!("{}", step as i64)
+    } else {
+        step.to_string()
+    }
+}
+
+/// Flatten nested color objects into `parent-child` names
+fn flatten_colors(colors: &Map<String, JsonValue>, prefix: &str, out: &mut BTreeMap<String, String>) {
+    for (name, value) in colors {
+        let name = match (prefix, name.as_str()) {
+            (prefix, "DEFAULT") => prefix.to_string(),
+            ("", name) => name.to_string(),
+            (prefix, name) => format!("{}-{}", prefix, name),
+        };
+        match value {
+            JsonValue::String(color) => {
+                if let Some(color) = parse_hex(color) {
+                    out.insert(name, color);
+                }
+            }
+            JsonValue::Object(nested) => flatten_colors(nested, &name, out),
+            _ => {}
+        }
+    }
+}
+
+/// Normalize `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` to lowercase `#rrggbb[aa]`
+///
+/// Fully opaque alpha is dropped.
+pub(crate) fn parse_hex(color: &str) -> Option<String> {
+    let hex = color.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_ascii_lowercase();
+    let full = match hex.len() {
+        3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex,
+        _ => return None,
+    };
+    if full.len() == 8 && full.ends_with("ff") {
+        Some(format!("#{}", &full[..6]))
+    } else {
+        Some(format!("#{}", full))
+    }
+}
+
+/// Length in pixels of a CSS value (`16px`, `1rem`, `0`), numbers are pixels
+fn parse_length(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some(px) = value.strip_suffix("px") {
+        px.trim().parse().ok()
+    } else if let Some(rem) = value.strip_suffix("rem") {
+        rem.trim().parse::<f64>().ok().map(|rem| rem * 16.0)
+    } else {
+        value.parse().ok()
+    }
+}
+
+/// Length entries of a section; `fontSize` tuples use their first element
+fn lengths(section: &Map<String, JsonValue>) -> BTreeMap<String, f64> {
+    section
+        .iter()
+        .filter_map(|(name, value)| {
+            let value = match value {
+                JsonValue::Array(items) => items.first()?,
+                value => value,
+            };
+            let length = match value {
+                JsonValue::Number(n) => n.as_f64()?,
+                JsonValue::String(s) => parse_length(s)?,
+                _ => return None,
+            };
+            Some((token(name), length))
+        })
+        .collect()
+}
+
+/// Plain number entries of a section, given as numbers or strings
+fn numbers(section: &Map<String, JsonValue>) -> BTreeMap<String, f64> {
+    section
+        .iter()
+        .filter_map(|(name, value)| {
+            let number = match value {
+                JsonValue::Number(n) => n.as_f64()?,
+                JsonValue::String(s) => s.trim().parse().ok()?,
+                _ => return None,
+            };
+            Some((token(name), number))
+        })
+        .collect()
+}
+
+/// First family of a `fontFamily` entry (`["Inter", "sans-serif"]` or `"Inter, sans-serif"`)
+fn first_family(value: &JsonValue) -> Option<String> {
+    let first = match value {
+        JsonValue::Array(items) => items.first()?.as_str()?,
+        JsonValue::String(s) => s.split(',').next()?,
+        _ => return None,
+    };
+    let family = first.trim().trim_matches(|c| c == '"' || c == '\'');
+    (!family.is_empty()).then(|| family.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_default_scales() {
+        let theme = Theme::default();
+
+        assert_eq!(theme.spacing["px"], 1.0);
+        assert_eq!(theme.spacing["0.5"], 2.0);
+        assert_eq!(theme.spacing["96"], 384.0);
+        assert_eq!(theme.border_radius[""], 4.0);
+        assert_eq!(theme.line_height["5"], 20.0);
+        assert!((theme.opacity["35"] - 0.35).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_theme_replaces_and_extend_merges() {
+        let theme = Theme::from_config(&json!({
+            "spacing": {"sm": "0.5rem", "lg": "24px", "fluid": "clamp(1rem, 2vw, 2rem)"},
+            "extend": {
+                "borderRadius": {"card": "10px"},
+                "fontSize": {"title": ["1.75rem", {"lineHeight": "2rem"}]},
+                "fontFamily": {"display": ["\"Playfair Display\"", "serif"]},
+                "lineHeight": {"tall": "1.8", "12": "3rem"},
+                "letterSpacing": {"caps": "0.08em"}
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(theme.spacing, scale(&[("sm", 8.0), ("lg", 24.0)]));
+        assert_eq!(theme.border_radius["card"], 10.0);
+        assert_eq!(theme.border_radius["lg"], 8.0);
+        assert_eq!(theme.font_size["title"], 28.0);
+        assert_eq!(theme.font_family["display"], "Playfair Display");
+        assert_eq!(theme.line_height_ratio["tall"], 1.8);
+        assert_eq!(theme.line_height["12"], 48.0);
+        assert_eq!(theme.letter_spacing["caps"], 0.08);
+    }
+
+    #[test]
+    fn test_colors() {
+        let theme = Theme::from_config(&json!({
+            "theme": {"colors": {
+                "ink": "#111",
+                "overlay": "#000000FF",
+                "glass": "#ffffff80",
+                "accent": {"DEFAULT": "#FF5500", "100": "#ffeedd"},
+                "current": "currentColor"
+            }}
+        }))
+        .unwrap();
+
+        assert_eq!(theme.colors.len(), 5);
+        assert_eq!(theme.colors["ink"], "#111111");
+        assert_eq!(theme.colors["overlay"], "#000000");
+        assert_eq!(theme.colors["glass"], "#ffffff80");
+        assert_eq!(theme.colors["accent"], "#ff5500");
+        assert_eq!(theme.colors["accent-100"], "#ffeedd");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(Theme::from_config(&json!([])).is_err());
+        assert!(Theme::from_config(&json!({"theme": {"colors": "blue"}})).is_err());
+    }
+}
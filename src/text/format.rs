@@ -0,0 +1,202 @@
+//! Output formats of the text extraction
+//!
+//! - [`to_csv`]: one row per string, for spreadsheets and copy review
+//! - [`to_xliff`]: an XLIFF 1.2 file with one `trans-unit` per string, for
+//!   translation tools
+//! - [`to_json`]: a string table from key to text, in document order
+
+use super::{TextEntry, TextSource};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::fmt::Write;
+
+/// Columns of the CSV output
+const CSV_HEADER: &str = "key,page,frame,id,source,text";
+
+/// Write the entries as CSV (RFC 4180), with a header row
+///
+/// # Arguments
+/// * `entries` - Result of [`extract`](super::extract)
+///
+/// # Returns
+/// The `key,page,frame,id,source,text` header and one row per entry
+pub fn to_csv(entries: &[TextEntry]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push_str("\r\n");
+    for entry in entries {
+        let source = match entry.source {
+            TextSource::Node => "node",
+            TextSource::Override => "override",
+        };
+        let fields = [
+            entry.key.as_str(),
+            entry.page(),
+            entry.frame(),
+            entry.id.as_str(),
+            source,
+            entry.text.as_str(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Write the entries as an XLIFF 1.2 document
+///
+/// Each entry becomes a `trans-unit` whose `id` is the node id, which stays
+/// the same when layers are renamed, and whose `resname` is the entry key;
+/// the texts are sources without targets.
+///
+/// # Arguments
+/// * `entries` - Result of [`extract`](super::extract)
+/// * `original` - Name of the source file, for the `original` attribute
+/// * `source_language` - Language of the texts, e.g. `en`
+///
+/// # Returns
+/// The XLIFF document, ending with a newline
+pub fn to_xliff(entries: &[TextEntry], original: &str, source_language: &str) -> String {
+    let mut xliff = String::new();
+    xliff.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xliff.push_str("<xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">\n");
+    let _ = writeln!(
+        xliff,
+        "  <file original=\"{}\" source-language=\"{}\" datatype=\"plaintext\">",
+        escape(original),
+        escape(source_language)
+    );
+    xliff.push_str("    <body>\n");
+    for entry in entries {
+        let _ = writeln!(
+            xliff,
+            "      <trans-unit id=\"{}\" resname=\"{}\" xml:space=\"preserve\">",
+            escape(&entry.id),
+            escape(&entry.key)
+        );
+        let _ = writeln!(xliff, "        <source>{}</source>", escape(&entry.text));
+        if entry.source == TextSource::Override {
+            xliff.push_str("        <note>Instance override</note>\n");
+        }
+        xliff.push_str("      </trans-unit>\n");
+    }
+    xliff.push_str("    </body>\n  </file>\n</xliff>\n");
+    xliff
+}
+
+/// Write the entries as a JSON object from key to text
+///
+/// Keys keep the document order of the entries.
+///
+/// # Arguments
+/// * `entries` - Result of [`extract`](super::extract)
+/// * `compact` - Compact JSON instead of pretty-printed
+///
+/// # Returns
+/// The JSON object, ending with a newline
+pub fn to_json(entries: &[TextEntry], compact: bool) -> String {
+    let table = StringTable(entries);
+    let json = if compact {
+        serde_json::to_string(&table)
+    } else {
+        serde_json::to_string_pretty(&table)
+    };
+    // Serializing strings to a String cannot fail
+    format!("{}\n", json.unwrap_or_default())
+}
+
+/// Entries serialized as a map, without going through `serde_json::Map`,
+/// which would sort the keys
+struct StringTable<'a>(&'a [TextEntry]);
+
+impl Serialize for StringTable<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for entry in self.0 {
+            map.serialize_entry(&entry.key, &entry.text)?;
+        }
+        map.end()
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escape text and attribute values, dropping the control characters XML 1.0
+/// does not allow
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<TextEntry> {
+        vec![
+            TextEntry {
+                id: "1:3".to_string(),
+                key: "Home/Header/Title".to_string(),
+                path: vec!["Home".into(), "Header".into(), "Title".into()],
+                text: "Say \"hi\", <friend>\nthere".to_string(),
+                source: TextSource::Node,
+            },
+            TextEntry {
+                id: "1:7/1:31".to_string(),
+                key: "Home/Actions/Label".to_string(),
+                path: vec!["Home".into(), "Actions".into(), "Label".into()],
+                text: "Delete".to_string(),
+                source: TextSource::Override,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&entries()),
+            "key,page,frame,id,source,text\r\n\
+             Home/Header/Title,Home,Header,1:3,node,\"Say \"\"hi\"\", <friend>\nthere\"\r\n\
+             Home/Actions/Label,Home,Actions,1:7/1:31,override,Delete\r\n"
+        );
+    }
+
+    #[test]
+    fn test_to_xliff() {
+        let xliff = to_xliff(&entries(), "design.fig", "en");
+
+        assert!(xliff.contains("<file original=\"design.fig\" source-language=\"en\""));
+        assert!(xliff.contains(
+            "<trans-unit id=\"1:3\" resname=\"Home/Header/Title\" xml:space=\"preserve\">\n        \
+             <source>Say &quot;hi&quot;, &lt;friend&gt;\nthere</source>\n      \
+             </trans-unit>"
+        ));
+        assert!(xliff.contains("<note>Instance override</note>"));
+        assert!(xliff.ends_with("</xliff>\n"));
+    }
+
+    #[test]
+    fn test_to_json_keeps_order() {
+        assert_eq!(
+            to_json(&entries(), true),
+            "{\"Home/Header/Title\":\"Say \\\"hi\\\", <friend>\\nthere\",\"Home/Actions/Label\":\"Delete\"}\n"
+        );
+    }
+}
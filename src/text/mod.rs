@@ -0,0 +1,366 @@
+//! Text content extraction
+//!
+//! [`extract`] lists every string of a design for copy review and
+//! localization, without the layout:
+//!
+//! - the `textData.characters` of each TEXT node
+//! - the text overrides of each INSTANCE (`symbolData.symbolOverrides`
+//!   entries with `textData`), which replace the strings of the main
+//!   component in that instance only
+//!
+//! Strings are listed in document order, the child order `build_tree` gives
+//! each frame, and keyed by the names of the page, frame and nodes leading to
+//! them (`Home/Header/Title`). Internal-only pages are skipped.
+//!
+//! [`format`] writes the entries as CSV, XLIFF 1.2 or a JSON string table.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::text::{extract, format::to_csv};
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let raw = fig2json::convert_raw(&bytes).unwrap();
+//!
+//! print!("{}", to_csv(&extract(&raw)));
+//! ```
+
+pub mod format;
+
+use crate::schema::tree::{guid_key, node_type};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::{HashMap, HashSet};
+
+/// A string of the design
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TextEntry {
+    /// GUID of the TEXT node, or GUID of the instance followed by the
+    /// override's GUID path (`1:7/1:20`)
+    pub id: String,
+    /// Names from the page to the node joined with `/`, unique in the file
+    /// (` (2)`, ` (3)`, ... are appended to repeated paths)
+    pub key: String,
+    /// Names from the page to the node
+    pub path: Vec<String>,
+    pub text: String,
+    pub source: TextSource,
+}
+
+/// Where a string comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TextSource {
+    /// A TEXT node
+    Node,
+    /// A text override of an instance
+    Override,
+}
+
+impl TextEntry {
+    /// Name of the page
+    pub fn page(&self) -> &str {
+        self.path.first().map(String::as_str).unwrap_or("")
+    }
+
+    /// Name of the top-level frame, empty for strings directly on a page
+    pub fn frame(&self) -> &str {
+        match self.path.len() {
+            0..=2 => "",
+            _ => &self.path[1],
+        }
+    }
+}
+
+/// List the strings of a file
+///
+/// # Arguments
+/// * `output` - Output of [`convert_raw`](crate::convert_raw), or its
+///   `document` node
+///
+/// # Returns
+/// Non-empty strings in document order
+///
+/// # Examples
+/// ```
+/// use fig2json::text::{extract, TextSource};
+/// use serde_json::json;
+///
+/// let guid = |local: u64| json!({"sessionID": 1, "localID": local});
+/// let document = json!({"children": [{"type": "CANVAS", "name": "Home", "children": [
+///     {"type": "SYMBOL", "name": "Button", "guid": guid(1), "children": [
+///         {"type": "TEXT", "name": "Label", "guid": guid(2), "textData": {"characters": "OK"}}
+///     ]},
+///     {"type": "INSTANCE", "name": "Cancel", "guid": guid(3), "symbolData": {
+///         "symbolID": guid(1),
+///         "symbolOverrides": [{"guidPath": {"guids": [guid(2)]}, "textData": {"characters": "Cancel"}}]
+///     }}
+/// ]}]});
+/// let entries = extract(&document);
+///
+/// assert_eq!(entries[0].key, "Home/Button/Label");
+/// assert_eq!(entries[1].key, "Home/Cancel/Label");
+/// assert_eq!(entries[1].id, "1:3/1:2");
+/// assert_eq!(entries[1].text, "Cancel");
+/// assert_eq!(entries[1].source, TextSource::Override);
+/// ```
+pub fn extract(output: &JsonValue) -> Vec<TextEntry> {
+    let Some(document) = output.get("document").unwrap_or(output).as_object() else {
+        return Vec::new();
+    };
+
+    let mut nodes = HashMap::new();
+    index_nodes(document, &mut nodes);
+
+    let mut walker = Walker {
+        nodes,
+        entries: Vec::new(),
+        keys: HashSet::new(),
+        suffixes: HashMap::new(),
+    };
+    for page in children(document) {
+        if page.get("internalOnly").and_then(|v| v.as_bool()) != Some(true) {
+            walker.walk(page, &[]);
+        }
+    }
+    walker.entries
+}
+
+/// Collects the entries, with the nodes by GUID to name override targets
+struct Walker<'a> {
+    nodes: HashMap<String, &'a Map<String, JsonValue>>,
+    entries: Vec<TextEntry>,
+    /// Keys handed out so far
+    keys: HashSet<String>,
+    /// Last suffix tried for each repeated path
+    suffixes: HashMap<String, usize>,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, node: &Map<String, JsonValue>, ancestors: &[String]) {
+        let mut path = ancestors.to_vec();
+        path.push(name(node).to_string());
+        let id = node.get("guid").and_then(guid_key).unwrap_or_default();
+
+        match node_type(node) {
+            Some("TEXT") => {
+                if let Some(text) = characters(node) {
+                    self.push(id, path.clone(), text, TextSource::Node);
+                }
+            }
+            Some("INSTANCE") => self.overrides(node, &id, &path),
+            _ => {}
+        }
+
+        for child in children(node) {
+            self.walk(child, &path);
+        }
+    }
+
+    /// Text overrides of an instance, in the order they are stored
+    fn overrides(&mut self, instance: &Map<String, JsonValue>, id: &str, path: &[String]) {
+        let overrides = instance
+            .get("symbolData")
+            .and_then(|d| d.get("symbolOverrides"))
+            .and_then(|o| o.as_array());
+        for entry in overrides
+            .into_iter()
+            .flatten()
+            .filter_map(|o| o.as_object())
+        {
+            let Some(text) = characters(entry) else {
+                continue;
+            };
+            let guids: Vec<String> = entry
+                .get("guidPath")
+                .and_then(|p| p.get("guids"))
+                .and_then(|g| g.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(guid_key)
+                .collect();
+            if guids.is_empty() {
+                continue;
+            }
+
+            let mut path = path.to_vec();
+            for guid in &guids {
+                let node = self.nodes.get(guid);
+                path.push(node.map(|n| name(n)).unwrap_or(guid).to_string());
+            }
+            let id = format!("{}/{}", id, guids.join("/"));
+            self.push(id, path, text, TextSource::Override);
+        }
+    }
+
+    fn push(&mut self, id: String, path: Vec<String>, text: &str, source: TextSource) {
+        let key = self.unique_key(path.join("/"));
+        self.entries.push(TextEntry {
+            id,
+            key,
+            path,
+            text: text.to_string(),
+            source,
+        });
+    }
+
+    /// `key`, or `key (2)`, `key (3)`, ... when already taken
+    fn unique_key(&mut self, key: String) -> String {
+        if self.keys.insert(key.clone()) {
+            return key;
+        }
+
+        // Skip suffixed keys already taken literally (a node named "Title (2)")
+        let suffix = self.suffixes.entry(key.clone()).or_insert(1);
+        loop {
+            *suffix += 1;
+            let candidate = format!("{} ({})", key, suffix);
+            if self.keys.insert(candidate.clone()) {
+                return candidate;
+            }
+        }
+    }
+}
+
+/// Index the nodes of a tree by GUID
+fn index_nodes<'a>(
+    node: &'a Map<String, JsonValue>,
+    nodes: &mut HashMap<String, &'a Map<String, JsonValue>>,
+) {
+    if let Some(id) = node.get("guid").and_then(guid_key) {
+        nodes.insert(id, node);
+    }
+    for child in children(node) {
+        index_nodes(child, nodes);
+    }
+}
+
+/// Non-empty `textData.characters` of a node or override
+fn characters(value: &Map<String, JsonValue>) -> Option<&str> {
+    value
+        .get("textData")
+        .and_then(|t| t.get("characters"))
+        .and_then(|c| c.as_str())
+        .filter(|c| !c.is_empty())
+}
+
+fn name(node: &Map<String, JsonValue>) -> &str {
+    node.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+/// Object children of a node
+fn children(node: &Map<String, JsonValue>) -> impl Iterator<Item = &Map<String, JsonValue>> {
+    node.get("children")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|child| child.as_object())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn guid(local: u64) -> JsonValue {
+        json!({"sessionID": 1, "localID": local})
+    }
+
+    fn enum_type(value: &str) -> JsonValue {
+        json!({"__enum__": "NodeType", "value": value})
+    }
+
+    fn text(local: u64, name: &str, characters: &str) -> JsonValue {
+        json!({"type": enum_type("TEXT"), "name": name, "guid": guid(local), "textData": {"characters": characters}})
+    }
+
+    fn raw_output() -> JsonValue {
+        json!({"version": 101, "document": {"type": enum_type("DOCUMENT"), "name": "Document", "children": [
+            {"type": enum_type("CANVAS"), "name": "Home", "guid": guid(1), "children": [
+                {"type": enum_type("FRAME"), "name": "Header", "guid": guid(2), "children": [
+                    text(3, "Title", "Welcome back"),
+                    text(4, "Title", "Sign in to continue"),
+                    text(5, "Empty", "")
+                ]},
+                {"type": enum_type("FRAME"), "name": "Dialog", "guid": guid(6), "children": [
+                    {"type": enum_type("INSTANCE"), "name": "Actions", "guid": guid(7), "symbolData": {
+                        "symbolID": guid(20),
+                        "symbolOverrides": [
+                            {"guidPath": {"guids": [guid(22), guid(31)]}, "textData": {"characters": "Delete"}},
+                            {"guidPath": {"guids": [guid(22)]}, "visible": false}
+                        ]
+                    }}
+                ]},
+                text(8, "Note", "Draft")
+            ]},
+            {"type": enum_type("CANVAS"), "name": "Components", "guid": guid(10), "children": [
+                {"type": enum_type("SYMBOL"), "name": "Button bar", "guid": guid(20), "children": [
+                    {"type": enum_type("INSTANCE"), "name": "Primary", "guid": guid(22), "symbolData": {"symbolID": guid(30)}}
+                ]},
+                {"type": enum_type("SYMBOL"), "name": "Button", "guid": guid(30), "children": [text(31, "Label", "OK")]}
+            ]},
+            {"type": enum_type("CANVAS"), "name": "Internal Only Canvas", "internalOnly": true, "children": [
+                text(40, "Label", "Hidden")
+            ]}
+        ]}})
+    }
+
+    #[test]
+    fn test_document_order() {
+        let entries = extract(&raw_output());
+        let texts: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+
+        assert_eq!(
+            texts,
+            [
+                "Welcome back",
+                "Sign in to continue",
+                "Delete",
+                "Draft",
+                "OK"
+            ]
+        );
+        assert_eq!(entries[0].page(), "Home");
+        assert_eq!(entries[0].frame(), "Header");
+        // Strings directly on a page have no frame
+        assert_eq!(entries[3].frame(), "");
+    }
+
+    #[test]
+    fn test_override_paths() {
+        let entries = extract(&raw_output());
+        let delete = &entries[2];
+
+        assert_eq!(delete.id, "1:7/1:22/1:31");
+        assert_eq!(delete.key, "Home/Dialog/Actions/Primary/Label");
+        assert_eq!(delete.source, TextSource::Override);
+        assert_eq!(entries[4].source, TextSource::Node);
+    }
+
+    #[test]
+    fn test_unique_keys() {
+        let entries = extract(&raw_output());
+
+        assert_eq!(entries[0].key, "Home/Header/Title");
+        assert_eq!(entries[1].key, "Home/Header/Title (2)");
+        assert_eq!(entries[1].path, ["Home", "Header", "Title"]);
+    }
+
+    #[test]
+    fn test_unique_keys_skip_literal_names() {
+        let output = json!({"document": {"type": enum_type("DOCUMENT"), "children": [
+            {"type": enum_type("CANVAS"), "name": "Home", "guid": guid(1), "children": [
+                text(2, "Title", "First"),
+                text(3, "Title (2)", "Literal"),
+                text(4, "Title", "Second"),
+                text(5, "Title (2)", "Literal again")
+            ]}
+        ]}});
+
+        let keys: Vec<String> = extract(&output).into_iter().map(|e| e.key).collect();
+        assert_eq!(
+            keys,
+            ["Home/Title", "Home/Title (2)", "Home/Title (3)", "Home/Title (2) (2)"]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Token serialization
+//!
+//! - [`to_design_tokens`]: W3C Design Tokens JSON, one nested group per path
+//!   segment and `$type` / `$value` on each token (readable by Style Dictionary)
+//! - [`to_css`]: custom properties on `:root`, composite tokens (typography,
+//!   shadows) expanded to CSS values
+
+use super::Token;
+use crate::css::format_number;
+use serde_json::{Map, Value as JsonValue};
+
+/// W3C Design Tokens document
+///
+/// # Arguments
+/// * `tokens` - Tokens from [`extract_tokens`](super::extract_tokens)
+///
+/// # Returns
+/// Nested groups keyed by path segment, e.g.
+/// `{"color": {"brand": {"primary": {"$type": "color", "$value": "#0a84ff"}}}}`
+pub fn to_design_tokens(tokens: &[Token]) -> JsonValue {
+    let mut root = Map::new();
+    for token in tokens {
+        let Some((name, groups)) = token.path.split_last() else {
+            continue;
+        };
+
+        let mut group = &mut root;
+        for segment in groups {
+            let entry = group
+                .entry(segment.clone())
+                .or_insert_with(|| JsonValue::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = JsonValue::Object(Map::new());
+            }
+            group = entry.as_object_mut().expect("group object");
+        }
+
+        let mut leaf = Map::new();
+        leaf.insert("$type".to_string(), JsonValue::from(token.token_type));
+        leaf.insert("$value".to_string(), token.value.clone());
+        group.insert(name.clone(), JsonValue::Object(leaf));
+    }
+    JsonValue::Object(root)
+}
+
+/// CSS custom properties
+///
+/// Typography tokens become one property per font property
+/// (`--typography-body-font-size`), shadows a `box-shadow` value.
+///
+/// # Arguments
+/// * `tokens` - Tokens from [`extract_tokens`](super::extract_tokens)
+///
+/// # Returns
+/// A `:root` rule declaring one property per token
+pub fn to_css(tokens: &[Token]) -> String {
+    let mut css = String::from(":root {\n");
+    for token in tokens {
+        let name = format!("--{}", token.path.join("-"));
+        for (suffix, value) in css_values(token) {
+            css.push_str(&format!("  {}{}: {};\n", name, suffix, value));
+        }
+    }
+    css.push_str("}\n");
+    css
+}
+
+/// CSS values of a token, with the property name suffix of each
+fn css_values(token: &Token) -> Vec<(&'static str, String)> {
+    let value = &token.value;
+    match token.token_type {
+        "typography" => {
+            let properties = [
+                ("-font-family", "fontFamily"),
+                ("-font-weight", "fontWeight"),
+                ("-font-size", "fontSize"),
+                ("-line-height", "lineHeight"),
+                ("-letter-spacing", "letterSpacing"),
+            ];
+            properties
+                .iter()
+                .filter_map(|(suffix, key)| {
+                    let value = match value.get(key)? {
+                        JsonValue::String(text) if *key == "fontFamily" => {
+                            format!("\"{}\"", text.replace('"', "\\\""))
+                        }
+                        JsonValue::String(text) => text.clone(),
+                        JsonValue::Number(number) => {
+                            format_number(number.as_f64().unwrap_or_default())
+                        }
+                        _ => return None,
+                    };
+                    Some((*suffix, value))
+                })
+                .collect()
+        }
+        "shadow" => {
+            let shadows = match value {
+                JsonValue::Array(shadows) => shadows.iter().map(box_shadow).collect(),
+                shadow => vec![box_shadow(shadow)],
+            };
+            vec![("", shadows.join(", "))]
+        }
+        _ => match value.as_str() {
+            Some(text) => vec![("", text.to_string())],
+            None => vec![("", value.to_string())],
+        },
+    }
+}
+
+fn box_shadow(shadow: &JsonValue) -> String {
+    let field = |key: &str| shadow.get(key).and_then(|v| v.as_str()).unwrap_or("0");
+    let inset = if shadow.get("inset").and_then(|v| v.as_bool()) == Some(true) {
+        "inset "
+    } else {
+        ""
+    };
+    format!(
+        "{}{} {} {} {} {}",
+        inset,
+        field("offsetX"),
+        field("offsetY"),
+        field("blur"),
+        field("spread"),
+        field("color")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tokens() -> Vec<Token> {
+        vec![
+            Token {
+                path: vec!["color".into(), "brand".into(), "primary".into()],
+                token_type: "color",
+                value: json!("#0a84ff"),
+            },
+            Token {
+                path: vec!["typography".into(), "body".into()],
+                token_type: "typography",
+                value: json!({"fontFamily": "Inter", "fontWeight": 400, "fontSize": "16px", "lineHeight": 1.5}),
+            },
+            Token {
+                path: vec!["shadow".into(), "card".into()],
+                token_type: "shadow",
+                value: json!([
+                    {"color": "#00000040", "offsetX": "0", "offsetY": "4px", "blur": "8px", "spread": "0"},
+                    {"color": "#ffffff", "offsetX": "0", "offsetY": "1px", "blur": "0", "spread": "0", "inset": true}
+                ]),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_to_design_tokens() {
+        let document = to_design_tokens(&tokens());
+
+        assert_eq!(
+            document["color"]["brand"]["primary"],
+            json!({"$type": "color", "$value": "#0a84ff"})
+        );
+        assert_eq!(
+            document["typography"]["body"]["$value"]["lineHeight"],
+            json!(1.5)
+        );
+        assert_eq!(document["shadow"]["card"]["$type"], json!("shadow"));
+    }
+
+    #[test]
+    fn test_to_css() {
+        let css = to_css(&tokens());
+
+        assert_eq!(
+            css,
+            ":root {\n  \
+             --color-brand-primary: #0a84ff;\n  \
+             --typography-body-font-family: \"Inter\";\n  \
+             --typography-body-font-weight: 400;\n  \
+             --typography-body-font-size: 16px;\n  \
+             --typography-body-line-height: 1.5;\n  \
+             --shadow-card: 0 4px 8px 0 #00000040, inset 0 1px 0 0 #ffffff;\n\
+             }\n"
+        );
+    }
+}
@@ -0,0 +1,600 @@
+//! Design token extraction
+//!
+//! [`extract_tokens`] collects the distinct design values used by the visible
+//! nodes of a document:
+//!
+//! - colors of solid fills and strokes
+//! - typography combinations (family, weight, size, line height, letter spacing)
+//! - corner radii
+//! - auto-layout spacings and paddings
+//! - drop and inner shadows
+//!
+//! Every style of the root `styles` table (see the `style_resolution` pass) is
+//! exported under its name ("Brand/Primary" becomes `color.brand.primary`), and
+//! nodes referencing a style by name (`styleIdForFill`, `styleIdForStrokeFill`,
+//! `styleIdForText`, `styleIdForEffect`) use its token. Other values get a name
+//! derived from the value.
+//!
+//! Tokens are written as a W3C Design Tokens file with [`to_design_tokens`] and
+//! as CSS custom properties with [`to_css`]. The input is the output of
+//! [`pipeline`], which keeps the paint and effect types and the effect objects
+//! that the standard pipeline drops.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::tokens::{extract_tokens, pipeline, to_css, to_design_tokens};
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let json = fig2json::convert_with_pipeline(&bytes, None, &pipeline()).unwrap();
+//!
+//! let tokens = extract_tokens(&json);
+//! std::fs::write("tokens.json", serde_json::to_string_pretty(&to_design_tokens(&tokens)).unwrap()).unwrap();
+//! std::fs::write("tokens.css", to_css(&tokens)).unwrap();
+//! ```
+
+pub mod format;
+
+pub use format::{to_css, to_design_tokens};
+
+use crate::css::{color_with_opacity, format_number, px};
+use crate::html::css::font_weight;
+use crate::html::slug;
+use crate::schema::tree::enum_str;
+use crate::Pipeline;
+use serde_json::{json, Map, Value as JsonValue};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Passes of the standard pipeline that drop the paint and effect types and
+/// the auto-layout spacings, or replace the effects with CSS
+const DISABLED_PASSES: &[&str] = &["type_removal", "effects_to_css", "redundant_padding_removal"];
+
+/// Token groups, in output order
+const GROUPS: &[&str] = &["color", "typography", "radius", "spacing", "shadow"];
+
+/// A design token
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    /// Group followed by the name segments (`["color", "brand", "primary"]`)
+    pub path: Vec<String>,
+    /// W3C `$type`: `color`, `typography`, `dimension` or `shadow`
+    pub token_type: &'static str,
+    /// W3C `$value`
+    pub value: JsonValue,
+}
+
+/// Standard pipeline without the passes removing paint and effect types and
+/// auto-layout properties, and converting effects to CSS
+pub fn pipeline() -> Pipeline {
+    Pipeline::standard_without(DISABLED_PASSES)
+}
+
+/// Collect the design tokens of a document
+///
+/// # Arguments
+/// * `output` - Output of [`convert_with_pipeline`](crate::convert_with_pipeline)
+///   with [`pipeline`], or a `document` node without styles
+///
+/// # Returns
+/// Tokens grouped by kind (colors, typography, radii, spacings, shadows), each
+/// group sorted by name
+///
+/// # Examples
+/// ```
+/// use fig2json::tokens::extract_tokens;
+/// use serde_json::json;
+///
+/// let document = json!({"children": [{"children": [
+///     {"fillPaints": [{"type": "SOLID", "color": "#0a84ff"}], "cornerRadius": 8.0}
+/// ]}]});
+/// let tokens = extract_tokens(&document);
+///
+/// assert_eq!(tokens[0].path, ["color", "0a84ff"]);
+/// assert_eq!(tokens[1].path, ["radius", "8"]);
+/// ```
+pub fn extract_tokens(output: &JsonValue) -> Vec<Token> {
+    let document = output.get("document").unwrap_or(output);
+    let mut collector = Collector::default();
+    if let Some(styles) = output.get("styles").and_then(|s| s.as_object()) {
+        collector.collect_styles(styles);
+    }
+    collector.visit(document);
+    collector.finish()
+}
+
+#[derive(Default)]
+struct Collector {
+    /// Named tokens by style name
+    named: BTreeMap<String, Token>,
+    /// Unnamed values by group, in order of appearance
+    values: BTreeMap<&'static str, Vec<(String, JsonValue)>>,
+}
+
+impl Collector {
+    /// Export each style of the `styles` table that has a usable value
+    fn collect_styles(&mut self, styles: &Map<String, JsonValue>) {
+        for (name, style) in styles {
+            let Some(style) = style.as_object() else {
+                continue;
+            };
+            let (group, token_type, value) = match style_type(style) {
+                Some("FILL") => match solid_colors(style.get("fillPaints")).pop() {
+                    Some(color) => ("color", "color", color),
+                    None => continue,
+                },
+                Some("TEXT") => match typography(style) {
+                    Some(value) => ("typography", "typography", value),
+                    None => continue,
+                },
+                Some("EFFECT") => match shadow(style) {
+                    Some(value) => ("shadow", "shadow", value),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            // Keys of styles sharing their name with another type are suffixed
+            let style_name = style.get("name").and_then(|n| n.as_str()).unwrap_or(name);
+            let mut path = vec![group.to_string()];
+            path.extend(style_name.split('/').map(|segment| slug(segment, "style")));
+            self.named.insert(
+                name.clone(),
+                Token {
+                    path,
+                    token_type,
+                    value,
+                },
+            );
+        }
+    }
+
+    /// Collect the values of visible nodes, skipping internal-only ones
+    fn visit(&mut self, value: &JsonValue) {
+        let Some(node) = value.as_object() else {
+            return;
+        };
+        let hidden = node.get("visible").and_then(|v| v.as_bool()) == Some(false)
+            || node.get("internalOnly").and_then(|v| v.as_bool()) == Some(true);
+        if hidden || node.contains_key("styleType") {
+            return;
+        }
+
+        for (paints, style) in [
+            ("fillPaints", "styleIdForFill"),
+            ("strokePaints", "styleIdForStrokeFill"),
+        ] {
+            if !self.use_style(node, style) {
+                for color in solid_colors(node.get(paints)) {
+                    self.add_value("color", color);
+                }
+            }
+        }
+        if !self.use_style(node, "styleIdForText") {
+            if let Some(typography) = typography(node) {
+                self.add_value("typography", typography);
+            }
+        }
+        if !self.use_style(node, "styleIdForEffect") {
+            if let Some(shadow) = shadow(node) {
+                self.add_value("shadow", shadow);
+            }
+        }
+
+        let radii = [
+            "cornerRadius",
+            "rectangleTopLeftCornerRadius",
+            "rectangleTopRightCornerRadius",
+            "rectangleBottomLeftCornerRadius",
+            "rectangleBottomRightCornerRadius",
+        ];
+        for key in radii {
+            if let Some(radius) = positive(node, key) {
+                self.add_value("radius", JsonValue::String(px(radius)));
+            }
+        }
+        let spacings = [
+            "stackSpacing",
+            "stackHorizontalPadding",
+            "stackVerticalPadding",
+            "stackPaddingRight",
+            "stackPaddingBottom",
+        ];
+        for key in spacings {
+            if let Some(spacing) = positive(node, key) {
+                self.add_value("spacing", JsonValue::String(px(spacing)));
+            }
+        }
+
+        for child in children(node) {
+            self.visit(child);
+        }
+    }
+
+    /// Whether `field` names a style exported as a token
+    fn use_style(&self, node: &Map<String, JsonValue>, field: &str) -> bool {
+        node.get(field)
+            .and_then(|name| name.as_str())
+            .is_some_and(|name| self.named.contains_key(name))
+    }
+
+    fn add_value(&mut self, group: &'static str, value: JsonValue) {
+        let values = self.values.entry(group).or_default();
+        if !values.iter().any(|(_, v)| *v == value) {
+            let name = value_name(group, &value, values.len() + 1);
+            values.push((name, value));
+        }
+    }
+
+    fn finish(self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = self.named.into_values().collect();
+
+        for (group, values) in self.values {
+            let token_type = match group {
+                "radius" | "spacing" => "dimension",
+                other => other,
+            };
+            for (name, value) in values {
+                // Values equal to a style are covered by the named token
+                let covered = tokens
+                    .iter()
+                    .any(|t| t.path[0] == group && t.value == value);
+                if !covered {
+                    tokens.push(Token {
+                        path: vec![group.to_string(), name],
+                        token_type,
+                        value,
+                    });
+                }
+            }
+        }
+
+        unique_paths(&mut tokens);
+        tokens.sort_by(|a, b| {
+            let group = |t: &Token| GROUPS.iter().position(|g| *g == t.path[0]);
+            group(a).cmp(&group(b)).then_with(|| a.path.cmp(&b.path))
+        });
+        tokens
+    }
+}
+
+/// Name of an unnamed value (`0a84ff`, `inter-16-400`, `8`, `shadow-1`)
+fn value_name(group: &str, value: &JsonValue, index: usize) -> String {
+    match group {
+        "color" => value
+            .as_str()
+            .unwrap_or("")
+            .trim_start_matches('#')
+            .to_string(),
+        "typography" => {
+            let family = value["fontFamily"].as_str().unwrap_or("");
+            let size = value["fontSize"]
+                .as_str()
+                .unwrap_or("")
+                .trim_end_matches("px");
+            format!(
+                "{}-{}-{}",
+                slug(family, "font"),
+                size.replace('.', "_"),
+                value["fontWeight"]
+            )
+        }
+        "radius" | "spacing" => value
+            .as_str()
+            .unwrap_or("")
+            .trim_end_matches("px")
+            .replace('.', "_"),
+        _ => format!("{}-{}", group, index),
+    }
+}
+
+/// Append `-2`, `-3`, ... to repeated paths
+fn unique_paths(tokens: &mut [Token]) {
+    let mut used: HashSet<Vec<String>> = HashSet::new();
+    // Last suffix tried for each repeated path
+    let mut suffixes: HashMap<Vec<String>, usize> = HashMap::new();
+    for token in tokens.iter_mut() {
+        if used.insert(token.path.clone()) {
+            continue;
+        }
+
+        // Skip suffixed paths already taken literally ("primary-2" before a second "primary")
+        let suffix = suffixes.entry(token.path.clone()).or_insert(1);
+        let mut path = token.path.clone();
+        loop {
+            *suffix += 1;
+            *path.last_mut().expect("token path") =
+                format!("{}-{}", token.path.last().expect("token path"), suffix);
+            if used.insert(path.clone()) {
+                break;
+            }
+        }
+        token.path = path;
+    }
+}
+
+/// Colors of the visible solid paints with their opacity applied, bottom to top
+fn solid_colors(paints: Option<&JsonValue>) -> Vec<JsonValue> {
+    paints
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|paint| paint.get("visible").and_then(|v| v.as_bool()) != Some(false))
+        .filter(|paint| paint.get("type").and_then(enum_str).is_none_or(|t| t == "SOLID"))
+        .filter_map(|paint| {
+            let color = paint.get("color")?.as_str()?;
+            let opacity = paint.get("opacity").and_then(|o| o.as_f64()).unwrap_or(1.0);
+            Some(JsonValue::String(color_with_opacity(color, opacity)))
+        })
+        .collect()
+}
+
+/// W3C typography value of a text node or text style
+///
+/// Line heights are unitless ratios of the font size, as the format requires.
+fn typography(node: &Map<String, JsonValue>) -> Option<JsonValue> {
+    let font_name = node.get("fontName")?;
+    let family = font_name.get("family")?.as_str()?;
+    let size = node.get("fontSize")?.as_f64()?;
+
+    let mut value = Map::new();
+    value.insert("fontFamily".to_string(), json!(family));
+    let style = font_name
+        .get("style")
+        .and_then(|s| s.as_str())
+        .unwrap_or("");
+    value.insert("fontWeight".to_string(), json!(font_weight(style)));
+    value.insert("fontSize".to_string(), json!(px(size)));
+
+    let line_height = node.get("lineHeight").and_then(enum_str).and_then(|line_height| {
+        if let Some(percent) = parse_unit(line_height, "%") {
+            Some(percent / 100.0)
+        } else {
+            parse_unit(line_height, "px")
+                .filter(|_| size > 0.0)
+                .map(|px| px / size)
+        }
+    });
+    if let Some(ratio) = line_height {
+        value.insert("lineHeight".to_string(), json!(round(ratio, 1000.0)));
+    }
+
+    if let Some(spacing) = node.get("letterSpacing").and_then(enum_str) {
+        let spacing = match parse_unit(spacing, "%") {
+            Some(percent) => format!("{}em", format_number(percent / 100.0)),
+            None => spacing.to_string(),
+        };
+        value.insert("letterSpacing".to_string(), json!(spacing));
+    }
+
+    Some(JsonValue::Object(value))
+}
+
+/// W3C shadow value of the visible drop and inner shadows of a node
+///
+/// A single shadow is an object, several shadows an array.
+fn shadow(node: &Map<String, JsonValue>) -> Option<JsonValue> {
+    let mut shadows: Vec<JsonValue> = node
+        .get("effects")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|effect| effect.get("visible").and_then(|v| v.as_bool()) != Some(false))
+        .filter_map(|effect| {
+            let inset = match effect.get("type").and_then(enum_str)? {
+                "DROP_SHADOW" => false,
+                "INNER_SHADOW" => true,
+                _ => return None,
+            };
+            let number = |value: Option<&JsonValue>| value.and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let offset = effect.get("offset");
+            let mut shadow = json!({
+                "color": effect.get("color").and_then(|c| c.as_str()).unwrap_or("#000000"),
+                "offsetX": px(number(offset.and_then(|o| o.get("x")))),
+                "offsetY": px(number(offset.and_then(|o| o.get("y")))),
+                "blur": px(number(effect.get("radius"))),
+                "spread": px(number(effect.get("spread"))),
+            });
+            if inset {
+                shadow["inset"] = json!(true);
+            }
+            Some(shadow)
+        })
+        .collect();
+
+    match shadows.len() {
+        0 => None,
+        1 => shadows.pop(),
+        _ => Some(JsonValue::Array(shadows)),
+    }
+}
+
+fn style_type(node: &Map<String, JsonValue>) -> Option<&str> {
+    node.get("styleType").and_then(enum_str)
+}
+
+fn positive(node: &Map<String, JsonValue>, key: &str) -> Option<f64> {
+    node.get(key)?.as_f64().filter(|v| *v > 0.0)
+}
+
+fn parse_unit(value: &str, unit: &str) -> Option<f64> {
+    value.strip_suffix(unit)?.trim().parse().ok()
+}
+
+fn round(value: f64, precision: f64) -> f64 {
+    (value * precision).round() / precision
+}
+
+fn children(node: &Map<String, JsonValue>) -> &[JsonValue] {
+    node.get("children")
+        .and_then(|c| c.as_array())
+        .map(|c| c.as_slice())
+        .unwrap_or(&[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_output() -> JsonValue {
+        json!({
+            "document": {"children": [{
+                "name": "Page 1",
+                "children": [{
+                    "name": "Card",
+                    "cornerRadius": 12.0,
+                    "stackMode": "VERTICAL",
+                    "stackSpacing": 8.0,
+                    "stackHorizontalPadding": 16.0,
+                    "stackVerticalPadding": 16.0,
+                    "fillPaints": [{"type": "SOLID", "color": "#0a84ff"}],
+                    "styleIdForFill": "Brand/Primary",
+                    "effects": [{"type": "DROP_SHADOW", "color": "#00000040", "offset": {"x": 0.0, "y": 4.0}, "radius": 8.0}],
+                    "children": [
+                        {
+                            "name": "Title",
+                            "fontName": {"family": "Inter", "style": "Bold"},
+                            "fontSize": 24.0,
+                            "lineHeight": "32px",
+                            "letterSpacing": "-2%",
+                            "styleIdForText": "Heading / H1",
+                            "fillPaints": [{"type": "SOLID", "color": "#111111"}, {"type": "SOLID", "color": "#ff0000", "visible": false}]
+                        },
+                        {
+                            "name": "Body",
+                            "fontName": {"family": "Inter", "style": "Regular"},
+                            "fontSize": 16.0,
+                            "lineHeight": "150%",
+                            "fillPaints": [{"type": "SOLID", "color": "#111111", "opacity": 0.5}],
+                            "strokePaints": [{"type": "SOLID", "color": "#0a84ff"}]
+                        },
+                        {"name": "Hidden", "visible": false, "cornerRadius": 99.0}
+                    ]
+                }]
+            }]},
+            "styles": {
+                "Brand/Primary": {
+                    "styleType": "FILL",
+                    "fillPaints": [{"type": "SOLID", "color": "#0a84ff"}]
+                },
+                "Heading / H1": {
+                    "styleType": "TEXT",
+                    "fontName": {"family": "Inter", "style": "Bold"},
+                    "fontSize": 24.0,
+                    "lineHeight": "32px",
+                    "letterSpacing": "-2%"
+                },
+                "Unused": {
+                    "styleType": "FILL",
+                    "fillPaints": [{"type": "SOLID", "color": "#00ff00"}]
+                }
+            }
+        })
+    }
+
+    fn paths(tokens: &[Token]) -> Vec<String> {
+        tokens.iter().map(|t| t.path.join(".")).collect()
+    }
+
+    #[test]
+    fn test_extract_tokens() {
+        let tokens = extract_tokens(&sample_output());
+
+        assert_eq!(
+            paths(&tokens),
+            [
+                "color.111111",
+                "color.11111180",
+                "color.brand.primary",
+                "color.unused",
+                "typography.heading.h1",
+                "typography.inter-16-400",
+                "radius.12",
+                "spacing.16",
+                "spacing.8",
+                "shadow.shadow-1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_values() {
+        let tokens = extract_tokens(&sample_output());
+        let get = |path: &str| tokens.iter().find(|t| t.path.join(".") == path).unwrap();
+
+        assert_eq!(get("color.brand.primary").value, json!("#0a84ff"));
+        assert_eq!(get("color.brand.primary").token_type, "color");
+        assert_eq!(
+            get("typography.heading.h1").value,
+            json!({"fontFamily": "Inter", "fontWeight": 700, "fontSize": "24px", "lineHeight": 1.333, "letterSpacing": "-0.02em"})
+        );
+        assert_eq!(
+            get("typography.inter-16-400").value["lineHeight"],
+            json!(1.5)
+        );
+        assert_eq!(get("radius.12").token_type, "dimension");
+        assert_eq!(
+            get("shadow.shadow-1").value,
+            json!({"color": "#00000040", "offsetX": "0", "offsetY": "4px", "blur": "8px", "spread": "0"})
+        );
+    }
+
+    #[test]
+    fn test_suffixed_style_names() {
+        let output = json!({
+            "document": {"children": []},
+            "styles": {
+                "Heading (FILL)": {
+                    "styleType": "FILL",
+                    "name": "Heading",
+                    "fillPaints": [{"type": "SOLID", "color": "#111111"}]
+                },
+                "Heading (TEXT)": {
+                    "styleType": "TEXT",
+                    "name": "Heading",
+                    "fontName": {"family": "Inter", "style": "Bold"},
+                    "fontSize": 24.0
+                }
+            }
+        });
+
+        assert_eq!(
+            paths(&extract_tokens(&output)),
+            ["color.heading", "typography.heading"]
+        );
+    }
+
+    #[test]
+    fn test_repeated_paths_skip_literal_names() {
+        let fill = |name: &str, color: &str| {
+            json!({"styleType": "FILL", "name": name, "fillPaints": [{"type": "SOLID", "color": color}]})
+        };
+        let output = json!({
+            "document": {"children": []},
+            "styles": {
+                "Primary": fill("Primary", "#111111"),
+                "Primary (1:2)": fill("Primary", "#222222"),
+                "Primary-2": fill("Primary-2", "#333333")
+            }
+        });
+
+        assert_eq!(
+            paths(&extract_tokens(&output)),
+            ["color.primary", "color.primary-2", "color.primary-2-2"]
+        );
+    }
+
+    #[test]
+    fn test_multiple_and_inner_shadows() {
+        let node = json!({"effects": [
+            {"type": {"__enum__": "EffectType", "value": "INNER_SHADOW"}, "color": "#ffffff", "radius": 2.0},
+            {"type": "DROP_SHADOW", "color": "#000000", "visible": false},
+            {"type": "DROP_SHADOW", "color": "#000000", "spread": 1.0},
+            {"type": "LAYER_BLUR", "radius": 4.0}
+        ]});
+        let shadow = shadow(node.as_object().unwrap()).unwrap();
+
+        assert_eq!(shadow.as_array().unwrap().len(), 2);
+        assert_eq!(shadow[0]["inset"], json!(true));
+        assert_eq!(shadow[1]["spread"], json!("1px"));
+    }
+}
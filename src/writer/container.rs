@@ -0,0 +1,172 @@
+use crate::error::{FigError, Result};
+use crate::parser::magic_header;
+use crate::types::{FileType, ParsedFile};
+use flate2::write::DeflateEncoder;
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Compression applied to the schema and data chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Raw DEFLATE (older files)
+    #[default]
+    Deflate,
+    /// Zstandard (newer files)
+    Zstd,
+}
+
+/// Compress a chunk
+///
+/// Both formats are read back by [`decompress_chunk`](crate::parser::decompress_chunk).
+///
+/// # Arguments
+/// * `bytes` - Uncompressed chunk
+/// * `compression` - Compression format
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - Compressed chunk
+/// * `Err(FigError)` - If compression fails
+pub fn compress_chunk(bytes: &[u8], compression: Compression) -> Result<Vec<u8>> {
+    match compression {
+        Compression::Deflate => {
+            let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(bytes)?;
+            Ok(encoder.finish()?)
+        }
+        Compression::Zstd => Ok(zstd::encode_all(bytes, 0)?),
+    }
+}
+
+/// Write the binary .fig container
+///
+/// Inverse of [`extract_chunks`](crate::parser::extract_chunks): magic header,
+/// version (uint32, little-endian), then each chunk prefixed with its length.
+///
+/// # Examples
+/// ```
+/// use fig2json::parser::extract_chunks;
+/// use fig2json::writer::write_chunks;
+/// use fig2json::{FileType, ParsedFile};
+///
+/// let parsed = ParsedFile::new(48, vec![vec![1, 2], vec![3]]);
+/// let bytes = write_chunks(FileType::Figma, &parsed);
+///
+/// let read = extract_chunks(&bytes).unwrap();
+/// assert_eq!(read.version, 48);
+/// assert_eq!(read.chunks, parsed.chunks);
+/// ```
+pub fn write_chunks(file_type: FileType, parsed: &ParsedFile) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(magic_header(file_type));
+    bytes.extend_from_slice(&parsed.version.to_le_bytes());
+
+    for chunk in &parsed.chunks {
+        bytes.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(chunk);
+    }
+
+    bytes
+}
+
+/// Wrap a canvas in the ZIP container
+///
+/// The canvas is stored as `canvas.fig` and each image as `images/<name>`.
+///
+/// # Arguments
+/// * `canvas` - Binary .fig file (see [`write_chunks`])
+/// * `images` - Image names (usually their hash) and contents
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - ZIP archive bytes
+/// * `Err(FigError)` - If writing the archive fails
+pub fn write_zip(canvas: &[u8], images: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    // Entries are already compressed (chunks, PNG/JPEG), store them as-is
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+    zip.start_file("canvas.fig", options)?;
+    zip.write_all(canvas)?;
+
+    for (name, bytes) in images {
+        zip.start_file(format!("images/{}", name), options)?;
+        zip.write_all(bytes)?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Read every file of an `images/` directory
+///
+/// Useful to write back images extracted with
+/// [`extract_zip_to_directory`](crate::parser::extract_zip_to_directory).
+/// Files are returned sorted by name.
+///
+/// # Arguments
+/// * `dir` - Directory containing the image files
+///
+/// # Returns
+/// * `Ok(Vec<(String, Vec<u8>)>)` - File names and contents
+/// * `Err(FigError)` - If the directory cannot be read
+pub fn read_images_dir(dir: &Path) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut images = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry
+            .file_name()
+            .into_string()
+            .map_err(|name| FigError::ZipError(format!("Invalid image file name: {:?}", name)))?;
+        images.push((name, fs::read(entry.path())?));
+    }
+
+    images.sort();
+    Ok(images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{decompress_chunk, extract_from_zip};
+    use std::io::Read;
+
+    #[test]
+    fn test_compress_round_trip() {
+        let data = b"nodeChanges nodeChanges nodeChanges".to_vec();
+        for compression in [Compression::Deflate, Compression::Zstd] {
+            let compressed = compress_chunk(&data, compression).unwrap();
+            assert_eq!(decompress_chunk(&compressed).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_write_chunks_figjam() {
+        let bytes = write_chunks(FileType::FigJam, &ParsedFile::new(20, vec![vec![9; 3]]));
+        assert_eq!(&bytes[..8], b"fig-jam.");
+        assert_eq!(&bytes[8..12], &[20, 0, 0, 0]);
+        assert_eq!(&bytes[12..16], &[3, 0, 0, 0]);
+        assert_eq!(&bytes[16..], &[9, 9, 9]);
+    }
+
+    #[test]
+    fn test_write_zip() {
+        let images = vec![("abc123".to_string(), vec![137, 80, 1])];
+        let bytes = write_zip(b"fig-kiwi", &images).unwrap();
+
+        assert_eq!(extract_from_zip(&bytes).unwrap(), b"fig-kiwi");
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut image = Vec::new();
+        archive
+            .by_name("images/abc123")
+            .unwrap()
+            .read_to_end(&mut image)
+            .unwrap();
+        assert_eq!(image, vec![137, 80, 1]);
+    }
+}
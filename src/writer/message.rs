@@ -0,0 +1,238 @@
+use crate::blobs::{encode_blob, parse_blob};
+use crate::error::{FigError, Result};
+use crate::schema::root_message;
+use base64::{engine::general_purpose, Engine as _};
+use kiwi_schema::{Def, DefKind, Schema, Value};
+use serde_json::{json, Value as JsonValue};
+use std::collections::{HashMap, HashSet};
+
+/// Encode a flat `nodeChanges` array and its blobs into a Kiwi message
+///
+/// The JSON is converted field by field following the schema:
+/// - `{"__enum__": ..., "value": "X"}` objects and plain strings become enum values
+/// - `null` floats (NaN or infinite when decoded) become NaN
+/// - base64 strings are accepted for `byte[]` fields
+/// - fields replaced by [`substitute_blobs`](crate::blobs::substitute_blobs)
+///   (`commands`, `vectorNetwork`) are encoded back into blobs. A blob with the
+///   same content is reused, otherwise a new one is appended.
+///
+/// The message `type` is set to `NODE_CHANGES` when the schema has it.
+///
+/// # Arguments
+/// * `node_changes` - Flat node objects (see [`flatten_tree`](super::flatten_tree))
+/// * `blobs` - Blob objects of the raw output
+/// * `schema` - Schema of the original file
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - Encoded data chunk (uncompressed)
+/// * `Err(FigError)` - If the JSON does not match the schema
+pub fn encode_message(
+    node_changes: &[JsonValue],
+    blobs: &[JsonValue],
+    schema: &Schema,
+) -> Result<Vec<u8>> {
+    let message = root_message(schema).ok_or_else(|| FigError::EncodeError {
+        path: "Message".to_string(),
+        message: "no root Message definition found in schema".to_string(),
+    })?;
+    let mut encoder = Encoder::new(schema, blobs.to_vec());
+    let mut fields = HashMap::new();
+
+    if let Some(field) = message.field("type") {
+        if let Some(def) = schema.defs.get(field.type_id as usize) {
+            if let Some(variant) = def.field("NODE_CHANGES") {
+                fields.insert("type", Value::Enum(def.name.as_str(), variant.name.as_str()));
+            }
+        }
+    }
+
+    let nodes_field = message.field("nodeChanges").expect("checked by root_message");
+    let nodes = node_changes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| encoder.single(nodes_field.type_id, node, &format!("nodeChanges[{}]", i)))
+        .collect::<Result<Vec<_>>>()?;
+    fields.insert("nodeChanges", Value::Array(nodes));
+
+    // Encoded last: nodes may have appended blobs
+    let blobs_field = message.field("blobs").expect("checked by root_message");
+    let blobs = std::mem::take(&mut encoder.blobs.blobs);
+    let blobs = encoder.value(blobs_field.type_id, true, &JsonValue::Array(blobs), "blobs")?;
+    fields.insert("blobs", blobs);
+
+    Ok(Value::Object(message.name.as_str(), fields).encode(schema))
+}
+
+/// Blobs of the file, with an index of their parsed content for reuse
+struct BlobTable {
+    blobs: Vec<JsonValue>,
+    parsed: HashMap<(String, String), usize>,
+    indexed_types: HashSet<String>,
+}
+
+impl BlobTable {
+    /// Index of a blob holding `value`, appending one if needed
+    fn intern(&mut self, blob_type: &str, value: &JsonValue) -> Option<usize> {
+        if self.indexed_types.insert(blob_type.to_string()) {
+            for (i, blob) in self.blobs.iter().enumerate() {
+                if let Ok(Some(parsed)) = parse_blob(blob_type, blob) {
+                    self.parsed
+                        .entry((blob_type.to_string(), parsed.to_string()))
+                        .or_insert(i);
+                }
+            }
+        }
+
+        let key = (blob_type.to_string(), value.to_string());
+        if let Some(index) = self.parsed.get(&key) {
+            return Some(*index);
+        }
+
+        let bytes = encode_blob(blob_type, value)?;
+        self.blobs
+            .push(json!({"bytes": general_purpose::STANDARD.encode(bytes)}));
+        self.parsed.insert(key, self.blobs.len() - 1);
+        Some(self.blobs.len() - 1)
+    }
+}
+
+/// Converts JSON values to Kiwi values borrowing names from the schema
+struct Encoder<'s> {
+    schema: &'s Schema,
+    blobs: BlobTable,
+}
+
+impl<'s> Encoder<'s> {
+    fn new(schema: &'s Schema, blobs: Vec<JsonValue>) -> Self {
+        Self {
+            schema,
+            blobs: BlobTable {
+                blobs,
+                parsed: HashMap::new(),
+                indexed_types: HashSet::new(),
+            },
+        }
+    }
+
+    fn value(
+        &mut self,
+        type_id: i32,
+        is_array: bool,
+        json: &JsonValue,
+        path: &str,
+    ) -> Result<Value<'s>> {
+        if !is_array {
+            return self.single(type_id, json, path);
+        }
+
+        if type_id == kiwi_schema::TYPE_BYTE {
+            if let Some(base64) = json.as_str() {
+                let bytes = general_purpose::STANDARD
+                    .decode(base64)
+                    .map_err(|e| error(path, &format!("invalid base64: {}", e)))?;
+                return Ok(Value::Array(bytes.into_iter().map(Value::Byte).collect()));
+            }
+        }
+
+        let items = json
+            .as_array()
+            .ok_or_else(|| error(path, "expected an array"))?;
+        let values = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| self.single(type_id, item, &format!("{}[{}]", path, i)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Value::Array(values))
+    }
+
+    fn single(&mut self, type_id: i32, json: &JsonValue, path: &str) -> Result<Value<'s>> {
+        let value = match type_id {
+            kiwi_schema::TYPE_BOOL => json.as_bool().map(Value::Bool),
+            kiwi_schema::TYPE_BYTE => json
+                .as_u64()
+                .and_then(|n| u8::try_from(n).ok())
+                .map(Value::Byte),
+            kiwi_schema::TYPE_INT => json
+                .as_i64()
+                .and_then(|n| i32::try_from(n).ok())
+                .map(Value::Int),
+            kiwi_schema::TYPE_UINT => json
+                .as_u64()
+                .and_then(|n| u32::try_from(n).ok())
+                .map(Value::UInt),
+            kiwi_schema::TYPE_FLOAT => match json {
+                JsonValue::Null => Some(Value::Float(f32::NAN)),
+                _ => json.as_f64().map(|n| Value::Float(n as f32)),
+            },
+            kiwi_schema::TYPE_STRING => json.as_str().map(|s| Value::String(s.to_string())),
+            kiwi_schema::TYPE_INT64 => json.as_i64().map(Value::Int64),
+            kiwi_schema::TYPE_UINT64 => json.as_u64().map(Value::UInt64),
+            _ => {
+                let def = self
+                    .schema
+                    .defs
+                    .get(type_id as usize)
+                    .ok_or_else(|| error(path, &format!("unknown type id {}", type_id)))?;
+                return match def.kind {
+                    DefKind::Enum => enum_value(def, json, path),
+                    DefKind::Struct | DefKind::Message => self.object(def, json, path),
+                };
+            }
+        };
+
+        value.ok_or_else(|| error(path, &format!("unexpected value {}", json)))
+    }
+
+    fn object(&mut self, def: &'s Def, json: &JsonValue, path: &str) -> Result<Value<'s>> {
+        let map = json
+            .as_object()
+            .ok_or_else(|| error(path, &format!("expected a {} object", def.name)))?;
+        let mut fields = HashMap::new();
+
+        for (key, value) in map {
+            let field_path = format!("{}.{}", path, key);
+
+            if let Some(field) = def.field(key) {
+                let value = self.value(field.type_id, field.is_array, value, &field_path)?;
+                fields.insert(field.name.as_str(), value);
+            } else if let Some(field) = def.field(&format!("{}Blob", key)) {
+                let index = self
+                    .blobs
+                    .intern(key, value)
+                    .ok_or_else(|| error(&field_path, "cannot encode blob"))?;
+                let value = self.single(field.type_id, &json!(index), &field_path)?;
+                fields.insert(field.name.as_str(), value);
+            } else {
+                return Err(error(&field_path, &format!("no such field in {}", def.name)));
+            }
+        }
+
+        if def.kind == DefKind::Struct {
+            if let Some(missing) = def.fields.iter().find(|f| !fields.contains_key(f.name.as_str())) {
+                return Err(error(path, &format!("missing struct field {}", missing.name)));
+            }
+        }
+
+        Ok(Value::Object(def.name.as_str(), fields))
+    }
+}
+
+/// Enum value from either `{"__enum__": ..., "value": "X"}` or `"X"`
+fn enum_value<'s>(def: &'s Def, json: &JsonValue, path: &str) -> Result<Value<'s>> {
+    let name = json
+        .get("value")
+        .unwrap_or(json)
+        .as_str()
+        .ok_or_else(|| error(path, &format!("expected a {} value", def.name)))?;
+    let variant = def
+        .field(name)
+        .ok_or_else(|| error(path, &format!("{} has no variant {}", def.name, name)))?;
+    Ok(Value::Enum(def.name.as_str(), variant.name.as_str()))
+}
+
+fn error(path: &str, message: &str) -> FigError {
+    FigError::EncodeError {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
@@ -0,0 +1,318 @@
+//! Writing .fig files back from JSON
+//!
+//! [`write_fig`] takes the output of [`convert_raw`](crate::convert_raw)
+//! (possibly edited) together with the schema of the original file and
+//! produces a file that Figma and [`convert_raw`](crate::convert_raw) can read:
+//!
+//! 1. The document tree is flattened into `nodeChanges` with `parentIndex`
+//! 2. Parsed blobs (`commands`, `vectorNetwork`) are encoded back into blobs
+//! 3. The message is Kiwi-encoded and both chunks are compressed
+//! 4. The magic header and version are written, optionally inside a ZIP container
+//!
+//! Output of the transformation pipeline cannot be written back: the passes
+//! drop or rename fields the schema needs.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fig2json::schema::read_schema;
+//! use fig2json::writer::{write_fig, WriteOptions};
+//! use fig2json::convert_raw;
+//!
+//! let bytes = std::fs::read("example.fig").unwrap();
+//! let schema = read_schema(&bytes).unwrap();
+//! let mut json = convert_raw(&bytes).unwrap();
+//!
+//! json["document"]["children"][0]["name"] = "Renamed page".into();
+//!
+//! let output = write_fig(&json, &schema, &WriteOptions::default()).unwrap();
+//! std::fs::write("edited.fig", output).unwrap();
+//! ```
+
+pub mod container;
+pub mod message;
+pub mod tree;
+
+pub use container::{compress_chunk, read_images_dir, write_chunks, write_zip, Compression};
+pub use message::encode_message;
+pub use tree::flatten_tree;
+
+use crate::error::{FigError, Result};
+use crate::types::{FileType, ParsedFile};
+use kiwi_schema::Schema;
+use serde_json::Value as JsonValue;
+
+/// Options of [`write_fig`]
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// Compression of the schema and data chunks
+    pub compression: Compression,
+    /// Wrap the file in a ZIP container (`canvas.fig` + `images/`)
+    pub zip: bool,
+    /// Images stored as `images/<name>` (only written when `zip` is set)
+    pub images: Vec<(String, Vec<u8>)>,
+}
+
+/// Write a .fig file from raw JSON
+///
+/// # Arguments
+/// * `raw` - Output of [`convert_raw`](crate::convert_raw): `version`, `fileType`, `document` and `blobs`
+/// * `schema` - Schema of the original file (see [`read_schema`](crate::schema::read_schema))
+/// * `options` - Compression and container options
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - Bytes of the .fig file
+/// * `Err(FigError)` - If the JSON does not match the schema
+pub fn write_fig(raw: &JsonValue, schema: &Schema, options: &WriteOptions) -> Result<Vec<u8>> {
+    let version = raw
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| missing_field("version"))?;
+    let file_type = match raw.get("fileType").and_then(|v| v.as_str()) {
+        Some("figjam") => FileType::FigJam,
+        _ => FileType::Figma,
+    };
+    let document = raw.get("document").ok_or_else(|| missing_field("document"))?;
+    let blobs = match raw.get("blobs") {
+        Some(blobs) => blobs.as_array().ok_or_else(|| FigError::EncodeError {
+            path: "blobs".to_string(),
+            message: "expected an array".to_string(),
+        })?,
+        None => &[][..],
+    };
+
+    let node_changes = flatten_tree(document)?;
+    let data = encode_message(&node_changes, blobs, schema)?;
+
+    let parsed = ParsedFile::new(
+        version,
+        vec![
+            compress_chunk(&schema.encode(), options.compression)?,
+            compress_chunk(&data, options.compression)?,
+        ],
+    );
+    let canvas = write_chunks(file_type, &parsed);
+
+    if options.zip {
+        write_zip(&canvas, &options.images)
+    } else {
+        Ok(canvas)
+    }
+}
+
+fn missing_field(name: &str) -> FigError {
+    FigError::EncodeError {
+        path: name.to_string(),
+        message: "missing field".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert_raw;
+    use kiwi_schema::{Def, DefKind, Field, TYPE_BOOL, TYPE_BYTE, TYPE_FLOAT, TYPE_STRING, TYPE_UINT};
+    use serde_json::json;
+
+    fn field(name: &str, type_id: i32, is_array: bool, value: u32) -> Field {
+        Field {
+            name: name.to_string(),
+            type_id,
+            is_array,
+            value,
+        }
+    }
+
+    /// Schema shaped like Figma's, with GUIDs, parent indices and blob fields
+    fn sample_schema() -> Schema {
+        Schema::new(vec![
+            Def::new(
+                "MessageType".to_string(),
+                DefKind::Enum,
+                vec![field("JOIN_START", 0, false, 0), field("NODE_CHANGES", 0, false, 1)],
+            ),
+            Def::new(
+                "NodeType".to_string(),
+                DefKind::Enum,
+                vec![
+                    field("DOCUMENT", 0, false, 1),
+                    field("CANVAS", 0, false, 2),
+                    field("FRAME", 0, false, 4),
+                    field("VECTOR", 0, false, 6),
+                ],
+            ),
+            Def::new(
+                "GUID".to_string(),
+                DefKind::Struct,
+                vec![field("sessionID", TYPE_UINT, false, 0), field("localID", TYPE_UINT, false, 0)],
+            ),
+            Def::new(
+                "ParentIndex".to_string(),
+                DefKind::Struct,
+                vec![field("guid", 2, false, 0), field("position", TYPE_STRING, false, 0)],
+            ),
+            Def::new(
+                "Vector".to_string(),
+                DefKind::Struct,
+                vec![field("x", TYPE_FLOAT, false, 0), field("y", TYPE_FLOAT, false, 0)],
+            ),
+            Def::new(
+                "VectorData".to_string(),
+                DefKind::Message,
+                vec![field("vectorNetworkBlob", TYPE_UINT, false, 1)],
+            ),
+            Def::new(
+                "Path".to_string(),
+                DefKind::Message,
+                vec![field("commandsBlob", TYPE_UINT, false, 1)],
+            ),
+            Def::new(
+                "NodeChange".to_string(),
+                DefKind::Message,
+                vec![
+                    field("guid", 2, false, 1),
+                    field("parentIndex", 3, false, 2),
+                    field("type", 1, false, 3),
+                    field("name", TYPE_STRING, false, 4),
+                    field("size", 4, false, 5),
+                    field("visible", TYPE_BOOL, false, 6),
+                    field("opacity", TYPE_FLOAT, false, 7),
+                    field("vectorData", 5, false, 8),
+                    field("fillGeometry", 6, true, 9),
+                ],
+            ),
+            Def::new(
+                "Blob".to_string(),
+                DefKind::Message,
+                vec![field("bytes", TYPE_BYTE, true, 1)],
+            ),
+            Def::new(
+                "Message".to_string(),
+                DefKind::Message,
+                vec![
+                    field("type", 0, false, 1),
+                    field("nodeChanges", 7, true, 2),
+                    field("blobs", 8, true, 3),
+                ],
+            ),
+        ])
+    }
+
+    fn sample_raw() -> JsonValue {
+        json!({
+            "version": 48,
+            "fileType": "figma",
+            "blobs": [{"bytes": "AQAAgD8AAAAAAA=="}],
+            "document": {
+                "guid": {"sessionID": 0, "localID": 0},
+                "type": {"__enum__": "NodeType", "value": "DOCUMENT"},
+                "name": "Document",
+                "children": [{
+                    "guid": {"sessionID": 0, "localID": 1},
+                    "type": {"__enum__": "NodeType", "value": "CANVAS"},
+                    "name": "Page 1",
+                    "children": [
+                        {
+                            "guid": {"sessionID": 1, "localID": 2},
+                            "type": {"__enum__": "NodeType", "value": "FRAME"},
+                            "name": "Frame",
+                            "size": {"x": 100.0, "y": 50.5},
+                            "opacity": null
+                        },
+                        {
+                            "guid": {"sessionID": 1, "localID": 3},
+                            "type": {"__enum__": "NodeType", "value": "VECTOR"},
+                            "name": "Line",
+                            "visible": false,
+                            "fillGeometry": [{"commandsBlob": 0}],
+                            "vectorData": {"vectorNetworkBlob": 0}
+                        }
+                    ]
+                }]
+            }
+        })
+    }
+
+    /// Encode `raw` as a file, the way Figma would have written it
+    fn fig_from_raw(raw: &JsonValue, options: &WriteOptions) -> Vec<u8> {
+        write_fig(raw, &sample_schema(), options).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_fig_json_fig_json() {
+        let schema = sample_schema();
+        let fig = fig_from_raw(&sample_raw(), &WriteOptions::default());
+
+        let json = convert_raw(&fig).unwrap();
+        let fig_again = write_fig(&json, &schema, &WriteOptions::default()).unwrap();
+        let json_again = convert_raw(&fig_again).unwrap();
+
+        assert_eq!(json_again, json);
+        // Blob 0 holds valid commands, so it was substituted on read and reused on write
+        let line = &json["document"]["children"][0]["children"][1];
+        assert_eq!(line["fillGeometry"][0]["commands"], json!(["M", 1.0, 0.0, "Z"]));
+        assert_eq!(json_again["blobs"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_round_trip_zstd_zip_figjam() {
+        let mut raw = sample_raw();
+        raw["fileType"] = json!("figjam");
+        let options = WriteOptions {
+            compression: Compression::Zstd,
+            zip: true,
+            images: vec![("0123abcd".to_string(), vec![137, 80, 78, 71])],
+        };
+        let fig = fig_from_raw(&raw, &options);
+        assert!(crate::parser::is_zip_container(&fig));
+
+        let json = convert_raw(&fig).unwrap();
+        assert_eq!(json["fileType"], json!("figjam"));
+        assert_eq!(json["version"], json!(48));
+
+        let json_again = convert_raw(&write_fig(&json, &sample_schema(), &options).unwrap()).unwrap();
+        assert_eq!(json_again, json);
+    }
+
+    #[test]
+    fn test_edited_blob_is_appended() {
+        let schema = sample_schema();
+        let mut json = convert_raw(&fig_from_raw(&sample_raw(), &WriteOptions::default())).unwrap();
+        json["document"]["children"][0]["children"][1]["fillGeometry"][0]["commands"] =
+            json!(["M", 0.0, 0.0, "L", 5.0, 5.0]);
+
+        let json_again =
+            convert_raw(&write_fig(&json, &schema, &WriteOptions::default()).unwrap()).unwrap();
+
+        assert_eq!(json_again["blobs"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            json_again["document"]["children"][0]["children"][1]["fillGeometry"][0]["commands"],
+            json!(["M", 0.0, 0.0, "L", 5.0, 5.0])
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_is_rejected() {
+        let mut raw = sample_raw();
+        raw["document"]["children"][0]["color"] = json!("red");
+
+        match write_fig(&raw, &sample_schema(), &WriteOptions::default()) {
+            Err(FigError::EncodeError { path, .. }) => {
+                assert_eq!(path, "nodeChanges[1].color")
+            }
+            other => panic!("expected an encode error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_struct_field_is_rejected() {
+        let mut raw = sample_raw();
+        raw["document"]["children"][0]["children"][0]["size"] = json!({"x": 1.0});
+
+        assert!(matches!(
+            write_fig(&raw, &sample_schema(), &WriteOptions::default()),
+            Err(FigError::EncodeError { .. })
+        ));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::{FigError, Result};
+use serde_json::{json, Value as JsonValue};
+
+/// First character used in generated positions
+const POSITION_FIRST: u8 = b'!';
+
+/// Number of characters available for positions (`!` to `~`)
+const POSITION_BASE: usize = 94;
+
+/// Flatten a document tree back into a `nodeChanges` array
+///
+/// Inverse of [`build_tree`](crate::schema::build_tree): nodes are emitted
+/// parents first, `children` arrays are removed and each child gets a
+/// `parentIndex` with its parent's GUID and a position string that sorts
+/// in the child's order.
+///
+/// # Arguments
+/// * `document` - Root node with children hierarchy
+///
+/// # Returns
+/// * `Ok(Vec<JsonValue>)` - Flat array of node objects
+/// * `Err(FigError)` - If a node is not an object or a parent has no GUID
+///
+/// # Examples
+/// ```
+/// use fig2json::writer::flatten_tree;
+/// use serde_json::json;
+///
+/// let document = json!({
+///     "guid": {"sessionID": 0, "localID": 0},
+///     "children": [{"guid": {"sessionID": 0, "localID": 1}}]
+/// });
+/// let nodes = flatten_tree(&document).unwrap();
+///
+/// assert_eq!(nodes.len(), 2);
+/// assert_eq!(nodes[1]["parentIndex"]["guid"], json!({"sessionID": 0, "localID": 0}));
+/// ```
+pub fn flatten_tree(document: &JsonValue) -> Result<Vec<JsonValue>> {
+    let mut nodes = Vec::new();
+    flatten_node(document, None, &mut nodes, "document")?;
+    Ok(nodes)
+}
+
+fn flatten_node(
+    node: &JsonValue,
+    parent_index: Option<JsonValue>,
+    nodes: &mut Vec<JsonValue>,
+    path: &str,
+) -> Result<()> {
+    let mut flat = node
+        .as_object()
+        .ok_or_else(|| encode_error(path, "node is not an object"))?
+        .clone();
+
+    let children = match flat.remove("children") {
+        Some(JsonValue::Array(children)) => children,
+        Some(_) => return Err(encode_error(path, "children is not an array")),
+        None => Vec::new(),
+    };
+
+    flat.remove("parentIndex");
+    if let Some(parent_index) = parent_index {
+        flat.insert("parentIndex".to_string(), parent_index);
+    }
+
+    let guid = flat.get("guid").cloned();
+    nodes.push(JsonValue::Object(flat));
+
+    if children.is_empty() {
+        return Ok(());
+    }
+
+    let guid = guid.ok_or_else(|| encode_error(path, "node with children has no guid"))?;
+    for (i, child) in children.iter().enumerate() {
+        let parent_index = json!({
+            "guid": guid,
+            "position": position(i, children.len()),
+        });
+        flatten_node(child, Some(parent_index), nodes, &format!("{}.children[{}]", path, i))?;
+    }
+
+    Ok(())
+}
+
+/// Position string of the `index`-th of `count` siblings
+///
+/// All positions of a sibling list have the same length, so their
+/// lexicographic order matches the numeric order.
+fn position(index: usize, count: usize) -> String {
+    let mut width = 1;
+    let mut capacity = POSITION_BASE;
+    while capacity < count {
+        width += 1;
+        capacity *= POSITION_BASE;
+    }
+
+    let mut digits = vec![POSITION_FIRST; width];
+    let mut rest = index;
+    for digit in digits.iter_mut().rev() {
+        *digit = POSITION_FIRST + (rest % POSITION_BASE) as u8;
+        rest /= POSITION_BASE;
+    }
+
+    String::from_utf8(digits).expect("positions are ASCII")
+}
+
+fn encode_error(path: &str, message: &str) -> FigError {
+    FigError::EncodeError {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::build_tree;
+
+    fn guid(local_id: u32) -> JsonValue {
+        json!({"sessionID": 1, "localID": local_id})
+    }
+
+    #[test]
+    fn test_position_order() {
+        for count in [1, 5, 94, 95, 10_000] {
+            let positions: Vec<String> = (0..count).map(|i| position(i, count)).collect();
+            assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(positions.iter().all(|p| p.len() == positions[0].len()));
+        }
+        assert_eq!(position(0, 1), "!");
+        assert_eq!(position(1, 95), "!\"");
+    }
+
+    #[test]
+    fn test_flatten_removes_children() {
+        let document = json!({
+            "guid": {"sessionID": 0, "localID": 0},
+            "name": "Document",
+            "children": [
+                {"guid": guid(1), "name": "A", "children": [{"guid": guid(3), "name": "C"}]},
+                {"guid": guid(2), "name": "B"}
+            ]
+        });
+        let nodes = flatten_tree(&document).unwrap();
+
+        let names: Vec<&str> = nodes.iter().map(|n| n["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["Document", "A", "C", "B"]);
+        assert!(nodes.iter().all(|n| n.get("children").is_none()));
+        assert!(nodes[0].get("parentIndex").is_none());
+        assert_eq!(nodes[2]["parentIndex"]["guid"], guid(1));
+    }
+
+    #[test]
+    fn test_flatten_then_build_tree() {
+        let children: Vec<JsonValue> = (1..=200)
+            .map(|i| json!({"guid": guid(i), "name": format!("Node {}", i)}))
+            .collect();
+        let document = json!({
+            "guid": {"sessionID": 0, "localID": 0},
+            "children": children
+        });
+
+        let rebuilt = build_tree(flatten_tree(&document).unwrap()).unwrap();
+        assert_eq!(rebuilt, document);
+    }
+
+    #[test]
+    fn test_flatten_parent_without_guid() {
+        let document = json!({"children": [{"guid": guid(1)}]});
+        assert!(matches!(
+            flatten_tree(&document),
+            Err(FigError::EncodeError { .. })
+        ));
+    }
+}
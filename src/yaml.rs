@@ -0,0 +1,182 @@
+//! YAML output
+//!
+//! [`to_yaml`] writes a JSON value as block-style YAML: no braces, no quotes
+//! around keys and plain strings, one indentation level per nesting level.
+//! Strings that YAML would read as another type (`true`, `1.5`, `null`) or
+//! that contain special characters are written as double-quoted JSON strings,
+//! which YAML reads back unchanged.
+
+use serde_json::Value as JsonValue;
+
+/// Write a JSON value as YAML
+///
+/// # Arguments
+/// * `value` - Any JSON value, usually the output of [`convert`](crate::convert)
+///
+/// # Returns
+/// A YAML document ending with a newline
+///
+/// # Examples
+/// ```
+/// use fig2json::yaml::to_yaml;
+/// use serde_json::json;
+///
+/// let value = json!({"name": "Button", "size": {"x": 96.0, "y": 40.0}, "children": [{"name": "Label"}]});
+///
+/// assert_eq!(
+///     to_yaml(&value),
+///     "children:\n- name: Label\nname: Button\nsize:\n  x: 96.0\n  y: 40.0\n"
+/// );
+/// ```
+pub fn to_yaml(value: &JsonValue) -> String {
+    let mut yaml = String::new();
+    match value {
+        JsonValue::Object(map) if !map.is_empty() => write_block(&mut yaml, value, 0),
+        JsonValue::Array(arr) if !arr.is_empty() => write_block(&mut yaml, value, 0),
+        _ => {
+            yaml.push_str(&scalar(value));
+            yaml.push('\n');
+        }
+    }
+    yaml
+}
+
+/// Write a non-empty object or array, each entry on its own line(s)
+fn write_block(yaml: &mut String, value: &JsonValue, indent: usize) {
+    let padding = "  ".repeat(indent);
+    match value {
+        JsonValue::Object(map) => {
+            for (key, val) in map {
+                yaml.push_str(&padding);
+                yaml.push_str(&string(key));
+                yaml.push(':');
+                write_value(yaml, val, indent + 1, false);
+            }
+        }
+        JsonValue::Array(arr) => {
+            for val in arr {
+                yaml.push_str(&padding);
+                yaml.push('-');
+                write_value(yaml, val, indent + 1, true);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Write the value of a mapping entry or a sequence item, after its `key:` or `-`
+fn write_value(yaml: &mut String, value: &JsonValue, indent: usize, in_sequence: bool) {
+    match value {
+        JsonValue::Object(map) if !map.is_empty() => {
+            if in_sequence {
+                // First entry on the dash line, the others aligned with it
+                yaml.push(' ');
+                let mut item = String::new();
+                write_block(&mut item, value, indent);
+                yaml.push_str(item.trim_start_matches(' '));
+            } else {
+                yaml.push('\n');
+                write_block(yaml, value, indent);
+            }
+        }
+        // Sequences in mappings are not indented, as is usual in YAML
+        JsonValue::Array(arr) if !arr.is_empty() => {
+            yaml.push('\n');
+            write_block(yaml, value, if in_sequence { indent } else { indent - 1 });
+        }
+        _ => {
+            yaml.push(' ');
+            yaml.push_str(&scalar(value));
+            yaml.push('\n');
+        }
+    }
+}
+
+fn scalar(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(text) => string(text),
+        JsonValue::Object(_) => "{}".to_string(),
+        JsonValue::Array(_) => "[]".to_string(),
+        // null, booleans and numbers are the same in JSON and YAML
+        _ => value.to_string(),
+    }
+}
+
+/// Plain string when YAML reads it back as the same string, quoted otherwise
+fn string(text: &str) -> String {
+    if is_plain(text) {
+        text.to_string()
+    } else {
+        JsonValue::from(text).to_string()
+    }
+}
+
+fn is_plain(text: &str) -> bool {
+    let Some(first) = text.chars().next() else {
+        return false;
+    };
+    let reserved = matches!(
+        text.to_ascii_lowercase().as_str(),
+        "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+    );
+    !reserved
+        && text.parse::<f64>().is_err()
+        && !"-?:,[]{}#&*!|>'\"%@` ".contains(first)
+        && !text.ends_with([' ', ':'])
+        && !text.contains(": ")
+        && !text.contains(" #")
+        && !text.chars().any(char::is_control)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_nested_sequences() {
+        let value = json!({
+            "children": [
+                {"name": "Card", "children": [{"name": "Title"}], "fillPaints": [{"color": "#ffffff"}]},
+                [1, 2],
+                []
+            ]
+        });
+
+        assert_eq!(
+            to_yaml(&value),
+            "children:\n\
+             - children:\n  \
+               - name: Title\n  \
+               fillPaints:\n  \
+               - color: \"#ffffff\"\n  \
+               name: Card\n\
+             -\n  \
+               - 1\n  \
+               - 2\n\
+             - []\n"
+        );
+    }
+
+    #[test]
+    fn test_strings() {
+        assert_eq!(string("Sign up"), "Sign up");
+        assert_eq!(string("Button/Primary"), "Button/Primary");
+        assert_eq!(string("true"), "\"true\"");
+        assert_eq!(string("16"), "\"16\"");
+        assert_eq!(string("#0a84ff"), "\"#0a84ff\"");
+        assert_eq!(string("Step 1: details"), "\"Step 1: details\"");
+        assert_eq!(string("Two\nlines"), "\"Two\\nlines\"");
+        assert_eq!(string(""), "\"\"");
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(to_yaml(&json!(null)), "null\n");
+        assert_eq!(to_yaml(&json!({})), "{}\n");
+        assert_eq!(
+            to_yaml(&json!({"visible": false, "opacity": 0.5, "tags": {}})),
+            "opacity: 0.5\ntags: {}\nvisible: false\n"
+        );
+    }
+}